@@ -0,0 +1,69 @@
+//! Runs guests from `local_test` through the interpreter. Use `cargo bench`.
+
+#![feature(test)]
+
+extern crate test;
+extern crate bobbin_wasm as wasm;
+
+use test::Bencher;
+
+use wasm::{ExportDesc, ImportDesc};
+use wasm::environ::{Environment, HostHandler};
+use wasm::interp::{self, Interp};
+use wasm::memory_inst::MemoryInst;
+use wasm::module_inst::{FuncInst, Value};
+
+struct Handler {}
+
+impl HostHandler for Handler {
+    fn import(&self, _module: &str, _export: &str, _import_desc: &ImportDesc) -> Result<usize, wasm::Error> {
+        Err(wasm::Error::InvalidImport)
+    }
+
+    fn dispatch(&self, _interp: &mut Interp, _mem: &MemoryInst, _type_index: usize, index: usize) -> Result<(), wasm::Error> {
+        Err(wasm::Error::InvalidFunction { id: index as u32 })
+    }
+}
+
+/// Calls `export` of `module` once per iteration, checking that it returns `expected`.
+fn bench_export(b: &mut Bencher, module: &[u8], export: &str, expected: i32) {
+    let mut buf = vec![0u8; 1 << 20];
+    let (buf, mut env) = Environment::new(&mut buf, Handler {}).unwrap();
+    let (buf, mi) = env.load_module("bench", buf, module).unwrap();
+    let function_index = mi.exports().iter()
+        .filter(|e| e.name == export)
+        .filter_map(|e| match e.export_desc {
+            ExportDesc::Func(index) => match mi.functions()[index as usize] {
+                FuncInst::Local { type_index: _, function_index } => Some(function_index),
+                _ => None,
+            },
+            _ => None,
+        })
+        .next()
+        .unwrap();
+    let cfg = interp::Config::default().value_stack_size(1024).call_stack_size(1024);
+    let mut interp = Interp::new_with_config(cfg, buf).unwrap();
+    b.iter(|| {
+        assert_eq!(interp.call(&env, mi, function_index).unwrap(), &[Value::from(expected)]);
+    });
+}
+
+#[bench]
+fn fac(b: &mut Bencher) {
+    bench_export(b, include_bytes!("../local_test/fac10.wasm"), "fac10", 3628800);
+}
+
+#[bench]
+fn fib(b: &mut Bencher) {
+    bench_export(b, include_bytes!("../local_test/fib.wasm"), "fib20", 6765);
+}
+
+#[bench]
+fn sieve(b: &mut Bencher) {
+    bench_export(b, include_bytes!("../local_test/sieve.wasm"), "sieve", 550);
+}
+
+#[bench]
+fn memcpy(b: &mut Bencher) {
+    bench_export(b, include_bytes!("../local_test/memcpy.wasm"), "memcpy", 2044);
+}
@@ -1,4 +1,4 @@
-import sys, os
+import sys, os, re
 
 OP_TR = 0
 OP_T1 = 1
@@ -9,69 +9,128 @@ OP_PREFIX = 5
 OP_CODE = 6
 OP_NAME = 7
 OP_TEXT = 8
+# Comment lines to copy above the row's constant
+OP_COMMENTS = 9
+
+# The prefix bytes of multi-byte opcodes, with the name of their constant and lookup function
+PREFIXES = [
+    (0xfc, 'MISC_PREFIX', 'misc'),
+    (0xfd, 'SIMD_PREFIX', 'simd'),
+    (0xfe, 'ATOMIC_PREFIX', 'atomic'),
+]
 
 def gen_prologue(out):
     out.write("""use types::ValueType;
 
+pub const BR_TABLE_ENTRY_SIZE: u32 = 12;
+pub const BR_TABLE_S_ENTRY_SIZE: u32 = 4;
+
 #[derive(Debug)]
 pub struct Op {
-    pub tr: Option<ValueType>,
-    pub t1: Option<ValueType>,
-    pub t2: Option<ValueType>,
+    pub tr: ValueType,
+    pub t1: ValueType,
+    pub t2: ValueType,
     pub m: u8,
+    pub prefix: u8,
     pub code: u8,
     pub text: &'static str,
 }
 
-pub const ___: Option<ValueType> = None;
-pub const I32: Option<ValueType> = Some(ValueType::I32);
-pub const I64: Option<ValueType> = Some(ValueType::I64);
-pub const F32: Option<ValueType> = Some(ValueType::F32);
-pub const F64: Option<ValueType> = Some(ValueType::F64);
+pub const ___: ValueType = ValueType::Void;
+pub const I32: ValueType = ValueType::I32;
+pub const I64: ValueType = ValueType::I64;
+pub const F32: ValueType = ValueType::F32;
+pub const F64: ValueType = ValueType::F64;
+pub const V128: ValueType = ValueType::V128;
+pub const VOID: ValueType = ValueType::Void;
 
 """)
 
 def to_name(row):
-    return row[OP_TEXT].upper().replace('.','_').replace('/','_').replace('"','')
+    return row[OP_NAME]
 
 def to_op_type(t):
     return t
-    # if t == '___':
-    #     return 'None'
-    # else:
-    #     return 'Some(ValueType::%s)' % t
 
-def gen_opcodes(out, rows):
+def prefix_of(row):
+    return int(row[OP_PREFIX], 16) if row[OP_PREFIX] != '0' else 0
+
+def prefix_info(prefix):
+    for p in PREFIXES:
+        if p[0] == prefix:
+            return p
+    raise ValueError('unknown prefix 0x%02x' % prefix)
+
+def groups(rows):
+    # Rows grouped by prefix, in the order the groups appear in the table
+    result = []
+    for row in rows:
+        if not result or prefix_of(result[-1][-1]) != prefix_of(row):
+            result.append([])
+        result[-1].append(row)
+    return result
+
+def gen_opcode_consts(out, rows):
     for row in rows:
+        for comment in row[OP_COMMENTS]:
+            out.write(comment + "\n")
         out.write("pub const {:24}: u8 = {};\n".format(to_name(row), row[OP_CODE]))
+
+def gen_opcodes(out, rows):
+    main, prefixed = groups(rows)[0], groups(rows)[1:]
+    gen_opcode_consts(out, main)
+    for (prefix, name, _) in PREFIXES:
+        out.write("pub const {:24}: u8 = 0x{:02x};\n".format(name, prefix))
+    for group in prefixed:
+        out.write("\n// %s sub-opcodes\n\n" % prefix_info(prefix_of(group[0]))[1])
+        gen_opcode_consts(out, group)
     out.write("\n\n")
 
 def gen_ops(out, rows):
+    for (i, group) in enumerate(groups(rows)):
+        if i > 0:
+            out.write("\n")
+        for row in group:
+            out.write("pub const {:24}: Op = Op {{".format(to_name(row)+'_OP'))
+            out.write(" tr: %s, " % to_op_type(row[OP_TR]))
+            out.write(" t1: %s, " % to_op_type(row[OP_T1]))
+            out.write(" t2: %s, " % to_op_type(row[OP_T2]))
+            # out.write(" t3: %s, " % to_op_type(row[OP_T3]))
+            out.write(" m: %s, " % row[OP_M])
+            out.write(" prefix: 0x%02x, " % prefix_of(row))
+            out.write(" code: %s, " % row[OP_CODE])
+            out.write(" text: %s, " % row[OP_TEXT])
+            out.write("};\n")
+
+def gen_match(out, rows):
+    width = max([20] + [len(to_name(row)) for row in rows])
+    out.write("        Some(match opc {\n")
     for row in rows:
-        out.write("pub const {:24}: Op = Op {{".format(to_name(row)+'_OP'))
-        out.write(" tr: %s, " % to_op_type(row[OP_TR]))
-        out.write(" t1: %s, " % to_op_type(row[OP_T1]))
-        out.write(" t2: %s, " % to_op_type(row[OP_T2]))
-        # out.write(" t3: %s, " % to_op_type(row[OP_T3]))
-        out.write(" m: %s, " % row[OP_M])
-        # out.write(" prefix: %s, " % row[OP_PREFIX])
-        out.write(" code: %s, " % row[OP_CODE])
-        out.write(" text: %s, " % row[OP_TEXT])
-        out.write("};\n")
+        out.write("            {} => {},\n".format(to_name(row).ljust(width), to_name(row)+'_OP'))
+    out.write("            {} => {},\n".format('_'.ljust(width), 'return None'))
+    out.write("        })\n")
 
 def gen_op_from(out, rows):
+    main = groups(rows)[0]
     out.write("""
 impl Op {
     pub fn from_opcode(opc: u8) -> Option<Op> {
-        Some(match opc {
 """)
-    for row in rows:
-        opc = to_name(row)
-        op = to_name(row)+'_OP'
-        out.write("            {:20} => {},\n".format(opc, op))
-    out.write("            {:20} => {},\n".format('_', 'return None'))
+    gen_match(out, main)
+    out.write("    }\n")
+    for (prefix, name, fn_name) in PREFIXES:
+        group = [row for row in rows if prefix_of(row) == prefix]
+        out.write("\n    /// Looks up an instruction in the %s (0x%02x) opcode space.\n" % (name, prefix))
+        out.write("    pub fn from_%s_opcode(opc: u8) -> Option<Op> {\n" % fn_name)
+        gen_match(out, group)
+        out.write("    }\n")
+    out.write("""
+    pub fn is_unop(&self) -> bool {
+        self.t1 != ValueType::Void && self.t2 == ValueType::Void
+    }
 
-    out.write("""        })
+    pub fn is_binop(&self) -> bool {
+        self.t1 != ValueType::Void && self.t2 != ValueType::Void
     }
 }
 """)
@@ -84,23 +143,24 @@ def gen_code(out, rows):
 
 def read_opcodes(f):
     rows = []
+    comments = []
     for line in f:
-        if line.find('WABT_OPCODE(') != 0:
-            continue
-        row = line.replace('WABT_OPCODE(','').replace(')','').split(',')
-        row = [c.strip() for c in row]
-
-        if row[OP_TR] == 'V128':
+        if line.startswith('//'):
+            comments.append(line.rstrip())
             continue
-        if row[OP_PREFIX] != '0':
+        if line.find('WABT_OPCODE(') != 0:
             continue
+        m = re.match(r'WABT_OPCODE\(([^"]*), (".*")\)', line)
+        row = [c.strip() for c in m.group(1).split(',')] + [m.group(2)]
+        row.append(comments)
+        comments = []
         rows.append(row)
     return rows
 
 def main():
     with open('wabt_opcode.def') as f:
-        with open('src/parser/opcode.rs','w') as out:
+        with open('src/opcode.rs','w') as out:
             gen_code(out, read_opcodes(f))
 
 if __name__ == '__main__':
-    main()
\ No newline at end of file
+    main()
@@ -1,8 +1,12 @@
+// Host functions return `Ok({ ... })`, as in the library.
+#![allow(clippy::unit_arg)]
+
 extern crate bobbin_wasm as wasm;
 extern crate clap;
 extern crate log;
 extern crate env_logger;
 
+use std::fmt;
 use std::process;
 use std::io::{self, stdout, Read, Write};
 use std::fs::File;
@@ -23,6 +27,17 @@ use wasm::memory_inst::MemoryInst;
 pub enum Error {
     IoError(io::Error),
     WasmError(wasm::Error),
+    LoadError(wasm::LoadError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::IoError(ref e) => write!(f, "error: {}", e),
+            Error::WasmError(ref e) => write!(f, "error: {}", e),
+            Error::LoadError(ref e) => write!(f, "{}", e),
+        }
+    }
 }
 
 impl From<io::Error> for Error {
@@ -37,6 +52,12 @@ impl From<wasm::Error> for Error {
     }
 }
 
+impl From<wasm::LoadError> for Error {
+    fn from(other: wasm::LoadError) -> Self {
+        Error::LoadError(other)
+    }
+}
+
 pub fn main() {
     env_logger::init();
     let matches = App::new("interp")
@@ -45,7 +66,7 @@ pub fn main() {
         .get_matches();
     
     if let Err(e) = run(matches) {
-        eprintln!("Error: {:?}", e);
+        eprintln!("{}", e);
         process::exit(1);
     }
 }
@@ -103,7 +124,7 @@ impl HostHandler for BoardHandler {
 #[allow(dead_code)]
 fn load_file(file_name: &str) -> Result<Vec<u8>, Error> {
     let path = Path::new(file_name);
-    let mut file = File::open(&path)?;
+    let mut file = File::open(path)?;
     let mut data: Vec<u8> = Vec::new();
     file.read_to_end(&mut data)?;
     Ok(data)
@@ -111,7 +132,7 @@ fn load_file(file_name: &str) -> Result<Vec<u8>, Error> {
 
 pub fn run(matches: ArgMatches) -> Result<(), Error> {
     let path = Path::new(matches.value_of("path").unwrap());
-    let mut file = File::open(&path)?;
+    let mut file = File::open(path)?;
     let mut data: Vec<u8> = Vec::new();
     file.read_to_end(&mut data)?;
 
@@ -122,13 +143,13 @@ pub fn run(matches: ArgMatches) -> Result<(), Error> {
     let h = BoardHandler {};
 
     let buf = &mut [0u8; 65536 * 2];
-    let (buf, mut env) = Environment::new(buf, h);    
+    let (buf, mut env) = Environment::new(buf, h)?;
 
     let (buf, mi) = env.load_module(path, buf, data.as_ref())?;
 
     // Interpreter
 
-    let mut interp = Interp::new(buf);
+    let mut interp = Interp::new(buf)?;
 
     for e in mi.exports() {
         // println!("export: {:?}", e);
@@ -138,38 +159,22 @@ pub fn run(matches: ArgMatches) -> Result<(), Error> {
                 continue
             }
             match &mi.functions()[index as usize] {
-                &FuncInst::Local { type_index: _, function_index } => {
+                &FuncInst::Local { type_index, function_index } => {
                     // println!("Calling Local Function {}", function_index);
-                    match interp.call(&env, &mi, function_index as usize) {
-                        Ok(Some(value)) => {
-                            println!("{}() => {:?}", id, value);
-                        },
-                        Ok(None) => {
-                            println!("{}() =>", id);
-                        },
-                        Err(wasm::Error::Unreachable) => {
-                            println!("{}() => error: unreachable executed", id);
-                        },
-                        Err(wasm::Error::UndefinedTableIndex { id: _ }) => {
-                            println!("{}() => error: undefined table index", id);
-                        },
-                        Err(wasm::Error::SignatureMismatch) => {
-                            println!("{}() => error: indirect call signature mismatch", id);
-                        },
-                        Err(e) => {
-                            println!("Error: {:?}", e);
-                            println!("---- Stack Dump ----");
-
-                            let mut i = 0;
-                            while let Ok(value) = interp.pop() {
-                                println!("{}: {:?}", i, value);
-                                i += 1;
+                    match interp.call(&env, mi, function_index) {
+                        Ok(values) => {
+                            print!("{}() =>", id);
+                            let results = mi.function_types()[type_index].results;
+                            for (i, value) in values.iter().enumerate() {
+                                if i > 0 { print!(","); }
+                                print!(" {}", value.typed(results[i]));
                             }
-                            println!("---- END ----");
-                        }
+                            println!();
+                        },
+                        Err(trap) => println!("{}() => error: {}", id, trap.error()),
                     }
                 },
-                f @ _ => {
+                f => {
                     println!("Unable to call {:?}", f);
                 }
             }
@@ -1,8 +1,12 @@
+// Host functions return `Ok({ ... })`, as in the library.
+#![allow(clippy::unit_arg)]
+
 extern crate bobbin_wasm as wasm;
 extern crate clap;
 extern crate log;
 extern crate env_logger;
 
+use std::fmt;
 use std::process;
 use std::io::{self, Read};
 use std::fs::File;
@@ -12,7 +16,8 @@ use std::path::Path;
 use clap::{App, Arg, ArgMatches};
 
 use wasm::{ExportDesc, ImportDesc};
-use wasm::interp::Interp;
+use wasm::parser::{self, Id, FallibleIterator};
+use wasm::interp::{self, Interp};
 use wasm::environ::{Environment, HostHandler};
 use wasm::module_inst::*;
 use wasm::memory_inst::MemoryInst;
@@ -21,6 +26,17 @@ use wasm::memory_inst::MemoryInst;
 pub enum Error {
     IoError(io::Error),
     WasmError(wasm::Error),
+    LoadError(wasm::LoadError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::IoError(ref e) => write!(f, "error: {}", e),
+            Error::WasmError(ref e) => write!(f, "error: {}", e),
+            Error::LoadError(ref e) => write!(f, "{}", e),
+        }
+    }
 }
 
 impl From<io::Error> for Error {
@@ -35,6 +51,12 @@ impl From<wasm::Error> for Error {
     }
 }
 
+impl From<wasm::LoadError> for Error {
+    fn from(other: wasm::LoadError) -> Self {
+        Error::LoadError(other)
+    }
+}
+
 pub fn main() {
     env_logger::init();
     let matches = App::new("interp")
@@ -42,12 +64,15 @@ pub fn main() {
             .required(true))
         .arg(Arg::with_name("dump").long("dump"))
         .arg(Arg::with_name("no-compile").long("no-compile"))
+        .arg(Arg::with_name("lazy").long("lazy"))
+        .arg(Arg::with_name("stats").long("stats"))
+        .arg(Arg::with_name("trace").long("trace"))
         .arg(Arg::with_name("run-all-exports").long("run-all-exports"))
         .arg(Arg::with_name("host-print").long("host-print"))
         .get_matches();
     
     if let Err(e) = run(matches) {
-        eprintln!("Error: {:?}", e);
+        eprintln!("{}", e);
         process::exit(1);
     }
 }
@@ -92,10 +117,32 @@ impl HostHandler for Handler {
     
 }
 
+/// Prints the size of the function bodies in the module and of the code compiled from them.
+fn print_stats(data: &[u8], mi: &ModuleInst) -> Result<(), wasm::Error> {
+    let m = parser::Module::new(data)?;
+    let mut body_len = 0;
+    let mut sections = m.sections();
+    while let Some(section) = sections.next()? {
+        if section.id() != Id::Code { continue }
+        let mut code = section.code();
+        while let Some(code) = code.next()? {
+            body_len += code.func.buf.len();
+        }
+    }
+    let code = mi.code();
+    let mut code_len = 0;
+    for index in 0..code.body_count() {
+        code_len += code.body_range(index)?.len();
+    }
+    let ratio = if body_len > 0 { code_len as f64 / body_len as f64 } else { 0.0 };
+    println!("function bodies: {} bytes, compiled: {} bytes ({:.2}x)", body_len, code_len, ratio);
+    Ok(())
+}
+
 #[allow(dead_code)]
 fn load_file(file_name: &str) -> Result<Vec<u8>, Error> {
     let path = Path::new(file_name);
-    let mut file = File::open(&path)?;
+    let mut file = File::open(path)?;
     let mut data: Vec<u8> = Vec::new();
     file.read_to_end(&mut data)?;
     Ok(data)
@@ -103,7 +150,7 @@ fn load_file(file_name: &str) -> Result<Vec<u8>, Error> {
 
 pub fn run(matches: ArgMatches) -> Result<(), Error> {
     let path = Path::new(matches.value_of("path").unwrap());
-    let mut file = File::open(&path)?;
+    let mut file = File::open(path)?;
     let mut data: Vec<u8> = Vec::new();
     file.read_to_end(&mut data)?;
 
@@ -114,7 +161,7 @@ pub fn run(matches: ArgMatches) -> Result<(), Error> {
     let h = Handler {};
 
     let buf = &mut [0u8; 65536 * 2];
-    let (buf, mut env) = Environment::new(buf, h);    
+    let (buf, mut env) = Environment::new(buf, h)?;
 
 
     // let math = load_file("local_test/math.wasm")?;
@@ -127,11 +174,25 @@ pub fn run(matches: ArgMatches) -> Result<(), Error> {
 
 
 
-    let (buf, mi) = env.load_module(path, buf, data.as_ref())?;
+    let (buf, mi) = if matches.is_present("lazy") {
+        env.load_module_lazy(path, buf, data.as_ref(), 32768)?
+    } else if matches.is_present("no-compile") {
+        env.load_module_in_place(path, buf, data.as_ref())?
+    } else {
+        env.load_module(path, buf, data.as_ref())?
+    };
+
+    if matches.is_present("stats") {
+        print_stats(data.as_ref(), mi)?;
+    }
 
     // Interpreter
 
-    let mut interp = Interp::new(buf);
+    let mut cfg = interp::Config::default();
+    if matches.is_present("trace") {
+        cfg = cfg.trace(interp::log_trace);
+    }
+    let mut interp = Interp::new_with_config(cfg, buf)?;
 
     if matches.is_present("run-all-exports") {
 
@@ -140,38 +201,22 @@ pub fn run(matches: ArgMatches) -> Result<(), Error> {
             if let ExportDesc::Func(index) = e.export_desc {
                 let id = &e.name;            
                 match &mi.functions()[index as usize] {
-                    &FuncInst::Local { type_index: _, function_index } => {
+                    &FuncInst::Local { type_index, function_index } => {
                         // println!("Calling Local Function {}", function_index);
-                        match interp.call(&env, &mi, function_index as usize) {
-                            Ok(Some(value)) => {
-                                println!("{}() => {:?}", id, value);
-                            },
-                            Ok(None) => {
-                                println!("{}() =>", id);
-                            },
-                            Err(wasm::Error::Unreachable) => {
-                                println!("{}() => error: unreachable executed", id);
-                            },
-                            Err(wasm::Error::UndefinedTableIndex { id: _ }) => {
-                                println!("{}() => error: undefined table index", id);
-                            },
-                            Err(wasm::Error::SignatureMismatch) => {
-                                println!("{}() => error: indirect call signature mismatch", id);
-                            },
-                            Err(e) => {
-                                println!("Error: {:?}", e);
-                                println!("---- Stack Dump ----");
-
-                                let mut i = 0;
-                                while let Ok(value) = interp.pop() {
-                                    println!("{}: {:?}", i, value);
-                                    i += 1;
+                        match interp.call(&env, mi, function_index) {
+                            Ok(values) => {
+                                print!("{}() =>", id);
+                                let results = mi.function_types()[type_index].results;
+                                for (i, value) in values.iter().enumerate() {
+                                    if i > 0 { print!(","); }
+                                    print!(" {}", value.typed(results[i]));
                                 }
-                                println!("---- END ----");
-                            }
+                                println!();
+                            },
+                            Err(trap) => println!("{}() => error: {}", id, trap.error()),
                         }
                     },
-                    f @ _ => {
+                    f => {
                         println!("Unable to call {:?}", f);
                     }
                 }
@@ -12,7 +12,7 @@ use clap::{App, Arg, ArgMatches};
 
 // use wasm::{Reader, BinaryReader};
 use wasm::types::Index;
-use wasm::parser::{self, Id, Module, FallibleIterator, ExportDesc, ImportDesc, Immediate, FuncItem, Instr, Local};
+use wasm::parser::{self, Id, Module, FallibleIterator, ExportDesc, ImportDesc, Immediate, FuncItem, Instr, Local, SegmentMode};
 // use wasm::visitor;
 
 
@@ -23,9 +23,22 @@ pub enum Error {
     IoError(io::Error),
     FmtError(fmt::Error),
     ParserError(parser::Error),
+    InvalidModule(parser::Location, parser::Error),
     WasmError(wasm::Error),
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::IoError(ref e) => write!(f, "error: {}", e),
+            Error::FmtError(ref e) => write!(f, "error: {}", e),
+            Error::ParserError(ref e) => write!(f, "error: {}", e),
+            Error::InvalidModule(loc, ref e) => write!(f, "{:07x}: error: {}", loc.offset, e),
+            Error::WasmError(ref e) => write!(f, "error: {}", e),
+        }
+    }
+}
+
 impl From<io::Error> for Error {
     fn from(other: io::Error) -> Self {
         Error::IoError(other)
@@ -69,14 +82,14 @@ pub fn main() {
         .get_matches();
     
     if let Err(e) = run(matches) {
-        eprintln!("Error: {:?}", e);
+        eprintln!("{}", e);
         process::exit(1);
     }
 }
 
 pub fn run(matches: ArgMatches) -> Result<(), Error> {
     let path = Path::new(matches.value_of("path").unwrap());
-    let mut file = File::open(&path)?;
+    let mut file = File::open(path)?;
     let mut data: Vec<u8> = Vec::new();
     file.read_to_end(&mut data)?;
 
@@ -86,6 +99,8 @@ pub fn run(matches: ArgMatches) -> Result<(), Error> {
 
     
     let m = parser::Module::new(data.as_ref())?;
+    let mut loc = parser::Location::default();
+    parser::validate_at(&mut vec![0; data.len() * 2], &m, &mut loc).map_err(|e| Error::InvalidModule(loc, e))?;
     
     writeln!(out, "\n{}:\tfile format wasm 0x{:x}\n", name, m.version())?;
     
@@ -146,6 +161,7 @@ pub fn dump_details<W: Write>(out: &mut W, m: &Module) -> Result<(), Error> {
     let mut import_tables = 0;
     let mut import_memory = 0;
     let mut import_globals = 0;    
+    let mut import_tags = 0;
     while let Some(s) = sections.next()? {
         let s_id = s.id();
         if s_id != Id::Code && s_id != Id::Custom {
@@ -164,12 +180,19 @@ pub fn dump_details<W: Write>(out: &mut W, m: &Module) -> Result<(), Error> {
                     }
                     write!(out, ")")?;
                     write!(out, " ->")?;
-                    if t.results.len() == 0 {
+                    if t.results.is_empty() {
                         write!(out, " nil")?
-                    } else {
+                    } else if t.results.len() == 1 {
                         write!(out, " {}", t.results[0])?;
+                    } else {
+                        write!(out, " (")?;
+                        for (n, r) in t.results.iter().enumerate() {
+                            if n > 0 { write!(out, ", ")? }
+                            write!(out, "{}", r)?;
+                        }
+                        write!(out, ")")?;
                     }
-                    writeln!(out, "")?;
+                    writeln!(out)?;
                     n += 1;
                 }
             },
@@ -197,6 +220,10 @@ pub fn dump_details<W: Write>(out: &mut W, m: &Module) -> Result<(), Error> {
                             writeln!(out, " - global[{}] {} mutable={} <- {}.{}", n, g.valtype, if g.mutable { 1 } else { 0 }, module, name)?;                        
                             import_globals += 1;
                         },                    
+                        ImportDesc::Tag(t) => {
+                            writeln!(out, " - tag[{}] sig={} <- {}.{}", n, t.type_index, module, name)?;
+                            import_tags += 1;
+                        },
                     }
                     n += 1;
                 }
@@ -233,7 +260,10 @@ pub fn dump_details<W: Write>(out: &mut W, m: &Module) -> Result<(), Error> {
                     if let Some(maximum) = limits.max {
                         write!(out, " maximum={}", maximum)?;
                     }
-                    writeln!(out, "")?;
+                    if limits.shared {
+                        write!(out, " shared")?;
+                    }
+                    writeln!(out)?;
                     
                     n += 1;
                 }
@@ -266,6 +296,7 @@ pub fn dump_details<W: Write>(out: &mut W, m: &Module) -> Result<(), Error> {
                         ExportDesc::Table(_) => "table",
                         ExportDesc::Memory(_) => "memory",
                         ExportDesc::Global(_) => "global",
+                        ExportDesc::Tag(_) => "tag",
                     };
                     writeln!(out, " - {}[{}] -> {:?}", kind, n, e.name)?;      
                 }
@@ -278,17 +309,35 @@ pub fn dump_details<W: Write>(out: &mut W, m: &Module) -> Result<(), Error> {
                 let mut elements = s.elements();
                 let mut n = 0;
                 while let Some(e) = elements.next()? {
-                    let imm = if let Immediate::I32Const { value } = e.offset.instr.immediate {
-                        value
-                    } else {
-                        // FIXME
-                        panic!("invalid immediate type");
-                    };
-                    
-                    writeln!(out,  " - init {}={}", "i32", imm)?;
-                    writeln!(out,  " - segment[{}] table={} count={}", n, e.table_index, e.init.len())?;
-                    for i in 0..e.init.len() {
-                        writeln!(out,  "  - elem[{}] = func[{}]", i, e.init[i])?;
+                    let mut funcs = Vec::new();
+                    let mut items = e.iter();
+                    while let Some(item) = items.next()? {
+                        funcs.push(item);
+                    }
+                    match e.mode {
+                        SegmentMode::Active { index, ref offset } => {
+                            let imm = if let Immediate::I32Const { value } = offset.instr.immediate {
+                                value
+                            } else {
+                                // FIXME
+                                panic!("invalid immediate type");
+                            };
+                            writeln!(out,  " - init i32={}", imm)?;
+                            writeln!(out,  " - segment[{}] table={} count={}", n, index, funcs.len())?;
+                        },
+                        SegmentMode::Passive => {
+                            writeln!(out,  " - segment[{}] passive count={}", n, funcs.len())?;
+                        },
+                        SegmentMode::Declarative => {
+                            writeln!(out,  " - segment[{}] declarative count={}", n, funcs.len())?;
+                        },
+                    }
+                    for (i, f) in funcs.iter().enumerate() {
+                        if let Some(f) = f {
+                            writeln!(out,  "  - elem[{}] = func[{}]", i, f)?;
+                        } else {
+                            writeln!(out,  "  - elem[{}] = ref.null", i)?;
+                        }
                     }
                     n += 1;
                 }
@@ -298,15 +347,20 @@ pub fn dump_details<W: Write>(out: &mut W, m: &Module) -> Result<(), Error> {
                 let mut data = s.data();
                 let mut n = 0;
                 while let Some(d) = data.next()? {                    
-                    let offset = d.offset;
-                    let imm = if let Immediate::I32Const { value } = offset.instr.immediate {
-                        value
+                    let init = d.init;
+                    let imm = if let SegmentMode::Active { index: _, offset } = d.mode {
+                        let imm = if let Immediate::I32Const { value } = offset.instr.immediate {
+                            value
+                        } else {
+                            // FIXME
+                            panic!("invalid immediate type");
+                        };
+                        writeln!(out, " - segment[{}] size={} - init i32={} ", n, init.len(), imm)?;
+                        imm
                     } else {
-                        // FIXME
-                        panic!("invalid immediate type");
+                        writeln!(out, " - segment[{}] passive size={}", n, init.len())?;
+                        0
                     };
-                    let init = d.init;
-                    writeln!(out, " - segment[{}] size={} - init {}={} ", n, init.len(), "i32", imm)?;
                     write!(out, "  - {:07x}:", imm)?;
                     for (i, d) in init.iter().enumerate() {
                         if i % 2 == 0 {
@@ -314,7 +368,18 @@ pub fn dump_details<W: Write>(out: &mut W, m: &Module) -> Result<(), Error> {
                         }
                         write!(out, "{:02x}", d)?;
                     }
-                    writeln!(out, "")?;                    
+                    writeln!(out)?;                    
+                    n += 1;
+                }
+            },
+            Id::DataCount => {
+                writeln!(out, " - data count: {}", s.data_count()?)?;
+            },
+            Id::Tag => {
+                let mut tags = s.tags();
+                let mut n = import_tags;
+                while let Some(t) = tags.next()? {
+                    writeln!(out, " - tag[{}] sig={}", n, t.type_index)?;
                     n += 1;
                 }
             },
@@ -325,7 +390,7 @@ pub fn dump_details<W: Write>(out: &mut W, m: &Module) -> Result<(), Error> {
 }
 
 pub fn dump_code<W: Write>(out: &mut W, m: &Module) -> Result<(), Error> {
-    use parser::opcode::*;
+    use wasm::opcode::*;
     use std::collections::HashMap;
 
 
@@ -340,24 +405,18 @@ pub fn dump_code<W: Write>(out: &mut W, m: &Module) -> Result<(), Error> {
             Id::Import => {
                 let mut imports = s.imports();
                 while let Some(i) = imports.next()? {
-                    match i.import_desc {
-                        ImportDesc::Func(_) => {
-                            func_names.insert(import_funcs, String::from(i.name));
-                            import_funcs += 1;
-                        },
-                        _ => {},
+                    if let ImportDesc::Func(_) = i.import_desc {
+                        func_names.insert(import_funcs, String::from(i.name));
+                        import_funcs += 1;
                     }
                 }                
             },
             Id::Export => {
                 let mut exports = s.exports();
                 while let Some(e) = exports.next()? {
-                    match e.export_desc {
-                        ExportDesc::Func(index) => {
-                            func_names.insert(index, String::from(e.name));
-                            // writeln!(out, "{} => {}", e.name, import_funcs + index)?;
-                        },
-                        _ => {},
+                    if let ExportDesc::Func(index) = e.export_desc {
+                        func_names.insert(index, String::from(e.name));
+                        // writeln!(out, "{} => {}", e.name, import_funcs + index)?;
                     }
                 }
             }
@@ -410,25 +469,24 @@ pub fn dump_code<W: Write>(out: &mut W, m: &Module) -> Result<(), Error> {
                         local_count += 1;
                         local_index += n;
                     },
-                    FuncItem::Instr(Instr { opcode, immediate: imm, data}) => {
-                        let offset = m.offset_to(data);
+                    FuncItem::Instr(instr) => {
+                        let offset = m.offset_to(instr.data);
                         
-                        let op = if let Some(op) = Op::from_opcode(opcode) {
+                        let op = if let Some(op) = instr.op() {
                             op
                         } else {
-                            panic!("Unrecognized opcode: {}", opcode);
+                            panic!("Unrecognized opcode: {}", instr.opcode);
                         };
-                        match op.code {
-                            ELSE | END => {
-                                if depth > 0 {
-                                    depth -= 1;
-                                }
+                        let Instr { opcode, sub_opcode: _, immediate: imm, data } = instr;
+                        match opcode {
+                            ELSE | CATCH | CATCH_ALL | END if depth > 0 => {
+                                depth -= 1;
                             },
                             _ => {},
                         }
                         write!(out, " {:06x}:", offset)?;
                         let mut w = 0;
-                        if op.code == I64_CONST {
+                        if opcode == I64_CONST {
                             for b in data.iter().take(10) {
                             write!(out, " {:02x}", b)?;
                                 w += 3;
@@ -450,7 +508,7 @@ pub fn dump_code<W: Write>(out: &mut W, m: &Module) -> Result<(), Error> {
                         for _ in 0..depth { write!(out, "  ")?; }
                         match imm {
                             Immediate::None | Immediate::BranchTable { table: _ } => writeln!(out, "{}", op.text)?,
-                            Immediate::Block { signature } => if signature != ::parser::ValueType::Void {
+                            Immediate::Block { signature } => if signature != ::parser::BlockType::Value(::parser::ValueType::Void) {
                                 writeln!(out, "{} {}", op.text, signature)?
                             } else {
                                 writeln!(out, "{}", op.text)?
@@ -463,8 +521,8 @@ pub fn dump_code<W: Write>(out: &mut W, m: &Module) -> Result<(), Error> {
                             _ => writeln!(out, "{} {:?}", op.text, imm)?,
                         }
 
-                        match op.code {
-                            BLOCK | LOOP | IF | ELSE => {
+                        match opcode {
+                            BLOCK | LOOP | IF | ELSE | TRY | CATCH | CATCH_ALL => {
                                 depth += 1;
                             },
                             _ => {},
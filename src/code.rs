@@ -0,0 +1,172 @@
+use error::Error;
+use cursor::Cursor;
+#[cfg(feature="compiler")]
+use writer::Writer;
+
+use core::cell::Cell;
+use core::marker::PhantomData;
+use core::ops::Range;
+use core::slice;
+
+/// The compiled function bodies of a module, preceded by an index of their ranges.
+///
+/// Lazily compiled code has no index. Bodies are appended as they are compiled and their
+/// ranges are kept separately, so that code already running is never written to.
+pub struct CompiledCode<'a> {
+    // The code compiled so far, which only grows when compiling lazily
+    ptr: *const u8,
+    len: Cell<usize>,
+    lazy: Option<LazyCode<'a>>,
+    _buf: PhantomData<&'a [u8]>,
+}
+
+// Only created by the compiler
+#[cfg_attr(not(feature="compiler"), allow(dead_code))]
+struct LazyCode<'a> {
+    ranges: &'a [Cell<(u32, u32)>],
+    // The whole code buffer, of which the first `len` bytes have been compiled
+    ptr: *mut u8,
+    cap: usize,
+}
+
+impl<'a> CompiledCode<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        CompiledCode { ptr: buf.as_ptr(), len: Cell::new(buf.len()), lazy: None, _buf: PhantomData }
+    }
+
+    /// Creates empty code with room for `buf.len()` bytes of bodies, one for each of
+    /// `ranges`, which are compiled by `compile_lazily`.
+    #[cfg(feature="compiler")]
+    pub fn new_lazy(buf: &'a mut [u8], ranges: &'a mut [Cell<(u32, u32)>]) -> Self {
+        for range in ranges.iter_mut() {
+            *range = Cell::new((0, 0));
+        }
+        let cap = buf.len();
+        let ptr = buf.as_mut_ptr();
+        let lazy = Some(LazyCode { ranges, ptr, cap });
+        CompiledCode { ptr: ptr as *const u8, len: Cell::new(0), lazy, _buf: PhantomData }
+    }
+
+    pub fn is_lazy(&self) -> bool {
+        self.lazy.is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn body_count(&self) -> usize {
+        if let Some(ref lazy) = self.lazy {
+            return lazy.ranges.len()
+        }
+        let buf = self.as_ref();
+        // Type checking without compiling leaves no index at all
+        if buf.len() < 4 { return 0 }
+        Cursor::new(buf).read_u32() as usize
+    }
+
+    /// Returns where body `index` is in the code. Lazily compiled bodies that have not been
+    /// compiled yet have an empty range.
+    pub fn body_range(&self, index: usize) -> Result<Range<usize>, Error> {
+        info!("body_range({})", index);
+        if index >= self.body_count() {
+            return Err(Error::InvalidFunction { id: index as u32 })
+        }
+        if let Some(ref lazy) = self.lazy {
+            let (body_beg, body_end) = lazy.ranges[index].get();
+            return Ok(body_beg as usize .. body_end as usize)
+        }
+        let entry = index.checked_mul(8).and_then(|n| n.checked_add(4)).ok_or(Error::InvalidImage)?;
+        let mut cur = Cursor::new(self.as_ref().get(entry..).filter(|e| e.len() >= 8).ok_or(Error::InvalidImage)?);
+        let body_beg = cur.read_u32() as usize;
+        let body_end = cur.read_u32() as usize;
+        info!("{:08x} to {:08x}", body_beg, body_end);
+        Ok(body_beg .. body_end)
+    }
+
+    /// Checks that the index of code loaded from an image fits in the code and that every
+    /// body lies after it, so that bodies can be looked up and entered without further checks.
+    pub fn check_index(&self) -> Result<(), Error> {
+        let buf = self.as_ref();
+        if buf.len() < 4 {
+            return Err(Error::InvalidImage)
+        }
+        let count = self.body_count();
+        let index_end = count.checked_mul(8).and_then(|n| n.checked_add(4));
+        let index_end = match index_end {
+            Some(end) if end <= buf.len() => end,
+            _ => return Err(Error::InvalidImage),
+        };
+        for index in 0..count {
+            let range = self.body_range(index)?;
+            if range.start < index_end || range.start > range.end || range.end > buf.len() {
+                return Err(Error::InvalidImage)
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the range of body `index`, first appending it to lazily compiled code with
+    /// `compile` if it has not been compiled yet. `compile` writes the body at the writer's
+    /// position, and must not compile any other body of this code.
+    #[cfg(feature="compiler")]
+    pub(crate) fn compile_lazily<F>(&self, index: usize, compile: F) -> Result<Range<usize>, Error>
+        where F: FnOnce(&mut Writer) -> Result<(), Error>
+    {
+        let lazy = match self.lazy {
+            Some(ref lazy) => lazy,
+            None => return self.body_range(index),
+        };
+        let range = lazy.ranges.get(index).ok_or(Error::InvalidFunction { id: index as u32 })?;
+        let (body_beg, body_end) = range.get();
+        if body_beg != body_end {
+            return Ok(body_beg as usize .. body_end as usize)
+        }
+        info!("compiling body {}", index);
+        let len = self.len.get();
+        // Only the bytes after the compiled code are written, so bodies that are running
+        // are left alone.
+        let rest = unsafe { slice::from_raw_parts_mut(lazy.ptr.add(len), lazy.cap - len) };
+        let mut w = Writer::new_at(rest, len);
+        compile(&mut w)?;
+        let end = w.pos();
+        range.set((len as u32, end as u32));
+        self.len.set(end);
+        Ok(len .. end)
+    }
+
+    pub fn iter(&self) -> RangeIter<'_> {
+        RangeIter { code: self, count: self.body_count(), index: 0 }
+    }
+}
+
+pub struct RangeIter<'a> {
+    code: &'a CompiledCode<'a>,
+    count: usize,
+    index: usize,
+}
+
+impl<'a> Iterator for RangeIter<'a> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> { 
+        if self.index < self.count {
+            let index = self.index;
+            self.index += 1;
+
+            self.code.body_range(index).ok()
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> AsRef<[u8]> for CompiledCode<'a> {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.ptr, self.len.get()) }
+    }
+}
@@ -6,15 +6,22 @@ use parser::FallibleIterator;
 use parser::module::*;
 
 use module_inst::*;
-use typeck::{TypeChecker, LabelType};
-use cursor::Cursor;
+use table_inst::TableInst;
+use typeck::{self, TypeChecker, LabelType};
 use writer::Writer;
 use stack::Stack;
+use small_vec::SmallVec;
+use limits::ResourceLimits;
+use parser::{validate, Location};
+#[cfg(feature="simd")]
+use simd;
 
 use parser::types::FunctionType;
 
-use core::fmt;
-use core::ops::{Range, Index};
+pub use code::{CompiledCode, RangeIter};
+
+use core::cell::Cell;
+use core::{fmt, mem};
 
 pub const FIXUP_OFFSET: u32 = 0xffff_ffff;
 
@@ -22,244 +29,186 @@ pub const FIXUP_OFFSET: u32 = 0xffff_ffff;
 pub struct Fixup {
     depth: u32,
     offset: u32,
+    kind: FixupKind,
 }
 
 impl fmt::Debug for Fixup {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Fixup {{ depth: {}, offset: 0x{:08x} }}", self.depth, self.offset)
+        write!(f, "Fixup {{ depth: {}, offset: 0x{:08x}, kind: {:?} }}", self.depth, self.offset, self.kind)
     }
 }
 
+// What the offset of a fixup refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FixupKind {
+    // A branch offset in the code, which is relative in short branches
+    Branch,
+    // An absolute offset in the code
+    Offset,
+    // A branch target in the side table
+    Side,
+}
+
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub struct Label {
     offset: u32,
     fixup_offset: u32,
+    // The module offset branches to a loop go to, and the side table entry of an if or else
+    // or the one a try or catch clause links to the next clause with
+    source: u32,
+    side_fixup: u32,
 }
 
 impl fmt::Debug for Label {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Label {{ offset: 0x{:08x}, fixup_offset: 0x{:08x} }}", self.offset, self.fixup_offset)
+        write!(f, "Label {{ offset: 0x{:08x}, fixup_offset: 0x{:08x}, source: 0x{:08x} }}", self.offset, self.fixup_offset, self.source)
     }
 }
 
+// The number of parameters and declared locals of the function being compiled. Their types
+// are looked up through `Locals`, so the compiler does not limit how many there are.
+#[derive(Debug, Default)]
 pub struct Context {
-    parameters: [ValueType; 64],
     parameters_count: usize,
-    locals: [ValueType; 64],
     locals_count: usize,
-    return_type: ValueType,
-}
-
-impl Default for Context {
-    fn default() -> Self {
-        Context {
-            parameters: [VOID; 64],
-            parameters_count: 0,
-            locals: [VOID; 64],
-            locals_count: 0,
-            return_type: VOID,
-        }
-    }
 }
 
 impl Context {
-    fn reset(&mut self) {
-        *self = Context::default()
+    fn begin(&mut self, t: &FunctionType) {
+        self.parameters_count = t.parameters.len();
+        self.locals_count = 0;
     }
 
     fn len(&self) -> usize {
         self.parameters_count + self.locals_count
     }
 
-    fn locals(&self) -> &[ValueType] {
-        &self.locals[..self.locals_count]
-    }
-
-    fn parameters(&self) -> &[ValueType] {
-        &self.parameters[..self.parameters_count]
-    }
-
-    fn add_parameter(&mut self, p: ValueType) {
-        self.parameters[self.parameters_count] = p;
-        self.parameters_count += 1;
-    }
-
-    fn set_parameters(&mut self, parameters: &[ValueType]) {
-        for (i, p) in parameters.iter().enumerate() {
-            self.parameters[i] = ValueType::from(*p);
-        }
-        self.parameters_count = parameters.len();
-    }
-
-    fn add_local(&mut self, n: u32, t: ValueType) {
-        for _ in 0..n {
-            self.locals[self.locals_count] = t;
-            self.locals_count += 1;
-        }
-    }
-
-    fn set_return(&mut self, t: ValueType) {
-        self.return_type = t;
-    }
-
-    fn return_type(&self) -> ValueType {
-        self.return_type
-    }
-
-    fn keep(&self) -> u32 {
-        if self.return_type == VOID {
-            0
-        } else {
-            1
-        }
+    fn add_local(&mut self, n: u32) {
+        // The validator has checked that the total fits in a u32
+        self.locals_count = self.locals_count.saturating_add(n as usize);
     }
 }
 
-impl<'t> From<FunctionType<'t>> for Context {
-    fn from(other: FunctionType<'t>) -> Self {
-        let mut c = Context::default();
-        c.set_parameters(other.parameters);
-        if other.results.len() > 0 {
-            c.set_return(ValueType::from(other.results[0]));
-        }
-        c
-    }
+/// The index spaces that the instructions of a module refer to: its types, functions, globals,
+/// tables and tags.
+#[derive(Clone, Copy)]
+pub struct Spaces<'a, 'c: 'a> {
+    pub types: &'a [FunctionType<'c>],
+    pub functions: &'a [FuncInst<'a>],
+    pub globals: &'a [GlobalInst],
+    pub tables: &'a [TableInst<'a>],
+    pub tags: &'a [Tag],
 }
 
-impl Index<usize> for Context {
-    type Output = ValueType;
-
-    fn index(&self, i: usize) -> &Self::Output {
-        if i < self.parameters_count {
-            &self.parameters[i]
-        } else {
-            &self.locals[i - self.parameters_count]
-        }
-    }
+// The types of the parameters and declared locals of the function being compiled, read from
+// its type and from the local declarations at the start of its body.
+#[derive(Clone, Copy)]
+struct Locals<'a> {
+    parameters: &'a [ValueType],
+    body: &'a [u8],
 }
 
-impl fmt::Debug for Context {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Context {{ (")?;
-        for i in 0..self.parameters_count {
-            if i > 0 { write!(f, ", ")?; }
-            write!(f, "{}", self.parameters[i])?;
+impl<'a> Locals<'a> {
+    fn get(&self, index: u32) -> Result<ValueType, Error> {
+        if let Some(&t) = self.parameters.get(index as usize) {
+            return Ok(t)
         }
-        write!(f, ") -> ")?;
-        if self.return_type != VOID {
-            write!(f, "{}", self.return_type)?;
-        }        
-        if self.locals_count > 0 {
-            write!(f, "locals[")?;
-            for i in 0..self.locals_count {
-                if i > 0 { write!(f, ", ")?; }
-                write!(f, "{:?}", self.locals[i])?;
+        let mut rest = index - self.parameters.len() as u32;
+        let mut items = Func { buf: self.body }.iter();
+        while let Some(FuncItem::Local(Local { n, t })) = items.next()? {
+            if rest < n {
+                return Ok(t)
             }
-            write!(f, "]")?;
-            
-        }
-        write!(f, " }}")?;
-        Ok(())
-    }
-}
-
-pub struct CompiledCode<'a> {
-    buf: &'a [u8]
-}
-
-impl<'a> CompiledCode<'a> {
-    pub fn len(&self) -> usize {
-        self.buf.len()
-    }
-
-    pub fn body_count(&self) -> usize {
-        Cursor::new(self.buf).read_u32() as usize
-    }
-
-    pub fn body_range(&self, index: usize) -> Range<usize> {
-        info!("body_range({})", index);
-        let mut cur = Cursor::new(self.buf);
-        assert!(index < cur.read_u32() as usize);
-        cur.advance(index * 8);
-        let body_beg = cur.read_u32() as usize;
-        let body_end = cur.read_u32() as usize;
-        info!("{:08x} to {:08x}", body_beg, body_end);
-        body_beg .. body_end
-    }
-
-    pub fn iter(&self) -> RangeIter {
-        RangeIter { code: self, count: self.body_count(), index: 0 }
-    }
-}
-
-pub struct RangeIter<'a> {
-    code: &'a CompiledCode<'a>,
-    count: usize,
-    index: usize,
-}
-
-impl<'a> Iterator for RangeIter<'a> {
-    type Item = Range<usize>;
-
-    fn next(&mut self) -> Option<Self::Item> { 
-        if self.index < self.count {
-            let index = self.index;
-            self.index += 1;
-
-            Some(self.code.body_range(index))
-        } else {
-            None
+            rest -= n;
         }
-    }
-}
-
-impl<'a> AsRef<[u8]> for CompiledCode<'a> {
-    fn as_ref(&self) -> &[u8] {
-        self.buf
+        Err(Error::InvalidLocal { id: index })
     }
 }
 
 pub struct Config {
-    pub compile: bool
+    /// Generate code. When false the compiler only type checks function bodies.
+    pub compile: bool,
+    /// Sizes the label, type and fixup stacks.
+    pub limits: ResourceLimits,
 }
 
 impl Default for Config {
     fn default() -> Config {
         let compile = true;
+        let limits = ResourceLimits::default();
 
-        Config { compile }
+        Config { compile, limits }
     }
 }
 
+/// A function body that failed to type check.
+#[derive(Debug, PartialEq)]
+pub struct CheckError {
+    /// The index of the function, counting imported functions.
+    pub function: u32,
+    /// The module offset of the instruction that failed, or of the body if it failed before
+    /// its first instruction.
+    pub offset: u32,
+    pub error: Error,
+}
+
 pub struct Compiler<'c> {
     cfg: Config,
     label_stack: Stack<'c, Label>,
     type_checker: TypeChecker<'c>,
-    fixups: [Option<Fixup>; 256],
+    fixups: &'c mut [Option<Fixup>],
     fixups_pos: usize,
     section_fixup: usize,
     body_fixup: usize,
     context: Context,
+    data_count: Option<u32>,
+    elem_count: u32,
+    // The number of imported functions, which come before the local functions
+    imported: u32,
+    location: Location,
+    in_place: bool,
+    // Whether branches in the current body are compiled to their full width forms
+    wide: bool,
 }
 
 impl<'c> Compiler<'c> {
-    pub fn new(buf: &'c mut [u8]) -> Self {
-        Compiler::new_with_config(buf, Config::default() )
+    /// Creates a compiler with its label and type stacks allocated from `buf`. Fails with
+    /// `Error::OutOfMemory` if `buf` is smaller than `Compiler::buffer_size` for the limits.
+    pub fn new(buf: &'c mut [u8]) -> Result<Self, Error> {
+        Compiler::new_with_config(buf, Config::default())
     }
-    pub fn new_with_config(buf: &'c mut [u8], cfg: Config, ) -> Self {
+
+    pub fn new_with_config(buf: &'c mut [u8], cfg: Config) -> Result<Self, Error> {
+        let limits = cfg.limits;
         let mut w = Writer::new(buf);
 
-        let label_stack = w.alloc_stack(16);        
-        let type_checker = TypeChecker::new(w.alloc_stack(16), w.alloc_stack(16));
+        w.align_to::<Label>()?;
+        w.split::<()>();
+        let label_stack = w.alloc_stack(limits.labels)?;
+        w.align_to::<typeck::Label>()?;
+        w.split::<()>();
+        let type_labels = w.alloc_stack(limits.labels)?;
+        let type_checker = TypeChecker::new(type_labels, w.alloc_stack(limits.operands)?);
 
         // TODO: Break out into separate struct
-        let fixups = [None; 256];
+        w.align_to::<Option<Fixup>>()?;
+        w.split::<()>();
+        let fixups = w.alloc_slice(limits.fixups)?;
+        for fixup in fixups.iter_mut() {
+            *fixup = None;
+        }
         let fixups_pos = 0;
 
         let section_fixup = 0;
         let body_fixup = 0;
         let context = Context::default();
-        Compiler { 
+        let data_count = None;
+        let elem_count = 0;
+        let imported = 0;
+        let location = Location::default();
+        let in_place = false;
+        let wide = false;
+        Ok(Compiler {
             cfg,
             label_stack,
             type_checker,
@@ -268,7 +217,30 @@ impl<'c> Compiler<'c> {
             section_fixup, 
             body_fixup,
             context,
+            data_count,
+            elem_count,
+            imported,
+            location,
+            in_place,
+            wide,
+        })
+    }
+
+    /// The size of the buffer `new_with_config` needs for `limits`.
+    pub fn buffer_size(limits: &ResourceLimits) -> usize {
+        fn size<T>(len: usize) -> usize {
+            // Each allocation may need padding to align it
+            len * mem::size_of::<T>() + mem::align_of::<T>() - 1
         }
+        size::<Label>(limits.labels) +
+        size::<typeck::Label>(limits.labels) +
+        size::<ValueType>(limits.operands) +
+        size::<Option<Fixup>>(limits.fixups)
+    }
+
+    /// Where the last failure returned by `compile` or reported by `check` was found.
+    pub fn location(&self) -> Location {
+        self.location
     }
 
     fn push_label(&mut self, offset: u32) -> Result<(), Error> {
@@ -279,6 +251,8 @@ impl<'c> Compiler<'c> {
         let label = Label {
             offset,
             fixup_offset,
+            source: FIXUP_OFFSET,
+            side_fixup: 0,
         };
         // info!("-- label: {} <= {:?}", self.label_stack.len(), label);
         Ok(self.label_stack.push(label)?)
@@ -308,8 +282,22 @@ impl<'c> Compiler<'c> {
     }
 
     fn add_fixup(&mut self, rel_depth: u32, offset: u32) -> Result<(), Error> {
+        self.push_fixup(rel_depth, offset, FixupKind::Branch)
+    }
+
+    fn add_offset_fixup(&mut self, rel_depth: u32, offset: u32) -> Result<(), Error> {
+        self.push_fixup(rel_depth, offset, FixupKind::Offset)
+    }
+
+    fn add_side_fixup(&mut self, rel_depth: u32, offset: u32) -> Result<(), Error> {
+        self.push_fixup(rel_depth, offset, FixupKind::Side)
+    }
+
+    fn push_fixup(&mut self, rel_depth: u32, offset: u32, kind: FixupKind) -> Result<(), Error> {
+        // Building a side table discards the code, so only the side table needs fixing up
+        if self.in_place && kind != FixupKind::Side { return Ok(()) }
         let depth = self.label_depth() - rel_depth;
-        let fixup = Fixup { depth: depth, offset: offset };
+        let fixup = Fixup { depth, offset, kind };
         info!("add_fixup: {:?}", fixup);
         for entry in self.fixups.iter_mut() {
             if entry.is_none() {
@@ -320,16 +308,24 @@ impl<'c> Compiler<'c> {
         Err(Error::FixupsFull)
     }
 
-    fn fixup(&mut self, w: &mut Writer) -> Result<(), Error> {
+    // Resolves the branches to the top label. In the side table, they go to `source` unless
+    // the label is a loop.
+    fn fixup(&mut self, w: &mut Writer, side: &mut Writer, source: u32) -> Result<(), Error> {
         let depth = self.label_depth();        
-        let offset = self.peek_label(0)?.offset;
-        let offset = if offset == FIXUP_OFFSET { w.pos() } else { offset as usize};
+        let label = self.peek_label(0)?;
+        let offset = if label.offset == FIXUP_OFFSET { w.pos() } else { label.offset as usize};
+        let source = if label.source == FIXUP_OFFSET { source } else { label.source };
         info!("fixup: {} -> 0x{:08x}", depth, offset);
+        let wide = self.wide;
         for entry in self.fixups.iter_mut() {
             let del = if let &mut Some(entry) = entry {
                 if entry.depth == depth {
                     info!(" {:?}", entry);
-                    w.write_u32_at(offset as u32, entry.offset as usize)?;
+                    match entry.kind {
+                        FixupKind::Branch => patch_branch(w, wide, entry.offset as usize, offset as u32)?,
+                        FixupKind::Offset => w.write_u32_at(offset as u32, entry.offset as usize)?,
+                        FixupKind::Side => side.write_u32_at(source, entry.offset as usize)?,
+                    }
                     true
                 } else {
                     info!(" ! {} 0x{:04x}", entry.depth, entry.offset);                    
@@ -346,25 +342,14 @@ impl<'c> Compiler<'c> {
         Ok(())
     }
 
-    fn translate_local_index(&self, local_index: u32) -> Result<u32, Error> {
-        info!("translate_local_index: {} {} {}", self.type_checker.type_stack_size(), self.context.len(), local_index);
-        let depth = (self.type_checker.type_stack_size() as u32 + self.context.len() as u32) - 1 - local_index;
-        info!("  => {}", depth);
-        Ok(depth)
-    }
-
     fn get_br_drop_keep_count(&mut self, depth: usize) -> Result<(u32, u32), Error> {        
         Ok({
             let label = self.type_checker.get_label(depth)?;
-            let keep = if label.label_type != LabelType::Loop {
-                if label.signature != VOID { 1 } else { 0 }
-            } else { 
-                0 
-            };
+            let keep = label.br_types().len();
             let drop = if self.type_checker.is_unreachable()? {
                 0
             } else {
-                self.type_checker.type_stack_size() - label.stack_limit - keep
+                stack_drop(self.type_checker.type_stack_size() - label.stack_limit, keep)?
             };
             info!("get_br_drop_keep_count() -> ({}, {})", drop, keep);
             (drop as u32, keep as u32)
@@ -382,31 +367,123 @@ impl<'c> Compiler<'c> {
         })
     }
 
-    fn write_br_offset(&mut self, w: &mut Writer, depth: u32, offset: u32) -> Result<(), Error> {
-        info!("write_br_offset({}, {:08x}) @ {:08x}", depth, offset, w.pos());
+    // A tail call keeps only the callee's operands and drops the rest of the current frame,
+    // including its parameters and locals.
+    fn get_return_call_drop_keep_count(&mut self, keep: u32) -> Result<(u32, u32), Error> {
+        info!("get_return_call_drop_keep_count({})", keep);
         Ok({
-            if offset == FIXUP_OFFSET {
-                let pos = w.pos();
-                self.add_fixup(depth, pos as u32)?;
-            }
-            w.write_u32(offset)?;
+            let label = self.type_checker.func_label()?;
+            let drop = if self.type_checker.is_unreachable()? {
+                0
+            } else {
+                stack_drop(self.type_checker.type_stack_size() - label.stack_limit, keep as usize)? as u32
+            };
+            let drop = drop + (self.context.len() as u32);
+            info!("  -> ({}, {})", drop, keep);
+            (drop, keep)
         })
     }
 
-    fn write_br_table_offset(&mut self, w: &mut Writer, depth: u32) -> Result<(), Error> {
+    // Ends the current part of a try block and starts a catch clause at the current position.
+    // The clause header is completed by the caller for `catch`.
+    fn write_catch_header(&mut self, w: &mut Writer, side: &mut Writer, opc: u8) -> Result<(), Error> {
+        if self.in_place {
+            // The clause branches to the end of the try when the previous part falls into
+            // it, and links to the next clause, which the previous link now points here
+            self.write_side_br(side, 0, 0, 0)?;
+            let label = self.top_label()?;
+            side.write_u32_at(self.location.offset, label.side_fixup as usize)?;
+            let pos = self.write_side_entry(side, FIXUP_OFFSET, 0, 0)?;
+            self.top_label_ref()?.side_fixup = pos as u32;
+        }
+        w.write_opcode(HANDLER_POP)?;
+        let pos = self.write_branch(w, BR)?;
+        self.add_fixup(0, pos as u32)?;
+
+        let header = w.pos();
+        let fixup_offset = self.top_label()?.fixup_offset;
+        w.write_u32_at(header as u32, fixup_offset as usize)?;
+        w.write_opcode(opc)?;
+        if opc == CATCH_ALL {
+            let next_pos = w.pos();
+            w.write_u32(FIXUP_OFFSET)?;
+            self.top_label_ref()?.fixup_offset = next_pos as u32;
+        }
+        Ok(())
+    }
+
+    // Writes branch opcode `opc`, or its short form unless the body is compiled wide, with
+    // an offset to be patched. Returns where the offset is.
+    fn write_branch(&mut self, w: &mut Writer, opc: u8) -> Result<usize, Error> {
+        if self.wide {
+            w.write_opcode(opc)?;
+        } else {
+            w.write_opcode(short_form(opc))?;
+        }
+        let pos = w.pos();
+        self.write_offset(w)?;
+        Ok(pos)
+    }
+
+    fn write_offset(&mut self, w: &mut Writer) -> Result<(), Error> {
+        if self.wide {
+            w.write_u32(FIXUP_OFFSET)
+        } else {
+            w.write_u16(FIXUP_OFFSET as u16)
+        }
+    }
+
+    fn write_br_offset(&mut self, w: &mut Writer, depth: u32, offset: u32) -> Result<(), Error> {
+        info!("write_br_offset({}, {:08x}) @ {:08x}", depth, offset, w.pos());
+        let pos = w.pos();
+        self.write_offset(w)?;
+        if offset == FIXUP_OFFSET {
+            self.add_fixup(depth, pos as u32)?;
+        } else {
+            patch_branch(w, self.wide, pos, offset)?;
+        }
+        Ok(())
+    }
+
+    fn write_br_table_offset(&mut self, w: &mut Writer, side: &mut Writer, depth: u32) -> Result<(), Error> {
         info!("write_br_table_offset({}) @ {:08x}", depth, w.pos());
-        Ok({
-            let (drop, keep) = self.get_br_drop_keep_count(depth as usize)?;
-            let label = self.peek_label(depth as usize)?;
-            self.write_br_offset(w, depth, label.offset)?;
+        let (drop, keep) = self.get_br_drop_keep_count(depth as usize)?;
+        let label = self.peek_label(depth as usize)?;
+        self.write_br_offset(w, depth, label.offset)?;
+        if self.wide {
             w.write_u32(drop)?;
             w.write_u32(keep)?;
+        } else if drop <= 0xff && keep <= 0xff {
+            w.write_u8(drop as u8)?;
+            w.write_u8(keep as u8)?;
+        } else {
+            return Err(Error::BranchOutOfRange)
+        }
+        self.write_side_br(side, depth, drop, keep)?;
 
 //   CHECK_RESULT(GetBrDropKeepCount(depth, &drop_count, &keep_count));
 //   CHECK_RESULT(EmitBrOffset(depth, GetLabel(depth)->offset));
 //   CHECK_RESULT(EmitI32(drop_count));
 //   CHECK_RESULT(EmitI8(keep_count));            
-        })
+        Ok(())
+    }
+
+    // Adds a side table entry for the instruction being compiled when building a side table,
+    // returning where its target is.
+    fn write_side_entry(&mut self, side: &mut Writer, target: u32, drop: u32, keep: u32) -> Result<usize, Error> {
+        if !self.in_place { return Ok(0) }
+        side.write_u32(self.location.offset)?;
+        let pos = side.pos();
+        side.write_u32(target)?;
+        side.write_u32(drop)?;
+        side.write_u32(keep)?;
+        Ok(pos)
+    }
+
+    fn write_side_br(&mut self, side: &mut Writer, depth: u32, drop: u32, keep: u32) -> Result<(), Error> {
+        if !self.in_place { return Ok(()) }
+        let pos = self.write_side_entry(side, FIXUP_OFFSET, drop, keep)?;
+        self.add_side_fixup(depth, pos as u32)
     }
 
     fn write_fixup_u32(&mut self, w: &mut Writer) -> Result<usize, Error> {
@@ -420,31 +497,64 @@ impl<'c> Compiler<'c> {
         w.write_u32_at(len as u32, fixup)?;
         Ok(len as u32)
     }
+
+    // memory.init and data.drop may only refer to segments declared in the data count section
+    fn check_data_segment(&self, index: u32) -> Result<(), Error> {
+        match self.data_count {
+            Some(count) if index < count => Ok(()),
+            Some(_) => Err(Error::InvalidDataSegment { id: index }),
+            None => Err(Error::MissingSection { id: Id::DataCount }),
+        }
+    }
+
+    fn check_element_segment(&self, index: u32) -> Result<(), Error> {
+        if index < self.elem_count {
+            Ok(())
+        } else {
+            Err(Error::InvalidElementSegment { id: index })
+        }
+    }
 }
 
 impl<'c> Compiler<'c> {
     pub fn compile<'buf>(&mut self, code_buf: &'buf mut [u8], 
-        types: &[FunctionType],
-        functions: &[FuncInst], 
-        globals: &[GlobalInst],        
+        spaces: &Spaces<'_, 'c>,
         m: &Module        
     ) -> Result<(&'buf mut [u8], CompiledCode<'buf>), Error> {
+        if !self.cfg.compile {
+            // Type check only, failing on the first error and producing no code.
+            let mut first = None;
+            self.check(spaces, m, &mut |e: CheckError| {
+                if first.is_none() { first = Some(e.error) }
+            })?;
+            if let Some(error) = first {
+                return Err(error)
+            }
+            let (buf, rest) = code_buf.split_at_mut(0);
+            return Ok((rest, CompiledCode::new(buf)))
+        }
+
         let mut w = Writer::new(code_buf);
+        self.compile_to(&mut w, spaces, m)?;
+        let buf = w.split_mut();
+        let rest = w.into_slice();
+
+        Ok((rest, CompiledCode::new(buf)))
+    }
+
+    /// Writes the body index and the compiled bodies to `w`.
+    fn compile_to(&mut self, w: &mut Writer,
+        spaces: &Spaces<'_, 'c>,
+        m: &Module
+    ) -> Result<(), Error> {
+        let count = self.prepare(spaces.functions, m)?;
 
         // Write Index
-        w.write_u32(0)?;
-        let mut n = 0;
-        let mut sections = m.sections();
-        while let Some(section) = sections.next()? {
-            if section.id() != Id::Code { continue }
-            let mut code = section.code();
-            while let Some(_) = code.next()? {
-                w.write_u32(0)?; // Offset
-                w.write_u32(0)?; // Size                
-                n += 1;
-            }
+        w.write_u32(count)?;
+        for _ in 0..count {
+            w.write_u32(0)?; // Offset
+            w.write_u32(0)?; // Size
         }
-        w.write_u32_at(n, 0)?;
         info!("{:08x}: Code Start", w.pos());
 
         let mut n: usize = 0;
@@ -453,59 +563,8 @@ impl<'c> Compiler<'c> {
             if section.id() != Id::Code { continue }
             let mut code = section.code();
             while let Some(code) = code.next()? {
-                let mut first = true;
                 let body_beg = w.pos();
-                let code_beg = m.offset_to(code.func.buf);
-                let code_len = code.func.buf.len();
-                let code_end = code_beg + code_len;
-                let type_index = m.function_signature(n as u32)?.unwrap();
-
-                self.context = Context::from(m.signature_type(type_index)?.unwrap());
-                info!("CONTEXT: {:?}", self.context);
-                info!("code_beg:  {:08x}", code_beg);
-                info!("code_end:  {:08x}", code_end);
-                info!("code_len:  {:08x}", code_len);
-
-                let mut items = code.func.iter();
-                while let Some(item) = items.next()? {
-                    match item {
-                        FuncItem::Local(Local { n, t }) => {
-                            self.context.add_local(n, t);                            
-                        },
-                        FuncItem::Instr(instr) => {
-                            if first {
-                                info!("{:08x}: V:{} | func[{}] {:?}", w.pos(), self.type_checker.type_stack_size(), n, self.context);  
-                                self.type_checker.begin_function(self.context.return_type)?;
-                                self.push_label(FIXUP_OFFSET)?;
-                                w.write_alloca(self.context.locals_count as u32)?;
-                                first = false;
-                            }
-                            let instr_beg = m.offset_to(instr.data);
-                            let instr_len = instr.data.len();
-                            let instr_end = instr_beg + instr_len;
-                            info!("instr_end: {:08x}", instr_end);
-                            if instr_end == code_end && instr.opcode == END {
-                                info!("SKIPPING TERMINAL END");
-                            } else {
-                                self.compile_instruction(&mut w, types, functions, globals, instr)?;
-                            }
-                        }
-                    }
-                }
-                info!("{:08x}: L: {} V:{} | {} ", w.pos(), self.label_stack.len(), self.type_checker.type_stack_size(), "EXIT");
-
-                //   CHECK_RESULT(GetReturnDropKeepCount(&drop_count, &keep_count));
-                //   CHECK_RESULT(typechecker_.EndFunction());
-                //   CHECK_RESULT(EmitDropKeep(drop_count, keep_count));
-                //   CHECK_RESULT(EmitOpcode(Opcode::Return));
-                //   PopLabel();
-
-                self.fixup(&mut w)?;
-                let (drop, keep) = self.get_return_drop_keep_count()?;
-                self.type_checker.end_function()?;
-                w.write_drop_keep(drop, keep)?;                                    
-                w.write_opcode(RETURN)?;
-                self.pop_label()?;
+                self.compile_body(w, &mut Writer::sink(), spaces, m, n as u32, &code)?;
                 let body_end = w.pos();
                 info!("body beg: {:08x}", body_beg);
                 info!("body end: {:08x}", body_end);
@@ -516,26 +575,286 @@ impl<'c> Compiler<'c> {
             }
         }
         info!("All Code Done");        
-        let buf = w.split_mut();
-        let rest = w.into_slice();
+        Ok(())
+    }
+
+    /// Compiles the body of local function `n` alone, writing it at the writer's position.
+    /// Used to compile a module one function at a time as each is first called.
+    pub fn compile_function(&mut self, w: &mut Writer,
+        spaces: &Spaces<'_, 'c>,
+        m: &Module,
+        n: u32,
+    ) -> Result<(), Error> {
+        self.prepare(spaces.functions, m)?;
+
+        let mut i = 0;
+        let mut sections = m.sections();
+        while let Some(section) = sections.next()? {
+            if section.id() != Id::Code { continue }
+            let mut code = section.code();
+            while let Some(code) = code.next()? {
+                if i == n {
+                    return self.compile_body(w, &mut Writer::sink(), spaces, m, n, &code)
+                }
+                i += 1;
+            }
+        }
+        Err(Error::InvalidFunction { id: n })
+    }
 
-        Ok((rest, CompiledCode { buf: buf }))
+    /// Type checks every function body like `compile`, but instead of compiling the module
+    /// builds the side table that `side_table::SideTable` executes it in place with. Returns
+    /// the rest of `buf` and the side table.
+    pub fn side_table<'buf>(&mut self, buf: &'buf mut [u8],
+        spaces: &Spaces<'_, 'c>,
+        m: &Module
+    ) -> Result<(&'buf mut [u8], &'buf [u8]), Error> {
+        let mut side = Writer::new(buf);
+        self.in_place = true;
+        let result = self.side_table_to(&mut side, spaces, m);
+        self.in_place = false;
+        result?;
+        let table = side.split_mut::<u8>();
+        let rest = side.into_slice();
+        Ok((rest, table))
+    }
+
+    /// Writes the body index and the branches of every body to `side`.
+    fn side_table_to(&mut self, side: &mut Writer,
+        spaces: &Spaces<'_, 'c>,
+        m: &Module
+    ) -> Result<(), Error> {
+        let count = self.prepare(spaces.functions, m)?;
+
+        // Write Index
+        side.write_u32(count)?;
+        for _ in 0..count {
+            side.write_u32(0)?; // Start
+            side.write_u32(0)?; // End
+            side.write_u32(0)?; // Parameters
+            side.write_u32(0)?; // Locals
+        }
+
+        let mut n: usize = 0;
+        let mut sections = m.sections();
+        while let Some(section) = sections.next()? {
+            if section.id() != Id::Code { continue }
+            let mut code = section.code();
+            while let Some(code) = code.next()? {
+                self.compile_body(&mut Writer::sink(), side, spaces, m, n as u32, &code)?;
+                let body_end = m.offset_to(code.func.buf) + code.func.buf.len();
+                let mut body_start = body_end;
+                let mut items = code.func.iter();
+                while let Some(item) = items.next()? {
+                    if let FuncItem::Instr(instr) = item {
+                        body_start = m.offset_to(instr.data);
+                        break
+                    }
+                }
+                let index = 4 + n * 16;
+                side.write_u32_at(body_start as u32, index)?;
+                side.write_u32_at(body_end as u32, index + 4)?;
+                side.write_u32_at(self.context.parameters_count as u32, index + 8)?;
+                side.write_u32_at(self.context.locals_count as u32, index + 12)?;
+                n += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Type checks every function body without generating code, calling `report` for each
+    /// body that fails and carrying on with the next one. Returns the number of failed bodies.
+    pub fn check(&mut self,
+        spaces: &Spaces<'_, 'c>,
+        m: &Module,
+        report: &mut dyn FnMut(CheckError),
+    ) -> Result<u32, Error> {
+        let mut w = Writer::sink();
+        self.prepare(spaces.functions, m)?;
+
+        let mut n: usize = 0;
+        let mut failed = 0;
+        let mut sections = m.sections();
+        while let Some(section) = sections.next()? {
+            if section.id() != Id::Code { continue }
+            let mut code = section.code();
+            while let Some(code) = code.next()? {
+                let function = self.imported + n as u32;
+                if let Err(error) = self.compile_body(&mut w, &mut Writer::sink(), spaces, m, n as u32, &code) {
+                    let offset = self.location.offset;
+                    info!("func[{}] failed at {:08x}: {:?}", function, offset, error);
+                    report(CheckError { function, offset, error });
+                    failed += 1;
+                }
+                n += 1;
+            }
+        }
+        Ok(failed)
+    }
+
+    /// Counts the segments that instructions refer to and returns the number of function
+    /// bodies, which must match the number of local functions.
+    fn prepare(&mut self, functions: &[FuncInst], m: &Module) -> Result<u32, Error> {
+        self.data_count = None;
+        self.elem_count = 0;
+        self.imported = imported_functions(functions) as u32;
+        let mut count = 0;
+        let mut sections = m.sections();
+        while let Some(section) = sections.next()? {
+            match section.id() {
+                Id::DataCount => self.data_count = Some(section.data_count()?),
+                Id::Element => self.elem_count = section.elements().count()? as u32,
+                Id::Code => count += section.code().count()? as u32,
+                _ => {},
+            }
+        }
+        // Every local function needs a body, otherwise calling it would find nothing to run
+        let locals = functions.len() - self.imported as usize;
+        if count as usize != locals {
+            return Err(Error::UnexpectedData { wanted: locals as u32, got: count })
+        }
+        Ok(count)
+    }
+
+    /// Compiles the body of local function `n`, adding its branches to `side` when building a side table. The location is kept at the
+    /// instruction being compiled so that a failure can be found.
+    ///
+    /// Branches are compiled to their short forms first. Whether those reach is only known
+    /// once the body has been compiled, so if one does not the body is compiled again with
+    /// full width branches.
+    fn compile_body<'w>(&mut self, w: &mut Writer<'w>, side: &mut Writer,
+        spaces: &Spaces<'_, 'c>,
+        m: &Module,
+        n: u32,
+        code: &Code,
+    ) -> Result<(), Error> {
+        let body_beg = w.pos();
+        // The side table has full width entries anyway
+        self.wide = self.in_place;
+        match self.compile_body_once(w, side, spaces, m, n, code) {
+            Err(Error::BranchOutOfRange) if !self.wide => {
+                info!("recompiling func[{}] with wide branches", self.imported + n);
+                w.rewind(body_beg)?;
+                self.wide = true;
+                self.compile_body_once(w, side, spaces, m, n, code)
+            },
+            result => result,
+        }
+    }
+
+    fn compile_body_once<'w>(&mut self, w: &mut Writer<'w>, side: &mut Writer,
+        spaces: &Spaces<'_, 'c>,
+        m: &Module,
+        n: u32,
+        code: &Code,
+    ) -> Result<(), Error> {
+        // The index of the function once imports are counted
+        let function = self.imported + n;
+        let mut first = true;
+        let code_beg = m.offset_to(code.func.buf);
+        let code_len = code.func.buf.len();
+        let code_end = code_beg + code_len;
+        self.location = Location { offset: code_beg as u32, section: Some(Id::Code), function: Some(function), instruction: None };
+        let type_index = m.function_signature(n)?.ok_or(Error::InvalidFunction { id: n })?;
+
+        #[cfg(feature="integer-only")]
+        {
+            let t = spaces.types.get(type_index as usize).ok_or(Error::InvalidSignature { id: type_index })?;
+            if has_float(t.parameters) || has_float(t.results) {
+                return Err(Error::FloatUnsupported { function, offset: code_beg as u32 })
+            }
+        }
+
+        let signature = m.signature_type(type_index)?.ok_or(Error::InvalidSignature { id: type_index })?;
+        self.context.begin(&signature);
+        let locals = Locals { parameters: signature.parameters, body: code.func.buf };
+        info!("CONTEXT: {:?}", self.context);
+        info!("code_beg:  {:08x}", code_beg);
+        info!("code_end:  {:08x}", code_end);
+        info!("code_len:  {:08x}", code_len);
+
+        let mut items = code.func.iter();
+        while let Some(item) = items.next()? {
+            match item {
+                FuncItem::Local(local) => {
+                    #[cfg(feature="integer-only")]
+                    {
+                        if has_float(&[local.t]) {
+                            return Err(Error::FloatUnsupported { function, offset: code_beg as u32 })
+                        }
+                    }
+                    self.context.add_local(local.n);
+                },
+                FuncItem::Instr(instr) => {
+                    let instr_beg = m.offset_to(instr.data);
+                    self.location.offset = instr_beg as u32;
+                    self.location.instruction = Some((instr_beg - code_beg) as u32);
+                    if first {
+                        info!("{:08x}: V:{} | func[{}] {:?}", w.pos(), self.type_checker.type_stack_size(), function, self.context);  
+                        let results = spaces.types.get(type_index as usize).ok_or(Error::InvalidSignature { id: type_index })?.results;
+                        self.type_checker.begin_function(results)?;
+                        // Discard anything left behind by a body that failed to check
+                        self.label_stack.reset()?;
+                        for fixup in self.fixups.iter_mut() {
+                            *fixup = None;
+                        }
+                        self.push_label(FIXUP_OFFSET)?;
+                        w.write_alloca(self.context.parameters_count as u32, self.context.locals_count as u32)?;
+                        first = false;
+                    }
+                    #[cfg(feature="integer-only")]
+                    {
+                        if uses_float(spaces, &instr)? {
+                            return Err(Error::FloatUnsupported { function, offset: instr_beg as u32 })
+                        }
+                    }
+                    let instr_len = instr.data.len();
+                    let instr_end = instr_beg + instr_len;
+                    info!("instr_end: {:08x}", instr_end);
+                    if instr_end == code_end && instr.opcode == END {
+                        info!("SKIPPING TERMINAL END");
+                    } else {
+                        self.compile_instruction(w, side, spaces, locals, instr)?;
+                    }
+                }
+            }
+        }
+        info!("{:08x}: L: {} V:{} | {} ", w.pos(), self.label_stack.len(), self.type_checker.type_stack_size(), "EXIT");
+
+        //   CHECK_RESULT(GetReturnDropKeepCount(&drop_count, &keep_count));
+        //   CHECK_RESULT(typechecker_.EndFunction());
+        //   CHECK_RESULT(EmitDropKeep(drop_count, keep_count));
+        //   CHECK_RESULT(EmitOpcode(Opcode::Return));
+        //   PopLabel();
+
+        // Branches to the function's label return through the final END, which is where the
+        // location is now
+        let terminal = self.location.offset;
+        self.fixup(w, side, terminal)?;
+        let (drop, keep) = self.get_return_drop_keep_count()?;
+        self.type_checker.end_function()?;
+        w.write_drop_keep(drop, keep)?;                                    
+        w.write_opcode(RETURN)?;
+        self.write_side_entry(side, 0, drop, keep)?;
+        self.pop_label()?;
+        Ok(())
     }
 
     fn compile_instruction<'w>(
         &mut self, 
         w: &mut Writer<'w>, 
-        types: &[FunctionType],
-        functions: &[FuncInst], 
-        globals: &[GlobalInst],        
+        side: &mut Writer,
+        spaces: &Spaces<'_, 'c>,
+        locals: Locals,
         i: Instr
     ) -> Result<(), Error> {
         use self::Immediate::*;
+        let Spaces { types, functions, globals, tables, tags } = *spaces;
 
-        let op = if let Some(op) = Op::from_opcode(i.opcode) {
+        let op = if let Some(op) = i.op() {
             let mut indent = self.label_stack.len();
-            if op.code == END || op.code == ELSE {
-                indent -= 1;
+            if i.opcode == END || i.opcode == ELSE {
+                indent = indent.saturating_sub(1);
             }
             info!("{:08x}: L: {} V:{} | {:0width$}{} {:?}" , w.pos(), self.label_stack.len(), self.type_checker.type_stack_size(),  "", op.text, i.immediate, width=indent);
             op
@@ -544,6 +863,8 @@ impl<'c> Compiler<'c> {
         };
 
         let opc = i.opcode;
+        // The module offset of the next instruction
+        let next = self.location.offset + i.data.len() as u32;
         match i.immediate {
             None => match opc {
                 SELECT => {
@@ -553,6 +874,10 @@ impl<'c> Compiler<'c> {
                 DROP => {
                     self.type_checker.on_drop()?;
                     w.write_opcode(opc)?;
+                },
+                REF_IS_NULL => {
+                    self.type_checker.on_ref_is_null()?;
+                    w.write_opcode(opc)?;
                 },                
                 END => if false {
                     info!("Skipping implicit END");
@@ -562,14 +887,28 @@ impl<'c> Compiler<'c> {
                     let ty_label = self.type_checker.get_label(0)?;
                     let label_type = ty_label.label_type;
                     self.type_checker.on_end()?;
-                    if label_type == LabelType::If || label_type == LabelType::Else {
+                    if label_type == LabelType::Try || label_type == LabelType::Catch {
+                        // Only reached by falling off the end of the last part; branches
+                        // from earlier parts have already popped their handler.
+                        w.write_opcode(HANDLER_POP)?;
+                    }
+                    if label_type == LabelType::If || label_type == LabelType::Else ||
+                        label_type == LabelType::Try || label_type == LabelType::Catch {
                         let label = self.top_label()?;
                         let pos = w.pos();
                         info!("fixup_offset: {:08x} at {:08x}", pos, label.fixup_offset);
-                        w.write_u32_at(pos as u32, label.fixup_offset as usize)?;                        
+                        if label_type == LabelType::If || label_type == LabelType::Else {
+                            patch_branch(w, self.wide, label.fixup_offset as usize, pos as u32)?;
+                        } else {
+                            // The end of the last catch clause's header
+                            w.write_u32_at(pos as u32, label.fixup_offset as usize)?;
+                        }
+                        if self.in_place {
+                            side.write_u32_at(next, label.side_fixup as usize)?;
+                        }
                     }
                     info!("FIXUP");
-                    self.fixup(w)?;
+                    self.fixup(w, side, next)?;
                     info!("POP_LABEL");
                     self.pop_label()?;
                     info!("end done");
@@ -599,20 +938,24 @@ impl<'c> Compiler<'c> {
                     // Get offset of BR_UNLESS OFFSET
                     let fixup_cond_offset = label.fixup_offset;
 
-                    // Write BR to end of block
-                    w.write_opcode(BR)?;
-                    // Write BR OFFSET fixup 
-                    let br_offset = w.pos() as u32;
+                    // Write BR to end of block, with its offset fixed up at the end
+                    let br_offset = self.write_branch(w, BR)? as u32;
                     {
-                        let mut label = self.top_label_ref()?;
+                        let label = self.top_label_ref()?;
                         label.fixup_offset = br_offset;
                     }
-                    w.write_u32(FIXUP_OFFSET)?;
 
                     // Fixup BR_UNLESS OFFSET
                     let br_pos = w.pos();
                     info!("fixup_offset: {:08x} at {:08x}", br_pos, fixup_cond_offset);
-                    w.write_u32_at(br_pos as u32, fixup_cond_offset as usize)?;
+                    patch_branch(w, self.wide, fixup_cond_offset as usize, br_pos as u32)?;
+
+                    // In the side table, the if now branches here and the else to the end
+                    if self.in_place {
+                        side.write_u32_at(next, label.side_fixup as usize)?;
+                        let pos = self.write_side_entry(side, FIXUP_OFFSET, 0, 0)?;
+                        self.top_label_ref()?.side_fixup = pos as u32;
+                    }
                 },
                 RETURN => {                    
                     // Index drop_count, keep_count;
@@ -625,10 +968,36 @@ impl<'c> Compiler<'c> {
                     self.type_checker.on_return()?;
                     w.write_drop_keep(drop, keep)?;                                    
                     w.write_opcode(RETURN)?;
+                    self.write_side_entry(side, 0, drop, keep)?;
                 },    
                 UNREACHABLE => {
+                    self.type_checker.on_unreachable()?;
                     w.write_opcode(UNREACHABLE)?;
                 },
+                CATCH_ALL => {
+                    // HANDLER_POP BR END:u32 CATCH_ALL NEXT:u32
+                    self.type_checker.on_catch(&[])?;
+                    self.write_catch_header(w, side, CATCH_ALL)?;
+                },
+                MISC_PREFIX => {
+                    // MISC_PREFIX SUB_OPCODE:u8
+                    self.type_checker.on_unary(&op)?;
+                    w.write_opcode(MISC_PREFIX)?;
+                    w.write_u8(i.sub_opcode)?;
+                },
+                #[cfg(feature="simd")]
+                SIMD_PREFIX => {
+                    // SIMD_PREFIX SUB_OPCODE:u8
+                    if i.sub_opcode == V128_BITSELECT {
+                        self.type_checker.on_simd_bitselect()?;
+                    } else if op.is_binop() {
+                        self.type_checker.on_binary(&op)?;
+                    } else {
+                        self.type_checker.on_unary(&op)?;
+                    }
+                    w.write_opcode(SIMD_PREFIX)?;
+                    w.write_u8(i.sub_opcode)?;
+                },
                 _ => {
                     info!("{:?} {}", op, op.is_binop());
                     if op.is_binop() {
@@ -638,21 +1007,44 @@ impl<'c> Compiler<'c> {
                         self.type_checker.on_unary(&op)?;
                         w.write_opcode(opc)?;
                     } else {
-                        panic!("{} not implemented", op.text);
+                        return Err(Error::Unimplemented(op.text))
                     }
                     
                 }           
                 // _ => {},
             },
-            Block { signature: sig } => match opc {                
+            Block { signature } => {
+                let (params, results) = block_signature(types, signature)?;
+                match opc {                
                 BLOCK => {
-                    self.type_checker.on_block(sig)?;
+                    self.type_checker.on_block(params, results)?;
                     self.push_label(FIXUP_OFFSET)?;                    
                 },
                 LOOP => {
-                    self.type_checker.on_loop(sig)?;
+                    self.type_checker.on_loop(params, results)?;
                     let pos = w.pos();
                     self.push_label(pos as u32)?;                    
+                    self.top_label_ref()?.source = next;
+                },
+                TRY => {
+                    // TRY PARAMS:u32 END:u32 CLAUSES:u32
+                    //
+                    // CLAUSES points to a chain of catch clauses, each starting with a header
+                    // that links to the next; the last links to END.
+                    self.type_checker.on_try(params, results)?;
+                    w.write_opcode(TRY)?;
+                    w.write_u32(params.len() as u32)?;
+                    let end_pos = w.pos();
+                    w.write_u32(FIXUP_OFFSET)?;
+                    let clauses_pos = w.pos();
+                    w.write_u32(FIXUP_OFFSET)?;
+                    self.push_label_fixup(FIXUP_OFFSET, clauses_pos as u32)?;
+                    self.add_offset_fixup(0, end_pos as u32)?;
+
+                    // In the side table, the try links to its first catch clause and its end
+                    let side_pos = self.write_side_entry(side, FIXUP_OFFSET, 0, params.len() as u32)?;
+                    self.top_label_ref()?.side_fixup = side_pos as u32;
+                    self.write_side_br(side, 0, 0, 0)?;
                 },
                 IF => {
                     // CHECK_RESULT(typechecker_.OnIf(&sig));
@@ -661,14 +1053,15 @@ impl<'c> Compiler<'c> {
                     // CHECK_RESULT(EmitI32(kInvalidIstreamOffset));
                     // PushLabel(kInvalidIstreamOffset, fixup_offset);
                                         
-                    self.type_checker.on_if(sig)?;
-                    w.write_opcode(BR_UNLESS_OP.code)?;                    
-                    let pos = w.pos();
+                    self.type_checker.on_if(params, results)?;
+                    let pos = self.write_branch(w, BR_UNLESS)?;
                     // push label with fixup pointer to BR_UNLESS offset
-                    w.write_u32(FIXUP_OFFSET)?;                    
                     self.push_label_fixup(FIXUP_OFFSET, pos as u32)?; 
+                    let side_pos = self.write_side_entry(side, FIXUP_OFFSET, 0, 0)?;
+                    self.top_label_ref()?.side_fixup = side_pos as u32;
                 },
-                _ => unreachable!(),
+                _ => return Err(Error::UnimplementedOpcode(opc)),
+                }
             },
             Branch { depth } => match opc {
                 BR => {
@@ -676,10 +1069,9 @@ impl<'c> Compiler<'c> {
                     self.type_checker.on_br(depth as usize)?;
                     w.write_drop_keep(drop, keep)?;
 
-                    w.write_opcode(opc)?;
-                    let pos = w.pos();
-                    self.add_fixup(depth as u32, pos as u32)?;
-                    w.write_u32(FIXUP_OFFSET)?;    
+                    let pos = self.write_branch(w, BR)?;
+                    self.add_fixup(depth, pos as u32)?;
+                    self.write_side_br(side, depth, drop, keep)?;
 
                     // CHECK_RESULT(GetBrDropKeepCount(depth, &drop_count, &keep_count));
                     // CHECK_RESULT(typechecker_.OnBr(depth));
@@ -689,18 +1081,21 @@ impl<'c> Compiler<'c> {
                     self.type_checker.on_br_if(depth as usize)?;
                     let (drop, keep) = self.get_br_drop_keep_count(depth as usize)?;
 
-                    w.write_opcode(BR_UNLESS_OP.code)?;
-                    let fixup_br_offset = w.pos();
-                    w.write_u32(FIXUP_OFFSET)?;    
-                    w.write_drop_keep(drop, keep)?;
+                    if drop == 0 {
+                        // Nothing to drop, so branch directly
+                        let pos = self.write_branch(w, BR_IF)?;
+                        self.add_fixup(depth, pos as u32)?;
+                    } else {
+                        let fixup_br_offset = self.write_branch(w, BR_UNLESS)?;
+                        w.write_drop_keep(drop, keep)?;
 
-                    w.write_opcode(BR)?;
-                    let pos = w.pos();
-                    self.add_fixup(depth as u32, pos as u32)?;
-                    w.write_u32(FIXUP_OFFSET)?;    
+                        let pos = self.write_branch(w, BR)?;
+                        self.add_fixup(depth, pos as u32)?;
 
-                    let pos = w.pos();
-                    w.write_u32_at(pos as u32, fixup_br_offset)?;
+                        let pos = w.pos();
+                        patch_branch(w, self.wide, fixup_br_offset, pos as u32)?;
+                    }
+                    self.write_side_br(side, depth, drop, keep)?;
                     
                     //   CHECK_RESULT(typechecker_.OnBrIf(depth));
                     //   CHECK_RESULT(GetBrDropKeepCount(depth, &drop_count, &keep_count));
@@ -711,7 +1106,23 @@ impl<'c> Compiler<'c> {
                     //   CHECK_RESULT(EmitBr(depth, drop_count, keep_count));
                     //   CHECK_RESULT(EmitI32At(fixup_br_offset, GetIstreamOffset()));                    
                 },  
-                _ => unimplemented!(),              
+                RETHROW => {
+                    // RETHROW CATCH_DEPTH:u32
+                    //
+                    // The exception is found at run time by counting the catch clauses
+                    // between here and the target.
+                    self.type_checker.on_rethrow(depth as usize)?;
+                    let mut catch_depth = 0;
+                    for d in 0..depth as usize {
+                        if self.type_checker.get_label(d)?.label_type == LabelType::Catch {
+                            catch_depth += 1;
+                        }
+                    }
+                    w.write_opcode(opc)?;
+                    w.write_u32(catch_depth)?;
+                    self.write_side_entry(side, catch_depth, 0, 0)?;
+                },
+                _ => return Err(Error::UnimplementedOpcode(opc)),              
                 // let label = self.label_stack.peek(depth as usize)?;
                 // let (drop, keep) = self.get_drop_keep(&label)?;
                 // w.write_drop_keep(drop, keep)?;
@@ -726,36 +1137,47 @@ impl<'c> Compiler<'c> {
                 // DATA_OP SIZE:u32
                 // [OFFSET:u32 DROP:u32 KEEP:u32]
                 // OFFSET:u32 DROP:u32 KEEP:u32
+                //
+                // or in short form, with offsets relative to the end of each offset:
+                //
+                // BR_TABLE_S COUNT:u32
+                // [OFFSET:i16 DROP:u8 KEEP:u8]
+                // OFFSET:i16 DROP:u8 KEEP:u8
 
                 info!("BR_TABLE TABLE");
-                for i in 0..table.len() {
-                    info!("   {}: {}", i, table[i]);
+                for (i, depth) in table.iter().enumerate() {
+                    info!("   {}: {}", i, depth);
                 }
 
                 let count = table.len() as u32;
 
                 self.type_checker.begin_br_table()?;
-                w.write_opcode(BR_TABLE)?;
-                w.write_u32(count as u32)?;
-
-                // Write offset of branch table
-                let table_pos = w.pos();
-                w.write_u32(FIXUP_OFFSET)?;
-                
-                // Write DATA_OP + SIZE
-                w.write_opcode(DATA)?;
-                // w.write_u32((count + 1) * BR_TABLE_ENTRY_SIZE)?;
-                w.write_u32(count * BR_TABLE_ENTRY_SIZE)?;
-
-                // Fixup branch table offset
-                let pos = w.pos();
-                w.write_u32_at(pos as u32, table_pos)?;
+                if self.wide {
+                    w.write_opcode(BR_TABLE)?;
+                    w.write_u32(count)?;
+
+                    // Write offset of branch table
+                    let table_pos = w.pos();
+                    w.write_u32(FIXUP_OFFSET)?;
+                    
+                    // Write DATA_OP + SIZE
+                    w.write_opcode(DATA)?;
+                    // w.write_u32((count + 1) * BR_TABLE_ENTRY_SIZE)?;
+                    w.write_u32(count * BR_TABLE_ENTRY_SIZE)?;
+
+                    // Fixup branch table offset
+                    let pos = w.pos();
+                    w.write_u32_at(pos as u32, table_pos)?;
+                } else {
+                    w.write_opcode(BR_TABLE_S)?;
+                    w.write_u32(count)?;
+                }
 
                 // Branch Table Starts Here
 
                 for depth in table.iter() {
                     self.type_checker.on_br_table_target(*depth as usize)?;
-                    self.write_br_table_offset(w, *depth as u32)?;
+                    self.write_br_table_offset(w, side, *depth as u32)?;
                 }
 
                 self.type_checker.end_br_table()?;
@@ -763,10 +1185,9 @@ impl<'c> Compiler<'c> {
             },
 
             Local { index } => {
-                // Emits OP DEPTH_TO_LOCAL
-                let id = index;
-                let ty = self.context[id as usize];
-                let local_id = self.translate_local_index(id)?;
+                // Emits OP INDEX, which locals are addressed by from the frame pointer
+                let ty = locals.get(index)?;
+                let local_id = index;
                 match opc {
                     GET_LOCAL => {                        
                         self.type_checker.on_get_local(ty)?;
@@ -777,11 +1198,16 @@ impl<'c> Compiler<'c> {
                     TEE_LOCAL => {
                         self.type_checker.on_tee_local(ty)?;
                     }
-                    _ => unreachable!()
+                    _ => return Err(Error::UnimplementedOpcode(opc)),
                 };
                 info!("-- local_id: {}", local_id);
-                w.write_opcode(opc)?;
-                w.write_u32(local_id)?;                
+                if local_id <= 0xff {
+                    w.write_opcode(short_form(opc))?;
+                    w.write_u8(local_id as u8)?;
+                } else {
+                    w.write_opcode(opc)?;
+                    w.write_u32(local_id)?;
+                }
 
             }
             Global { index } => {
@@ -789,7 +1215,7 @@ impl<'c> Compiler<'c> {
                     GET_GLOBAL => {
                         let index = index as usize;
                         if index < globals.len() {
-                            let global = &globals[index as usize];
+                            let global = &globals[index];
                             let global_type = global.global_type();
                             info!("Global: {:?}", global);
                             info!("Global Type: {:?}", global_type);
@@ -819,12 +1245,12 @@ impl<'c> Compiler<'c> {
                         //   CHECK_RESULT(EmitI32(TranslateGlobalIndexToEnv(global_index)));                        
                         let index = index as usize;
                         if index < globals.len() {                        
-                            let global = &globals[index as usize];
+                            let global = &globals[index];
                             let global_type = global.global_type();
                             info!("Global: {:?}", global);
                             info!("Global Type: {:?}", global_type);
                             if !global_type.mutable {
-                                return Err(Error::InvalidGlobal { id: index as u32 });
+                                return Err(Error::ImmutableGlobal { id: index as u32 });
                             }
                             self.type_checker.on_set_global(global_type.valtype)?;
                             w.write_opcode(SET_GLOBAL)?    ;
@@ -834,7 +1260,7 @@ impl<'c> Compiler<'c> {
                         }                        
 
                     },
-                    _ => unimplemented!(),
+                    _ => return Err(Error::UnimplementedOpcode(opc)),
                 }              
             },
             Call { index } => {
@@ -846,7 +1272,16 @@ impl<'c> Compiler<'c> {
                     let func_type = &types[type_index];
                     info!("Type Index: {:?}", type_index);
                     info!("Type: {:?}", func_type);            
-                    self.type_checker.on_call(func_type.parameters, func_type.results)?;
+                    if opc == RETURN_CALL {
+                        // DROP_KEEP RETURN_CALL FUNC_INDEX:u32
+                        let keep = func_type.parameters.len() as u32;
+                        let (drop, keep) = self.get_return_call_drop_keep_count(keep)?;
+                        self.type_checker.on_return_call(func_type.parameters, func_type.results)?;
+                        w.write_drop_keep(drop, keep)?;
+                        self.write_side_entry(side, 0, drop, keep)?;
+                    } else {
+                        self.type_checker.on_call(func_type.parameters, func_type.results)?;
+                    }
 
                     w.write_opcode(opc)?;
                     w.write_u32(index as u32)?;
@@ -854,21 +1289,40 @@ impl<'c> Compiler<'c> {
                     return Err(Error::InvalidFunction { id: index as u32})
                 }
             },
-            CallIndirect { index, reserved: _ } => {
-                info!("CALL_INDIRECT: {}", index);
+            CallIndirect { index, table } => {
+                // CALL_INDIRECT SIG:u32 TABLE:u32
+                info!("CALL_INDIRECT: {} {}", index, table);
 
+                if table_elemtype(tables, table)? != ValueType::AnyFunc {
+                    return Err(Error::InvalidTable { id: table })
+                }
                 let index = index as usize;
                 let func_type = &types[index];
                 info!("Type: {:?}", func_type);
-                self.type_checker.on_call_indirect(func_type.parameters, func_type.results)?;
-                w.write_opcode(CALL_INDIRECT)?;
+                if opc == RETURN_CALL_INDIRECT {
+                    // DROP_KEEP RETURN_CALL_INDIRECT SIG:u32 TABLE:u32
+                    // The table index on top of the operands is kept for the call.
+                    let keep = func_type.parameters.len() as u32 + 1;
+                    let (drop, keep) = self.get_return_call_drop_keep_count(keep)?;
+                    self.type_checker.on_return_call_indirect(func_type.parameters, func_type.results)?;
+                    w.write_drop_keep(drop, keep)?;
+                    self.write_side_entry(side, 0, drop, keep)?;
+                } else {
+                    self.type_checker.on_call_indirect(func_type.parameters, func_type.results)?;
+                }
+                w.write_opcode(opc)?;
                 w.write_u32(index as u32)?;                        
-
+                w.write_u32(table)?;
             },
             I32Const { value } => {
                 self.type_checker.on_const(I32)?;
-                w.write_opcode(opc)?;
-                w.write_i32(value)?;
+                if value >= i16::MIN as i32 && value <= i16::MAX as i32 {
+                    w.write_opcode(I32_CONST_S)?;
+                    w.write_i16(value as i16)?;
+                } else {
+                    w.write_opcode(opc)?;
+                    w.write_i32(value)?;
+                }
             },
             F32Const { value } => { 
                 self.type_checker.on_const(F32)?;
@@ -887,7 +1341,7 @@ impl<'c> Compiler<'c> {
             },
             LoadStore { align, offset } => {
                 match opc {
-                    I32_LOAD | I32_LOAD8_S | I32_LOAD8_U | I32_LOAD16_S | I32_LOAD16_U => {
+                    I32_LOAD ..= I64_LOAD32_U => {
                         // CHECK_RESULT(CheckHasMemory(opcode));
                         // CHECK_RESULT(CheckAlign(alignment_log2, opcode.GetMemorySize()));
                         // CHECK_RESULT(typechecker_.OnLoad(opcode));
@@ -897,7 +1351,7 @@ impl<'c> Compiler<'c> {
 
                         self.type_checker.on_load(&op)?;
                     },
-                    I32_STORE | I32_STORE8 | I32_STORE16 => {
+                    I32_STORE ..= I64_STORE32 => {
                         //   CHECK_RESULT(CheckHasMemory(opcode));
                         //   CHECK_RESULT(CheckAlign(alignment_log2, opcode.GetMemorySize()));
                         //   CHECK_RESULT(typechecker_.OnStore(opcode));
@@ -906,10 +1360,39 @@ impl<'c> Compiler<'c> {
                         //   CHECK_RESULT(EmitI32(offset));
                         self.type_checker.on_store(&op)?;
                     },
+                    ATOMIC_PREFIX => {
+                        // Atomic accesses must be naturally aligned
+                        if (op.m as u32).trailing_zeros() != align {
+                            return Err(Error::InvalidAlignment { align })
+                        }
+                        match i.sub_opcode {
+                            MEMORY_ATOMIC_NOTIFY => self.type_checker.on_atomic_notify(&op)?,
+                            MEMORY_ATOMIC_WAIT32 | MEMORY_ATOMIC_WAIT64 => self.type_checker.on_atomic_wait(&op)?,
+                            I32_ATOMIC_LOAD ..= I64_ATOMIC_LOAD32_U => self.type_checker.on_atomic_load(&op)?,
+                            I32_ATOMIC_STORE ..= I64_ATOMIC_STORE32 => self.type_checker.on_atomic_store(&op)?,
+                            I32_ATOMIC_RMW_CMPXCHG ..= I64_ATOMIC_RMW32_CMPXCHG_U => self.type_checker.on_atomic_cmpxchg(&op)?,
+                            _ => self.type_checker.on_atomic_rmw(&op)?,
+                        }
+                    },
+                    #[cfg(feature="simd")]
+                    SIMD_PREFIX => {
+                        if align > (op.m as u32).trailing_zeros() {
+                            return Err(Error::InvalidAlignment { align })
+                        }
+                        if i.sub_opcode == V128_STORE {
+                            self.type_checker.on_store(&op)?;
+                        } else {
+                            self.type_checker.on_load(&op)?;
+                        }
+                    },
                     _ => return Err(Error::UnimplementedOpcode(opc)),
                 }
+                // The alignment is only a hint, so it is left out
                 w.write_opcode(opc)?;
-                w.write_u32(align)?;
+                if opc == ATOMIC_PREFIX || opc == SIMD_PREFIX {
+                    // ATOMIC_PREFIX | SIMD_PREFIX SUB_OPCODE:u8 OFFSET:u32
+                    w.write_u8(i.sub_opcode)?;
+                }
                 w.write_u32(offset)?;
             },
             Memory { reserved: _ } => {
@@ -920,9 +1403,183 @@ impl<'c> Compiler<'c> {
                     MEM_GROW => {
                         self.type_checker.on_grow_memory(&op)?;
                     },
-                    _ => unimplemented!()
+                    MISC_PREFIX => {
+                        self.type_checker.on_memory_fill()?;
+                    },
+                    ATOMIC_PREFIX => {
+                        self.type_checker.on_atomic_fence()?;
+                    },
+                    _ => return Err(Error::UnimplementedOpcode(opc)),
+                }
+                w.write_opcode(opc)?;
+                if opc == MISC_PREFIX || opc == ATOMIC_PREFIX {
+                    // MISC_PREFIX MEMORY_FILL | ATOMIC_PREFIX ATOMIC_FENCE
+                    w.write_u8(i.sub_opcode)?;
+                }
+            },
+            MemoryInit { index, reserved: _ } => {
+                // MISC_PREFIX MEMORY_INIT DATA_INDEX:u32
+                self.check_data_segment(index)?;
+                self.type_checker.on_memory_init()?;
+                w.write_opcode(MISC_PREFIX)?;
+                w.write_u8(i.sub_opcode)?;
+                w.write_u32(index)?;
+            },
+            DataDrop { index } => {
+                // MISC_PREFIX DATA_DROP DATA_INDEX:u32
+                self.check_data_segment(index)?;
+                w.write_opcode(MISC_PREFIX)?;
+                w.write_u8(i.sub_opcode)?;
+                w.write_u32(index)?;
+            },
+            MemoryCopy { dst: _, src: _ } => {
+                // MISC_PREFIX MEMORY_COPY
+                self.type_checker.on_memory_copy()?;
+                w.write_opcode(MISC_PREFIX)?;
+                w.write_u8(i.sub_opcode)?;
+            },
+            Tag { index } => {
+                let payload = tag_parameters(types, tags, index)?;
+                match opc {
+                    CATCH => {
+                        // HANDLER_POP BR END:u32 CATCH TAG:u32 NEXT:u32
+                        self.type_checker.on_catch(payload)?;
+                        self.write_catch_header(w, side, CATCH)?;
+                        w.write_u32(index)?;
+                        let next_pos = w.pos();
+                        w.write_u32(FIXUP_OFFSET)?;
+                        self.top_label_ref()?.fixup_offset = next_pos as u32;
+                    },
+                    THROW => {
+                        // THROW TAG:u32
+                        self.type_checker.on_throw(payload)?;
+                        w.write_opcode(opc)?;
+                        w.write_u32(index)?;
+                    },
+                    _ => return Err(Error::UnimplementedOpcode(opc)),
+                }
+            },
+            SelectT { types } => {
+                // Types are only needed for validation, so this compiles to a plain SELECT
+                self.type_checker.on_select_t(types)?;
+                w.write_opcode(SELECT)?;
+            },
+            RefNull { reftype } => {
+                if !reftype.is_ref() {
+                    return Err(Error::UnexpectedType { wanted: ValueType::AnyFunc, got: reftype })
+                }
+                self.type_checker.on_ref_null(reftype)?;
+                w.write_opcode(REF_NULL)?;
+            },
+            RefFunc { index } => {
+                // REF_FUNC FUNC_INDEX:u32
+                if index as usize >= functions.len() {
+                    return Err(Error::InvalidFunction { id: index })
+                }
+                self.type_checker.on_ref_func()?;
+                w.write_opcode(REF_FUNC)?;
+                w.write_u32(index)?;
+            },
+            Table { index } => {
+                // TABLE_GET | TABLE_SET TABLE_INDEX:u32
+                // MISC_PREFIX TABLE_GROW | TABLE_SIZE | TABLE_FILL TABLE_INDEX:u32
+                let elemtype = table_elemtype(tables, index)?;
+                match (opc, i.sub_opcode) {
+                    (TABLE_GET, _) => self.type_checker.on_table_get(elemtype)?,
+                    (TABLE_SET, _) => self.type_checker.on_table_set(elemtype)?,
+                    (MISC_PREFIX, TABLE_GROW) => self.type_checker.on_table_grow(elemtype)?,
+                    (MISC_PREFIX, TABLE_SIZE) => self.type_checker.on_table_size()?,
+                    (MISC_PREFIX, TABLE_FILL) => self.type_checker.on_table_fill(elemtype)?,
+                    _ => return Err(Error::UnimplementedOpcode(opc)),
                 }
                 w.write_opcode(opc)?;
+                if opc == MISC_PREFIX {
+                    w.write_u8(i.sub_opcode)?;
+                }
+                w.write_u32(index)?;
+            },
+            TableInit { index, table } => {
+                // MISC_PREFIX TABLE_INIT ELEM_INDEX:u32 TABLE_INDEX:u32
+                table_elemtype(tables, table)?;
+                self.check_element_segment(index)?;
+                self.type_checker.on_table_init()?;
+                w.write_opcode(MISC_PREFIX)?;
+                w.write_u8(i.sub_opcode)?;
+                w.write_u32(index)?;
+                w.write_u32(table)?;
+            },
+            ElemDrop { index } => {
+                // MISC_PREFIX ELEM_DROP ELEM_INDEX:u32
+                self.check_element_segment(index)?;
+                w.write_opcode(MISC_PREFIX)?;
+                w.write_u8(i.sub_opcode)?;
+                w.write_u32(index)?;
+            },
+            TableCopy { dst, src } => {
+                // MISC_PREFIX TABLE_COPY DST_INDEX:u32 SRC_INDEX:u32
+                if table_elemtype(tables, dst)? != table_elemtype(tables, src)? {
+                    return Err(Error::InvalidTable { id: src })
+                }
+                self.type_checker.on_table_copy()?;
+                w.write_opcode(MISC_PREFIX)?;
+                w.write_u8(i.sub_opcode)?;
+                w.write_u32(dst)?;
+                w.write_u32(src)?;
+            },
+            #[cfg(feature="simd")]
+            LoadStoreLane { align, offset, lane } => {
+                // SIMD_PREFIX SUB_OPCODE:u8 OFFSET:u32 LANE:u8
+                if align > (op.m as u32).trailing_zeros() {
+                    return Err(Error::InvalidAlignment { align })
+                }
+                if lane >= simd::lane_count(i.sub_opcode) {
+                    return Err(Error::InvalidLaneIndex { index: lane })
+                }
+                self.type_checker.on_binary(&op)?;
+                w.write_opcode(SIMD_PREFIX)?;
+                w.write_u8(i.sub_opcode)?;
+                w.write_u32(offset)?;
+                w.write_u8(lane)?;
+            },
+            #[cfg(feature="simd")]
+            V128Const { value } => {
+                // SIMD_PREFIX V128_CONST VALUE:[u8; 16]
+                self.type_checker.on_const(V128)?;
+                w.write_opcode(SIMD_PREFIX)?;
+                w.write_u8(i.sub_opcode)?;
+                for b in value.iter() {
+                    w.write_u8(*b)?;
+                }
+            },
+            #[cfg(feature="simd")]
+            Shuffle { lanes } => {
+                // SIMD_PREFIX I8X16_SHUFFLE LANES:[u8; 16]
+                for l in lanes.iter() {
+                    if *l >= 32 {
+                        return Err(Error::InvalidLaneIndex { index: *l })
+                    }
+                }
+                self.type_checker.on_binary(&op)?;
+                w.write_opcode(SIMD_PREFIX)?;
+                w.write_u8(i.sub_opcode)?;
+                for l in lanes.iter() {
+                    w.write_u8(*l)?;
+                }
+            },
+            #[cfg(feature="simd")]
+            Lane { index } => {
+                // SIMD_PREFIX SUB_OPCODE:u8 LANE:u8
+                if index >= simd::lane_count(i.sub_opcode) {
+                    return Err(Error::InvalidLaneIndex { index })
+                }
+                if op.is_binop() {
+                    self.type_checker.on_binary(&op)?;
+                } else {
+                    self.type_checker.on_unary(&op)?;
+                }
+                w.write_opcode(SIMD_PREFIX)?;
+                w.write_u8(i.sub_opcode)?;
+                w.write_u8(index)?;
             },
         } 
         Ok(())
@@ -931,6 +1588,272 @@ impl<'c> Compiler<'c> {
 
 
 
+/// Validates `m` and type checks all of its function bodies without instantiating it, calling
+/// `report` for every body that fails. The module's types, functions, globals, tables and tags
+/// are listed in `buf`, which also holds the compiler's stacks; imports are not resolved.
+/// Returns the number of failed bodies.
+pub fn check_module(buf: &mut [u8], m: &Module, report: &mut dyn FnMut(CheckError)) -> Result<u32, Error> {
+    validate(buf, m)?;
+
+    let mut w = Writer::new(buf);
+    let spaces = IndexSpaces::new(&mut w, m)?;
+    let cfg = Config { compile: false, ..Config::default() };
+    let compiler_buf = w.alloc_slice(Compiler::buffer_size(&cfg.limits))?;
+    Compiler::new_with_config(compiler_buf, cfg)?.check(&spaces.as_spaces(), m, report)
+}
+
+/// Returns the size of the code that compiling the already validated module `m` with `limits`
+/// generates, without keeping it. The module's index spaces and the compiler's stacks are
+/// allocated from `buf`.
+pub fn code_size(buf: &mut [u8], m: &Module, limits: ResourceLimits) -> Result<usize, Error> {
+    let mut w = Writer::new(buf);
+    let spaces = IndexSpaces::new(&mut w, m)?;
+    let cfg = Config { limits, ..Config::default() };
+    let compiler_buf = w.alloc_slice(Compiler::buffer_size(&cfg.limits))?;
+    let mut code = Writer::sink();
+    Compiler::new_with_config(compiler_buf, cfg)?.compile_to(&mut code, &spaces.as_spaces(), m)?;
+    Ok(code.pos())
+}
+
+/// The types, functions, globals, tables and tags of a module, as far as the type checker and
+/// code generator need them: tables have no elements, globals hold zero and imports are not
+/// resolved.
+struct IndexSpaces<'a> {
+    types: SmallVec<'a, FunctionType<'a>>,
+    functions: SmallVec<'a, FuncInst<'a>>,
+    globals: SmallVec<'a, GlobalInst>,
+    tables: SmallVec<'a, TableInst<'a>>,
+    tags: SmallVec<'a, Tag>,
+}
+
+impl<'a> IndexSpaces<'a> {
+    fn as_spaces(&self) -> Spaces<'_, 'a> {
+        Spaces {
+            types: self.types.as_ref(),
+            functions: self.functions.as_ref(),
+            globals: self.globals.as_ref(),
+            tables: self.tables.as_ref(),
+            tags: self.tags.as_ref(),
+        }
+    }
+
+    fn new(w: &mut Writer<'a>, m: &'a Module) -> Result<Self, Error> {
+        let (mut n_types, mut n_functions, mut n_globals, mut n_tables, mut n_tags) = (0, 0, 0, 0, 0);
+        let mut sections = m.sections();
+        while let Some(section) = sections.next()? {
+            match section.id() {
+                Id::Type => n_types += section.types().count()?,
+                Id::Import => {
+                    let mut imports = section.imports();
+                    while let Some(i) = imports.next()? {
+                        match i.import_desc {
+                            ImportDesc::Func(_) => n_functions += 1,
+                            ImportDesc::Table(_) => n_tables += 1,
+                            ImportDesc::Global(_) => n_globals += 1,
+                            ImportDesc::Tag(_) => n_tags += 1,
+                            ImportDesc::Memory(_) => {},
+                        }
+                    }
+                },
+                Id::Function => n_functions += section.functions().count()?,
+                Id::Table => n_tables += section.tables().count()?,
+                Id::Global => n_globals += section.globals().count()?,
+                Id::Tag => n_tags += section.tags().count()?,
+                _ => {},
+            }
+        }
+
+        let mut types: SmallVec<FunctionType> = w.alloc_smallvec(n_types)?;
+        let mut functions: SmallVec<FuncInst> = w.alloc_smallvec(n_functions)?;
+        w.align_to::<GlobalInst>()?;
+        w.split::<()>();
+        let mut globals: SmallVec<GlobalInst> = w.alloc_smallvec(n_globals)?;
+        let mut tables: SmallVec<TableInst> = w.alloc_smallvec(n_tables)?;
+        let mut tags: SmallVec<Tag> = w.alloc_smallvec(n_tags)?;
+
+        // Only the types matter to the type checker, so tables have no elements and globals
+        // hold zero.
+        let mut sections = m.sections();
+        while let Some(section) = sections.next()? {
+            match section.id() {
+                Id::Type => {
+                    let mut items = section.types();
+                    while let Some(t) = items.next()? {
+                        types.push(t)?;
+                    }
+                },
+                Id::Import => {
+                    let mut imports = section.imports();
+                    let mut import_index = 0;
+                    while let Some(i) = imports.next()? {
+                        let Import { module, name, import_desc } = i;
+                        match import_desc {
+                            ImportDesc::Func(type_index) => {
+                                let type_index = type_index as usize;
+                                functions.push(FuncInst::Import { type_index, module, name, module_index: 0, import_index })?;
+                            },
+                            ImportDesc::Table(TableType { elemtype, .. }) => tables.push(TableInst::new(elemtype, &[], 0))?,
+                            ImportDesc::Global(global_type) => globals.push(GlobalInst::Import { global_type, import_index })?,
+                            ImportDesc::Tag(tag) => tags.push(tag)?,
+                            ImportDesc::Memory(_) => {},
+                        }
+                        import_index += 1;
+                    }
+                },
+                Id::Function => {
+                    let mut items = section.functions();
+                    let mut function_index = 0;
+                    while let Some(type_index) = items.next()? {
+                        functions.push(FuncInst::Local { type_index: type_index as usize, function_index })?;
+                        function_index += 1;
+                    }
+                },
+                Id::Table => {
+                    let mut items = section.tables();
+                    while let Some(TableType { elemtype, .. }) = items.next()? {
+                        tables.push(TableInst::new(elemtype, &[], 0))?;
+                    }
+                },
+                Id::Global => {
+                    let mut items = section.globals();
+                    let mut global_index = 0;
+                    while let Some(global) = items.next()? {
+                        let value = Cell::new(Value::from(0));
+                        globals.push(GlobalInst::Local { global_type: global.global_type, global_index, value })?;
+                        global_index += 1;
+                    }
+                },
+                Id::Tag => {
+                    let mut items = section.tags();
+                    while let Some(tag) = items.next()? {
+                        tags.push(tag)?;
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        Ok(IndexSpaces { types, functions, globals, tables, tags })
+    }
+}
+
+// The number of functions that are imported rather than defined by the module.
+fn imported_functions(functions: &[FuncInst]) -> usize {
+    functions.iter().filter(|f| !matches!(f, FuncInst::Local { .. })).count()
+}
+
+// The compiled opcode with 16-bit branch offsets or an 8-bit local index for `opc`.
+fn short_form(opc: u8) -> u8 {
+    match opc {
+        BR => BR_S,
+        BR_IF => BR_IF_S,
+        BR_UNLESS => BR_UNLESS_S,
+        GET_LOCAL => GET_LOCAL_S,
+        SET_LOCAL => SET_LOCAL_S,
+        TEE_LOCAL => TEE_LOCAL_S,
+        _ => opc,
+    }
+}
+
+// Patches the branch offset at `at` to go to `target`. Short branches are relative to the
+// end of the offset and fail with `BranchOutOfRange` if it does not fit.
+fn patch_branch(w: &mut Writer, wide: bool, at: usize, target: u32) -> Result<(), Error> {
+    if wide {
+        return w.write_u32_at(target, at)
+    }
+    let offset = target as i64 - (at as i64 + 2);
+    if offset < i16::MIN as i64 || offset > i16::MAX as i64 {
+        return Err(Error::BranchOutOfRange)
+    }
+    w.write_u16_at(offset as i16 as u16, at)
+}
+
+// The number of values to drop from a label's part of the stack while keeping `keep`.
+fn stack_drop(depth: usize, keep: usize) -> Result<usize, Error> {
+    depth.checked_sub(keep).ok_or(Error::UnexpectedTypeStackDepth { wanted: keep as u32, got: depth as u32 })
+}
+
+#[cfg(feature="integer-only")]
+fn has_float(types: &[ValueType]) -> bool {
+    types.iter().any(|&t| t == F32 || t == F64)
+}
+
+/// Returns true if an instruction operates on, produces or passes through a float value,
+/// either through its opcode or through the block, function, global or tag type it refers to.
+#[cfg(feature="integer-only")]
+fn uses_float(spaces: &Spaces, i: &Instr) -> Result<bool, Error> {
+    let Spaces { types, functions, globals, tags, .. } = *spaces;
+    use self::Immediate::*;
+    if let Some(op) = i.op() {
+        if has_float(&[op.t1, op.t2, op.tr]) {
+            return Ok(true)
+        }
+    }
+    Ok(match i.immediate {
+        Block { signature } => {
+            let (params, results) = block_signature(types, signature)?;
+            has_float(params) || has_float(results)
+        },
+        Call { index } => match functions.get(index as usize).and_then(|f| types.get(f.type_index())) {
+            Some(t) => has_float(t.parameters) || has_float(t.results),
+            _ => return Err(Error::InvalidFunction { id: index }),
+        },
+        CallIndirect { index, table: _ } => match types.get(index as usize) {
+            Some(t) => has_float(t.parameters) || has_float(t.results),
+            _ => return Err(Error::InvalidSignature { id: index }),
+        },
+        Global { index } => match globals.get(index as usize) {
+            Some(g) => has_float(&[g.global_type().valtype]),
+            _ => return Err(Error::InvalidGlobal { id: index }),
+        },
+        SelectT { types } => has_float(types),
+        Tag { index } => has_float(tag_parameters(types, tags, index)?),
+        _ => false,
+    })
+}
+
+/// Resolves a block type to its parameter and result types. Single-value block types
+/// resolve to static slices, type indices to the module's function types.
+fn block_signature<'t>(types: &[FunctionType<'t>], signature: BlockType) -> Result<(&'t [ValueType], &'t [ValueType]), Error> {
+    static EMPTY: [ValueType; 0] = [];
+    static SINGLE: [ValueType; 7] = [I32, I64, F32, F64, ValueType::AnyFunc, ValueType::ExternRef, V128];
+    Ok(match signature {
+        BlockType::Value(VOID) => (&EMPTY, &EMPTY),
+        BlockType::Value(I32) => (&EMPTY, &SINGLE[0..1]),
+        BlockType::Value(I64) => (&EMPTY, &SINGLE[1..2]),
+        BlockType::Value(F32) => (&EMPTY, &SINGLE[2..3]),
+        BlockType::Value(F64) => (&EMPTY, &SINGLE[3..4]),
+        BlockType::Value(ValueType::AnyFunc) => (&EMPTY, &SINGLE[4..5]),
+        BlockType::Value(ValueType::ExternRef) => (&EMPTY, &SINGLE[5..6]),
+        BlockType::Value(V128) => (&EMPTY, &SINGLE[6..7]),
+        BlockType::Value(_) => return Err(Error::InvalidBlockType),
+        BlockType::Index(index) => {
+            if let Some(t) = types.get(index as usize) {
+                (t.parameters, t.results)
+            } else {
+                return Err(Error::InvalidSignature { id: index })
+            }
+        }
+    })
+}
+
+fn tag_parameters<'t>(types: &[FunctionType<'t>], tags: &[Tag], index: u32) -> Result<&'t [ValueType], Error> {
+    match tags.get(index as usize) {
+        Some(tag) => match types.get(tag.type_index as usize) {
+            Some(t) if t.results.is_empty() => Ok(t.parameters),
+            _ => Err(Error::InvalidTag { id: index }),
+        },
+        None => Err(Error::InvalidTag { id: index }),
+    }
+}
+
+fn table_elemtype(tables: &[TableInst], index: u32) -> Result<ValueType, Error> {
+    match tables.get(index as usize) {
+        Some(t) => Ok(t.elemtype()),
+        None => Err(Error::InvalidTable { id: index }),
+    }
+}
+
 pub trait ModuleWrite {
     fn write_section_type(&mut self, st: Id) -> Result<(), Error>;
     fn write_section_start(&mut self, st: Id) -> Result<usize, Error>;
@@ -952,7 +1875,7 @@ pub trait ModuleWrite {
     // Code
 
     fn write_drop_keep(&mut self, drop_count: u32, keep_count: u32) -> Result<(), Error>;
-    fn write_alloca(&mut self, count: u32) -> Result<(), Error>;
+    fn write_alloca(&mut self, parameters: u32, locals: u32) -> Result<(), Error>;
     
 }
 
@@ -965,10 +1888,9 @@ impl<'a> ModuleWrite for Writer<'a> {
     }    
 
     fn write_section_end(&mut self, fixup: usize) -> Result<(), Error> {
-        Ok({
-            let len = self.pos() - (fixup + 4);
-            self.write_u32_at(len as u32, fixup)?;
-        })
+        let len = self.pos() - (fixup + 4);
+        self.write_u32_at(len as u32, fixup)?;
+        Ok(())
     }
 
     fn write_section_type(&mut self, st: Id) -> Result<(), Error> {
@@ -978,12 +1900,11 @@ impl<'a> ModuleWrite for Writer<'a> {
         self.write_u8(t as u8)
     }
     fn write_bytes(&mut self, buf: &[u8]) -> Result<(), Error> {
-        Ok({
-            self.write_u32(buf.len() as u32)?;
-            for b in buf {
-                self.write_u8(*b)?;
-            }
-        })
+        self.write_u32(buf.len() as u32)?;
+        for b in buf {
+            self.write_u8(*b)?;
+        }
+        Ok(())
     }
 
     fn write_identifier(&mut self, id: &str) -> Result<(), Error> {
@@ -991,15 +1912,14 @@ impl<'a> ModuleWrite for Writer<'a> {
     }
 
     fn write_initializer(&mut self, init: Initializer) -> Result<(), Error> {
-        Ok({
-            self.write_opcode(init.instr.opcode)?;
-            if let Some(value) = init.i32_value() {
-                self.write_i32(value)?;
-            } else {
-                panic!("Only i32 initializers are supported");
-            }
-            self.write_opcode(init.end)?;
-        })
+        self.write_opcode(init.instr.opcode)?;
+        if let Some(value) = init.i32_value() {
+            self.write_i32(value)?;
+        } else {
+            return Err(Error::Unimplemented("non-i32 initializers"))
+        }
+        self.write_opcode(init.end)?;
+        Ok(())
     }
 
     fn write_opcode(&mut self, op: u8) -> Result<(), Error> {
@@ -1007,66 +1927,65 @@ impl<'a> ModuleWrite for Writer<'a> {
     }
 
     fn write_limits(&mut self, limits: Limits) -> Result<(), Error> {
-        Ok({
-            if let Some(max) = limits.max {
-                self.write_u32(1)?;
-                self.write_u32(limits.min)?;
-                self.write_u32(max)?;
-            } else {
-                self.write_u32(0)?;
-                self.write_u32(limits.min)?;            
-            }
-        })
+        if let Some(max) = limits.max {
+            self.write_u32(if limits.shared { 3 } else { 1 })?;
+            self.write_u32(limits.min)?;
+            self.write_u32(max)?;
+        } else {
+            self.write_u32(0)?;
+            self.write_u32(limits.min)?;            
+        }
+        Ok(())
     }    
 
     fn write_table(&mut self, table: TableType) -> Result<(), Error> {
-        Ok({
-            self.write_i8(table.elemtype as i8)?;
-            self.write_limits(table.limits)?;
-        })
+        self.write_i8(table.elemtype as i8)?;
+        self.write_limits(table.limits)?;
+        Ok(())
     }
 
     fn write_memory(&mut self, memory: MemoryType) -> Result<(), Error> {
-        Ok({
-            self.write_limits(memory.limits)?;
-        })
+        self.write_limits(memory.limits)?;
+        Ok(())
     }
 
     fn write_global_type(&mut self, global_type: GlobalType) -> Result<(), Error> {
-        Ok({
-            self.write_i8(global_type.valtype as i8)?;
-            self.write_u8(if global_type.mutable { 1 } else { 0 })?;
-        })
+        self.write_i8(global_type.valtype as i8)?;
+        self.write_u8(if global_type.mutable { 1 } else { 0 })?;
+        Ok(())
     }
 
     fn write_import_desc(&mut self, desc: ImportDesc) -> Result<(), Error> {
-        Ok({
-            match desc {
-                ImportDesc::Func(t) => {
-                    self.write_u8(0x00)?;
-                    self.write_u32(t)?;
-                },
-                ImportDesc::Table(t) => {
-                    self.write_u8(0x01)?;
-                    self.write_table(t)?;
-                },
-                ImportDesc::Memory(m) => {
-                    self.write_u8(0x02)?;
-                    self.write_memory(m)?;
-                },
-                ImportDesc::Global(g) => {
-                    self.write_u8(0x03)?;                    
-                    self.write_global_type(g)?;
-                }
+        match desc {
+            ImportDesc::Func(t) => {
+                self.write_u8(0x00)?;
+                self.write_u32(t)?;
+            },
+            ImportDesc::Table(t) => {
+                self.write_u8(0x01)?;
+                self.write_table(t)?;
+            },
+            ImportDesc::Memory(m) => {
+                self.write_u8(0x02)?;
+                self.write_memory(m)?;
+            },
+            ImportDesc::Global(g) => {
+                self.write_u8(0x03)?;                    
+                self.write_global_type(g)?;
+            },
+            ImportDesc::Tag(t) => {
+                self.write_u8(0x04)?;
+                self.write_u8(t.attribute)?;
+                self.write_u32(t.type_index)?;
             }
-        })
+        }
+        Ok(())
     }
     fn write_import(&mut self, import: Import) -> Result<(), Error> {
-        Ok({
-            self.write_identifier(import.module)?;        
-            self.write_identifier(import.name)?;
-            self.write_import_desc(import.import_desc)?;
-        })
+        self.write_identifier(import.module)?;        
+        self.write_identifier(import.name)?;
+        self.write_import_desc(import.import_desc)?;
+        Ok(())
     }
     
     fn write_code_start(&mut self) -> Result<usize, Error> {
@@ -1078,12 +1997,11 @@ impl<'a> ModuleWrite for Writer<'a> {
     }
 
     fn write_code_end(&mut self, fixup: usize) -> Result<(), Error> {
-        Ok({
-            let len = self.pos() - (fixup + 4);
-            // info!("code_end pos: {:08x}", self.pos());
-            // info!("code_end len: {:08x}", len);
-            self.write_u32_at(len as u32, fixup)?;
-        })
+        let len = self.pos() - (fixup + 4);
+        // info!("code_end pos: {:08x}", self.pos());
+        // info!("code_end len: {:08x}", len);
+        self.write_u32_at(len as u32, fixup)?;
+        Ok(())
     }
 
     // Code
@@ -1092,20 +2010,160 @@ impl<'a> ModuleWrite for Writer<'a> {
         // info!("drop_keep {}, {}", drop_count, keep_count);
         if drop_count == 1 && keep_count == 0 {
             self.write_opcode(DROP)?;            
+        } else if drop_count > 0 && drop_count <= 0xff && keep_count <= 0xff {
+            self.write_opcode(DROP_KEEP_S)?;
+            self.write_u8(drop_count as u8)?;
+            self.write_u8(keep_count as u8)?;
         } else if drop_count > 0 {
             self.write_opcode(DROP_KEEP_OP.code)?;
-            self.write_u32(drop_count as u32)?;
-            self.write_u32(keep_count as u32)?;
+            self.write_u32(drop_count)?;
+            self.write_u32(keep_count)?;
         }
         Ok(())
     }
 
-    fn write_alloca(&mut self, count: u32) -> Result<(), Error> {
-        Ok(
-            if count > 0 {
-                self.write_opcode(ALLOCA_OP.code)?;
-                self.write_u32(count as u32)?;
-            }
-        )
+    fn write_alloca(&mut self, parameters: u32, locals: u32) -> Result<(), Error> {
+        if parameters == 0 && locals == 0 {
+            // Without locals the frame pointer is never used
+            Ok(())
+        } else if parameters <= 0xff && locals <= 0xff {
+            self.write_opcode(ALLOCA_S)?;
+            self.write_u8(parameters as u8)?;
+            self.write_u8(locals as u8)
+        } else {
+            self.write_opcode(ALLOCA_OP.code)?;
+            self.write_u32(parameters)?;
+            self.write_u32(locals)
+        }
     }        
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_support::*;
+    use Types;
+
+    #[test]
+    #[cfg(feature="integer-only")]
+    fn test_integer_only() {
+        use environ::Environment;
+
+        let mut buf = [0u8; 32768];
+        let (buf, mut env) = Environment::new(&mut buf, Handler {}).unwrap();
+        // func[2] starts with the first float instruction, f32.const at 0x36
+        match env.load_module("conv", buf, CONVERSION_MODULE) {
+            Err(e) => {
+                assert_eq!(e.error(), &Error::FloatUnsupported { function: 2, offset: 0x36 });
+                assert_eq!(e.location().map(|loc| loc.offset), Some(0x36));
+            },
+            Ok(_) => panic!("float module loaded"),
+        }
+    }
+
+    // (import "env" "f" (func (result i32)))
+    // (func (result i32) i32.const 1)
+    // (func (result i32) i64.const 1)
+    // (func (result i32) i32.const 1 drop drop)
+    #[test]
+    fn test_check() {
+        let mut module = [0u8; 64];
+        let module = assemble(&mut module, &[
+            &[0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f],
+            &[0x02, 0x09, 0x01, 0x03, b'e', b'n', b'v', 0x01, b'f', 0x00, 0x00],
+            &[0x03, 0x04, 0x03, 0x00, 0x00, 0x00],
+            &[0x0a, 0x12, 0x03,
+                0x04, 0x00, 0x41, 0x01, 0x0b,
+                0x04, 0x00, 0x42, 0x01, 0x0b,
+                0x06, 0x00, 0x41, 0x01, 0x1a, 0x1a, 0x0b],
+        ]);
+        let m = Module::new(module).unwrap();
+        let mut buf = [0u8; 8192];
+        let mut errors = [None, None, None];
+        let mut n = 0;
+        let failed = check_module(&mut buf, &m, &mut |e: CheckError| {
+            errors[n] = Some(e);
+            n += 1;
+        }).unwrap();
+        assert_eq!(failed, 2);
+        assert_eq!(errors[0], Some(CheckError { function: 2, offset: 44, error: Error::TypeMismatch { desc: "function", expected: Types::new(&[ValueType::I32]), got: Types::new(&[ValueType::I64]) } }));
+        assert_eq!(errors[1], Some(CheckError { function: 3, offset: 50, error: Error::TypeMismatch { desc: "drop", expected: Types::new(&[ValueType::Any]), got: Types::new(&[]) } }));
+        assert_eq!(errors[2], None);
+    }
+
+    // (global i32 (i32.const 0))
+    // (func i32.const 1 global.set 0)
+    #[test]
+    fn test_immutable_global() {
+        let mut module = [0u8; 64];
+        let module = assemble(&mut module, &[
+            &[0x01, 0x04, 0x01, 0x60, 0x00, 0x00],
+            &[0x03, 0x02, 0x01, 0x00],
+            &[0x06, 0x06, 0x01, 0x7f, 0x00, 0x41, 0x00, 0x0b],
+            &[0x0a, 0x08, 0x01, 0x06, 0x00, 0x41, 0x01, 0x24, 0x00, 0x0b],
+        ]);
+        let m = Module::new(module).unwrap();
+        let mut buf = [0u8; 8192];
+        let mut error = None;
+        let failed = check_module(&mut buf, &m, &mut |e: CheckError| error = Some(e)).unwrap();
+        assert_eq!(failed, 1);
+        assert_eq!(error, Some(CheckError { function: 0, offset: 33, error: Error::ImmutableGlobal { id: 0 } }));
+    }
+
+    // func 0 returns 5 through a br_if that drops a value when its argument is non-zero and 9
+    // otherwise, func 1 counts its argument down to 0 in a loop. Both bodies drop `n` i64
+    // constants in the middle, which compile to 10 bytes each and are branched over.
+    fn far_branches(buf: &mut [u8], n: usize) -> &[u8] {
+        fn put_body(buf: &mut [u8], len: &mut usize, prefix: &[u8], n: usize, suffix: &[u8]) {
+            put_leb(buf, len, 1 + prefix.len() + 3 * n + suffix.len());
+            put(buf, len, &[0x00]);
+            put(buf, len, prefix);
+            for _ in 0..n {
+                put(buf, len, &[0x42, 0x00, 0x1a]);
+            }
+            put(buf, len, suffix);
+        }
+
+        let mut bodies = [0u8; 32768];
+        let mut bodies_len = 0;
+        put(&mut bodies, &mut bodies_len, &[0x02]);
+        put_body(&mut bodies, &mut bodies_len,
+            &[0x02, 0x7f, 0x41, 0x07, 0x41, 0x05, 0x20, 0x00, 0x0d, 0x00, 0x1a, 0x1a], n,
+            &[0x41, 0x09, 0x0b, 0x0b]);
+        put_body(&mut bodies, &mut bodies_len, &[0x03, 0x40], n,
+            &[0x20, 0x00, 0x41, 0x01, 0x6b, 0x22, 0x00, 0x0d, 0x00, 0x0b, 0x20, 0x00, 0x0b]);
+
+        let mut len = 0;
+        put(buf, &mut len, HEADER);
+        put(buf, &mut len, &[0x01, 0x06, 0x01, 0x60, 0x01, 0x7f, 0x01, 0x7f]);
+        put(buf, &mut len, &[0x03, 0x03, 0x02, 0x00, 0x00]);
+        put(buf, &mut len, &[0x0a]);
+        put_leb(buf, &mut len, bodies_len);
+        put(buf, &mut len, &bodies[..bodies_len]);
+        &buf[..len]
+    }
+
+    #[test]
+    fn test_compact_encoding() {
+        let mut module = [0u8; 32768];
+        let module = far_branches(&mut module, 8);
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load(&mut buf, module);
+        // The code other than the constants
+        let near = f.mi.code().len() - 2 * 8 * 10;
+        assert_eq!(f.call(0, &[0]).unwrap(), &[Value(9)]);
+        assert_eq!(f.call(0, &[1]).unwrap(), &[Value(5)]);
+        assert_eq!(f.call(1, &[3]).unwrap(), &[Value(0)]);
+
+        // Too far for 16-bit offsets, so both bodies are compiled again with wide branches
+        let mut module = [0u8; 32768];
+        let module = far_branches(&mut module, 4000);
+        let mut buf = [0u8; 131072];
+        let mut f = Fixture::load(&mut buf, module);
+        assert!(f.mi.code().len() - 2 * 4000 * 10 > near);
+        assert_eq!(f.call(0, &[0]).unwrap(), &[Value(9)]);
+        assert_eq!(f.call(0, &[1]).unwrap(), &[Value(5)]);
+        assert_eq!(f.call(1, &[3]).unwrap(), &[Value(0)]);
+        assert_eq!(f.interp.stack_len(), 0);
+    }
+}
@@ -25,8 +25,12 @@ impl<'a> Cursor<'a> {
         self.buf.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
     pub fn done(&self) -> bool {
-        self.buf.len() == 0
+        self.buf.is_empty()
     }
 
     pub fn advance(&mut self, count: usize) -> &mut Self {
@@ -1,20 +1,63 @@
 use error::Error;
+#[cfg(feature="compiler")]
+use error::LoadError;
 use writer::Writer;
 use small_vec::SmallVec;
+#[cfg(feature="compiler")]
+use parser::Location;
+#[cfg(feature="compiler")]
 use parser::module::Module;
+#[cfg(feature="compiler")]
+use parser::validator::validate_at;
 use memory_inst::MemoryInst;
-use module_inst::{ModuleInst, FuncInst, Value};
+use module_inst::{ModuleInst, FuncInst};
 use types::{ImportDesc};
 use interp::Interp;
+#[cfg(feature="compiler")]
+use compiler::Compiler;
+use limits::ResourceLimits;
+
+#[cfg(feature="compiler")]
+use core::cell::{RefCell, RefMut};
+use core::mem;
 
 pub struct Config {
-    memory_size: usize
+    memory_size: usize,
+    pub(crate) limits: ResourceLimits,
+}
+
+impl Config {
+    /// Sets the capacities used for the module table and for instantiating and compiling
+    /// every module loaded into the environment.
+    pub fn limits(self, limits: ResourceLimits) -> Config {
+        Config { limits, ..self }
+    }
+
+    /// The number of bytes `Environment::new_with_config` takes from its buffer: linear
+    /// memory, the module table and, with the `compiler` feature, the compiler's scratch
+    /// buffer.
+    pub fn buffer_size(&self) -> usize {
+        self.memory_size +
+        self.limits.modules * mem::size_of::<(&str, &ModuleInst)>() +
+        self.compiler_buffer_size()
+    }
+
+    #[cfg(feature="compiler")]
+    fn compiler_buffer_size(&self) -> usize {
+        Compiler::buffer_size(&self.limits)
+    }
+
+    #[cfg(not(feature="compiler"))]
+    fn compiler_buffer_size(&self) -> usize {
+        0
+    }
 }
 
 impl Default for Config {
     fn default() -> Config {
         Config {
             memory_size: 8192,
+            limits: ResourceLimits::default(),
         }
     }
 }
@@ -22,47 +65,142 @@ impl Default for Config {
 pub trait HostHandler {
     fn import(&self, module: &str, export: &str, import_desc: &ImportDesc) -> Result<usize, Error>;
     fn dispatch(&self, interp: &mut Interp, mem: &MemoryInst, type_index: usize, index: usize) -> Result<(), Error>;
+
+    /// Called by `memory.atomic.wait32` and `memory.atomic.wait64` once the value at `addr`
+    /// matches the expected value. A negative `timeout` waits forever. Returns 0 if woken or
+    /// 2 if timed out; with no other threads to notify, the default reports a timeout.
+    fn wait(&self, _mem: &MemoryInst, _addr: usize, _timeout: i64) -> Result<i32, Error> {
+        Ok(2)
+    }
+
+    /// Called by `memory.atomic.notify`, returning the number of waiters woken.
+    fn notify(&self, _mem: &MemoryInst, _addr: usize, _count: u32) -> Result<i32, Error> {
+        Ok(0)
+    }
 }
 
 pub struct Environment<'env, H: HostHandler> {
     cfg: Config,
     mem: MemoryInst<'env>,
     modules: SmallVec<'env, (&'env str, &'env ModuleInst<'env>)>,
+    #[cfg(feature="compiler")]
+    compiler_buf: RefCell<&'env mut [u8]>,
     host_handler: H,
 }
 
 impl<'env, H: HostHandler> Environment<'env, H> {
-    pub fn new(buf: &'env mut [u8], host_handler: H) -> (&'env mut [u8], Self) {   
+    /// Creates an environment using the start of `buf` for linear memory and returns the
+    /// rest of `buf` for loading modules. Fails with `Error::OutOfMemory` if `buf` is too
+    /// small for the configured memory, the module table and the compiler's stacks.
+    pub fn new(buf: &'env mut [u8], host_handler: H) -> Result<(&'env mut [u8], Self), Error> {
         Environment::new_with_config(buf, host_handler, Config::default())
     }
 
-    pub fn new_with_config(buf: &'env mut [u8], host_handler: H, cfg: Config) -> (&'env mut [u8], Self) {   
+    pub fn new_with_config(buf: &'env mut [u8], host_handler: H, cfg: Config) -> Result<(&'env mut [u8], Self), Error> {
+        if buf.len() < cfg.memory_size { return Err(Error::OutOfMemory) }
         let (mem_buf, buf) = buf.split_at_mut(cfg.memory_size);
-        let mem = MemoryInst::new(mem_buf, 1, None);
+        let mem = MemoryInst::new(mem_buf, 1, None)?;
         let mut w = Writer::new(buf);
-        let modules = w.alloc_smallvec(4);
+        let modules = w.alloc_smallvec(cfg.limits.modules)?;
+        #[cfg(feature="compiler")]
+        let compiler_buf = RefCell::new(w.alloc_slice(Compiler::buffer_size(&cfg.limits))?);
         let buf = w.into_slice();
-        (buf, Environment { cfg, mem, modules, host_handler })
+        Ok((buf, Environment {
+            cfg, mem, modules,
+            #[cfg(feature="compiler")]
+            compiler_buf,
+            host_handler,
+        }))
     }
 
     pub fn cfg(&self) -> &Config {
         &self.cfg
     }
 
+    pub fn limits(&self) -> &ResourceLimits {
+        &self.cfg.limits
+    }
+
+    /// Borrows the scratch buffer that modules are compiled with, which is shared by every
+    /// module loaded into the environment.
+    #[cfg(feature="compiler")]
+    pub(crate) fn compiler_buf(&self) -> Result<RefMut<'_, &'env mut [u8]>, Error> {
+        // Only one module is instantiated at a time, so this is never already borrowed
+        self.compiler_buf.try_borrow_mut().map_err(|_| Error::OutOfMemory)
+    }
+
     pub fn mem(&self) -> &MemoryInst<'env> {
         &self.mem
     }
 
-    pub fn load_module(&mut self, name: &'env str, buf: &'env mut [u8], module_data: &[u8]) -> Result<(&'env mut [u8], &'env ModuleInst<'env>), Error> {
-        let m = Module::new(module_data)?;
-        let (buf, mi) = ModuleInst::new(buf, &self, &self.mem, m)?;
+    /// Validates, instantiates and compiles a module. Errors found in the module itself
+    /// carry their location.
+    #[cfg(feature="compiler")]
+    pub fn load_module(&mut self, name: &'env str, buf: &'env mut [u8], module_data: &[u8]) -> Result<(&'env mut [u8], &'env ModuleInst<'env>), LoadError> {
+        let mut loc = Location::default();
+        let m = Module::new(module_data).map_err(|e| LoadError::new(e.into(), Some(loc)))?;
+        validate_at(buf, &m, &mut loc).map_err(|e| LoadError::new(e.into(), Some(loc)))?;
+        let mut loc = None;
+        let (buf, mi) = match ModuleInst::new_at(buf, self, &self.mem, m, &mut loc) {
+            Ok(instance) => instance,
+            Err(e) => return Err(LoadError::new(e, loc)),
+        };
         let mut w = Writer::new(buf);
         let mi = w.copy(mi)?;
-        self.modules.push((name, mi));
+        self.modules.push((name, mi))?;
         let buf = w.into_slice();        
         Ok((buf, mi))
     }
 
+    /// Validates and instantiates a module like `load_module`, but compiles each function the
+    /// first time it is called, into `code_size` bytes taken from `buf`. Modules whose
+    /// functions are mostly unused load faster and need less code space, in exchange for
+    /// keeping `module_data` and reporting errors in a function body when it is first called.
+    #[cfg(feature="compiler")]
+    pub fn load_module_lazy(&mut self, name: &'env str, buf: &'env mut [u8], module_data: &'env [u8], code_size: usize) -> Result<(&'env mut [u8], &'env ModuleInst<'env>), LoadError> {
+        let mut loc = Location::default();
+        let m = Module::new(module_data).map_err(|e| LoadError::new(e.into(), Some(loc)))?;
+        validate_at(buf, &m, &mut loc).map_err(|e| LoadError::new(e.into(), Some(loc)))?;
+        let (buf, mi) = ModuleInst::new_lazy(buf, self, &self.mem, m, code_size).map_err(|e| LoadError::new(e, None))?;
+        let mut w = Writer::new(buf);
+        let mi = w.copy(mi)?;
+        self.modules.push((name, mi))?;
+        let buf = w.into_slice();
+        Ok((buf, mi))
+    }
+
+    /// Validates and instantiates a module like `load_module`, but executes its function bodies
+    /// in place from `module_data` rather than compiling them. Only a side table with the
+    /// branch targets of each body is kept in `buf`, so `module_data` can stay in flash and
+    /// much less RAM is needed, in exchange for slower execution.
+    #[cfg(feature="compiler")]
+    pub fn load_module_in_place(&mut self, name: &'env str, buf: &'env mut [u8], module_data: &'env [u8]) -> Result<(&'env mut [u8], &'env ModuleInst<'env>), LoadError> {
+        let mut loc = Location::default();
+        let m = Module::new(module_data).map_err(|e| LoadError::new(e.into(), Some(loc)))?;
+        validate_at(buf, &m, &mut loc).map_err(|e| LoadError::new(e.into(), Some(loc)))?;
+        let mut loc = None;
+        let (buf, mi) = match ModuleInst::new_in_place(buf, self, &self.mem, m, module_data, &mut loc) {
+            Ok(instance) => instance,
+            Err(e) => return Err(LoadError::new(e, loc)),
+        };
+        let mut w = Writer::new(buf);
+        let mi = w.copy(mi)?;
+        self.modules.push((name, mi))?;
+        let buf = w.into_slice();
+        Ok((buf, mi))
+    }
+
+    /// Instantiates a module from an image written by `image::write_image`. The image is
+    /// used in place, so it can live in read-only memory.
+    pub fn load_image(&mut self, name: &'env str, buf: &'env mut [u8], image: &'env [u8]) -> Result<(&'env mut [u8], &'env ModuleInst<'env>), Error> {
+        let (buf, mi) = ModuleInst::from_image(buf, self, &self.mem, image)?;
+        let mut w = Writer::new(buf);
+        let mi = w.copy(mi)?;
+        self.modules.push((name, mi))?;
+        let buf = w.into_slice();
+        Ok((buf, mi))
+    }
+
     pub fn import_host_function(&self, module: &str, export: &str, import_desc: &ImportDesc) -> Result<usize, Error> {
         self.host_handler.import(module, export, import_desc)
     }
@@ -71,26 +209,196 @@ impl<'env, H: HostHandler> Environment<'env, H> {
         self.host_handler.dispatch(interp, &self.mem, type_index, index)
     }
 
+    pub fn wait(&self, addr: usize, timeout: i64) -> Result<i32, Error> {
+        self.host_handler.wait(&self.mem, addr, timeout)
+    }
+
+    pub fn notify(&self, addr: usize, count: u32) -> Result<i32, Error> {
+        self.host_handler.notify(&self.mem, addr, count)
+    }
+
     pub fn call_module_function(&self, interp: &mut Interp, module_index: usize, function_index: usize) -> Result<(), Error> {
-        let &(name, mi) = &self.modules[module_index];
+        let &(name, mi) = self.modules.as_ref().get(module_index).ok_or(Error::InvalidImport)?;
         let id = function_index;
         info!("calling {}:{}", name, function_index);
 
-        match &mi.functions()[id] {
-            &FuncInst::Host { type_index, module: _, name: _, host_index } => {
+        match *mi.functions().get(id).ok_or(Error::InvalidFunction { id: id as u32 })? {
+            FuncInst::Host { type_index, module: _, name: _, host_index } => {
                 self.call_host_function(interp, type_index, host_index)
             },
-            &FuncInst::Import { type_index, ref module, ref name, module_index, import_index } => {
+            FuncInst::Import { type_index, ref module, ref name, module_index, import_index } => {
                 info!("CALL IMPORT: type_index: {} module: {}, name: {}, module_index: {}, import_index: {}", type_index, module, name, module_index, import_index);
-                self.call_module_function(interp, module_index, import_index)
-            },
-            &FuncInst::Local { type_index: _, function_index } => {
-                if let Some(Value(v)) = interp.call(self, mi, function_index)? {
-                    Ok(interp.push(v)?)
-                } else {
-                    Ok(())
+                // Imports between modules are not linked, so an import can only resolve to a
+                // function defined in the target module. Following another import could
+                // recurse forever.
+                let (_, target) = self.modules.as_ref().get(module_index).ok_or(Error::InvalidImport)?;
+                match target.functions().get(import_index) {
+                    Some(FuncInst::Import { .. }) | None => Err(Error::InvalidImport),
+                    Some(_) => self.call_module_function(interp, module_index, import_index),
                 }
+            },
+            FuncInst::Local { type_index: _, function_index } => {
+                interp.execute(self, mi, function_index)
             }
         }
     }        
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_support::*;
+    #[cfg(feature="compiler")]
+    use {interp, Types, ValueType};
+    #[cfg(feature="compiler")]
+    use module_inst::Value;
+    #[cfg(feature="compiler")]
+    use parser::module::Id;
+
+    #[cfg(feature="compiler")]
+    fn load_error(sections: &[&[u8]]) -> LoadError {
+        let mut module = [0u8; 64];
+        let module = assemble(&mut module, sections);
+        let mut buf = [0u8; 32768];
+        match Fixture::load_with(&mut buf, module, Load::Compiled, interp::Config::default()) {
+            Ok(_) => panic!("malformed module loaded"),
+            Err(e) => e,
+        }
+    }
+
+    #[cfg(feature="compiler")]
+    #[test]
+    fn test_malformed() {
+        const TYPES: &[u8] = &[0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f];
+        const FUNCS: &[u8] = &[0x03, 0x02, 0x01, 0x00];
+        const CODE: &[u8] = &[0x0a, 0x06, 0x01, 0x04, 0x00, 0x41, 0x01, 0x0b];
+
+        let mut module = [0u8; 64];
+        let module = assemble(&mut module, &[TYPES, FUNCS, CODE]);
+        let mut buf = [0u8; 32768];
+        assert_eq!(Fixture::load(&mut buf, module).call(0, &[]).unwrap(), &[Value(1)]);
+
+        let error = |sections: &[&[u8]]| -> Error { load_error(sections).into() };
+        // (result <0x55>)
+        assert_eq!(error(&[&[0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x55], FUNCS, CODE]),
+            Error::ParserError(::parser::Error::InvalidValueType));
+        // (func (type 5))
+        assert_eq!(error(&[TYPES, &[0x03, 0x02, 0x01, 0x05], CODE]),
+            Error::ParserError(::parser::Error::UnknownType));
+        // Two functions but only one body
+        assert_eq!(error(&[TYPES, &[0x03, 0x03, 0x02, 0x00, 0x00], CODE]),
+            Error::ParserError(::parser::Error::InconsistentFunctionCount));
+        // (local i32 × 0xffffffff) (local i32)
+        assert_eq!(error(&[TYPES, FUNCS,
+            &[0x0a, 0x0e, 0x01, 0x0c, 0x02, 0xff, 0xff, 0xff, 0xff, 0x0f, 0x7f, 0x01, 0x7f, 0x41, 0x01, 0x0b]]),
+            Error::ParserError(::parser::Error::TooManyLocals));
+        // (memory 2 1)
+        assert_eq!(error(&[&[0x05, 0x04, 0x01, 0x01, 0x02, 0x01]]),
+            Error::ParserError(::parser::Error::InvalidLimits));
+        // (memory 0x10000) is more than the host provides
+        assert_eq!(error(&[&[0x05, 0x05, 0x01, 0x00, 0x80, 0x80, 0x04]]),
+            Error::OutOfMemory);
+        // Truncated body
+        load_error(&[TYPES, FUNCS, &CODE[..CODE.len() - 1]]);
+    }
+
+    #[cfg(feature="compiler")]
+    #[test]
+    fn test_error_location() {
+        const TYPES: &[u8] = &[0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f];
+        const FUNCS: &[u8] = &[0x03, 0x02, 0x01, 0x00];
+
+        let location = |sections: &[&[u8]]| load_error(sections).location().cloned();
+        // (func (type 5)), with the entry at 0x12
+        assert_eq!(location(&[TYPES, &[0x03, 0x02, 0x01, 0x05]]),
+            Some(Location { offset: 0x12, section: Some(Id::Function), function: None, instruction: None }));
+        // (func (result i32) local.get 5), with the body at 0x17
+        assert_eq!(location(&[TYPES, FUNCS, &[0x0a, 0x06, 0x01, 0x04, 0x00, 0x20, 0x05, 0x0b]]),
+            Some(Location { offset: 0x18, section: Some(Id::Code), function: Some(0), instruction: Some(1) }));
+        // (func (result i32) i64.const 1) fails to type check at its end
+        assert_eq!(location(&[TYPES, FUNCS, &[0x0a, 0x06, 0x01, 0x04, 0x00, 0x42, 0x01, 0x0b]]),
+            Some(Location { offset: 0x1a, section: Some(Id::Code), function: Some(0), instruction: Some(3) }));
+        // Two functions but only one body
+        assert_eq!(location(&[TYPES, &[0x03, 0x03, 0x02, 0x00, 0x00], &[0x0a, 0x06, 0x01, 0x04, 0x00, 0x41, 0x01, 0x0b]]),
+            Some(Location { offset: 0x1c, section: None, function: None, instruction: None }));
+    }
+
+    // 40 functions of type (func), the first nesting 20 blocks
+    #[cfg(feature="compiler")]
+    #[test]
+    fn test_resource_limits() {
+        let mut funcs = [0u8; 43];
+        funcs[..3].copy_from_slice(&[0x03, 0x29, 0x28]);
+        let mut code = [0x0bu8; 184];
+        code[..6].copy_from_slice(&[0x0a, 0xb5, 0x01, 0x28, 0x3e, 0x00]);
+        for i in 0..20 {
+            code[6 + i * 2..8 + i * 2].copy_from_slice(&[0x02, 0x40]);
+        }
+        for i in 0..39 {
+            code[67 + i * 3..69 + i * 3].copy_from_slice(&[0x02, 0x00]);
+        }
+        let mut module = [0u8; 256];
+        let module = assemble(&mut module, &[&[0x01, 0x04, 0x01, 0x60, 0x00, 0x00], &funcs, &code]);
+
+        let load = |limits: ResourceLimits| {
+            let mut buf = [0u8; 32768];
+            let cfg = Config::default().limits(limits);
+            let (buf, mut env) = Environment::new_with_config(&mut buf, Handler {}, cfg).unwrap();
+            match env.load_module("limits", buf, module) {
+                Ok((buf, mi)) => Interp::new(buf).unwrap().call(&env, mi, 39).map(|v| v.len()).map_err(|e| e.into()),
+                Err(e) => Err(e.into()),
+            }
+        };
+        assert_eq!(load(ResourceLimits::default()), Err(Error::OutOfMemory));
+        assert_eq!(load(ResourceLimits { functions: 40, ..ResourceLimits::default() }), Err(Error::StackError(::stack::Error::Overflow)));
+        assert_eq!(load(ResourceLimits { functions: 40, labels: 21, ..ResourceLimits::default() }), Ok(0));
+    }
+
+    #[test]
+    fn test_buffer_too_small() {
+        let mut buf = [0u8; 1024];
+        assert_eq!(Environment::new(&mut buf, Handler {}).err(), Some(Error::OutOfMemory));
+        assert_eq!(Interp::new(&mut buf).err(), Some(Error::OutOfMemory));
+    }
+
+    #[cfg(feature="compiler")]
+    #[test]
+    fn test_lazy_compile() {
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load_with(&mut buf, REFERENCE_TYPES_MODULE, Load::Lazy(512), interp::Config::default()).unwrap();
+
+        assert_eq!(f.mi.code().len(), 0);
+        // Compiles func 2, then func 0 when it is reached through call_indirect
+        assert_eq!(f.call(2, &[]).unwrap(), &[Value(7)]);
+        assert!(!f.mi.code().body_range(0).unwrap().is_empty());
+        assert_eq!(f.mi.code().body_range(1).unwrap().len(), 0);
+        let len = f.mi.code().len();
+        assert_eq!(f.call(2, &[]).unwrap(), &[Value(7)]);
+        assert_eq!(f.mi.code().len(), len);
+        assert_eq!(f.call(3, &[]).unwrap(), &[Value(11)]);
+        assert_eq!(f.call(9, &[]).unwrap(), &[Value(7)]);
+        assert_eq!(f.interp.stack_len(), 0);
+
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load_with(&mut buf, TAIL_CALL_MODULE, Load::Lazy(512), interp::Config::default()).unwrap();
+        assert_eq!(f.call(3, &[]).unwrap(), &[Value(500501)]);
+        assert_eq!(f.call(2, &[]).unwrap(), &[Value(5050)]);
+
+        // A body that fails to type check is only found when it is called
+        let mut module = [0u8; 64];
+        let module = assemble(&mut module, &[
+            &[0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f],
+            &[0x03, 0x03, 0x02, 0x00, 0x00],
+            &[0x0a, 0x0b, 0x02,
+                0x04, 0x00, 0x41, 0x01, 0x0b,
+                0x04, 0x00, 0x42, 0x01, 0x0b],
+        ]);
+        let mut buf = [0u8; 32768];
+        assert!(Fixture::load_with(&mut buf, module, Load::Compiled, interp::Config::default()).is_err());
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load_with(&mut buf, module, Load::Lazy(512), interp::Config::default()).unwrap();
+        assert_eq!(f.call(0, &[]).unwrap(), &[Value(1)]);
+        assert_eq!(f.call_err(1, &[]), Error::TypeMismatch { desc: "function", expected: Types::new(&[ValueType::I32]), got: Types::new(&[ValueType::I64]) });
+        assert_eq!(f.call(0, &[]).unwrap(), &[Value(1)]);
+    }
+}
@@ -1,7 +1,7 @@
-use {ValueType};
+use {ValueType, Id, Location};
 use stack;
+#[cfg(feature="compiler")]
 use parser;
-use parser::module::Id;
 
 use core::{fmt, str};
 
@@ -11,23 +11,36 @@ pub enum Error {
     Return,
     End,
     Unimplemented(&'static str),
+    Nondeterministic(&'static str),
     InvalidOpcode(u8),
     UnimplementedOpcode(u8),
     InvalidBlockType,
     ScopesFull,
     FixupsFull,
+    BranchOutOfRange,
     OutOfBounds,
     Leb128Overflow,
     UndefinedTableIndex { id: i32 },
+    UninitializedElement,
+    TableOutOfBounds,
+    UnalignedAtomic,
+    UncaughtException { tag: u32 },
     SignatureMismatch,
+    IntegerDivideByZero,
+    IntegerOverflow,
+    InvalidConversionToInteger,
     TypeCheck(&'static str),
+    TypeMismatch { desc: &'static str, expected: Types, got: Types },
 
     MissingSection { id: Id },
     OutOfMemory,
+    MemoryTooLarge,
     ReservedMemoryArea,
     NoHostFunction,
     NoHostImportFunction,
     InvalidHeader,
+    InvalidImage,
+    UnsupportedImage { version: u32, flags: u32 },
     InvalidSection { id: u32 },
     InvalidGlobalKind { id: u8 },
     UnknownSignatureType,
@@ -35,12 +48,20 @@ pub enum Error {
     InvalidReturnType,
     InvalidIfSignature,
     InvalidReservedValue,
+    InvalidAlignment { align: u32 },
+    InvalidLaneIndex { index: u8 },
     InvalidBranchTableDefault { id: u32, len: u32},
     InvalidImport,
     InvalidLocal { id: u32 },
     InvalidGlobal { id: u32 },
+    ImmutableGlobal { id: u32 },
     InvalidFunction { id: u32 },
+    InvalidDataSegment { id: u32 },
+    InvalidElementSegment { id: u32 },
+    InvalidTable { id: u32 },
+    InvalidTag { id: u32 },
     InvalidSignature { id: u32 },
+    FloatUnsupported { function: u32, offset: u32 },
     UnexpectedData { wanted: u32, got: u32 },
     UnexpectedStackDepth { wanted: u32, got: u32},
     UnexpectedTypeStackDepth { wanted: u32, got: u32},
@@ -51,9 +72,198 @@ pub enum Error {
     Utf8Error(str::Utf8Error),
     // OpcodeError(opcode::Error),
     StackError(stack::Error),
+    #[cfg(feature="compiler")]
     ParserError(parser::Error)
 }
 
+/// A list of value types in a type mismatch. Only the last four are kept, which are the
+/// ones nearest the top of the stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Types {
+    types: [ValueType; 4],
+    len: u32,
+}
+
+impl Types {
+    pub fn new(types: &[ValueType]) -> Self {
+        let skip = types.len().saturating_sub(4);
+        Types::tail(&types[skip..], types.len())
+    }
+
+    /// Creates a list of `len` types from its last `types.len()` types.
+    pub fn tail(types: &[ValueType], len: usize) -> Self {
+        let mut list = Types { types: [ValueType::Any; 4], len: len as u32 };
+        let n = types.len().min(4);
+        list.types[..n].copy_from_slice(&types[..n]);
+        list
+    }
+
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The types that were kept.
+    pub fn kept(&self) -> &[ValueType] {
+        &self.types[..self.len().min(4)]
+    }
+}
+
+impl fmt::Display for Types {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        if self.len() > 4 {
+            write!(f, "..., ")?;
+        }
+        for (i, t) in self.kept().iter().enumerate() {
+            if i > 0 { write!(f, ", ")?; }
+            write!(f, "{}", t)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// The error returned by `Environment::load_module`, along with where in the module it was
+/// found when that is known.
+#[derive(Debug, PartialEq)]
+pub struct LoadError {
+    error: Error,
+    location: Option<Location>,
+}
+
+impl LoadError {
+    pub fn new(error: Error, location: Option<Location>) -> Self {
+        LoadError { error, location }
+    }
+
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+}
+
+/// Formats the error the way WABT reports it, as `0000023: error: ...`.
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(location) = self.location {
+            write!(f, "{:07x}: ", location.offset)?;
+        }
+        write!(f, "error: {}", self.error)
+    }
+}
+
+#[cfg(feature="std")]
+impl ::std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn (::std::error::Error) + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<Error> for LoadError {
+    fn from(other: Error) -> LoadError {
+        LoadError { error: other, location: None }
+    }
+}
+
+impl From<LoadError> for Error {
+    fn from(other: LoadError) -> Error {
+        other.error
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::Error::*;
+        match *self {
+            Unreachable => write!(f, "unreachable"),
+            Return => write!(f, "return"),
+            End => write!(f, "unexpected end"),
+            Unimplemented(what) => write!(f, "unimplemented: {}", what),
+            Nondeterministic(what) => write!(f, "nondeterministic result: {}", what),
+            InvalidOpcode(opcode) => write!(f, "illegal opcode {:#04x}", opcode),
+            UnimplementedOpcode(opcode) => write!(f, "unimplemented opcode {:#04x}", opcode),
+            InvalidBlockType => write!(f, "invalid block type"),
+            ScopesFull => write!(f, "too many nested scopes"),
+            FixupsFull => write!(f, "too many branch fixups"),
+            BranchOutOfRange => write!(f, "branch out of range"),
+            OutOfBounds => write!(f, "out of bounds memory access"),
+            Leb128Overflow => write!(f, "integer too large"),
+            UndefinedTableIndex { id } => write!(f, "undefined element {}", id),
+            UninitializedElement => write!(f, "uninitialized element"),
+            TableOutOfBounds => write!(f, "out of bounds table access"),
+            UnalignedAtomic => write!(f, "unaligned atomic"),
+            UncaughtException { tag } => write!(f, "uncaught exception with tag {}", tag),
+            SignatureMismatch => write!(f, "indirect call type mismatch"),
+            IntegerDivideByZero => write!(f, "integer divide by zero"),
+            IntegerOverflow => write!(f, "integer overflow"),
+            InvalidConversionToInteger => write!(f, "invalid conversion to integer"),
+            TypeCheck(what) => write!(f, "type mismatch: {}", what),
+            TypeMismatch { desc, expected, got } => write!(f, "type mismatch in {}, expected {} but got {}", desc, expected, got),
+            MissingSection { id } => write!(f, "missing {} section", id.as_str()),
+            OutOfMemory => write!(f, "out of memory"),
+            MemoryTooLarge => write!(f, "linear memory buffer is larger than 255 mini-pages"),
+            ReservedMemoryArea => write!(f, "reserved memory area"),
+            NoHostFunction => write!(f, "no host function"),
+            NoHostImportFunction => write!(f, "no host import function"),
+            InvalidHeader => write!(f, "magic header not detected"),
+            InvalidImage => write!(f, "malformed image"),
+            UnsupportedImage { version, flags } => write!(f, "unsupported image version {} with flags {:#x}", version, flags),
+            InvalidSection { id } => write!(f, "malformed section id {}", id),
+            InvalidGlobalKind { id } => write!(f, "malformed global kind {}", id),
+            UnknownSignatureType => write!(f, "unknown signature type"),
+            UnknownExternalKind => write!(f, "malformed external kind"),
+            InvalidReturnType => write!(f, "invalid return type"),
+            InvalidIfSignature => write!(f, "invalid if signature"),
+            InvalidReservedValue => write!(f, "zero byte expected"),
+            InvalidAlignment { align } => write!(f, "alignment 2**{} must not be larger than natural", align),
+            InvalidLaneIndex { index } => write!(f, "invalid lane index {}", index),
+            InvalidBranchTableDefault { id, len } => write!(f, "invalid br_table default {} with {} targets", id, len),
+            InvalidImport => write!(f, "unknown import"),
+            InvalidLocal { id } => write!(f, "unknown local {}", id),
+            InvalidGlobal { id } => write!(f, "unknown global {}", id),
+            ImmutableGlobal { id } => write!(f, "global {} is immutable", id),
+            InvalidFunction { id } => write!(f, "unknown function {}", id),
+            InvalidDataSegment { id } => write!(f, "unknown data segment {}", id),
+            InvalidElementSegment { id } => write!(f, "unknown elem segment {}", id),
+            InvalidTable { id } => write!(f, "unknown table {}", id),
+            InvalidTag { id } => write!(f, "unknown tag {}", id),
+            InvalidSignature { id } => write!(f, "unknown type {}", id),
+            FloatUnsupported { function, offset } => write!(f, "floating point in function {} at {:#x} is not supported", function, offset),
+            UnexpectedData { wanted, got } => write!(f, "expected {} items but got {}", wanted, got),
+            UnexpectedStackDepth { wanted, got } => write!(f, "expected stack depth {} but got {}", wanted, got),
+            UnexpectedTypeStackDepth { wanted, got } => write!(f, "expected type stack depth {} but got {}", wanted, got),
+            UnexpectedType { wanted, got } => write!(f, "type mismatch, expected {} but got {}", wanted, got),
+            UnexpectedReturnValue { wanted, got } => write!(f, "expected return value of type {} but got {}", wanted, got),
+            UnexpectedReturnLength { got } => write!(f, "unexpected return length {}", got),
+            FmtError(ref e) => write!(f, "{}", e),
+            Utf8Error(_) => write!(f, "malformed UTF-8 encoding"),
+            StackError(ref e) => write!(f, "{}", e),
+            #[cfg(feature="compiler")]
+            ParserError(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(feature="std")]
+impl ::std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn (::std::error::Error) + 'static)> {
+        match *self {
+            Error::FmtError(ref e) => Some(e),
+            Error::Utf8Error(ref e) => Some(e),
+            Error::StackError(ref e) => Some(e),
+            #[cfg(feature="compiler")]
+            Error::ParserError(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
 impl From<fmt::Error> for Error {
     fn from(other: fmt::Error) -> Error {
         Error::FmtError(other)
@@ -72,6 +282,7 @@ impl From<stack::Error> for Error {
     }
 }
 
+#[cfg(feature="compiler")]
 impl From<parser::Error> for Error {
     fn from(other: parser::Error) -> Error {
         Error::ParserError(other)
@@ -82,4 +293,27 @@ impl From<str::Utf8Error> for Error {
     fn from(other: str::Utf8Error) -> Error {
         Error::Utf8Error(other)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_support::Text;
+
+    #[test]
+    fn test_error_display() {
+        use ValueType::*;
+        let e = Error::TypeMismatch { desc: "i32.add", expected: Types::new(&[I32, I32]), got: Types::new(&[I64]) };
+        assert_eq!(Text::format(e).as_str(), "type mismatch in i32.add, expected [i32, i32] but got [i64]");
+        let e = Error::TypeMismatch { desc: "return", expected: Types::new(&[I32]), got: Types::new(&[I32, I64, F32, F64, I32]) };
+        assert_eq!(Text::format(e).as_str(), "type mismatch in return, expected [i32] but got [..., i64, f32, f64, i32]");
+        assert_eq!(Text::format(Error::IntegerDivideByZero).as_str(), "integer divide by zero");
+        assert_eq!(Text::format(Error::ImmutableGlobal { id: 0 }).as_str(), "global 0 is immutable");
+        #[cfg(feature="compiler")]
+        assert_eq!(Text::format(Error::from(parser::Error::UnexpectedEof)).as_str(), "unexpected end");
+        assert_eq!(Text::format(Error::from(stack::Error::Overflow)).as_str(), "stack overflow");
+
+        let loc = Location { offset: 0x1a, section: None, function: None, instruction: None };
+        assert_eq!(Text::format(LoadError::new(Error::InvalidFunction { id: 3 }, Some(loc))).as_str(), "000001a: error: unknown function 3");
+        assert_eq!(Text::format(LoadError::new(Error::OutOfMemory, None)).as_str(), "error: out of memory");
+    }
+}
@@ -0,0 +1,230 @@
+use error::Error;
+use types::ValueType;
+use compiler::code_size;
+use environ;
+use interp;
+use memory_inst::{self, MINI_SIZE};
+use module_inst::{ModuleInst, FuncInst, GlobalInst, ExportInst};
+use table_inst::TableInst;
+use parser::{validate, FallibleIterator};
+use parser::module::*;
+use parser::types::{FunctionType, TableType};
+
+use core::cell::Cell;
+use core::mem;
+
+/// The largest alignment of anything allocated from the buffers. The sizes in
+/// `Requirements` are exact for buffers aligned to `ALIGN`; a buffer that is not may need up
+/// to `ALIGN - 1` more bytes.
+pub const ALIGN: usize = 16;
+
+/// The number of bytes each buffer needs to load and run a module.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Requirements {
+    /// The buffer passed to `Environment::new_with_config`, including its linear memory.
+    pub environment: usize,
+    /// The part of the buffer passed to `Environment::load_module` that holds the module
+    /// instance: its tables, copied names and segments, and the `ModuleInst` itself.
+    pub instance: usize,
+    /// The part of the buffer passed to `Environment::load_module` that holds compiled code.
+    pub code: usize,
+    /// The buffer passed to `Interp::new_with_config` for its stacks.
+    pub interp: usize,
+    /// The linear memory that the module's active data segments are written to, which must
+    /// fit in the environment's memory. Memory that the module only touches when it runs is
+    /// not included.
+    pub memory: usize,
+}
+
+impl Requirements {
+    /// The size of the buffer passed to `Environment::load_module`.
+    pub fn module(&self) -> usize {
+        self.instance + self.code
+    }
+}
+
+/// Works out the buffer sizes needed to load `m` into an environment configured by `cfg`
+/// and to run it with an interpreter configured by `interp_cfg`, without instantiating it.
+/// The module is validated and compiled to find the size of its code, using `buf` as
+/// scratch space. Modules that `Environment::load_module` would reject for exceeding the
+/// resource limits are rejected with the same error.
+pub fn estimate(buf: &mut [u8], m: &Module, cfg: &environ::Config, interp_cfg: &interp::Config) -> Result<Requirements, Error> {
+    validate(buf, m)?;
+
+    let limits = cfg.limits;
+    let mut layout = Layout::default();
+    let (mut n_types, mut n_functions, mut n_globals, mut n_tables) = (0, 0, 0, 0);
+    let (mut n_tags, mut n_exports, mut n_elements, mut n_data) = (0, 0, 0, 0);
+    let mut pages = [0u32; 8];
+
+    // Allocated in the same order as `ModuleInst::new_at`
+    layout.align::<FunctionType>();
+    layout.alloc::<FunctionType>(limits.types);
+    layout.alloc::<FuncInst>(limits.functions);
+    layout.align::<GlobalInst>();
+    layout.alloc::<GlobalInst>(limits.globals);
+    layout.alloc::<TableInst>(limits.tables);
+    layout.alloc::<Tag>(limits.tags);
+    layout.alloc::<ExportInst>(limits.exports);
+    layout.alloc::<Cell<&[u32]>>(limits.elements);
+    layout.alloc::<Cell<&[u8]>>(limits.data);
+
+    let mut sections = m.sections();
+    while let Some(section) = sections.next()? {
+        match section.id() {
+            Id::Type => {
+                let mut types = section.types();
+                while let Some(t) = types.next()? {
+                    layout.copy_slice::<ValueType>(t.parameters.len());
+                    layout.copy_slice::<ValueType>(t.results.len());
+                    n_types += 1;
+                }
+            },
+            Id::Import => {
+                let mut imports = section.imports();
+                while let Some(i) = imports.next()? {
+                    match i.import_desc {
+                        ImportDesc::Func(_) => {
+                            layout.copy_slice::<u8>(i.module.len());
+                            layout.copy_slice::<u8>(i.name.len());
+                            n_functions += 1;
+                        },
+                        ImportDesc::Global(_) => n_globals += 1,
+                        ImportDesc::Tag(_) => n_tags += 1,
+                        ImportDesc::Table(_) | ImportDesc::Memory(_) => {},
+                    }
+                }
+            },
+            Id::Function => n_functions += section.functions().count()?,
+            Id::Global => n_globals += section.globals().count()?,
+            Id::Tag => n_tags += section.tags().count()?,
+            Id::Table => {
+                let mut tables = section.tables();
+                while let Some(TableType { limits, .. }) = tables.next()? {
+                    layout.align::<Cell<u32>>();
+                    layout.alloc::<Cell<u32>>(limits.max.unwrap_or(limits.min) as usize);
+                    n_tables += 1;
+                }
+            },
+            Id::Export => {
+                let mut exports = section.exports();
+                while let Some(export) = exports.next()? {
+                    layout.copy_slice::<u8>(export.name.len());
+                    n_exports += 1;
+                }
+            },
+            Id::Element => {
+                let mut segments = section.elements();
+                while let Some(element) = segments.next()? {
+                    layout.align::<u32>();
+                    layout.alloc::<u32>(element.iter().count()?);
+                    n_elements += 1;
+                }
+            },
+            Id::Data => {
+                let mut segments = section.data();
+                while let Some(Data { mode, init }) = segments.next()? {
+                    if let SegmentMode::Active { offset, .. } = mode {
+                        // Segments placed by a global are only known at instantiation
+                        if let Some(offset) = offset.i32_value() {
+                            mark_pages(&mut pages, offset as u32 as usize, init.len())?;
+                        }
+                    } else {
+                        layout.copy_slice::<u8>(init.len());
+                    }
+                    n_data += 1;
+                }
+            },
+            _ => {},
+        }
+    }
+
+    if n_types > limits.types || n_functions > limits.functions ||
+        n_globals > limits.globals || n_tables > limits.tables ||
+        n_tags > limits.tags || n_exports > limits.exports ||
+        n_elements > limits.elements || n_data > limits.data {
+        return Err(Error::OutOfMemory)
+    }
+
+    let code = code_size(buf, m, limits)?;
+    layout.alloc::<u8>(code);
+    // `load_module` copies the instance after the code
+    layout.align::<ModuleInst>();
+    layout.alloc::<ModuleInst>(1);
+
+    Ok(Requirements {
+        environment: cfg.buffer_size(),
+        instance: layout.pos - code,
+        code,
+        interp: interp_cfg.buffer_size(),
+        memory: pages.iter().map(|p| p.count_ones() as usize).sum::<usize>() * MINI_SIZE,
+    })
+}
+
+/// Tracks where `Writer` places allocations in a buffer aligned to `ALIGN`.
+#[derive(Default)]
+struct Layout {
+    pos: usize,
+}
+
+impl Layout {
+    fn align<T>(&mut self) {
+        let align = mem::align_of::<T>();
+        self.pos = self.pos.div_ceil(align) * align;
+    }
+
+    fn alloc<T>(&mut self, len: usize) {
+        self.pos += len * mem::size_of::<T>();
+    }
+
+    fn copy_slice<T>(&mut self, len: usize) {
+        self.align::<T>();
+        self.alloc::<T>(len);
+    }
+}
+
+// Marks the page table entries that `len` bytes at `addr` are mapped to, in the same way that
+// `MemoryInst` maps them when the segment is written.
+fn mark_pages(pages: &mut [u32; 8], addr: usize, len: usize) -> Result<(), Error> {
+    if len == 0 { return Ok(()) }
+    let end = addr.saturating_add(len);
+    let mut page = addr - addr % MINI_SIZE;
+    while page < end {
+        let entry = memory_inst::mini_page(memory_inst::translate(page)?) as usize;
+        pages[entry / 32] |= 1 << (entry % 32);
+        page += MINI_SIZE;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_support::*;
+    use environ::Environment;
+    use limits::ResourceLimits;
+
+    #[test]
+    fn test_estimate() {
+        let mut scratch = [0u8; 16384];
+        for &(module, memory) in &[(REFERENCE_TYPES_MODULE, 0), (BULK_MEMORY_MODULE, 0), (TAIL_CALL_MODULE, 0), (ACTIVE_DATA_MODULE, 8192)] {
+            let m = Module::new(module).unwrap();
+            let req = estimate(&mut scratch, &m, &environ::Config::default(), &interp::Config::default()).unwrap();
+            assert_eq!(req.memory, memory);
+            assert_eq!(req.interp, interp::Config::default().buffer_size());
+
+            let mut module_buf = Aligned([0u8; 16384]);
+            let mut buf = [0u8; 32768];
+            let (rest, mut env) = Environment::new(&mut buf, Handler {}).unwrap();
+            assert_eq!(32768 - rest.len(), req.environment);
+            let (rest, mi) = env.load_module("estimate", &mut module_buf.0, module).unwrap();
+            assert_eq!(16384 - rest.len(), req.module());
+            assert_eq!(mi.code().len(), req.code);
+        }
+
+        // Rejected like `load_module` rejects it
+        let m = Module::new(REFERENCE_TYPES_MODULE).unwrap();
+        let cfg = environ::Config::default().limits(ResourceLimits { functions: 8, ..ResourceLimits::default() });
+        assert_eq!(estimate(&mut scratch, &m, &cfg, &interp::Config::default()), Err(Error::OutOfMemory));
+    }
+}
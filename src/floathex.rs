@@ -55,7 +55,7 @@ pub fn f32_hex<W: Write>(out: &mut W, v: f32) -> fmt::Result {
                 while f != 0 {
                     let v = f >> 28;
                     write!(out, "{:x}", v)?;
-                    f = f << 4;
+                    f <<= 4;
                 }
             }
             let e = e as u32 as i32;
@@ -69,7 +69,7 @@ pub fn f32_hex<W: Write>(out: &mut W, v: f32) -> fmt::Result {
 pub fn f64_parts(v: f64) -> (u8, u16, u64) {
     let v = v.to_bits();
     let s = (v >> 63) as u8;
-    let e = (((v >> 52) & (1 << 11) - 1)) as u16;
+    let e = ((v >> 52) & ((1 << 11) - 1)) as u16;
     let f = v << 12;
     (s, e, f)
 }
@@ -120,7 +120,7 @@ pub fn f64_hex<W: Write>(out: &mut W, v: f64) -> fmt::Result {
                 while f != 0 {
                     let v = f >> 60;
                     write!(out, "{:x}", v)?;
-                    f = f << 4;
+                    f <<= 4;
                 }
             }
             let e = e as u32 as i32;
@@ -134,49 +134,65 @@ pub fn f64_hex<W: Write>(out: &mut W, v: f64) -> fmt::Result {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use test_support::Text;
+
+    fn f32_text(v: f32) -> Text {
+        let mut text = Text::new();
+        f32_hex(&mut text, v).unwrap();
+        text
+    }
+
+    fn f64_text(v: f64) -> Text {
+        let mut text = Text::new();
+        f64_hex(&mut text, v).unwrap();
+        text
+    }
+
     #[test]
+    // 0.857421875 is exact, while clippy asks for the shortest literal that rounds to it
+    #[allow(clippy::excessive_precision)]
     fn test_f32() {
         assert_eq!(f32_parts(1.0), (0, 127, 0));
         assert_eq!(f32_parts(0.5), (0, 126, 0));
         assert_eq!(f32_parts(2.0), (0, 128, 0));
         assert_eq!(f32_parts(3.0), (0, 128, 0x8000_0000));
         assert_eq!(f32_parts(4.0), (0, 129, 0));        
-        assert_eq!(f32_hex(0.0),"0x0p+0");
-        assert_eq!(f32_hex(1.0),"0x1p+0");
-        assert_eq!(f32_hex(0.5),"0x1p-1");
-        assert_eq!(f32_hex(2.0),"0x1p+1");
-        assert_eq!(f32_hex(3.0),"0x1.8p+1");
-        assert_eq!(f32_hex(4.0),"0x1p+2");
-        assert_eq!(f32_hex(2.0f32.powi(8)),"0x1p+8");
-        assert_eq!(f32_hex(0.857421875f32), "0x1.b7p-1");
-        assert_eq!(f32_hex(f32::from_bits(0x65a9_6816)), "0x1.52d02cp+76");
-        assert_eq!(f32_hex(f32::from_bits(0x374f_2040)), "0x1.9e408p-17");
-        assert_eq!(f32_hex(f32::from_bits(0x7fc0_0000)), "nan");
-        assert_eq!(f32_hex(f32::from_bits(0x7f80_0abc)), "nan:0xabc");
-        assert_eq!(f32_hex(f32::from_bits(0xff80_0abc)), "-nan:0xabc");
-        assert_eq!(f32_hex(f32::from_bits(0x7f80_0000)), "inf");
+        assert_eq!(f32_text(0.0).as_str(),"0x0p+0");
+        assert_eq!(f32_text(1.0).as_str(),"0x1p+0");
+        assert_eq!(f32_text(0.5).as_str(),"0x1p-1");
+        assert_eq!(f32_text(2.0).as_str(),"0x1p+1");
+        assert_eq!(f32_text(3.0).as_str(),"0x1.8p+1");
+        assert_eq!(f32_text(4.0).as_str(),"0x1p+2");
+        assert_eq!(f32_text(2.0f32.powi(8)).as_str(),"0x1p+8");
+        assert_eq!(f32_text(0.857421875f32).as_str(), "0x1.b7p-1");
+        assert_eq!(f32_text(f32::from_bits(0x65a9_6816)).as_str(), "0x1.52d02cp+76");
+        assert_eq!(f32_text(f32::from_bits(0x374f_2040)).as_str(), "0x1.9e408p-17");
+        assert_eq!(f32_text(f32::from_bits(0x7fc0_0000)).as_str(), "nan");
+        assert_eq!(f32_text(f32::from_bits(0x7f80_0abc)).as_str(), "nan:0xabc");
+        assert_eq!(f32_text(f32::from_bits(0xff80_0abc)).as_str(), "-nan:0xabc");
+        assert_eq!(f32_text(f32::from_bits(0x7f80_0000)).as_str(), "inf");
     }
     #[test]
     fn test_f64() {
-        assert_eq!(f64_hex(0.0),"0x0p+0");
-        assert_eq!(f64_hex(1.0),"0x1p+0");
-        assert_eq!(f64_hex(0.5),"0x1p-1");
-        assert_eq!(f64_hex(2.0),"0x1p+1");
-        assert_eq!(f64_hex(3.0),"0x1.8p+1");
-        assert_eq!(f64_hex(4.0),"0x1p+2");
-        assert_eq!(f64_hex(2.0f64.powi(8)),"0x1p+8");
-        assert_eq!(f64_hex(0.857421875f64), "0x1.b7p-1");
+        assert_eq!(f64_text(0.0).as_str(),"0x0p+0");
+        assert_eq!(f64_text(1.0).as_str(),"0x1p+0");
+        assert_eq!(f64_text(0.5).as_str(),"0x1p-1");
+        assert_eq!(f64_text(2.0).as_str(),"0x1p+1");
+        assert_eq!(f64_text(3.0).as_str(),"0x1.8p+1");
+        assert_eq!(f64_text(4.0).as_str(),"0x1p+2");
+        assert_eq!(f64_text(2.0f64.powi(8)).as_str(),"0x1p+8");
+        assert_eq!(f64_text(0.857421875f64).as_str(), "0x1.b7p-1");
         // assert_eq!(f64_parts(f64::from_bits(0xbfef_9add_3c0e_56b8)), (0,0,0));        
-        assert_eq!(f64_hex(f64::from_bits(0xbfef_9add_3c0e_56b8)), "-0x1.f9add3c0e56b8p-1");
-        assert_eq!(f64_hex(f64::from_bits(0x4019_21fb_5444_2d18)), "0x1.921fb54442d18p+2");
+        assert_eq!(f64_text(f64::from_bits(0xbfef_9add_3c0e_56b8)).as_str(), "-0x1.f9add3c0e56b8p-1");
+        assert_eq!(f64_text(f64::from_bits(0x4019_21fb_5444_2d18)).as_str(), "0x1.921fb54442d18p+2");
         // assert_eq!(f64_parts(f64::from_bits(0x7ff8_0000_0000_0000)), (0,0,0));        
-        assert_eq!(f64_hex(f64::from_bits(0x7ff8_0000_0000_0000)), "nan");
+        assert_eq!(f64_text(f64::from_bits(0x7ff8_0000_0000_0000)).as_str(), "nan");
         // assert_eq!(f64_parts(f64::from_bits(0xfff8_0000_0000_0000)), (0,0,0));        
-        assert_eq!(f64_hex(f64::from_bits(0xfff8_0000_0000_0000)), "-nan");
-        assert_eq!(f64_hex(f64::from_bits(0x7ff0_0000_0000_0abc)), "nan:0xabc");
-        assert_eq!(f64_hex(f64::from_bits(0xfff0_0000_0000_0abc)), "-nan:0xabc");
-        assert_eq!(f64_hex(f64::from_bits(0x7ff0_0000_0000_0000)), "inf");
+        assert_eq!(f64_text(f64::from_bits(0xfff8_0000_0000_0000)).as_str(), "-nan");
+        assert_eq!(f64_text(f64::from_bits(0x7ff0_0000_0000_0abc)).as_str(), "nan:0xabc");
+        assert_eq!(f64_text(f64::from_bits(0xfff0_0000_0000_0abc)).as_str(), "-nan:0xabc");
+        assert_eq!(f64_text(f64::from_bits(0x7ff0_0000_0000_0000)).as_str(), "inf");
         // assert_eq!(f64_parts(f64::from_bits(0xbfe0_0000_0000_0000)), (0,0,0));        
-        assert_eq!(f64_hex(f64::from_bits(0xbfe0_0000_0000_0000)), "-0x1p-1");
+        assert_eq!(f64_text(f64::from_bits(0xbfe0_0000_0000_0000)).as_str(), "-0x1p-1");
     }    
 }
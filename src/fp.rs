@@ -0,0 +1,97 @@
+//! Float operations used by the interpreter. With the `soft-float` feature these come from
+//! the integer-only implementation in `soft_float`; otherwise they use the target's native
+//! float instructions. Both give bit-identical results: the native arithmetic takes the NaN
+//! it returns for NaN operands from `soft_float`, as the hardware's choice between two NaNs
+//! depends on how the code generator orders them.
+
+#[cfg(feature="soft-float")]
+pub use soft_float::{single, double, promote, demote};
+
+#[cfg(not(feature="soft-float"))]
+pub use self::native::{single, double, promote, demote};
+
+/// The canonical `f32` NaN: positive and quiet, with an otherwise empty payload.
+pub const CANONICAL_NAN_F32: u32 = 0x7fc0_0000;
+/// The canonical `f64` NaN: positive and quiet, with an otherwise empty payload.
+pub const CANONICAL_NAN_F64: u64 = 0x7ff8_0000_0000_0000;
+
+/// Replaces any NaN with the canonical NaN.
+pub fn canonical_f32(a: f32) -> f32 {
+    if single::is_nan(a) { f32::from_bits(CANONICAL_NAN_F32) } else { a }
+}
+
+/// Replaces any NaN with the canonical NaN.
+pub fn canonical_f64(a: f64) -> f64 {
+    if double::is_nan(a) { f64::from_bits(CANONICAL_NAN_F64) } else { a }
+}
+
+#[cfg(not(feature="soft-float"))]
+mod native {
+    macro_rules! native {
+        ($m:ident, $f:ident, $sqrt:ident, $ceil:ident, $floor:ident, $trunc:ident, $nearest:ident) => {
+            pub mod $m {
+                use core::intrinsics;
+                use soft_float::$m::propagate_nan;
+
+                pub fn add(a: $f, b: $f) -> $f { if a.is_nan() || b.is_nan() { propagate_nan(a, b) } else { a + b } }
+                pub fn sub(a: $f, b: $f) -> $f { if a.is_nan() || b.is_nan() { propagate_nan(a, b) } else { a - b } }
+                pub fn mul(a: $f, b: $f) -> $f { if a.is_nan() || b.is_nan() { propagate_nan(a, b) } else { a * b } }
+                pub fn div(a: $f, b: $f) -> $f { if a.is_nan() || b.is_nan() { propagate_nan(a, b) } else { a / b } }
+                pub fn sqrt(a: $f) -> $f { intrinsics::$sqrt(a) }
+                pub fn ceil(a: $f) -> $f { intrinsics::$ceil(a) }
+                pub fn floor(a: $f) -> $f { intrinsics::$floor(a) }
+                pub fn trunc(a: $f) -> $f { intrinsics::$trunc(a) }
+                pub fn nearest(a: $f) -> $f { intrinsics::$nearest(a) }
+                pub fn is_nan(a: $f) -> bool { a.is_nan() }
+                pub fn eq(a: $f, b: $f) -> bool { a == b }
+                pub fn lt(a: $f, b: $f) -> bool { a < b }
+                pub fn le(a: $f, b: $f) -> bool { a <= b }
+                pub fn from_u64(x: u64) -> $f { x as $f }
+                pub fn from_i64(x: i64) -> $f { x as $f }
+                pub fn from_u32(x: u32) -> $f { x as $f }
+                pub fn from_i32(x: i32) -> $f { x as $f }
+                pub fn to_i64(a: $f) -> i64 { a as i64 }
+                pub fn to_u64(a: $f) -> u64 { a as u64 }
+                pub fn to_i32(a: $f) -> i32 { a as i32 }
+                pub fn to_u32(a: $f) -> u32 { a as u32 }
+            }
+        }
+    }
+
+    native!(single, f32, sqrtf32, ceilf32, floorf32, truncf32, round_ties_even_f32);
+    native!(double, f64, sqrtf64, ceilf64, floorf64, truncf64, round_ties_even_f64);
+
+    pub fn promote(a: f32) -> f64 { a as f64 }
+    pub fn demote(a: f64) -> f32 { a as f32 }
+}
+
+#[cfg(all(test, feature="compiler"))]
+mod tests {
+    use interp::{Config, Interp};
+    use module_inst::Value;
+    use test_support::Fixture;
+
+    // (func (result i32) f32.const nan:0x401234 f32.const 1 f32.add i32.reinterpret_f32)
+    // (func (result i32) f32.const nan:0x401234 f32.neg i32.reinterpret_f32)
+    const NAN_MODULE: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+        0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f,
+        0x03, 0x03, 0x02, 0x00, 0x00,
+        0x0a, 0x1a, 0x02,
+            0x0e, 0x00, 0x43, 0x34, 0x12, 0xc0, 0x7f, 0x43, 0x00, 0x00, 0x80, 0x3f, 0x92, 0xbc, 0x0b,
+            0x09, 0x00, 0x43, 0x34, 0x12, 0xc0, 0x7f, 0x8c, 0xbc, 0x0b,
+    ];
+
+    #[test]
+    fn test_deterministic_nan() {
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load(&mut buf, NAN_MODULE);
+        assert_eq!(f.call(0, &[]).unwrap(), &[Value::from(0x7fc01234)]);
+        assert_eq!(f.call(1, &[]).unwrap(), &[Value::from(0xffc01234u32 as i32)]);
+
+        let mut interp_buf = [0u8; 8192];
+        let mut interp = Interp::new_with_config(Config::default().deterministic(true), &mut interp_buf).unwrap();
+        assert_eq!(interp.call(&f.env, f.mi, 0).unwrap(), &[Value::from(0x7fc00000)]);
+        assert_eq!(interp.call(&f.env, f.mi, 1).unwrap(), &[Value::from(0xffc01234u32 as i32)]);
+    }
+}
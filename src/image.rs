@@ -0,0 +1,481 @@
+//! Ahead-of-time compiled images.
+//!
+//! An image holds a module's compiled code along with everything `ModuleInst` needs to
+//! instantiate it, so that `Environment::load_image` can load it without the parser or the
+//! compiler. Function types, names, passive data segments and code are used in place, so
+//! an image can be run straight from flash; only tables, element segments and the
+//! instance's own lists are built in RAM.
+//!
+//! Images are not trusted. The lists, the code index and the indices that refer into them
+//! are checked when the image is loaded, and anything read from the code itself is checked
+//! as it is executed, so a corrupted image fails with an error rather than panicking.
+//!
+//! Images are written with `write_image` from a freshly instantiated module. All integers
+//! are little endian `u32`s unless noted, lists are preceded by their length and byte
+//! strings by their length in bytes:
+//!
+//! ```text
+//! header     magic "bwai", IMAGE_VERSION, flags
+//! types      parameters: [u8], results: [u8]
+//! functions  kind: u8, type index, then module, name, host or module index and import
+//!            index for imports, or the function index for local functions
+//! globals    kind: u8, value type: u8, mutable: u8, then the import index for imports,
+//!            or the global index and the value as two u64 words for local globals
+//! tables     element type: u8, size, capacity, size elements
+//! tags       attribute: u8, type index
+//! exports    name, kind: u8, index
+//! elements   [u32]
+//! data       [u8]
+//! memory     present: u8, minimum pages, maximum pages or NO_MAX
+//! active     offset, [u8], for each active data segment
+//! code       [u8]
+//! ```
+
+use error::Error;
+use cursor::Cursor;
+use types::ValueType;
+use module_inst::{RawValue, Value};
+
+use core::{slice, str};
+
+#[cfg(feature="compiler")]
+use writer::Writer;
+#[cfg(feature="compiler")]
+use module_inst::{ModuleInst, FuncInst, GlobalInst};
+#[cfg(feature="compiler")]
+use parser::FallibleIterator;
+#[cfg(feature="compiler")]
+use parser::module::{Module, Id, ExportDesc, SegmentMode, Data};
+
+/// The first word of every image, "bwai" read as a little endian word.
+pub const IMAGE_MAGIC: u32 = 0x6961_7762;
+/// The version of the image format written by this crate.
+pub const IMAGE_VERSION: u32 = 3;
+
+/// Set when the code was compiled with the `simd` feature. Such images can only be loaded
+/// by builds that also have it.
+pub const FLAG_SIMD: u32 = 1 << 0;
+
+#[cfg(not(feature="simd"))]
+const FLAGS: u32 = 0;
+#[cfg(feature="simd")]
+const FLAGS: u32 = FLAG_SIMD;
+
+/// The maximum number of pages written for a memory with no declared maximum.
+pub const NO_MAX: u32 = 0xffff_ffff;
+
+pub(crate) const FUNC_HOST: u8 = 0;
+pub(crate) const FUNC_IMPORT: u8 = 1;
+pub(crate) const FUNC_LOCAL: u8 = 2;
+
+pub(crate) const GLOBAL_IMPORT: u8 = 0;
+pub(crate) const GLOBAL_LOCAL: u8 = 1;
+
+/// Reads the fields of an image, failing with `InvalidImage` rather than panicking when it
+/// is truncated.
+pub(crate) struct ImageReader<'a> {
+    cur: Cursor<'a>,
+}
+
+impl<'a> ImageReader<'a> {
+    /// Checks the header of `image`, returning a reader positioned after it.
+    pub fn new(image: &'a [u8]) -> Result<Self, Error> {
+        let mut r = ImageReader { cur: Cursor::new(image) };
+        if r.read_u32()? != IMAGE_MAGIC {
+            return Err(Error::InvalidHeader)
+        }
+        let version = r.read_u32()?;
+        let flags = r.read_u32()?;
+        if version != IMAGE_VERSION || flags & !FLAGS != 0 {
+            return Err(Error::UnsupportedImage { version, flags })
+        }
+        Ok(r)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if len > self.cur.len() {
+            return Err(Error::InvalidImage)
+        }
+        Ok(self.cur.slice(len))
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, Error> {
+        Ok(Cursor::new(self.take(4)?).read_u32())
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, Error> {
+        Ok(Cursor::new(self.take(8)?).read_u64())
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, Error> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::InvalidImage),
+        }
+    }
+
+    /// Reads the length of a list whose items take at least `size` bytes each, so that
+    /// lengths larger than the rest of the image are rejected before anything is allocated.
+    pub fn read_len(&mut self, size: usize) -> Result<usize, Error> {
+        let len = self.read_u32()? as usize;
+        match len.checked_mul(size) {
+            Some(bytes) if bytes <= self.cur.len() => Ok(len),
+            _ => Err(Error::InvalidImage),
+        }
+    }
+
+    pub fn read_bytes(&mut self) -> Result<&'a [u8], Error> {
+        let len = self.read_len(1)?;
+        self.take(len)
+    }
+
+    pub fn read_str(&mut self) -> Result<&'a str, Error> {
+        Ok(str::from_utf8(self.read_bytes()?)?)
+    }
+
+    pub fn read_value_type(&mut self) -> Result<ValueType, Error> {
+        ValueType::from_u8(self.read_u8()?).ok_or(Error::InvalidImage)
+    }
+
+    pub fn read_value_types(&mut self) -> Result<&'a [ValueType], Error> {
+        // Used in place, so every byte is checked before it is reinterpreted
+        let buf = self.read_bytes()?;
+        for &b in buf {
+            ValueType::from_u8(b).ok_or(Error::InvalidImage)?;
+        }
+        Ok(unsafe { slice::from_raw_parts(buf.as_ptr() as *const ValueType, buf.len()) })
+    }
+
+    pub fn read_value(&mut self) -> Result<Value, Error> {
+        let lo = self.read_u64()?;
+        let hi = self.read_u64()?;
+        if cfg!(feature="simd") {
+            Ok(Value(lo as RawValue | (hi as RawValue) << 32 << 32))
+        } else if hi == 0 {
+            Ok(Value(lo as RawValue))
+        } else {
+            Err(Error::InvalidImage)
+        }
+    }
+
+    /// Fails unless the whole image has been read.
+    pub fn finish(self) -> Result<(), Error> {
+        if self.cur.done() { Ok(()) } else { Err(Error::InvalidImage) }
+    }
+}
+
+/// Writes an image of `mi`, which must have just been compiled from `m` by `load_module`, to
+/// `buf` and returns its length. `m` supplies the memory and the active data segments, which
+/// have already been applied to the instance and are written into memory again when the
+/// image is loaded.
+#[cfg(feature="compiler")]
+pub fn write_image(buf: &mut [u8], m: &Module, mi: &ModuleInst) -> Result<usize, Error> {
+    if mi.code().is_lazy() {
+        return Err(Error::Unimplemented("images of lazily compiled modules"))
+    }
+    if mi.side_table().is_some() {
+        return Err(Error::Unimplemented("images of modules executed in place"))
+    }
+    let mut w = Writer::new(buf);
+
+    w.write_u32(IMAGE_MAGIC)?;
+    w.write_u32(IMAGE_VERSION)?;
+    w.write_u32(FLAGS)?;
+
+    let types = mi.function_types();
+    w.write_len(types.len())?;
+    for t in types {
+        write_bytes(&mut w, value_type_bytes(t.parameters))?;
+        write_bytes(&mut w, value_type_bytes(t.results))?;
+    }
+
+    let functions = mi.functions();
+    w.write_len(functions.len())?;
+    for f in functions {
+        match *f {
+            FuncInst::Host { type_index, module, name, host_index: _ } => {
+                w.write_u8(FUNC_HOST)?;
+                w.write_u32(type_index as u32)?;
+                write_bytes(&mut w, module.as_bytes())?;
+                write_bytes(&mut w, name.as_bytes())?;
+            },
+            FuncInst::Import { type_index, module, name, module_index, import_index } => {
+                w.write_u8(FUNC_IMPORT)?;
+                w.write_u32(type_index as u32)?;
+                write_bytes(&mut w, module.as_bytes())?;
+                write_bytes(&mut w, name.as_bytes())?;
+                w.write_u32(module_index as u32)?;
+                w.write_u32(import_index as u32)?;
+            },
+            FuncInst::Local { type_index, function_index } => {
+                w.write_u8(FUNC_LOCAL)?;
+                w.write_u32(type_index as u32)?;
+                w.write_u32(function_index as u32)?;
+            },
+        }
+    }
+
+    let globals = mi.globals();
+    w.write_len(globals.len())?;
+    for g in globals {
+        let global_type = g.global_type();
+        match *g {
+            GlobalInst::Import { import_index, .. } => {
+                w.write_u8(GLOBAL_IMPORT)?;
+                w.write_u8(global_type.valtype as u8)?;
+                w.write_u8(global_type.mutable as u8)?;
+                w.write_u32(import_index as u32)?;
+            },
+            GlobalInst::Local { global_index, ref value, .. } => {
+                w.write_u8(GLOBAL_LOCAL)?;
+                w.write_u8(global_type.valtype as u8)?;
+                w.write_u8(global_type.mutable as u8)?;
+                w.write_u32(global_index as u32)?;
+                let value = value.get();
+                w.write_i64(value.as_i64())?;
+                w.write_i64(Value(value.0 >> 32 >> 32).as_i64())?;
+            },
+        }
+    }
+
+    let tables = mi.tables();
+    w.write_len(tables.len())?;
+    for t in tables {
+        w.write_u8(t.elemtype() as u8)?;
+        w.write_u32(t.size())?;
+        w.write_u32(t.cap())?;
+        for i in 0..t.size() {
+            w.write_u32(t.get(i)?)?;
+        }
+    }
+
+    let tags = mi.tags();
+    w.write_len(tags.len())?;
+    for tag in tags {
+        w.write_u8(tag.attribute)?;
+        w.write_u32(tag.type_index)?;
+    }
+
+    let exports = mi.exports();
+    w.write_len(exports.len())?;
+    for e in exports {
+        write_bytes(&mut w, e.name.as_bytes())?;
+        let (kind, index) = match e.export_desc {
+            ExportDesc::Func(index) => (0x00, index),
+            ExportDesc::Table(index) => (0x01, index),
+            ExportDesc::Memory(index) => (0x02, index),
+            ExportDesc::Global(index) => (0x03, index),
+            ExportDesc::Tag(index) => (0x04, index),
+        };
+        w.write_u8(kind)?;
+        w.write_u32(index)?;
+    }
+
+    w.write_len(mi.element_segment_count())?;
+    for i in 0..mi.element_segment_count() {
+        let items = mi.element_segment(i as u32)?;
+        w.write_len(items.len())?;
+        for &item in items {
+            w.write_u32(item)?;
+        }
+    }
+
+    w.write_len(mi.data_segment_count())?;
+    for i in 0..mi.data_segment_count() {
+        write_bytes(&mut w, mi.data_segment(i as u32)?)?;
+    }
+
+    let mut memory = None;
+    let mut active = 0;
+    let mut sections = m.sections();
+    while let Some(section) = sections.next()? {
+        match section.id() {
+            Id::Memory => memory = section.memory().next()?.map(|m| m.limits),
+            Id::Data => {
+                let mut segments = section.data();
+                while let Some(Data { mode, .. }) = segments.next()? {
+                    if let SegmentMode::Active { .. } = mode {
+                        active += 1;
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+    match memory {
+        Some(limits) => {
+            w.write_u8(1)?;
+            w.write_u32(limits.min)?;
+            w.write_u32(limits.max.unwrap_or(NO_MAX))?;
+        },
+        None => {
+            w.write_u8(0)?;
+            w.write_u32(0)?;
+            w.write_u32(NO_MAX)?;
+        }
+    }
+
+    w.write_len(active)?;
+    let mut sections = m.sections();
+    while let Some(section) = sections.next()? {
+        if section.id() != Id::Data { continue }
+        let mut segments = section.data();
+        let mut index = 0;
+        while let Some(Data { mode, init }) = segments.next()? {
+            if let SegmentMode::Active { offset, .. } = mode {
+                let offset = offset.i32_value().ok_or(Error::InvalidDataSegment { id: index })?;
+                w.write_u32(offset as u32)?;
+                write_bytes(&mut w, init)?;
+            }
+            index += 1;
+        }
+    }
+
+    write_bytes(&mut w, mi.code().as_ref())?;
+
+    Ok(w.pos())
+}
+
+#[cfg(feature="compiler")]
+fn write_bytes(w: &mut Writer, bytes: &[u8]) -> Result<(), Error> {
+    w.write_len(bytes.len())?;
+    for &b in bytes {
+        w.write_u8(b)?;
+    }
+    Ok(())
+}
+
+#[cfg(feature="compiler")]
+fn value_type_bytes(types: &[ValueType]) -> &[u8] {
+    // ValueType is repr(u8)
+    unsafe { slice::from_raw_parts(types.as_ptr() as *const u8, types.len()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_support::*;
+    use environ::Environment;
+
+    // The image of `TAIL_CALL_MODULE`, so that images can be tested without the compiler
+    const TAIL_CALL_IMAGE: &[u8] = &[
+        0x62, 0x77, 0x61, 0x69, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x7f, 0x7f, 0x01, 0x00, 0x00, 0x00, 0x7f, 0x00, 0x00, 0x00, 0x00, 0x01,
+        0x00, 0x00, 0x00, 0x7f, 0x04, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x02, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02, 0x01, 0x00, 0x00, 0x00, 0x02,
+        0x00, 0x00, 0x00, 0x02, 0x01, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0x00, 0x00, 0x70, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00,
+        0x00, 0x00, 0x7a, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x24, 0x00, 0x00, 0x00, 0x51, 0x00,
+        0x00, 0x00, 0x51, 0x00, 0x00, 0x00, 0x5d, 0x00, 0x00, 0x00, 0x5d, 0x00, 0x00, 0x00, 0x70, 0x00,
+        0x00, 0x00, 0x70, 0x00, 0x00, 0x00, 0x7a, 0x00, 0x00, 0x00, 0xef, 0x02, 0x01, 0xeb, 0x00, 0x45,
+        0xe8, 0x06, 0x00, 0xeb, 0x01, 0xea, 0x03, 0x01, 0x0f, 0xeb, 0x00, 0xec, 0x02, 0xee, 0x63, 0x00,
+        0xeb, 0x02, 0xee, 0x01, 0x00, 0x6b, 0xeb, 0x01, 0xeb, 0x02, 0x6a, 0xea, 0x04, 0x02, 0x12, 0x00,
+        0x00, 0x00, 0x00, 0xea, 0x03, 0x01, 0x0f, 0xee, 0xe8, 0x03, 0xee, 0x00, 0x00, 0x12, 0x00, 0x00,
+        0x00, 0x00, 0x0f, 0xee, 0x64, 0x00, 0xee, 0x00, 0x00, 0xee, 0x00, 0x00, 0x13, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x0f, 0x10, 0x01, 0x00, 0x00, 0x00, 0xee, 0x01, 0x00, 0x6a, 0x0f,
+    ];
+
+    #[cfg(feature="compiler")]
+    fn image_of(image: &mut [u8], module: &[u8]) -> usize {
+        let mut buf = [0u8; 32768];
+        let (buf, mut env) = Environment::new(&mut buf, Handler {}).unwrap();
+        let (_, mi) = env.load_module("image", buf, module).unwrap();
+        write_image(image, &Module::new(module).unwrap(), mi).unwrap()
+    }
+
+    fn load_err(image: &[u8]) -> Option<Error> {
+        let mut buf = [0u8; 32768];
+        let (buf, mut env) = Environment::new(&mut buf, Handler {}).unwrap();
+        env.load_image("data", buf, image).err()
+    }
+
+    #[test]
+    fn test_precompiled_image() {
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load_image(&mut buf, TAIL_CALL_IMAGE);
+        assert_eq!(f.call(2, &[]).unwrap(), &[Value(5050)]);
+        assert_eq!(f.call(3, &[]).unwrap(), &[Value(500501)]);
+        assert_eq!(f.interp.stack_len(), 0);
+    }
+
+    #[test]
+    fn test_corrupt_image() {
+        // The code follows its length at 0x82, starting with the number of bodies and
+        // their ranges
+        let mut image = [0u8; 256];
+        image.copy_from_slice(TAIL_CALL_IMAGE);
+        image[0x86] = 0xff;
+        assert_eq!(load_err(&image), Some(Error::InvalidImage));
+        image[0x86] = 1;
+        assert_eq!(load_err(&image), Some(Error::InvalidFunction { id: 1 }));
+        image[0x86] = 4;
+        image[0x8e] = 0xff;
+        assert_eq!(load_err(&image), Some(Error::InvalidImage));
+        image[0x8e] = 0x51;
+
+        // Calls in the code are checked when they are made
+        image[0xf7] = 0x7f;
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load_image(&mut buf, &image);
+        assert_eq!(f.call(2, &[]).unwrap(), &[Value(5050)]);
+        assert_eq!(f.call_err(3, &[]), Error::InvalidFunction { id: 0x7f });
+    }
+
+    #[cfg(feature="compiler")]
+    #[test]
+    fn test_image() {
+        let mut image = [0u8; 4096];
+        let len = image_of(&mut image, REFERENCE_TYPES_MODULE);
+        let image = &image[..len];
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load_image(&mut buf, image);
+
+        // Code is run from the image rather than copied
+        assert!(image.as_ptr_range().contains(&f.mi.code().as_ref().as_ptr()));
+        assert_eq!(f.call(2, &[]).unwrap(), &[Value(7)]);
+        assert_eq!(f.call(3, &[]).unwrap(), &[Value(11)]);
+        assert_eq!(f.call(4, &[]).unwrap(), &[Value(4)]);
+        assert_eq!(f.call_err(6, &[]), Error::TableOutOfBounds);
+        assert_eq!(f.call(9, &[]).unwrap(), &[Value(7)]);
+
+        // The checked-in image is current, apart from the flags in the header
+        let mut image = [0u8; 4096];
+        let len = image_of(&mut image, TAIL_CALL_MODULE);
+        assert_eq!(&image[12..len], &TAIL_CALL_IMAGE[12..]);
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load_image(&mut buf, &image[..len]);
+        assert_eq!(f.call(2, &[]).unwrap(), &[Value(5050)]);
+        assert_eq!(f.call(3, &[]).unwrap(), &[Value(500501)]);
+
+        let mut image = [0u8; 4096];
+        let len = image_of(&mut image, BULK_MEMORY_MODULE);
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load_image(&mut buf, &image[..len]);
+        assert_eq!(f.call(0, &[]).unwrap(), &[Value(0x6f6c6c65)]);
+
+        // Active data segments are written when the image is loaded
+        let mut image = [0u8; 4096];
+        let len = image_of(&mut image, ACTIVE_DATA_MODULE);
+        let image = &mut image[..len];
+        {
+            let mut buf = [0u8; 32768];
+            let f = Fixture::load_image(&mut buf, image);
+            assert_eq!(f.env.mem().num_pages(), 18);
+            assert_eq!(f.env.mem().load(0x100ffe).unwrap(), 0x64636261);
+        }
+
+        assert_eq!(load_err(&image[..len - 1]), Some(Error::InvalidImage));
+        let flags = image[8] as u32;
+        image[4] = (IMAGE_VERSION + 1) as u8;
+        assert_eq!(load_err(image), Some(Error::UnsupportedImage { version: IMAGE_VERSION + 1, flags }));
+        image[0] = 0;
+        assert_eq!(load_err(image), Some(Error::InvalidHeader));
+    }
+}
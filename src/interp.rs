@@ -3,16 +3,73 @@ use {Error};
 // use module_inst::{ FuncInst};
 use environ::{Environment, HostHandler};
 use module_inst::{ModuleInst, FuncInst, Value};
+use table_inst::NULL_REF;
+use memory_inst::MemoryInst;
 use reader::Reader;
 use writer::Writer;
-use stack::Stack;
+use stack::{self, Stack};
+use side_table::SideTable;
 use opcode::*;
+#[cfg(feature="simd")]
+use simd;
+
+#[cfg(not(feature="integer-only"))]
+use fp::{single, double, promote, demote, canonical_f32, canonical_f64};
+
+use core::{fmt, mem};
 
 pub type InterpResult<T> = Result<T, Error>;
 
+/// Maximum number of frames captured in a `Trap`.
+pub const TRAP_FRAMES: usize = 16;
+
+/// Called before each instruction is executed with the interpreter, the offset of the
+/// instruction in the code and its opcode.
+pub type TraceHook = fn(&Interp, usize, u8);
+
 pub struct Config {
     value_stack_size: usize,
     call_stack_size: usize,
+    handler_stack_size: usize,
+    exception_stack_size: usize,
+    deterministic: bool,
+    trace: Option<TraceHook>,
+}
+
+impl Config {
+    /// Enables deterministic execution, so that a module run with the same inputs gives
+    /// bit-identical results and memory on every target. NaNs produced by float arithmetic
+    /// are replaced by the canonical NaN, and `memory.grow` only depends on the maximum
+    /// the module declares, trapping with `Error::Nondeterministic` if the host cannot
+    /// provide the memory.
+    pub fn deterministic(self, deterministic: bool) -> Config {
+        Config { deterministic, ..self }
+    }
+
+    /// Sets the number of values the value stack holds. Every call frame takes its
+    /// parameters and locals from it, so this bounds how many locals a function can have.
+    /// Each value takes 8 bytes, or 16 with the `simd` feature.
+    pub fn value_stack_size(self, value_stack_size: usize) -> Config {
+        Config { value_stack_size, ..self }
+    }
+
+    /// Sets the number of words the call stack holds, two for every nested call.
+    pub fn call_stack_size(self, call_stack_size: usize) -> Config {
+        Config { call_stack_size, ..self }
+    }
+
+    /// Installs a hook that is called before every instruction. The dispatch loop does no
+    /// logging of its own; `log_trace` logs each instruction and the value stack.
+    pub fn trace(self, trace: TraceHook) -> Config {
+        Config { trace: Some(trace), ..self }
+    }
+
+    /// The number of bytes `Interp::new_with_config` takes from a buffer aligned for `Value`.
+    pub fn buffer_size(&self) -> usize {
+        (self.value_stack_size + self.exception_stack_size) * mem::size_of::<Value>() +
+        self.call_stack_size * mem::size_of::<u32>() +
+        self.handler_stack_size * mem::size_of::<ExceptionHandler>()
+    }
 }
 
 impl Default for Config {
@@ -20,37 +77,171 @@ impl Default for Config {
         Config {
             value_stack_size: 64,
             call_stack_size: 64,
+            handler_stack_size: 16,
+            exception_stack_size: 16,
+            deterministic: false,
+            trace: None,
         }
     }
 }
 
+/// A `TraceHook` that logs the value stack and the instruction about to be executed.
+#[allow(unused_variables)]
+pub fn log_trace(interp: &Interp, offset: usize, opcode: u8) {
+    interp.dump_stack();
+    if let Some(op) = Op::from_opcode(opcode) {
+        info!("V: {} 0x{:08x}: {}", interp.stack_len(), offset, op.text);
+    }
+}
+
+/// A function activation that was live when a trap occurred. `offset` is relative to the
+/// start of the function's compiled body.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Frame {
+    pub function_index: usize,
+    pub offset: usize,
+}
+
+/// The error returned by `Interp::call`, along with the frames that were active when it
+/// occurred, innermost first. The frames are kept by the interpreter until its next call.
+#[derive(Debug)]
+pub struct Trap<'a> {
+    error: Error,
+    frames: &'a [Frame],
+}
+
+impl<'a> Trap<'a> {
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        self.frames
+    }
+}
+
+impl<'a> fmt::Display for Trap<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+#[cfg(feature="std")]
+impl<'a> ::std::error::Error for Trap<'a> {
+    fn source(&self) -> Option<&(dyn (::std::error::Error) + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<'a> From<Trap<'a>> for Error {
+    fn from(other: Trap<'a>) -> Error {
+        other.error
+    }
+}
+
+/// An entry on the handler stack, live only while the frame at call depth `depth` is
+/// executing code in `start..end`. Branching out of a try block or catch clause leaves
+/// a stale entry behind, which is discarded the next time the stack is searched.
+#[derive(Debug, Clone, Copy)]
+struct ExceptionHandler {
+    depth: u32,
+    start: u32,
+    end: u32,
+    kind: HandlerKind,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum HandlerKind {
+    /// The body of a try block. `clauses` is the offset of the first catch clause and
+    /// `base` is the value stack height to unwind to.
+    Try { clauses: u32, try_end: u32, base: u32 },
+    /// An exception being handled by a catch clause. Its payload is kept on the
+    /// exception stack at `base` so that it can be rethrown.
+    Caught { tag: u32, base: u32, len: u32 },
+}
+
+/// An exception being thrown: its tag and the number of payload values it carries.
+#[derive(Debug, Clone, Copy)]
+struct Exception {
+    tag: u32,
+    len: u32,
+}
+
+/// The state of a run of code executed in place: its side table, the depth of the call
+/// stack that returns to the host, and the frame pointer of the current function.
+struct InPlace<'t> {
+    side_table: &'t SideTable<'t>,
+    call_base: usize,
+    fp: usize,
+}
+
+/// What `run_in_place` does after an instruction executed in place.
+enum Step {
+    /// Continue with the next instruction.
+    Next,
+    /// Return from the function `run_in_place` was called for.
+    Return,
+    /// Execute the instruction like compiled code, which only differs in its immediates.
+    Shared,
+}
+
 pub struct Interp<'a> {
-    #[allow(dead_code)]
     cfg: Config,
     value_stack: Stack<'a, Value>,
+    exception_stack: Stack<'a, Value>,
     call_stack: Stack<'a, u32>,
+    handler_stack: Stack<'a, ExceptionHandler>,
+    frames: [Frame; TRAP_FRAMES],
+    frames_len: usize,
 }
 
 impl<'a> Interp<'a> {
-    pub fn new(buf: &'a mut [u8]) -> Self {
+    /// Creates an interpreter with its stacks allocated from `buf`. Fails with
+    /// `Error::OutOfMemory` if `buf` is too small for the stacks sized by the configuration.
+    pub fn new(buf: &'a mut [u8]) -> Result<Self, Error> {
         Self::new_with_config(Config::default(), buf)
     }
 
-    pub fn new_with_config(cfg: Config, buf: &'a mut [u8]) -> Self {
+    /// Creates an interpreter with its stacks sized by `cfg` and allocated from `buf`.
+    pub fn new_with_config(cfg: Config, buf: &'a mut [u8]) -> Result<Self, Error> {
         let mut w = Writer::new(buf);
-        let value_stack = w.alloc_stack(cfg.value_stack_size);
-        let call_stack = w.alloc_stack(cfg.call_stack_size);
-        Interp { cfg, value_stack, call_stack }
+        w.align_to::<Value>()?;
+        w.split::<()>();
+        let value_stack = w.alloc_stack(cfg.value_stack_size)?;
+        let exception_stack = w.alloc_stack(cfg.exception_stack_size)?;
+        let call_stack = w.alloc_stack(cfg.call_stack_size)?;
+        let handler_stack = w.alloc_stack(cfg.handler_stack_size)?;
+        let frames = [Frame::default(); TRAP_FRAMES];
+        let frames_len = 0;
+        Ok(Interp { cfg, value_stack, exception_stack, call_stack, handler_stack, frames, frames_len })
+    }
+
+    #[cfg(not(feature="integer-only"))]
+    fn canon_f32(&self, v: f32) -> f32 {
+        if self.cfg.deterministic { canonical_f32(v) } else { v }
+    }
+
+    #[cfg(not(feature="integer-only"))]
+    fn canon_f64(&self, v: f64) -> f64 {
+        if self.cfg.deterministic { canonical_f64(v) } else { v }
     }
 
     // Value Stack
 
     pub fn push(&mut self, value: i32) -> Result<(), Error> {
-        Ok(self.value_stack.push(Value(value))?)
+        Ok(self.value_stack.push(Value::from(value))?)
     }
 
     pub fn pop(&mut self) -> Result<i32, Error> {
-        Ok(self.value_stack.pop()?.0)
+        Ok(self.value_stack.pop()?.as_i32())
+    }
+
+    pub fn push_value<T: Into<Value>>(&mut self, value: T) -> Result<(), Error> {
+        Ok(self.value_stack.push(value.into())?)
+    }
+
+    pub fn pop_value(&mut self) -> Result<Value, Error> {
+        Ok(self.value_stack.pop()?)
     }
 
     pub fn stack_len(&self) -> usize {
@@ -65,49 +256,505 @@ impl<'a> Interp<'a> {
         info!("---");
     }
 
-    pub fn call<H: HostHandler>(&mut self, env: &Environment<H>, mi: &ModuleInst, func_index: usize) -> Result<Option<Value>, Error> {
-        let code_buf = mi.code().as_ref();                    
+    /// Calls local function `func_index`, whose parameters must already have been pushed,
+    /// and returns its results in order. The results are popped from the value stack.
+    ///
+    /// If the call fails, the value and call stacks are restored to their state before
+    /// the parameters were pushed, so that the interpreter can be used for further calls.
+    pub fn call<H: HostHandler>(&mut self, env: &Environment<H>, mi: &ModuleInst, func_index: usize) -> Result<&[Value], Trap<'_>> {
+        let (param_count, result_count) = mi.local_function_type(func_index)
+            .map(|t| (t.parameters.len(), t.results.len()))
+            .unwrap_or((0, 0));
+        let value_base = self.value_stack.len().saturating_sub(param_count);
+        let call_base = self.call_stack.len();
+        self.frames_len = 0;
+
+        let error = match self.execute(env, mi, func_index) {
+            Ok(()) => {
+                let len = self.value_stack.len();
+                if len == value_base + result_count {
+                    return Ok(self.value_stack.split_off(value_base).unwrap_or(&[]))
+                }
+                Error::UnexpectedReturnLength { got: len.saturating_sub(value_base) as u32 }
+            },
+            Err(e) => e,
+        };
+
+        let _ = self.value_stack.set_pos(value_base);
+        let _ = self.call_stack.set_pos(call_base);
+        Err(Trap { error, frames: &self.frames[..self.frames_len] })
+    }
+
+    /// Executes local function `func_index`, leaving its results on the value stack.
+    pub(crate) fn execute<H: HostHandler>(&mut self, env: &Environment<H>, mi: &ModuleInst, func_index: usize) -> Result<(), Error> {
+        let mut code;
+        let mut fp = 0;
+        if let Some(side_table) = mi.side_table() {
+            code = Reader::new_leb(side_table.module());
+            fp = self.enter_in_place(side_table, &mut code, func_index)?;
+        } else {
+            let body_range = mi.body_range(env, func_index)?;        
+            info!("body: {:08x} to {:08x}", body_range.start, body_range.end);
+
+            let code_buf = mi.code().as_ref();                    
+            info!("code section len: {:08x}", code_buf.len());
+
+            code = Reader::new(code_buf);
+            code.set_pos(body_range.start);
+        }
+
+        let call_base = self.call_stack.len();
+        let handler_base = self.handler_stack.len();
+        let exception_base = self.exception_stack.len();
+        let mut pc = code.pos();
+
+        let result = match mi.side_table() {
+            Some(side_table) => self.run_in_place(env, mi, &mut InPlace { side_table, call_base, fp }, &mut code, &mut pc),
+            None => self.run(env, mi, &mut code, call_base, fp, &mut pc),
+        };
+        if result.is_err() {
+            self.capture_frames(mi, pc, call_base);
+        }
+        // Exceptions cannot propagate past this call, so drop any handlers it left behind
+        let _ = self.handler_stack.set_pos(handler_base);
+        let _ = self.exception_stack.set_pos(exception_base);
+        result
+    }
+
+    fn capture_frames(&mut self, mi: &ModuleInst, pc: usize, call_base: usize) {
+        self.push_frame(mi, pc);
+        // Each call keeps the caller's frame pointer after the return address
+        let mut i = self.call_stack.len();
+        while i >= call_base + 2 {
+            i -= 2;
+            if let Ok(ret) = self.call_stack.get(i) {
+                self.push_frame(mi, ret as usize);
+            }
+        }
+    }
 
-        info!("code section len: {:08x}", code_buf.len());
+    fn push_frame(&mut self, mi: &ModuleInst, offset: usize) {
+        if self.frames_len == TRAP_FRAMES {
+            return
+        }
+        let found = match mi.side_table() {
+            Some(side_table) => (0..side_table.body_count())
+                .filter_map(|i| side_table.body_range(i).ok().map(|body| (i, body)))
+                .find(|(_, body)| body.start <= offset && offset < body.end),
+            None => mi.code().iter().enumerate()
+                .find(|(_, body)| body.start <= offset && offset < body.end),
+        };
+        if let Some((function_index, body)) = found {
+            let offset = offset - body.start;
+            self.frames[self.frames_len] = Frame { function_index, offset };
+            self.frames_len += 1;
+        }
+    }
+
+    /// Looks up element `index` (popped from the value stack) of `table` and checks that
+    /// the function it refers to has signature `sig`.
+    fn indirect_function(&mut self, mi: &ModuleInst, sig: u32, table: u32) -> Result<u32, Error> {
+        let sig_type = mi.function_types().get(sig as usize).ok_or(Error::InvalidSignature { id: sig })?;
+        info!("   sig_type: {:?}", sig_type);
+        let table_index = self.pop()?;
+        info!("   table_index: {:?}", table_index);
 
-        let body_range = mi.code().body_range(func_index);        
-        info!("body: {:08x} to {:08x}", body_range.start, body_range.end);
+        let table = mi.table(table)?;
+        if table_index as u32 >= table.size() {
+            return Err(Error::UndefinedTableIndex { id: table_index })
+        }
 
-        // info!("code_buf len: {:08x}", code_buf.len());
-        // for i in 0..code_buf.len() {
-        //     info!("{:08x}: {:02x}", i, code_buf[i]);
-        // }
+        let func_index = table.get(table_index as u32)?;
+        if func_index == NULL_REF {
+            return Err(Error::UninitializedElement)
+        }
+        let func = mi.functions().get(func_index as usize).ok_or(Error::InvalidFunction { id: func_index })?;
+        if let FuncInst::Local { type_index, function_index: _ } = *func {
+            let func_type = &mi.function_types()[type_index];
+            if sig_type.parameters != func_type.parameters {
+                return Err(Error::SignatureMismatch)
+            }
+            if sig_type.results != func_type.results {
+                return Err(Error::SignatureMismatch)
+            }
+        }
+        Ok(func_index)
+    }
 
-        let mut code = Reader::new(code_buf);
-        code.set_pos(body_range.start);
+    fn pop_handler_entry(&mut self) -> Result<(), Error> {
+        if let HandlerKind::Caught { tag: _, base, len: _ } = self.handler_stack.pop()?.kind {
+            self.exception_stack.set_pos(base as usize)?;
+        }
+        Ok(())
+    }
 
-        let mut _count = 0;
+    /// Discards stale handlers, leaving the innermost live handler for `pc` on top.
+    fn discard_stale_handlers(&mut self, pc: usize) -> Result<(), Error> {
+        let depth = self.call_stack.len() as u32;
+        while let Ok(h) = self.handler_stack.top() {
+            let pc = pc as u32;
+            if h.depth > depth || (h.depth == depth && !(h.start < pc && pc < h.end)) {
+                self.pop_handler_entry()?;
+            } else {
+                break;
+            }
+        }
+        Ok(())
+    }
 
-        // Set up locals
+    /// Discards all handlers belonging to the current frame before it is left.
+    fn discard_frame_handlers(&mut self) -> Result<(), Error> {
+        let depth = self.call_stack.len() as u32;
+        while let Ok(h) = self.handler_stack.top() {
+            if h.depth < depth {
+                break;
+            }
+            self.pop_handler_entry()?;
+        }
+        Ok(())
+    }
 
+    /// Throws `exception`, whose payload values are on top of the value stack, transferring
+    /// control to the innermost matching catch clause. Frames
+    /// below `call_base` are not searched; if no clause matches, the interpreter state is
+    /// left untouched and `UncaughtException` is returned. Code executed in place passes
+    /// its side table, which links each catch clause to the next.
+    fn throw(&mut self, code: &mut Reader, side_table: Option<&SideTable>, call_base: usize, pc: usize, fp: &mut usize, exception: Exception) -> Result<(), Error> {
+        let Exception { tag, len } = exception;
+        info!("THROW: tag {} len {}", tag, len);
+        let mut depth = self.call_stack.len();
+        let mut pc = pc as u32;
+        let mut i = self.handler_stack.len();
         loop {
-            self.dump_stack();
-            if code.pos() >= code.len() {
-                info!("V: {} 0x{:08x}: CODE END {:08x}", self.value_stack.len(), code.pos(), code.len());
+            // Search the frame at `depth`, innermost handler first
+            while i > 0 {
+                let h = self.handler_stack.get(i - 1)?;
+                if (h.depth as usize) < depth {
+                    break;
+                }
+                i -= 1;
+                if h.depth as usize > depth || !(h.start < pc && pc < h.end) {
+                    continue;
+                }
+                if let HandlerKind::Try { clauses, try_end, base } = h.kind {
+                    let mut clause = clauses;
+                    while clause != try_end {
+                        code.set_pos(clause as usize);
+                        let opc = code.read_u8()?;
+                        let catches = match opc {
+                            CATCH => code.read_u32()? == tag,
+                            CATCH_ALL => true,
+                            _ => return Err(Error::InvalidOpcode(opc)),
+                        };
+                        let next = match side_table {
+                            Some(side_table) => side_table.entry(clause as usize, 1)?.target,
+                            None => code.read_u32()?,
+                        };
+                        if catches {
+                            self.unwind(i, depth, fp)?;
+                            return self.catch(depth, clause, next, base, exception, opc == CATCH)
+                        }
+                        clause = next;
+                    }
+                }
+            }
+            if depth == call_base {
+                return Err(Error::UncaughtException { tag })
+            }
+            // Continue in the caller, inside the call that precedes its return address. In
+            // place, the return address may already be the start of a catch clause.
+            pc = self.call_stack.get(depth - 2)?.saturating_sub(1);
+            depth -= 2;
+        }
+    }
+
+    // Unwinds to the frame at call depth `depth`, discarding the handlers from index `i` of
+    // the handler stack up.
+    fn unwind(&mut self, i: usize, depth: usize, fp: &mut usize) -> Result<(), Error> {
+        while self.handler_stack.len() > i {
+            self.pop_handler_entry()?;
+        }
+        if depth < self.call_stack.len() {
+            // Restore the frame pointer the catching function saved when it made its call
+            *fp = self.call_stack.get(depth + 1)? as usize;
+        }
+        self.call_stack.set_pos(depth)?;
+        Ok(())
+    }
+
+    // Enters the catch clause at `start` of the frame at call depth `depth`, whose body
+    // follows the clause header that has just been read. `base` is the value stack height
+    // of the try block.
+    fn catch(&mut self, depth: usize, start: u32, end: u32, base: u32, exception: Exception, push_payload: bool) -> Result<(), Error> {
+        let Exception { tag, len } = exception;
+        info!("CATCH: {:08x} depth {}", start, depth);
+
+        // Keep a copy of the payload for rethrow
+        let top = self.value_stack.len();
+        let payload = top - len as usize;
+        let exception_base = self.exception_stack.len() as u32;
+        for j in payload..top {
+            let value = self.value_stack.get(j)?;
+            self.exception_stack.push(value)?;
+        }
+        if push_payload {
+            self.value_stack.drop_keep(payload - base as usize, len as usize)?;
+        } else {
+            self.value_stack.set_pos(base as usize)?;
+        }
+        let kind = HandlerKind::Caught { tag, base: exception_base, len };
+        Ok(self.handler_stack.push(ExceptionHandler { depth: depth as u32, start, end, kind })?)
+    }
+
+    /// Enters the try block at `pos`, whose body ends where its first catch clause at
+    /// `clauses` starts.
+    fn enter_try(&mut self, pos: usize, params: u32, try_end: u32, clauses: u32) -> Result<(), Error> {
+        info!("TRY: {} {:08x} {:08x}", params, try_end, clauses);
+        self.discard_stale_handlers(pos)?;
+        let depth = self.call_stack.len() as u32;
+        let base = self.value_stack.len().checked_sub(params as usize).ok_or(stack::Error::Underflow)? as u32;
+        let kind = HandlerKind::Try { clauses, try_end, base };
+        Ok(self.handler_stack.push(ExceptionHandler { depth, start: pos as u32, end: clauses, kind })?)
+    }
+
+    /// Rethrows the exception caught by the catch clause that `catch_depth` other catch
+    /// clauses of the current frame are nested in.
+    fn rethrow(&mut self, code: &mut Reader, side_table: Option<&SideTable>, call_base: usize, pos: usize, fp: &mut usize, catch_depth: u32) -> Result<(), Error> {
+        self.discard_stale_handlers(pos)?;
+        let depth = self.call_stack.len() as u32;
+        let mut n = 0;
+        let mut exception = None;
+        for i in (0..self.handler_stack.len()).rev() {
+            let h = self.handler_stack.get(i)?;
+            if h.depth != depth {
                 break;
             }
+            if let HandlerKind::Caught { tag, base, len } = h.kind {
+                if n == catch_depth {
+                    exception = Some((tag, base, len));
+                    break;
+                }
+                n += 1;
+            }
+        }
+        let (tag, base, len) = match exception {
+            Some(exception) => exception,
+            None => return Err(Error::InvalidOpcode(RETHROW)),
+        };
+        info!("RETHROW: {} tag {}", catch_depth, tag);
+        for j in base..base + len {
+            let value = self.exception_stack.get(j as usize)?;
+            self.push_value(value)?;
+        }
+        self.throw(code, side_table, call_base, pos, fp, Exception { tag, len })
+    }
+
+    /// Allocates the frame of a function whose `parameters` are on top of the value stack,
+    /// pushing its zeroed locals and returning the frame pointer, the value stack index of
+    /// its first parameter.
+    fn alloc_frame(&mut self, parameters: u32, locals: u32) -> Result<usize, Error> {
+        let fp = self.value_stack.len().checked_sub(parameters as usize).ok_or(stack::Error::Underflow)?;
+        for _ in 0..locals {
+            self.push(0)?;
+        }
+        Ok(fp)
+    }
+
+    /// Enters local function `function_index` of code executed in place, returning its frame
+    /// pointer.
+    fn enter_in_place(&mut self, side_table: &SideTable, code: &mut Reader, function_index: usize) -> Result<usize, Error> {
+        let body = side_table.body(function_index)?;
+        let fp = self.alloc_frame(body.parameters, body.locals)?;
+        info!("ENTER: {} at {:08x} fp {}", function_index, body.start, fp);
+        code.set_pos(body.start as usize);
+        Ok(fp)
+    }
+
+    // Returns to the caller of the current function, restoring its frame pointer.
+    fn return_to_caller(&mut self, code: &mut Reader, call_base: usize, fp: &mut usize) -> Result<Step, Error> {
+        if self.call_stack.len() == call_base {
+            info!("RETURN");
+            return Ok(Step::Return)
+        }
+        *fp = self.call_stack.pop()? as usize;
+        let offset = self.call_stack.pop()?;
+        info!("RETURN: to {:08x}", offset);
+        code.set_pos(offset as usize);
+        Ok(Step::Next)
+    }
+
+    // Calls function `func_index` from code executed in place. A tail call reuses the
+    // caller's frame, whose values the side table entry has already dropped.
+    fn call_in_place<H: HostHandler>(&mut self, env: &Environment<H>, mi: &ModuleInst, ip: &mut InPlace, code: &mut Reader, func_index: u32, tail: bool) -> Result<Step, Error> {
+        match *mi.function(func_index)? {
+            FuncInst::Host { type_index, module: _, name:_ , host_index } => {
+                env.call_host_function(self, type_index, host_index)?;
+            },
+            FuncInst::Import { type_index: _, module: _, name: _, module_index, import_index } => {
+                env.call_module_function(self, module_index, import_index)?;
+            },
+            FuncInst::Local { type_index: _, function_index } => {
+                if !tail {
+                    self.call_stack.push(code.pos() as u32)?;
+                    self.call_stack.push(ip.fp as u32)?;
+                }
+                ip.fp = self.enter_in_place(ip.side_table, code, function_index)?;
+                return Ok(Step::Next)
+            }
+        }
+        if tail {
+            self.return_to_caller(code, ip.call_base, &mut ip.fp)
+        } else {
+            Ok(Step::Next)
+        }
+    }
+
+    // Takes the branch at `pos` through entry `n` of the side table.
+    fn branch_in_place(&mut self, side_table: &SideTable, code: &mut Reader, pos: usize, n: usize) -> Result<(), Error> {
+        let entry = side_table.entry(pos, n)?;
+        info!("  => {:08x} drop: {} keep: {}", entry.target, entry.drop, entry.keep);
+        self.value_stack.drop_keep(entry.drop as usize, entry.keep as usize)?;
+        code.set_pos(entry.target as usize);
+        Ok(())
+    }
+
+    /// Executes the instruction at `pos` of code executed in place if its original encoding
+    /// differs from compiled code in more than its immediates: blocks and branches, calls
+    /// and returns, exception handling, and instructions whose type immediates are not
+    /// compiled.
+    fn step_in_place<H: HostHandler>(&mut self, env: &Environment<H>, mi: &ModuleInst, ip: &mut InPlace, code: &mut Reader, opc: u8, pos: usize) -> Result<Step, Error> {
+        let side_table = ip.side_table;
+        match opc {
+            BLOCK | LOOP => {
+                code.read_var_i64()?;
+            },
+            IF => {
+                let val = self.pop()?;
+                if val == 0 {
+                    self.branch_in_place(side_table, code, pos, 0)?;
+                } else {
+                    code.read_var_i64()?;
+                }
+            },
+            ELSE | BR => {
+                self.branch_in_place(side_table, code, pos, 0)?;
+            },
+            BR_IF => {
+                let val = self.pop()?;
+                if val != 0 {
+                    self.branch_in_place(side_table, code, pos, 0)?;
+                } else {
+                    code.read_u32()?;
+                }
+            },
+            BR_TABLE => {
+                let count = code.read_u32()?;
+                let val = self.pop()?;
+                let index = if val < 0 || val > count as i32 {
+                    count
+                } else {
+                    val as u32
+                };
+                self.branch_in_place(side_table, code, pos, index as usize)?;
+            },
+            // Only the end of a body has an entry
+            END => if side_table.has_entry(pos) {
+                self.discard_frame_handlers()?;
+                self.branch_in_place(side_table, code, pos, 0)?;
+                return self.return_to_caller(code, ip.call_base, &mut ip.fp)
+            },
+            RETURN => {
+                self.discard_frame_handlers()?;
+                self.branch_in_place(side_table, code, pos, 0)?;
+                return self.return_to_caller(code, ip.call_base, &mut ip.fp)
+            },
+            TRY => {
+                code.read_var_i64()?;
+                let clauses = side_table.entry(pos, 0)?;
+                let try_end = side_table.entry(pos, 1)?.target;
+                self.enter_try(pos, clauses.keep, try_end, clauses.target)?;
+            },
+            // Reached by falling off the end of the previous part of a try block
+            CATCH | CATCH_ALL => {
+                self.discard_stale_handlers(pos)?;
+                self.branch_in_place(side_table, code, pos, 0)?;
+            },
+            THROW => {
+                let tag = code.read_u32()?;
+                let len = mi.tag_parameters(tag)?.len() as u32;
+                self.throw(code, Some(side_table), ip.call_base, pos, &mut ip.fp, Exception { tag, len })?;
+            },
+            RETHROW => {
+                code.read_u32()?;
+                let catch_depth = side_table.entry(pos, 0)?.target;
+                self.rethrow(code, Some(side_table), ip.call_base, pos, &mut ip.fp, catch_depth)?;
+            },
+            CALL => {
+                let func_index = code.read_u32()?;
+                return self.call_in_place(env, mi, ip, code, func_index, false)
+            },
+            CALL_INDIRECT | RETURN_CALL_INDIRECT => {
+                let sig = code.read_u32()?;
+                let table = code.read_u32()?;
+                let tail = opc == RETURN_CALL_INDIRECT;
+                if tail {
+                    // The entry keeps the table index along with the operands
+                    let entry = side_table.entry(pos, 0)?;
+                    self.value_stack.drop_keep(entry.drop as usize, entry.keep as usize)?;
+                }
+                let func_index = self.indirect_function(mi, sig, table)?;
+                if tail {
+                    self.discard_frame_handlers()?;
+                }
+                return self.call_in_place(env, mi, ip, code, func_index, tail)
+            },
+            RETURN_CALL => {
+                let func_index = code.read_u32()?;
+                let entry = side_table.entry(pos, 0)?;
+                self.value_stack.drop_keep(entry.drop as usize, entry.keep as usize)?;
+                self.discard_frame_handlers()?;
+                return self.call_in_place(env, mi, ip, code, func_index, true)
+            },
+            SELECT_T => {
+                let count = code.read_u32()?;
+                for _ in 0..count {
+                    code.read_u8()?;
+                }
+                let cond: i32 = self.pop()?;
+                let _false = self.pop_value()?;
+                let _true = self.pop_value()?;
+                self.push_value(if cond != 0 { _true } else { _false })?;
+            },
+            REF_NULL => {
+                // The reference type is only needed for validation
+                code.read_u8()?;
+                return Ok(Step::Shared)
+            },
+            _ => return Ok(Step::Shared),
+        }
+        Ok(Step::Next)
+    }
+
+    fn run<'m, H: HostHandler>(&mut self, env: &Environment<H>, mi: &'m ModuleInst, code: &mut Reader<'m>, call_base: usize, fp: usize, pc: &mut usize) -> Result<(), Error> {
+        let trace = self.cfg.trace;
+        let mut fp = fp;
+
+        while code.pos() < code.len() {
             let pos = code.pos();
+            *pc = pos;
             let opc = code.read_u8()?;
-            let op = Op::from_opcode(opc).unwrap();
-            info!("V: {} 0x{:08x}: {}", self.value_stack.len(), pos, op.text);
+            if let Some(trace) = trace {
+                trace(self, pos, opc);
+            }
             match opc {
-                NOP => {},
-                UNREACHABLE => return Err(Error::Unreachable),
                 BR => {
                     let offset = code.read_u32()?;
-                    info!("  => {:08x}", offset);
                     code.set_pos(offset as usize);
                 },
                 BR_IF => {
                     let offset = code.read_u32()?;
                     let val = self.pop()?;
                     if val != 0 {             
-                        info!("  => {:08x}", offset);
                         code.set_pos(offset as usize);
                     }
                 },
@@ -119,48 +766,70 @@ impl<'a> Interp<'a> {
 
                     let count = code.read_u32()?;
                     let table_offset = code.read_u32()?;
-                    info!("  => count: {} table_offset: {:08x}", count, table_offset);
                     let val = self.pop()?;
-                    info!("  => value: {}", val);
 
-                    let index = if val < 0 || val > count as i32 {
-                        count
-                    } else {
-                        val as u32
-                    };
-                    info!("  => index: {}", index);
-                    let entry_offset = table_offset + (index * BR_TABLE_ENTRY_SIZE);
-                    info!("  => entry_offset: {:08x}", entry_offset);
+                    let index = br_table_index(val, count);
+                    let entry_offset = index.checked_mul(BR_TABLE_ENTRY_SIZE)
+                        .and_then(|n| n.checked_add(table_offset))
+                        .ok_or(Error::InvalidImage)?;
                     code.set_pos(entry_offset as usize);
                     let dst = code.read_u32()?;
                     let drop = code.read_u32()?;
                     let keep = code.read_u32()?;
-                    info!("  => dst: {:08x} drop: {} keep: {}", dst, drop, keep);
                     self.value_stack.drop_keep(drop as usize, keep as usize)?;
                     code.set_pos(dst as usize);
-                    info!("  => done");
                 },                             
-                DROP => {
-                    self.value_stack.pop()?;
+                BR_S => {
+                    let dst = short_target(code)?;
+                    code.set_pos(dst);
+                },
+                BR_IF_S => {
+                    let dst = short_target(code)?;
+                    let val = self.pop()?;
+                    if val != 0 {
+                        code.set_pos(dst);
+                    }
+                },
+                BR_UNLESS_S => {
+                    let dst = short_target(code)?;
+                    let val = self.pop()?;
+                    if val == 0 {
+                        code.set_pos(dst);
+                    }
+                },
+                BR_TABLE_S => {
+                    // BR_TABLE_S COUNT:u32
+                    // [OFFSET:i16 DROP:u8 KEEP:u8]
+                    // OFFSET:i16 DROP:u8 KEEP:u8
+
+                    let count = code.read_u32()?;
+                    let val = self.pop()?;
+                    let index = br_table_index(val, count);
+                    let entry_offset = code.pos() + (index * BR_TABLE_S_ENTRY_SIZE) as usize;
+                    code.set_pos(entry_offset);
+                    let dst = short_target(code)?;
+                    let drop = code.read_u8()?;
+                    let keep = code.read_u8()?;
+                    self.value_stack.drop_keep(drop as usize, keep as usize)?;
+                    code.set_pos(dst);
                 },
                 CALL => {
                     let id = code.read_u32()?;
-                    match &mi.functions()[id as usize] {
-                        &FuncInst::Host { type_index, module: _, name:_ , host_index } => {
+                    match *mi.function(id)? {
+                        FuncInst::Host { type_index, module: _, name:_ , host_index } => {
                             env.call_host_function(self, type_index, host_index)?;
                         },
-                        &FuncInst::Import { type_index, ref module, ref name, module_index, import_index } => {
+                        FuncInst::Import { type_index, ref module, ref name, module_index, import_index } => {
                             info!("CALL IMPORT: type_index: {} module: {}, name: {}, module_index: {}, import_index: {}", type_index, module, name, module_index, import_index);
                             env.call_module_function(self, module_index, import_index)?;
                         },
-                        &FuncInst::Local { type_index: _, function_index } => {
-                            let body_range = mi.code().body_range(function_index);
-                            let offset = body_range.start;
+                        FuncInst::Local { type_index: _, function_index } => {
+                            let offset = enter(env, mi, code, function_index)?;
                             let pos = code.pos();
-                            info!("CALL: {:08x} to {:08x}", pos, offset);
 
                             self.call_stack.push(pos as u32)?;
-                            code.set_pos(offset as usize);                            
+                            self.call_stack.push(fp as u32)?;
+                            code.set_pos(offset);                            
                         }
                     }
                 },
@@ -173,270 +842,1424 @@ impl<'a> Interp<'a> {
                     // jump
 
                     let sig = code.read_u32()?;
-                    info!("CALL_INDIRECT {}", sig);
-                    let sig_type = &mi.function_types()[sig as usize];
-                    info!("   sig_type: {:?}", sig_type);
-                    let table_index = self.value_stack.pop()?;
-                    info!("   table_index: {:?}", table_index);
-
-                    let table = mi.table(0);
-                    if table_index.0 as usize >= table.len() {
-                        return Err(Error::UndefinedTableIndex { id: table_index.0 })
-                    }
-
-                    let func_index = mi.indirect_function_id(table_index.0 as usize);
-                    let func_inst = &mi.functions()[func_index as usize];
-                    info!("   func_inst: {:?}", func_inst);
-                    match func_inst {
-                        &FuncInst::Host { type_index, module: _, name:_ , host_index } => {
+                    let table = code.read_u32()?;
+                    let func_index = self.indirect_function(mi, sig, table)?;
+                    match *mi.function(func_index)? {
+                        FuncInst::Host { type_index, module: _, name:_ , host_index } => {
                             env.call_host_function(self, type_index, host_index)?;
                         },                        
-                        &FuncInst::Import { type_index, ref module, ref name, module_index, import_index } => {
+                        FuncInst::Import { type_index, ref module, ref name, module_index, import_index } => {
                             info!("CALL IMPORT: type_index: {} module: {}, name: {}, module_index: {}, import_index: {}", type_index, module, name, module_index, import_index);
                             env.call_module_function(self, module_index, import_index)?;
                         }
-                        &FuncInst::Local { type_index, function_index } => {
-                            let func_type = &mi.function_types()[type_index];
-                            // info!("Local Function: {}", function_index);
-                            // info!("Sig: {:?} Func: {:?}", sig_type, func_type);
-
-                            if sig_type.parameters != func_type.parameters {
-                                return Err(Error::SignatureMismatch)
-                            }
-                            if sig_type.results != func_type.results {
-                                return Err(Error::SignatureMismatch)
-                            }
-
-                            let body_range = mi.code().body_range(function_index);
-                            let offset = body_range.start;
+                        FuncInst::Local { type_index: _, function_index } => {
+                            let offset = enter(env, mi, code, function_index)?;
 
                             // let body = m.body(function_index as u32).unwrap();
                             // let offset = code_buf.as_ptr().offset_to(body.buf.as_ptr()).unwrap() as usize;
                             let pos = code.pos();
-                            info!("  => {:08x} to {:08x}", pos, offset);
 
                             self.call_stack.push(pos as u32)?;
-                            code.set_pos(offset as usize);
+                            self.call_stack.push(fp as u32)?;
+                            code.set_pos(offset);
                         }
                     }
                 }
+                RETURN_CALL | RETURN_CALL_INDIRECT => {
+                    // The compiler has already dropped the current frame, leaving only the
+                    // callee's operands, so a local callee is entered without pushing a
+                    // return address and returns directly to our caller.
+                    let func_index = if opc == RETURN_CALL {
+                        code.read_u32()?
+                    } else {
+                        let sig = code.read_u32()?;
+                        let table = code.read_u32()?;
+                        self.indirect_function(mi, sig, table)?
+                    };
+                    self.discard_frame_handlers()?;
+                    match *mi.function(func_index)? {
+                        FuncInst::Host { type_index, module: _, name:_ , host_index } => {
+                            env.call_host_function(self, type_index, host_index)?;
+                        },
+                        FuncInst::Import { type_index: _, module: _, name: _, module_index, import_index } => {
+                            env.call_module_function(self, module_index, import_index)?;
+                        },
+                        FuncInst::Local { type_index: _, function_index } => {
+                            let offset = enter(env, mi, code, function_index)?;
+                            code.set_pos(offset);
+                            continue;
+                        }
+                    }
+                    // Host and imported callees have returned, so return to our caller
+                    if let Step::Return = self.return_to_caller(code, call_base, &mut fp)? {
+                        break;
+                    }
+                },
                 RETURN => {
-                    if self.call_stack.len() == 0 {
-                        info!("RETURN");
+                    self.discard_frame_handlers()?;
+                    if let Step::Return = self.return_to_caller(code, call_base, &mut fp)? {
                         break;
                     }
-
-                    let offset = self.call_stack.pop()?;
-                    info!("RETURN: to {:08x}", offset);
-                    code.set_pos(offset as usize);
                 },
                 END => {
-                    panic!("unexpected END");
+                    return Err(Error::InvalidOpcode(opc))
                 }
-                SELECT => {
-                    let cond: i32 = self.pop()?;
-                    let _false: i32 = self.pop()?;
-                    let _true: i32 = self.pop()?;
-                    self.push(if cond != 0 { _true } else { _false })?;
-                },                
-                I32_CONST => {
-                    let value = Value(code.read_i32()?);
-                    self.value_stack.push(value)?;
+                TRY => {
+                    let params = code.read_u32()?;
+                    let try_end = code.read_u32()?;
+                    let clauses = code.read_u32()?;
+                    self.enter_try(pos, params, try_end, clauses)?;
                 },
-                GET_LOCAL => {
-                    let depth: u32 = code.read_u32()?;
-                    info!("GET_LOCAL: {} ", depth);
-                    let value: i32 = self.value_stack.peek(depth as usize)?.0;
-                    info!("   => {}", value);
-                    self.push(value)?;
+                HANDLER_POP => {
+                    self.discard_stale_handlers(pos)?;
+                    self.pop_handler_entry()?;
                 },
-                SET_LOCAL => {
-                    let depth: u32 = code.read_u32()?;
-                    info!("SET_LOCAL: {} ", depth);
-                    let value: i32 = self.pop()?;
-                    info!("   <= {}", value);
-                    self.value_stack.pick((depth - 1) as usize)?.0 = value;
+                THROW => {
+                    let tag = code.read_u32()?;
+                    let len = mi.tag_parameters(tag)?.len() as u32;
+                    self.throw(code, None, call_base, pos, &mut fp, Exception { tag, len })?;
                 },
-                TEE_LOCAL => {
-                    let depth: u32 = code.read_u32()?;
-                    info!("TEE_LOCAL: {}", depth);
-                    let src = self.value_stack.peek(0)?;
-                    *self.value_stack.pick(depth as usize)? = src;
-                    info!("   <= {}", src.0);                    
-                },                
-                GET_GLOBAL => {
-                    let index = code.read_u32()?;
-                    let value: i32 = mi.get_global(index)?;
-                    self.push(value)?;
-                },
-                SET_GLOBAL => {
-                    let index = code.read_u32()?;
-                    let value: i32 = self.pop()?;
-                    mi.set_global(index, value)?;
-                },
-                MEM_GROW => {
-                    let pages = self.pop()?;
-                    info!("MEM_GROW: {}", pages);
-                    let ret = env.mem().grow_memory(pages);
-                    info!("  => {}", ret);
-                    self.push(ret)?;
-                },
-                MEM_SIZE => {
-                    let size = env.mem().num_pages();
-                    self.push(size as i32)?;
-                }
-                // I32 load
-                0x28 ... 0x30 => {
-                    let _flags = code.read_u32()?;
-                    let offset = code.read_u32()?;
-                    let base: u32 = self.pop()? as u32;
-                    let addr = (offset + base) as usize;
-                    let mem = env.mem();
-
-                    let res = match opc {
-                        I32_LOAD => {
-                            mem.load(addr)?
-                        },
-                        I32_LOAD8_S => {
-                            mem.load8_s(addr)?
-                        },
-                        I32_LOAD8_U => {
-                            mem.load8_u(addr)?
-                        },
-                        I32_LOAD16_S => {
-                            mem.load16_s(addr)?
-                        },
-                        I32_LOAD16_U => {
-                            mem.load16_u(addr)?
-                        }                                               
-                        _ => unimplemented!(),
-                    };
-                    info!("load @ {:08x} => {}", addr, res);
-                    self.push(res)?;
+                RETHROW => {
+                    let catch_depth = code.read_u32()?;
+                    self.rethrow(code, None, call_base, pos, &mut fp, catch_depth)?;
                 },
-                // I32 store
-                0x36 | 0x38 | 0x3a | 0x3b => {
-                    let _flags = code.read_u32()?;
-                    let offset = code.read_u32()?;
-                    let value: i32 = self.pop()?;
-                    let base: u32 = self.pop()? as u32;
-                    let addr = (offset + base) as usize;
-                    let mem = env.mem();
-                    info!("STORE {} @ {:08x}", value, addr);
-
-                    match opc {
-                        I32_STORE => mem.store(addr, value)?,
-                        I32_STORE8 => mem.store8(addr, value)?,
-                        I32_STORE16 => mem.store16(addr, value)?,
-                        _ => unimplemented!(),
-                    }
+                I32_CONST_S => {
+                    let value = code.read_i16()? as i32;
+                    self.push_value(value)?;
                 },
-                // I32 cmpops
-                0x46 ... 0x50 => {
-                    let (rhs, lhs): (i32, i32) = (self.pop()?, self.pop()?);
-                    info!("lhs: {} rhs: {}", lhs, rhs);
-                    let res = match opc {
-                        I32_EQ => lhs == rhs,
-                        I32_NE => lhs != rhs,
-                        I32_LT_U => (lhs as u32) < (rhs as u32),
-                        I32_LT_S => lhs < rhs,
-                        I32_GT_U => (lhs as u32) > (rhs as u32),
-                        I32_GT_S => lhs > rhs,
-                        I32_LE_U => (lhs as u32) <= (rhs as u32),
-                        I32_LE_S => lhs <= rhs,
-                        I32_GE_U => (lhs as u32) >= (rhs as u32),
-                        I32_GE_S => lhs >= rhs,                        
-                        _ => unimplemented!(),
-                    };
-                    info!("res: {}", res);
-                    self.push(if res { 1 } else { 0 })?;
+                GET_LOCAL_S => {
+                    let index = code.read_u8()?;
+                    let value = self.value_stack.get(fp + index as usize)?;
+                    self.push_value(value)?;
                 },
-                // I32 binops
-                0x6a ... 0x79 => {
-                    let (rhs, lhs): (i32, i32) = (self.pop()?, self.pop()?);
-                    info!("lhs: {} rhs: {}", lhs, rhs);
-                    let res = match opc {
-                        I32_ADD => lhs.wrapping_add(rhs),
-                        I32_SUB => lhs.wrapping_sub(rhs),
-                        I32_MUL => lhs.wrapping_mul(rhs),
-                        I32_DIV_S => lhs / rhs,
-                        I32_DIV_U => ((lhs as u32) / (rhs as u32)) as i32,
-                        I32_REM_S => lhs % rhs,
-                        I32_REM_U => ((lhs as u32) % (rhs as u32)) as i32,
-                        I32_AND => lhs & rhs,
-                        I32_OR => lhs | rhs,
-                        I32_XOR => lhs ^ rhs,
-                        I32_SHL => lhs << rhs,
-                        I32_SHR_S => lhs >> rhs,
-                        I32_SHR_U => ((lhs as u32) >> rhs) as i32,
-                        I32_ROTL => lhs.rotate_left(rhs as u32),
-                        I32_ROTR => lhs.rotate_right(rhs as u32),
-                        _ => unimplemented!()
-                    };
-                    info!("res: {}", res);
-                    self.push(res)?;
+                SET_LOCAL_S => {
+                    let index = code.read_u8()?;
+                    let value = self.pop_value()?;
+                    self.value_stack.set(fp + index as usize, value)?;
                 },
-                // I32 unops                
-                0x45 | 0x67 ... 0x6a => {
-                    let val: i32 = self.pop()?;
-                    info!("val: {}", val);
-                    let res = match opc {
-                        I32_EQZ => if val == 0 { 1 } else { 0 },
-                        I32_CLZ => val.leading_zeros(),
-                        I32_CTZ => val.trailing_zeros(),
-                        I32_POPCNT => val.count_ones(),
-                        _ => unimplemented!(),
-                    };
-                    info!("res: {}", res);                    
-                    self.push(res as i32)?;
+                TEE_LOCAL_S => {
+                    let index = code.read_u8()?;
+                    let value = self.value_stack.peek(0)?;
+                    self.value_stack.set(fp + index as usize, value)?;
                 },
                 ALLOCA => {
-                    let count = code.read_u32()?;
-                    info!("INTERP_ALLOCA: {}", count);
-                    for _ in 0..count {
-                        self.push(0)?;
-                    }
+                    let parameters = code.read_u32()?;
+                    let locals = code.read_u32()?;
+                    fp = self.alloc_frame(parameters, locals)?;
+                },
+                ALLOCA_S => {
+                    let parameters = code.read_u8()?;
+                    let locals = code.read_u8()?;
+                    fp = self.alloc_frame(parameters as u32, locals as u32)?;
                 },
                 BR_UNLESS => {
                     let offset = code.read_u32()?;
-                    info!("BR_UNLESS: {:08x}", offset);
                     let val = self.pop()?;
                     if val == 0 {             
-                        info!("  => {:08x}", offset);
                         code.set_pos(offset as usize);
                     }
                 },                
                 DROP_KEEP => {
                     let drop = code.read_u32()?;
                     let keep = code.read_u32()?;
-                    info!("INTERP_DROP_KEEP {} {}", drop, keep);
-                    let val = if keep > 0 {
-                        Some(self.pop()?)
-                    } else {
-                        None
-                    };
-                    info!("keeping {:?}", val);
-                    for _ in 0..drop {
-                        let v = self.pop()?;
-                        info!("popped {:?}", v);
-                    }
-                    if let Some(val) = val {
-                        info!("pushed {:?}", val);
-                        self.push(val)?;
-                    }
-                    info!("V: {}", self.value_stack.len());
+                    self.value_stack.drop_keep(drop as usize, keep as usize)?;
                 },
-                _ => return Err(Error::InvalidOpcode(opc)),
+                DROP_KEEP_S => {
+                    let drop = code.read_u8()?;
+                    let keep = code.read_u8()?;
+                    self.value_stack.drop_keep(drop as usize, keep as usize)?;
+                },
+                _ => self.exec(env, mi, code, opc, fp)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs code executed in place from the instruction at the current position of `code`.
+    fn run_in_place<'m, H: HostHandler>(&mut self, env: &Environment<H>, mi: &'m ModuleInst, ip: &mut InPlace, code: &mut Reader<'m>, pc: &mut usize) -> Result<(), Error> {
+        let trace = self.cfg.trace;
+
+        while code.pos() < code.len() {
+            let pos = code.pos();
+            *pc = pos;
+            let opc = code.read_u8()?;
+            if let Some(trace) = trace {
+                trace(self, pos, opc);
+            }
+            match self.step_in_place(env, mi, ip, code, opc, pos)? {
+                Step::Next => {},
+                Step::Return => break,
+                Step::Shared => self.exec(env, mi, code, opc, ip.fp)?,
             }
-            // info!("{:08x}: END INST {:08x}", code.pos(), code.len());
-            _count += 1;
         }
 
-        match self.stack_len() {
-            0 => Ok(None),
-            1 => Ok(Some(Value(self.pop()?))),
-            _ => Err(Error::UnexpectedReturnLength { got: self.stack_len() as u32 }),
+        Ok(())
+    }
+
+    /// Executes an instruction that is encoded the same way in compiled code and in place,
+    /// apart from the width of its immediates: everything except control flow and the
+    /// compiled code's own instructions. Inlined so that each loop gets its own copy.
+    #[inline(always)]
+    fn exec<H: HostHandler>(&mut self, env: &Environment<H>, mi: &ModuleInst, code: &mut Reader, opc: u8, fp: usize) -> Result<(), Error> {
+        match opc {
+            NOP => {},
+            UNREACHABLE => return Err(Error::Unreachable),
+            DROP => {
+                self.value_stack.pop()?;
+            },
+            SELECT => {
+                let cond: i32 = self.pop()?;
+                let _false = self.pop_value()?;
+                let _true = self.pop_value()?;
+                self.push_value(if cond != 0 { _true } else { _false })?;
+            },                
+            REF_NULL => {
+                self.push_value(NULL_REF)?;
+            },
+            REF_IS_NULL => {
+                let value = self.pop_value()?;
+                self.push(if value.as_u32() == NULL_REF { 1 } else { 0 })?;
+            },
+            REF_FUNC => {
+                let index = code.read_u32()?;
+                self.push_value(index)?;
+            },
+            TABLE_GET => {
+                let table = mi.table(code.read_u32()?)?;
+                let index = self.pop()? as u32;
+                self.push_value(table.get(index)?)?;
+            },
+            TABLE_SET => {
+                let table = mi.table(code.read_u32()?)?;
+                let value = self.pop_value()?.as_u32();
+                let index = self.pop()? as u32;
+                table.set(index, value)?;
+            },
+            I32_CONST => {
+                let value = code.read_i32()?;
+                self.push_value(value)?;
+            },
+            I64_CONST => {
+                let value = code.read_i64()?;
+                self.push_value(value)?;
+            },
+            #[cfg(not(feature="integer-only"))]
+            F32_CONST => {
+                let value = code.read_f32()?;
+                self.push_value(value)?;
+            },
+            #[cfg(not(feature="integer-only"))]
+            F64_CONST => {
+                let value = code.read_f64()?;
+                self.push_value(value)?;
+            },
+            GET_LOCAL => {
+                let index = code.read_u32()?;
+                let value = self.value_stack.get(fp + index as usize)?;
+                self.push_value(value)?;
+            },
+            SET_LOCAL => {
+                let index = code.read_u32()?;
+                let value = self.pop_value()?;
+                self.value_stack.set(fp + index as usize, value)?;
+            },
+            TEE_LOCAL => {
+                let index = code.read_u32()?;
+                let value = self.value_stack.peek(0)?;
+                self.value_stack.set(fp + index as usize, value)?;
+            },                
+            GET_GLOBAL => {
+                let index = code.read_u32()?;
+                let value = mi.get_global(index)?;
+                self.push_value(value)?;
+            },
+            SET_GLOBAL => {
+                let index = code.read_u32()?;
+                let value = self.pop_value()?;
+                mi.set_global(index, value)?;
+            },
+            MEM_GROW => {
+                code.read_reserved()?;
+                let pages = self.pop()?;
+                info!("MEM_GROW: {}", pages);
+                let ret = if self.cfg.deterministic {
+                    env.mem().grow_memory_declared(pages)?
+                } else {
+                    env.mem().grow_memory(pages)
+                };
+                info!("  => {}", ret);
+                self.push(ret)?;
+            },
+            MEM_SIZE => {
+                code.read_reserved()?;
+                let size = env.mem().num_pages();
+                self.push(size as i32)?;
+            }
+            // Loads
+            I32_LOAD ..= I64_LOAD32_U => {
+                code.read_align()?;
+                let offset = code.read_u32()?;
+                let base: u32 = self.pop()? as u32;
+                let addr = effective_address(base, offset)?;
+                let mem = env.mem();
+
+                let res = match opc {
+                    I32_LOAD => Value::from(mem.load(addr)?),
+                    I64_LOAD => Value::from(mem.load64(addr)?),
+                    F32_LOAD => Value::from(mem.load(addr)? as u32),
+                    F64_LOAD => Value::from(mem.load64(addr)? as u64),
+                    I32_LOAD8_S => Value::from(mem.load8_s(addr)?),
+                    I32_LOAD8_U => Value::from(mem.load8_u(addr)?),
+                    I32_LOAD16_S => Value::from(mem.load16_s(addr)?),
+                    I32_LOAD16_U => Value::from(mem.load16_u(addr)?),
+                    I64_LOAD8_S => Value::from(mem.load8_s(addr)? as i64),
+                    I64_LOAD8_U => Value::from(mem.load8_u(addr)? as i64),
+                    I64_LOAD16_S => Value::from(mem.load16_s(addr)? as i64),
+                    I64_LOAD16_U => Value::from(mem.load16_u(addr)? as i64),
+                    I64_LOAD32_S => Value::from(mem.load(addr)? as i64),
+                    I64_LOAD32_U => Value::from(mem.load(addr)? as u32 as i64),
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                self.push_value(res)?;
+            },
+            // Stores
+            I32_STORE ..= I64_STORE32 => {
+                code.read_align()?;
+                let offset = code.read_u32()?;
+                let value = self.pop_value()?;
+                let base: u32 = self.pop()? as u32;
+                let addr = effective_address(base, offset)?;
+                let mem = env.mem();
+
+                match opc {
+                    I32_STORE | F32_STORE | I64_STORE32 => mem.store(addr, value.as_i32())?,
+                    I64_STORE | F64_STORE => mem.store64(addr, value.as_i64())?,
+                    I32_STORE8 | I64_STORE8 => mem.store8(addr, value.as_i32())?,
+                    I32_STORE16 | I64_STORE16 => mem.store16(addr, value.as_i32())?,
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                }
+            },
+            // I32 cmpops
+            I32_EQ ..= I32_GE_U => {
+                let (rhs, lhs): (i32, i32) = (self.pop()?, self.pop()?);
+                let res = match opc {
+                    I32_EQ => lhs == rhs,
+                    I32_NE => lhs != rhs,
+                    I32_LT_U => (lhs as u32) < (rhs as u32),
+                    I32_LT_S => lhs < rhs,
+                    I32_GT_U => (lhs as u32) > (rhs as u32),
+                    I32_GT_S => lhs > rhs,
+                    I32_LE_U => (lhs as u32) <= (rhs as u32),
+                    I32_LE_S => lhs <= rhs,
+                    I32_GE_U => (lhs as u32) >= (rhs as u32),
+                    I32_GE_S => lhs >= rhs,                        
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                self.push(if res { 1 } else { 0 })?;
+            },
+            // I64 cmpops
+            I64_EQ ..= I64_GE_U => {
+                let (rhs, lhs) = (self.pop_value()?.as_i64(), self.pop_value()?.as_i64());
+                let res = match opc {
+                    I64_EQ => lhs == rhs,
+                    I64_NE => lhs != rhs,
+                    I64_LT_U => (lhs as u64) < (rhs as u64),
+                    I64_LT_S => lhs < rhs,
+                    I64_GT_U => (lhs as u64) > (rhs as u64),
+                    I64_GT_S => lhs > rhs,
+                    I64_LE_U => (lhs as u64) <= (rhs as u64),
+                    I64_LE_S => lhs <= rhs,
+                    I64_GE_U => (lhs as u64) >= (rhs as u64),
+                    I64_GE_S => lhs >= rhs,                        
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                self.push(if res { 1 } else { 0 })?;
+            },
+            // F32 cmpops
+            #[cfg(not(feature="integer-only"))]
+            F32_EQ ..= F32_GE => {
+                let (rhs, lhs) = (self.pop_value()?.as_f32(), self.pop_value()?.as_f32());
+                let res = match opc {
+                    F32_EQ => single::eq(lhs, rhs),
+                    F32_NE => !single::eq(lhs, rhs),
+                    F32_LT => single::lt(lhs, rhs),
+                    F32_GT => single::lt(rhs, lhs),
+                    F32_LE => single::le(lhs, rhs),
+                    F32_GE => single::le(rhs, lhs),
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                self.push(if res { 1 } else { 0 })?;
+            },
+            // F64 cmpops
+            #[cfg(not(feature="integer-only"))]
+            F64_EQ ..= F64_GE => {
+                let (rhs, lhs) = (self.pop_value()?.as_f64(), self.pop_value()?.as_f64());
+                let res = match opc {
+                    F64_EQ => double::eq(lhs, rhs),
+                    F64_NE => !double::eq(lhs, rhs),
+                    F64_LT => double::lt(lhs, rhs),
+                    F64_GT => double::lt(rhs, lhs),
+                    F64_LE => double::le(lhs, rhs),
+                    F64_GE => double::le(rhs, lhs),
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                self.push(if res { 1 } else { 0 })?;
+            },
+            // I32 binops
+            I32_ADD ..= I32_ROTR => {
+                let (rhs, lhs): (i32, i32) = (self.pop()?, self.pop()?);
+                let res = match opc {
+                    I32_ADD => lhs.wrapping_add(rhs),
+                    I32_SUB => lhs.wrapping_sub(rhs),
+                    I32_MUL => lhs.wrapping_mul(rhs),
+                    I32_DIV_S => {
+                        if rhs == 0 { return Err(Error::IntegerDivideByZero) }
+                        if lhs == i32::MIN && rhs == -1 { return Err(Error::IntegerOverflow) }
+                        lhs / rhs
+                    },
+                    I32_DIV_U => {
+                        if rhs == 0 { return Err(Error::IntegerDivideByZero) }
+                        ((lhs as u32) / (rhs as u32)) as i32
+                    },
+                    I32_REM_S => {
+                        if rhs == 0 { return Err(Error::IntegerDivideByZero) }
+                        lhs.wrapping_rem(rhs)
+                    },
+                    I32_REM_U => {
+                        if rhs == 0 { return Err(Error::IntegerDivideByZero) }
+                        ((lhs as u32) % (rhs as u32)) as i32
+                    },
+                    I32_AND => lhs & rhs,
+                    I32_OR => lhs | rhs,
+                    I32_XOR => lhs ^ rhs,
+                    I32_SHL => lhs.wrapping_shl(rhs as u32),
+                    I32_SHR_S => lhs.wrapping_shr(rhs as u32),
+                    I32_SHR_U => ((lhs as u32).wrapping_shr(rhs as u32)) as i32,
+                    I32_ROTL => lhs.rotate_left(rhs as u32),
+                    I32_ROTR => lhs.rotate_right(rhs as u32),
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                self.push(res)?;
+            },
+            // I32 unops                
+            I32_EQZ | I32_CLZ ..= I32_POPCNT => {
+                let val: i32 = self.pop()?;
+                let res = match opc {
+                    I32_EQZ => if val == 0 { 1 } else { 0 },
+                    I32_CLZ => val.leading_zeros(),
+                    I32_CTZ => val.trailing_zeros(),
+                    I32_POPCNT => val.count_ones(),
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                self.push(res as i32)?;
+            },
+            // I64 binops
+            I64_ADD ..= I64_ROTR => {
+                let (rhs, lhs) = (self.pop_value()?.as_i64(), self.pop_value()?.as_i64());
+                let res = match opc {
+                    I64_ADD => lhs.wrapping_add(rhs),
+                    I64_SUB => lhs.wrapping_sub(rhs),
+                    I64_MUL => lhs.wrapping_mul(rhs),
+                    I64_DIV_S => {
+                        if rhs == 0 { return Err(Error::IntegerDivideByZero) }
+                        if lhs == i64::MIN && rhs == -1 { return Err(Error::IntegerOverflow) }
+                        lhs / rhs
+                    },
+                    I64_DIV_U => {
+                        if rhs == 0 { return Err(Error::IntegerDivideByZero) }
+                        ((lhs as u64) / (rhs as u64)) as i64
+                    },
+                    I64_REM_S => {
+                        if rhs == 0 { return Err(Error::IntegerDivideByZero) }
+                        lhs.wrapping_rem(rhs)
+                    },
+                    I64_REM_U => {
+                        if rhs == 0 { return Err(Error::IntegerDivideByZero) }
+                        ((lhs as u64) % (rhs as u64)) as i64
+                    },
+                    I64_AND => lhs & rhs,
+                    I64_OR => lhs | rhs,
+                    I64_XOR => lhs ^ rhs,
+                    I64_SHL => lhs.wrapping_shl(rhs as u32),
+                    I64_SHR_S => lhs.wrapping_shr(rhs as u32),
+                    I64_SHR_U => ((lhs as u64).wrapping_shr(rhs as u32)) as i64,
+                    I64_ROTL => lhs.rotate_left((rhs & 63) as u32),
+                    I64_ROTR => lhs.rotate_right((rhs & 63) as u32),
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                self.push_value(res)?;
+            },
+            I64_EQZ => {
+                let val = self.pop_value()?.as_i64();
+                self.push(if val == 0 { 1 } else { 0 })?;
+            },
+            // I64 unops
+            I64_CLZ ..= I64_POPCNT => {
+                let val = self.pop_value()?.as_i64();
+                let res = match opc {
+                    I64_CLZ => val.leading_zeros(),
+                    I64_CTZ => val.trailing_zeros(),
+                    I64_POPCNT => val.count_ones(),
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                self.push_value(res as i64)?;
+            },
+            // F32 unops
+            #[cfg(not(feature="integer-only"))]
+            F32_ABS ..= F32_SQRT => {
+                let val = self.pop_value()?.as_f32();
+                let res = match opc {
+                    F32_ABS => val.abs(),
+                    F32_NEG => -val,
+                    F32_CEIL => single::ceil(val),
+                    F32_FLOOR => single::floor(val),
+                    F32_TRUNC => single::trunc(val),
+                    F32_NEAREST => single::nearest(val),
+                    F32_SQRT => single::sqrt(val),
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                // abs and neg only change the sign, keeping any NaN payload
+                let res = if opc == F32_ABS || opc == F32_NEG { res } else { self.canon_f32(res) };
+                self.push_value(res)?;
+            },
+            // F32 binops
+            #[cfg(not(feature="integer-only"))]
+            F32_ADD ..= F32_COPYSIGN => {
+                let (rhs, lhs) = (self.pop_value()?.as_f32(), self.pop_value()?.as_f32());
+                let res = match opc {
+                    F32_ADD => single::add(lhs, rhs),
+                    F32_SUB => single::sub(lhs, rhs),
+                    F32_MUL => single::mul(lhs, rhs),
+                    F32_DIV => single::div(lhs, rhs),
+                    F32_MIN => f32_min(lhs, rhs),
+                    F32_MAX => f32_max(lhs, rhs),
+                    F32_COPYSIGN => lhs.copysign(rhs),
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                let res = if opc == F32_COPYSIGN { res } else { self.canon_f32(res) };
+                self.push_value(res)?;
+            },
+            // F64 unops
+            #[cfg(not(feature="integer-only"))]
+            F64_ABS ..= F64_SQRT => {
+                let val = self.pop_value()?.as_f64();
+                let res = match opc {
+                    F64_ABS => val.abs(),
+                    F64_NEG => -val,
+                    F64_CEIL => double::ceil(val),
+                    F64_FLOOR => double::floor(val),
+                    F64_TRUNC => double::trunc(val),
+                    F64_NEAREST => double::nearest(val),
+                    F64_SQRT => double::sqrt(val),
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                let res = if opc == F64_ABS || opc == F64_NEG { res } else { self.canon_f64(res) };
+                self.push_value(res)?;
+            },
+            // F64 binops
+            #[cfg(not(feature="integer-only"))]
+            F64_ADD ..= F64_COPYSIGN => {
+                let (rhs, lhs) = (self.pop_value()?.as_f64(), self.pop_value()?.as_f64());
+                let res = match opc {
+                    F64_ADD => double::add(lhs, rhs),
+                    F64_SUB => double::sub(lhs, rhs),
+                    F64_MUL => double::mul(lhs, rhs),
+                    F64_DIV => double::div(lhs, rhs),
+                    F64_MIN => f64_min(lhs, rhs),
+                    F64_MAX => f64_max(lhs, rhs),
+                    F64_COPYSIGN => lhs.copysign(rhs),
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                let res = if opc == F64_COPYSIGN { res } else { self.canon_f64(res) };
+                self.push_value(res)?;
+            },
+            // Integer conversions
+            I32_WRAP_I64 | I64_EXTEND_S_I32 | I64_EXTEND_U_I32 => {
+                let val = self.pop_value()?;
+                let res = match opc {
+                    I32_WRAP_I64 => Value::from(val.as_i64() as i32),
+                    I64_EXTEND_S_I32 => Value::from(val.as_i32() as i64),
+                    I64_EXTEND_U_I32 => Value::from(val.as_u32() as u64),
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                self.push_value(res)?;
+            },
+            // Float conversions
+            #[cfg(not(feature="integer-only"))]
+            I32_TRUNC_S_F32 ..= F64_REINTERPRET_I64 => {
+                let val = self.pop_value()?;
+                let res = match opc {
+                    I32_TRUNC_S_F32 => Value::from(single::to_i32(f32_to_int(val.as_f32(), -2147483904.0, 2147483648.0)?)),
+                    I32_TRUNC_U_F32 => Value::from(single::to_u32(f32_to_int(val.as_f32(), -1.0, 4294967296.0)?)),
+                    I32_TRUNC_S_F64 => Value::from(double::to_i32(f64_to_int(val.as_f64(), -2147483649.0, 2147483648.0)?)),
+                    I32_TRUNC_U_F64 => Value::from(double::to_u32(f64_to_int(val.as_f64(), -1.0, 4294967296.0)?)),
+                    I64_TRUNC_S_F32 => Value::from(single::to_i64(f32_to_int(val.as_f32(), -9223373136366403584.0, 9223372036854775808.0)?)),
+                    I64_TRUNC_U_F32 => Value::from(single::to_u64(f32_to_int(val.as_f32(), -1.0, 18446744073709551616.0)?)),
+                    I64_TRUNC_S_F64 => Value::from(double::to_i64(f64_to_int(val.as_f64(), -9223372036854777856.0, 9223372036854775808.0)?)),
+                    I64_TRUNC_U_F64 => Value::from(double::to_u64(f64_to_int(val.as_f64(), -1.0, 18446744073709551616.0)?)),
+                    F32_CONVERT_S_I32 => Value::from(single::from_i32(val.as_i32())),
+                    F32_CONVERT_U_I32 => Value::from(single::from_u32(val.as_u32())),
+                    F32_CONVERT_S_I64 => Value::from(single::from_i64(val.as_i64())),
+                    F32_CONVERT_U_I64 => Value::from(single::from_u64(val.as_u64())),
+                    F32_DEMOTE_F64 => Value::from(self.canon_f32(demote(val.as_f64()))),
+                    F64_CONVERT_S_I32 => Value::from(double::from_i32(val.as_i32())),
+                    F64_CONVERT_U_I32 => Value::from(double::from_u32(val.as_u32())),
+                    F64_CONVERT_S_I64 => Value::from(double::from_i64(val.as_i64())),
+                    F64_CONVERT_U_I64 => Value::from(double::from_u64(val.as_u64())),
+                    F64_PROMOTE_F32 => Value::from(self.canon_f64(promote(val.as_f32()))),
+                    I32_REINTERPRET_F32 | F32_REINTERPRET_I32 => Value::from(val.as_u32()),
+                    I64_REINTERPRET_F64 | F64_REINTERPRET_I64 => val,
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                self.push_value(res)?;
+            },
+            // Sign extension
+            I32_EXTEND8_S ..= I64_EXTEND32_S => {
+                let val = self.pop_value()?;
+                let res = match opc {
+                    I32_EXTEND8_S => Value::from(val.as_i32() as i8 as i32),
+                    I32_EXTEND16_S => Value::from(val.as_i32() as i16 as i32),
+                    I64_EXTEND8_S => Value::from(val.as_i64() as i8 as i64),
+                    I64_EXTEND16_S => Value::from(val.as_i64() as i16 as i64),
+                    I64_EXTEND32_S => Value::from(val.as_i64() as i32 as i64),
+                    _ => return Err(Error::InvalidOpcode(opc)),
+                };
+                self.push_value(res)?;
+            },
+            MISC_PREFIX => {
+                let sub = code.read_sub_opcode()?;
+                match sub {
+                    #[cfg(not(feature="integer-only"))]
+                    I32_TRUNC_SAT_F32_S ..= I64_TRUNC_SAT_F64_U => {
+                        let val = self.pop_value()?;
+                        // Conversions to integers saturate and map NaN to 0, which is exactly
+                        // the trunc_sat semantics.
+                        let res = match sub {
+                            I32_TRUNC_SAT_F32_S => Value::from(single::to_i32(val.as_f32())),
+                            I32_TRUNC_SAT_F32_U => Value::from(single::to_u32(val.as_f32())),
+                            I32_TRUNC_SAT_F64_S => Value::from(double::to_i32(val.as_f64())),
+                            I32_TRUNC_SAT_F64_U => Value::from(double::to_u32(val.as_f64())),
+                            I64_TRUNC_SAT_F32_S => Value::from(single::to_i64(val.as_f32())),
+                            I64_TRUNC_SAT_F32_U => Value::from(single::to_u64(val.as_f32())),
+                            I64_TRUNC_SAT_F64_S => Value::from(double::to_i64(val.as_f64())),
+                            I64_TRUNC_SAT_F64_U => Value::from(double::to_u64(val.as_f64())),
+                            _ => return Err(Error::InvalidOpcode(sub)),
+                        };
+                        self.push_value(res)?;
+                    },
+                    MEMORY_INIT => {
+                        let index = code.read_u32()?;
+                        code.read_reserved()?;
+                        let len = self.pop()? as u32 as usize;
+                        let src = self.pop()? as u32 as usize;
+                        let dst = self.pop()? as u32 as usize;
+                        info!("MEMORY_INIT: {} {:08x} {:08x} {}", index, dst, src, len);
+                        let segment = mi.data_segment(index)?;
+                        match src.checked_add(len) {
+                            Some(end) if end <= segment.len() => {
+                                env.mem().copy_from_slice(dst, &segment[src..end])?;
+                            },
+                            _ => return Err(Error::OutOfBounds),
+                        }
+                    },
+                    DATA_DROP => {
+                        let index = code.read_u32()?;
+                        info!("DATA_DROP: {}", index);
+                        mi.drop_data_segment(index)?;
+                    },
+                    MEMORY_COPY => {
+                        code.read_reserved()?;
+                        code.read_reserved()?;
+                        let len = self.pop()? as u32 as usize;
+                        let src = self.pop()? as u32 as usize;
+                        let dst = self.pop()? as u32 as usize;
+                        env.mem().copy(dst, src, len)?;
+                    },
+                    MEMORY_FILL => {
+                        code.read_reserved()?;
+                        let len = self.pop()? as u32 as usize;
+                        let value = self.pop()?;
+                        let dst = self.pop()? as u32 as usize;
+                        env.mem().fill(dst, value as u8, len)?;
+                    },
+                    TABLE_INIT => {
+                        let index = code.read_u32()?;
+                        let table = mi.table(code.read_u32()?)?;
+                        let len = self.pop()? as u32 as usize;
+                        let src = self.pop()? as u32 as usize;
+                        let dst = self.pop()? as u32;
+                        info!("TABLE_INIT: {} {:08x} {:08x} {}", index, dst, src, len);
+                        let segment = mi.element_segment(index)?;
+                        match src.checked_add(len) {
+                            Some(end) if end <= segment.len() => {
+                                table.init(dst, &segment[src..end])?;
+                            },
+                            _ => return Err(Error::TableOutOfBounds),
+                        }
+                    },
+                    ELEM_DROP => {
+                        let index = code.read_u32()?;
+                        info!("ELEM_DROP: {}", index);
+                        mi.drop_element_segment(index)?;
+                    },
+                    TABLE_COPY => {
+                        let dst_table = mi.table(code.read_u32()?)?;
+                        let src_table = mi.table(code.read_u32()?)?;
+                        let len = self.pop()? as u32;
+                        let src = self.pop()? as u32;
+                        let dst = self.pop()? as u32;
+                        dst_table.copy_from(dst, src_table, src, len)?;
+                    },
+                    TABLE_GROW => {
+                        let table = mi.table(code.read_u32()?)?;
+                        let n = self.pop()? as u32;
+                        let init = self.pop_value()?.as_u32();
+                        self.push(table.grow(n, init))?;
+                    },
+                    TABLE_SIZE => {
+                        let table = mi.table(code.read_u32()?)?;
+                        self.push(table.size() as i32)?;
+                    },
+                    TABLE_FILL => {
+                        let table = mi.table(code.read_u32()?)?;
+                        let len = self.pop()? as u32;
+                        let value = self.pop_value()?.as_u32();
+                        let dst = self.pop()? as u32;
+                        table.fill(dst, value, len)?;
+                    },
+                    _ => return Err(Error::InvalidOpcode(sub)),
+                }
+            },
+            #[cfg(feature="simd")]
+            SIMD_PREFIX => {
+                let sub = code.read_sub_opcode()?;
+                match sub {
+                    V128_LOAD ..= V128_STORE | V128_LOAD32_ZERO | V128_LOAD64_ZERO => {
+                        code.read_align()?;
+                        let offset = code.read_u32()?;
+                        let mem = env.mem();
+                        if sub == V128_STORE {
+                            let value = self.pop_value()?.as_v128();
+                            let addr = effective_address(self.pop()? as u32, offset)?;
+                            mem.store128(addr, value)?;
+                        } else if sub == V128_LOAD {
+                            let addr = effective_address(self.pop()? as u32, offset)?;
+                            self.push_value(mem.load128(addr)?)?;
+                        } else {
+                            let (size, expand) = simd::load_kind(sub).ok_or(Error::InvalidOpcode(sub))?;
+                            let addr = effective_address(self.pop()? as u32, offset)?;
+                            self.push_value(expand(load_sized(mem, addr, size)?))?;
+                        }
+                    },
+                    V128_LOAD8_LANE ..= V128_STORE64_LANE => {
+                        code.read_align()?;
+                        let offset = code.read_u32()?;
+                        let lane = code.read_u8()?;
+                        let mem = env.mem();
+                        let v = self.pop_value()?.as_v128();
+                        let addr = effective_address(self.pop()? as u32, offset)?;
+                        let size = 16 / simd::lane_count(sub) as usize;
+                        if sub <= V128_LOAD64_LANE {
+                            let x = load_sized(mem, addr, size)? as u128;
+                            self.push_value(simd::replace_lane(sub, v, lane, x).ok_or(Error::InvalidOpcode(sub))?)?;
+                        } else {
+                            let x = simd::extract_lane(sub, v, lane).ok_or(Error::InvalidOpcode(sub))?;
+                            store_sized(mem, addr, size, x as u64)?;
+                        }
+                    },
+                    V128_CONST => {
+                        let lo = code.read_u64()? as u128;
+                        let hi = code.read_u64()? as u128;
+                        self.push_value(lo | hi << 64)?;
+                    },
+                    I8X16_SHUFFLE => {
+                        let mut lanes = [0u8; 16];
+                        for l in lanes.iter_mut() {
+                            *l = code.read_u8()?;
+                        }
+                        let b = self.pop_value()?.as_v128();
+                        let a = self.pop_value()?.as_v128();
+                        self.push_value(simd::shuffle(a, b, &lanes))?;
+                    },
+                    I8X16_EXTRACT_LANE_S ..= F64X2_REPLACE_LANE => {
+                        let lane = code.read_u8()?;
+                        let op = Op::from_simd_opcode(sub).ok_or(Error::InvalidOpcode(sub))?;
+                        let res = if op.is_binop() {
+                            let x = self.pop_value()?.as_v128();
+                            let v = self.pop_value()?.as_v128();
+                            simd::replace_lane(sub, v, lane, x)
+                        } else {
+                            let v = self.pop_value()?.as_v128();
+                            simd::extract_lane(sub, v, lane)
+                        };
+                        self.push_value(res.ok_or(Error::InvalidOpcode(sub))?)?;
+                    },
+                    V128_BITSELECT => {
+                        let c = self.pop_value()?.as_v128();
+                        let b = self.pop_value()?.as_v128();
+                        let a = self.pop_value()?.as_v128();
+                        self.push_value(simd::bitselect(a, b, c))?;
+                    },
+                    _ => {
+                        let op = Op::from_simd_opcode(sub).ok_or(Error::InvalidOpcode(sub))?;
+                        let res = if op.is_binop() {
+                            let b = self.pop_value()?.as_v128();
+                            let a = self.pop_value()?.as_v128();
+                            simd::binary(sub, a, b)
+                        } else {
+                            let a = self.pop_value()?.as_v128();
+                            simd::unary(sub, a)
+                        };
+                        let res = if self.cfg.deterministic { res.map(|v| simd::canonicalize(sub, v)) } else { res };
+                        self.push_value(res.ok_or(Error::InvalidOpcode(sub))?)?;
+                    },
+                }
+            },
+            ATOMIC_PREFIX => {
+                let sub = code.read_sub_opcode()?;
+                // ATOMIC_FENCE has nothing to order against in a single-threaded instance
+                if sub == ATOMIC_FENCE {
+                    code.read_reserved()?;
+                } else {
+                    code.read_align()?;
+                    let offset = code.read_u32()?;
+                    let mem = env.mem();
+                    match sub {
+                        MEMORY_ATOMIC_NOTIFY => {
+                            let count = self.pop()? as u32;
+                            let addr = atomic_address(mem, self.pop()? as u32, offset, 4)?;
+                            info!("NOTIFY @ {:08x} {}", addr, count);
+                            self.push(env.notify(addr, count)?)?;
+                        },
+                        MEMORY_ATOMIC_WAIT32 | MEMORY_ATOMIC_WAIT64 => {
+                            let timeout = self.pop_value()?.as_i64();
+                            let expected = self.pop_value()?.as_u64();
+                            let size = if sub == MEMORY_ATOMIC_WAIT32 { 4 } else { 8 };
+                            let addr = atomic_address(mem, self.pop()? as u32, offset, size)?;
+                            info!("WAIT @ {:08x} {} {}", addr, expected, timeout);
+                            // 1 is "not-equal"
+                            let res = if load_sized(mem, addr, size)? != expected {
+                                1
+                            } else {
+                                env.wait(addr, timeout)?
+                            };
+                            self.push(res)?;
+                        },
+                        I32_ATOMIC_LOAD ..= I64_ATOMIC_LOAD32_U => {
+                            let size = atomic_width(sub);
+                            let addr = atomic_address(mem, self.pop()? as u32, offset, size)?;
+                            self.push_value(Value::from(load_sized(mem, addr, size)?))?;
+                        },
+                        I32_ATOMIC_STORE ..= I64_ATOMIC_STORE32 => {
+                            let value = self.pop_value()?.as_u64();
+                            let size = atomic_width(sub);
+                            let addr = atomic_address(mem, self.pop()? as u32, offset, size)?;
+                            store_sized(mem, addr, size, value)?;
+                        },
+                        I32_ATOMIC_RMW_ADD ..= I64_ATOMIC_RMW32_XCHG_U => {
+                            let value = self.pop_value()?.as_u64();
+                            let size = atomic_width(sub);
+                            let addr = atomic_address(mem, self.pop()? as u32, offset, size)?;
+                            let old = load_sized(mem, addr, size)?;
+                            // Each operation covers seven consecutive sub-opcodes
+                            let new = match (sub - I32_ATOMIC_RMW_ADD) / 7 {
+                                0 => old.wrapping_add(value),
+                                1 => old.wrapping_sub(value),
+                                2 => old & value,
+                                3 => old | value,
+                                4 => old ^ value,
+                                _ => value,
+                            };
+                            store_sized(mem, addr, size, new)?;
+                            self.push_value(Value::from(old))?;
+                        },
+                        I32_ATOMIC_RMW_CMPXCHG ..= I64_ATOMIC_RMW32_CMPXCHG_U => {
+                            let replacement = self.pop_value()?.as_u64();
+                            let expected = self.pop_value()?.as_u64();
+                            let size = atomic_width(sub);
+                            let addr = atomic_address(mem, self.pop()? as u32, offset, size)?;
+                            let old = load_sized(mem, addr, size)?;
+                            // Narrow accesses compare against the wrapped expected value
+                            if size == 8 || old == expected & ((1 << (size * 8)) - 1) {
+                                store_sized(mem, addr, size, replacement)?;
+                            }
+                            self.push_value(Value::from(old))?;
+                        },
+                        _ => return Err(Error::InvalidOpcode(sub)),
+                    }
+                }
+            },
+            _ => return Err(Error::InvalidOpcode(opc)),
         }
+        Ok(())
+    }
+
+}
+
+/// Returns the entry of a compiled branch table of `count` entries that `val` selects. The
+/// default target is the last entry and is taken by any index past the labels, including
+/// negative ones.
+fn br_table_index(val: i32, count: u32) -> u32 {
+    let default = count.saturating_sub(1);
+    if val < 0 || val as u32 > default {
+        default
+    } else {
+        val as u32
+    }
+}
+
+/// Reads the 16-bit offset of a short branch and returns its target, relative to the end of
+/// the offset.
+fn short_target(code: &mut Reader) -> Result<usize, Error> {
+    let offset = code.read_i16()?;
+    Ok((code.pos() as isize + offset as isize) as usize)
+}
+
+/// Returns where local function `function_index` starts. Compiling it lazily appends to the
+/// code, in which case `code` is moved to the longer code at the same position.
+fn enter<'m, H: HostHandler>(env: &Environment<H>, mi: &'m ModuleInst, code: &mut Reader<'m>, function_index: usize) -> Result<usize, Error> {
+    let body_range = mi.body_range(env, function_index)?;
+    let code_buf = mi.code().as_ref();
+    if code_buf.len() != code.len() {
+        let pos = code.pos();
+        *code = Reader::new(code_buf);
+        code.set_pos(pos);
+    }
+    Ok(body_range.start)
+}
+
+fn effective_address(base: u32, offset: u32) -> Result<usize, Error> {
+    (base as usize).checked_add(offset as usize).ok_or(Error::OutOfBounds)
+}
+
+/// Returns the access width in bytes of an atomic load, store or read-modify-write. Each
+/// comes in seven forms: i32, i64, i32 8/16-bit and i64 8/16/32-bit.
+fn atomic_width(sub: u8) -> usize {
+    [4, 8, 1, 2, 1, 2, 4][((sub - I32_ATOMIC_LOAD) % 7) as usize]
+}
+
+/// Computes the address of an atomic access, trapping if it is out of bounds or not
+/// naturally aligned.
+fn atomic_address(mem: &MemoryInst, base: u32, offset: u32, size: usize) -> Result<usize, Error> {
+    let addr = effective_address(base, offset)?;
+    mem.check_access(addr, size)?;
+    if addr % size != 0 {
+        return Err(Error::UnalignedAtomic)
     }
+    Ok(addr)
+}
+
+/// Loads `size` bytes zero-extended to 64 bits.
+fn load_sized(mem: &MemoryInst, addr: usize, size: usize) -> Result<u64, Error> {
+    Ok(match size {
+        1 => mem.load8_u(addr)? as u64,
+        2 => mem.load16_u(addr)? as u64,
+        4 => mem.load(addr)? as u32 as u64,
+        _ => mem.load64(addr)? as u64,
+    })
+}
+
+/// Stores the low `size` bytes of `value`.
+fn store_sized(mem: &MemoryInst, addr: usize, size: usize, value: u64) -> Result<(), Error> {
+    match size {
+        1 => mem.store8(addr, value as i32),
+        2 => mem.store16(addr, value as i32),
+        4 => mem.store(addr, value as i32),
+        _ => mem.store64(addr, value as i64),
+    }
+}
+
+#[cfg(not(feature="integer-only"))]
+/// Checks that `v` can be truncated to an integer, trapping if it is NaN or not strictly
+/// between `lo` and `hi`.
+fn f32_to_int(v: f32, lo: f32, hi: f32) -> Result<f32, Error> {
+    if single::is_nan(v) {
+        Err(Error::InvalidConversionToInteger)
+    } else if single::lt(lo, v) && single::lt(v, hi) {
+        Ok(v)
+    } else {
+        Err(Error::IntegerOverflow)
+    }
+}
 
-}
\ No newline at end of file
+#[cfg(not(feature="integer-only"))]
+/// Checks that `v` can be truncated to an integer, trapping if it is NaN or not strictly
+/// between `lo` and `hi`.
+fn f64_to_int(v: f64, lo: f64, hi: f64) -> Result<f64, Error> {
+    if double::is_nan(v) {
+        Err(Error::InvalidConversionToInteger)
+    } else if double::lt(lo, v) && double::lt(v, hi) {
+        Ok(v)
+    } else {
+        Err(Error::IntegerOverflow)
+    }
+}
+
+// min and max propagate NaN and order -0.0 below +0.0, unlike `f32::min` / `f32::max`.
+
+#[cfg(not(feature="integer-only"))]
+pub(crate) fn f32_min(lhs: f32, rhs: f32) -> f32 {
+    if single::is_nan(lhs) || single::is_nan(rhs) {
+        single::add(lhs, rhs)
+    } else if single::eq(lhs, rhs) {
+        f32::from_bits(lhs.to_bits() | rhs.to_bits())
+    } else if single::lt(lhs, rhs) { lhs } else { rhs }
+}
+
+#[cfg(not(feature="integer-only"))]
+pub(crate) fn f32_max(lhs: f32, rhs: f32) -> f32 {
+    if single::is_nan(lhs) || single::is_nan(rhs) {
+        single::add(lhs, rhs)
+    } else if single::eq(lhs, rhs) {
+        f32::from_bits(lhs.to_bits() & rhs.to_bits())
+    } else if single::lt(rhs, lhs) { lhs } else { rhs }
+}
+
+#[cfg(not(feature="integer-only"))]
+pub(crate) fn f64_min(lhs: f64, rhs: f64) -> f64 {
+    if double::is_nan(lhs) || double::is_nan(rhs) {
+        double::add(lhs, rhs)
+    } else if double::eq(lhs, rhs) {
+        f64::from_bits(lhs.to_bits() | rhs.to_bits())
+    } else if double::lt(lhs, rhs) { lhs } else { rhs }
+}
+
+#[cfg(not(feature="integer-only"))]
+pub(crate) fn f64_max(lhs: f64, rhs: f64) -> f64 {
+    if double::is_nan(lhs) || double::is_nan(rhs) {
+        double::add(lhs, rhs)
+    } else if double::eq(lhs, rhs) {
+        f64::from_bits(lhs.to_bits() & rhs.to_bits())
+    } else if double::lt(rhs, lhs) { lhs } else { rhs }
+}
+
+// Every test compiles or validates the module it runs.
+#[cfg(all(test, feature="compiler"))]
+mod tests {
+    use super::*;
+    use test_support::*;
+    use types::ExportDesc;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    // (func $trap (result i32) i32.const 1 i32.const 2 unreachable)
+    // (func $ok (result i32) i32.const 7)
+    // (func $nested (result i32) call $trap)
+    const TRAP_MODULE: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+        0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f,
+        0x03, 0x04, 0x03, 0x00, 0x00, 0x00,
+        0x07, 0x16, 0x03,
+            0x04, b't', b'r', b'a', b'p', 0x00, 0x00,
+            0x02, b'o', b'k', 0x00, 0x01,
+            0x06, b'n', b'e', b's', b't', b'e', b'd', 0x00, 0x02,
+        0x0a, 0x13, 0x03,
+            0x07, 0x00, 0x41, 0x01, 0x41, 0x02, 0x00, 0x0b,
+            0x04, 0x00, 0x41, 0x07, 0x0b,
+            0x04, 0x00, 0x10, 0x00, 0x0b,
+    ];
+
+    #[test]
+    fn test_trap_recovery() {
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load(&mut buf, TRAP_MODULE);
+
+        let trap = f.call(0, &[]).unwrap_err();
+        assert_eq!(trap.error(), &Error::Unreachable);
+        assert_eq!(trap.frames().len(), 1);
+        assert_eq!(trap.frames()[0].function_index, 0);
+        assert_eq!(f.interp.stack_len(), 0);
+
+        assert_eq!(f.call(1, &[]).unwrap(), &[Value(7)]);
+        assert_eq!(f.interp.stack_len(), 0);
+
+        let trap = f.call(2, &[]).unwrap_err();
+        assert_eq!(trap.error(), &Error::Unreachable);
+        assert_eq!(trap.frames().len(), 2);
+        assert_eq!(trap.frames()[0].function_index, 0);
+        assert_eq!(trap.frames()[1].function_index, 2);
+        assert_eq!(f.interp.stack_len(), 0);
+
+        assert_eq!(f.call(1, &[]).unwrap(), &[Value(7)]);
+    }
+
+    #[test]
+    fn test_multi_value() {
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load(&mut buf, MULTI_VALUE_MODULE);
+
+        assert_eq!(f.call(0, &[]).unwrap(), &[Value(1), Value(2)]);
+        assert_eq!(f.call(1, &[]).unwrap(), &[Value(4), Value(5)]);
+        assert_eq!(f.call(2, &[]).unwrap(), &[Value::from(-1), Value(10)]);
+        assert_eq!(f.call(3, &[]).unwrap(), &[Value(3), Value(100)]);
+        assert_eq!(f.interp.stack_len(), 0);
+    }
+
+    #[test]
+    #[cfg(not(feature="integer-only"))]
+    fn test_conversions() {
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load(&mut buf, CONVERSION_MODULE);
+
+        assert_eq!(f.call(0, &[]).unwrap(), &[Value::from(-128)]);
+        assert_eq!(f.call(1, &[]).unwrap(), &[Value::from(-32768i64)]);
+        assert_eq!(f.call(2, &[]).unwrap(), &[Value::from(i32::MAX)]);
+        assert_eq!(f.call(3, &[]).unwrap(), &[Value::from(0i64)]);
+        assert_eq!(f.call(4, &[]).unwrap(), &[Value::from(0)]);
+        assert_eq!(f.call_err(5, &[]), Error::InvalidConversionToInteger);
+        assert_eq!(f.call_err(6, &[]), Error::IntegerDivideByZero);
+        assert_eq!(f.call(7, &[]).unwrap(), &[Value::from(-0.0f32)]);
+        assert_eq!(f.interp.stack_len(), 0);
+    }
+
+    #[test]
+    fn test_bulk_memory() {
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load(&mut buf, BULK_MEMORY_MODULE);
+
+        assert_eq!(f.call(0, &[]).unwrap(), &[Value(0x6f6c6c65)]);
+        assert_eq!(f.call(1, &[]).unwrap(), &[Value(0x6c652a2a)]);
+        assert_eq!(f.call_err(2, &[]), Error::OutOfBounds);
+        assert_eq!(f.call_err(0, &[]), Error::OutOfBounds);
+        assert_eq!(f.call_err(3, &[]), Error::OutOfBounds);
+        assert_eq!(f.env.mem().load(0).unwrap(), 0);
+        assert_eq!(f.interp.stack_len(), 0);
+    }
+
+    #[test]
+    fn test_reference_types() {
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load(&mut buf, REFERENCE_TYPES_MODULE);
+
+        assert_eq!(f.call(2, &[]).unwrap(), &[Value(7)]);
+        assert_eq!(f.call(3, &[]).unwrap(), &[Value(11)]);
+        assert_eq!(f.call(4, &[]).unwrap(), &[Value(4)]);
+        assert_eq!(f.mi.table(0).unwrap().get(3), Ok(1));
+        assert_eq!(f.call(5, &[]).unwrap(), &[Value(1)]);
+        assert_eq!(f.call_err(6, &[]), Error::TableOutOfBounds);
+        assert_eq!(f.call_err(7, &[]), Error::UninitializedElement);
+        assert_eq!(f.call_err(8, &[]), Error::TableOutOfBounds);
+        assert_eq!(f.call(9, &[]).unwrap(), &[Value(7)]);
+        assert_eq!(f.interp.stack_len(), 0);
+    }
+
+    #[test]
+    fn test_tail_calls() {
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load(&mut buf, TAIL_CALL_MODULE);
+
+        // Far deeper than the call stack if each iteration pushed a frame
+        assert_eq!(f.call(1, &[]).unwrap(), &[Value(500500)]);
+        assert_eq!(f.call(2, &[]).unwrap(), &[Value(5050)]);
+        assert_eq!(f.call(3, &[]).unwrap(), &[Value(500501)]);
+        assert_eq!(f.interp.stack_len(), 0);
+    }
+
+    #[test]
+    fn test_exceptions() {
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load(&mut buf, EXCEPTION_MODULE);
+
+        assert_eq!(f.call(1, &[]).unwrap(), &[Value(43)]);
+        assert_eq!(f.call(2, &[]).unwrap(), &[Value(7)]);
+        assert_eq!(f.call(3, &[]).unwrap(), &[Value(1009)]);
+        assert_eq!(f.call_err(4, &[]), Error::UncaughtException { tag: 1 });
+        assert_eq!(f.call(5, &[]).unwrap(), &[Value(11)]);
+        // More iterations than the handler stack holds
+        assert_eq!(f.call(6, &[]).unwrap(), &[Value(100)]);
+        assert_eq!(f.call(1, &[]).unwrap(), &[Value(43)]);
+        assert_eq!(f.interp.stack_len(), 0);
+    }
+
+    #[test]
+    fn test_atomics() {
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load(&mut buf, ATOMIC_MODULE);
+
+        assert_eq!(f.call(0, &[]).unwrap(), &[Value(13)]);
+        assert_eq!(f.call(1, &[]).unwrap(), &[Value(91)]);
+        assert_eq!(f.call(2, &[]).unwrap(), &[Value(264)]);
+        assert_eq!(f.call(3, &[]).unwrap(), &[Value(0xffff_fffe)]);
+        // not-equal, then the default hook times out and wakes nobody
+        assert_eq!(f.call(4, &[]).unwrap(), &[Value(12)]);
+        assert_eq!(f.call_err(5, &[]), Error::UnalignedAtomic);
+        assert_eq!(f.call_err(6, &[]), Error::OutOfBounds);
+        assert_eq!(f.interp.stack_len(), 0);
+    }
+
+    #[cfg(feature="simd")]
+    #[test]
+    fn test_simd() {
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load(&mut buf, SIMD_MODULE);
+
+        assert_eq!(f.call(0, &[]).unwrap(), &[Value(33)]);
+        assert_eq!(f.call(1, &[]).unwrap(), &[Value(0x0c0d0e0f)]);
+        assert_eq!(f.call(2, &[]).unwrap(), &[Value(5)]);
+        assert_eq!(f.call(3, &[]).unwrap(), &[Value(5)]);
+        assert_eq!(f.call(4, &[]).unwrap(), &[Value(0x00000004_00000003_00000002_00000001)]);
+        assert_eq!(f.call_err(5, &[]), Error::OutOfBounds);
+        assert_eq!(f.interp.stack_len(), 0);
+    }
+
+    #[test]
+    fn test_in_place() {
+        // Calls `funcs` in order both compiled and in place, which must give the same results
+        fn compare(module: &[u8], funcs: &[usize]) {
+            let mut buf = [0u8; 32768];
+            let mut compiled = Fixture::load(&mut buf, module);
+            let mut buf = [0u8; 32768];
+            let mut in_place = Fixture::load_with(&mut buf, module, Load::InPlace, Config::default()).unwrap();
+            assert_eq!(in_place.mi.code().len(), 0);
+
+            for &i in funcs {
+                let expected = compiled.call(i, &[]).map_err(Error::from);
+                let got = in_place.call(i, &[]).map_err(Error::from);
+                assert_eq!(got, expected, "func {}", i);
+            }
+            assert_eq!(in_place.interp.stack_len(), 0);
+        }
+
+        compare(MULTI_VALUE_MODULE, &[0, 1, 2, 3]);
+        #[cfg(not(feature="integer-only"))]
+        compare(CONVERSION_MODULE, &[0, 1, 2, 3, 4, 5, 6, 7]);
+        compare(REFERENCE_TYPES_MODULE, &[2, 3, 4, 5, 6, 7, 8, 9]);
+        compare(TAIL_CALL_MODULE, &[1, 2, 3]);
+        compare(BULK_MEMORY_MODULE, &[0, 1, 2, 0, 3]);
+        compare(ATOMIC_MODULE, &[0, 1, 2, 3, 4, 5, 6]);
+        compare(EXCEPTION_MODULE, &[1, 2, 3, 4, 5, 6, 1]);
+        #[cfg(feature="simd")]
+        compare(SIMD_MODULE, &[0, 1, 2, 3, 4, 5]);
+    }
+
+    // func 0 is `block block local.get 0 br_table 0 1 end i32.const 10 return end i32.const 20`
+    // with `n` i64 constants dropped between the two ends, so that index 0 returns 10 and the
+    // default returns 20.
+    fn br_table_default(buf: &mut [u8], n: usize) -> &[u8] {
+        let mut body = [0u8; 16384];
+        let mut body_len = 0;
+        put(&mut body, &mut body_len, &[0x00, 0x02, 0x40, 0x02, 0x40, 0x20, 0x00, 0x0e, 0x01, 0x00, 0x01, 0x0b]);
+        for _ in 0..n {
+            put(&mut body, &mut body_len, &[0x42, 0x00, 0x1a]);
+        }
+        put(&mut body, &mut body_len, &[0x41, 0x0a, 0x0f, 0x0b, 0x41, 0x14, 0x0b]);
+
+        let mut len = 0;
+        put(buf, &mut len, HEADER);
+        put(buf, &mut len, &[0x01, 0x06, 0x01, 0x60, 0x01, 0x7f, 0x01, 0x7f]);
+        put(buf, &mut len, &[0x03, 0x02, 0x01, 0x00]);
+        put(buf, &mut len, &[0x0a]);
+        let mut size = [0u8; 4];
+        let mut size_len = 0;
+        put_leb(&mut size, &mut size_len, body_len);
+        put_leb(buf, &mut len, 1 + size_len + body_len);
+        put(buf, &mut len, &[0x01]);
+        put(buf, &mut len, &size[..size_len]);
+        put(buf, &mut len, &body[..body_len]);
+        &buf[..len]
+    }
+
+    #[test]
+    fn test_br_table_default() {
+        // Short and wide tables, both compiled and in place
+        let mut short_len = 0;
+        for &n in [0, 4000].iter() {
+            let mut module = [0u8; 16384];
+            let module = br_table_default(&mut module, n);
+            for &load in [Load::Compiled, Load::InPlace].iter() {
+                let mut buf = [0u8; 131072];
+                let mut f = Fixture::load_with(&mut buf, module, load, Config::default()).unwrap();
+                // The code other than the constants is longer with a wide table
+                if load == Load::Compiled && n == 0 {
+                    short_len = f.mi.code().len();
+                } else if load == Load::Compiled {
+                    assert!(f.mi.code().len() - n * 10 > short_len);
+                }
+                // The only label, then the label count, one past it and -1
+                for &(index, expected) in [(0, 10), (1, 20), (2, 20), (-1, 20), (i32::MIN, 20)].iter() {
+                    assert_eq!(f.call(0, &[index]).unwrap(), &[Value::from(expected)], "index {} n {}", index, n);
+                }
+                assert_eq!(f.interp.stack_len(), 0);
+            }
+        }
+    }
+
+    // Each function takes an i32 index and returns an i32:
+    // - func 0 has `br_table 0 1 2 3` out of four nested blocks, returning 100 to 103
+    // - func 1 has `br_table` with only a default, carrying 7 out of a block that adds 1
+    // - func 2 sums its argument down to 1 in a loop that `br_table 1 0` branches back to
+    const BR_TABLE_MODULE: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+        0x01, 0x06, 0x01, 0x60, 0x01, 0x7f, 0x01, 0x7f,
+        0x03, 0x04, 0x03, 0x00, 0x00, 0x00,
+        0x0a, 0x56, 0x03,
+            0x25, 0x00,
+                0x02, 0x40, 0x02, 0x40, 0x02, 0x40, 0x02, 0x40,
+                0x20, 0x00, 0x0e, 0x03, 0x00, 0x01, 0x02, 0x03, 0x0b,
+                0x41, 0xe4, 0x00, 0x0f, 0x0b,
+                0x41, 0xe5, 0x00, 0x0f, 0x0b,
+                0x41, 0xe6, 0x00, 0x0f, 0x0b,
+                0x41, 0xe7, 0x00, 0x0b,
+            0x0f, 0x00,
+                0x02, 0x7f, 0x41, 0x07, 0x20, 0x00, 0x0e, 0x00, 0x00, 0x0b,
+                0x41, 0x01, 0x6a, 0x0b,
+            0x1e, 0x01, 0x01, 0x7f,
+                0x02, 0x40, 0x03, 0x40,
+                    0x20, 0x01, 0x20, 0x00, 0x6a, 0x21, 0x01,
+                    0x20, 0x00, 0x41, 0x01, 0x6b, 0x22, 0x00,
+                    0x0e, 0x01, 0x01, 0x00,
+                0x0b, 0x0b,
+                0x20, 0x01, 0x0b,
+    ];
+
+    // Calls `func` of `module` once for each of `args`, both compiled and in place, and
+    // checks that the two engines agree.
+    fn differential(buf: &mut [u8], module: &[u8], func: usize, args: &[i32]) {
+        let (compiled_buf, in_place_buf) = buf.split_at_mut(buf.len() / 2);
+        let mut compiled = Fixture::load(compiled_buf, module);
+        let mut in_place = Fixture::load_with(in_place_buf, module, Load::InPlace, Config::default()).unwrap();
+        for &arg in args {
+            let expected = compiled.call(func, &[arg]).map_err(Error::from);
+            let got = in_place.call(func, &[arg]).map_err(Error::from);
+            assert_eq!(got, expected, "func {} arg {}", func, arg);
+        }
+        assert_eq!(compiled.interp.stack_len(), 0);
+        assert_eq!(in_place.interp.stack_len(), 0);
+    }
+
+    // The sample modules in `local_test` that need no imports (global.wasm reads a global it
+    // does not declare, so it fails to load)
+    const SAMPLE_MODULES: &[&[u8]] = &[
+        include_bytes!("../local_test/add.wasm"), include_bytes!("../local_test/basic.wasm"),
+        include_bytes!("../local_test/binary-32.wasm"), include_bytes!("../local_test/call.wasm"),
+        include_bytes!("../local_test/double2.wasm"), include_bytes!("../local_test/empty.wasm"),
+        include_bytes!("../local_test/fac0.wasm"), include_bytes!("../local_test/fac1.wasm"),
+        include_bytes!("../local_test/fac2.wasm"), include_bytes!("../local_test/fac3.wasm"),
+        include_bytes!("../local_test/fac10.wasm"), include_bytes!("../local_test/fib.wasm"),
+        include_bytes!("../local_test/if-else-false.wasm"), include_bytes!("../local_test/if-else-true.wasm"),
+        include_bytes!("../local_test/if-false.wasm"), include_bytes!("../local_test/if-true.wasm"),
+        include_bytes!("../local_test/load-32.wasm"), include_bytes!("../local_test/local.wasm"),
+        include_bytes!("../local_test/memcpy.wasm"), include_bytes!("../local_test/multi-basic.wasm"),
+        include_bytes!("../local_test/param.wasm"), include_bytes!("../local_test/return-void.wasm"),
+        include_bytes!("../local_test/select.wasm"), include_bytes!("../local_test/sieve.wasm"),
+        include_bytes!("../local_test/tee.wasm"), include_bytes!("../local_test/unary-32.wasm"),
+    ];
+
+    #[test]
+    fn test_differential_samples() {
+        // Calls every export without parameters in order, both compiled and in place
+        let mut calls = 0;
+        for (i, &module) in SAMPLE_MODULES.iter().enumerate() {
+            let mut buf = [0u8; 65536];
+            let (compiled_buf, in_place_buf) = buf.split_at_mut(32768);
+            let mut compiled = Fixture::load(compiled_buf, module);
+            let mut in_place = Fixture::load_with(in_place_buf, module, Load::InPlace, Config::default()).unwrap();
+            let mi = compiled.mi;
+            for e in mi.exports() {
+                let func = match e.export_desc {
+                    ExportDesc::Func(index) => match mi.functions()[index as usize] {
+                        FuncInst::Local { type_index, function_index }
+                            if mi.function_types()[type_index].parameters.is_empty() => function_index,
+                        _ => continue,
+                    },
+                    _ => continue,
+                };
+                let expected = compiled.call(func, &[]).map_err(Error::from);
+                let got = in_place.call(func, &[]).map_err(Error::from);
+                assert_eq!(got, expected, "module {} export {}", i, e.name);
+                calls += 1;
+            }
+        }
+        assert!(calls > SAMPLE_MODULES.len());
+    }
+
+    #[test]
+    fn test_differential() {
+        // Every label, then the first indices past the table, the sign bit and -1
+        const INDICES: &[i32] = &[0, 1, 2, 3, 4, 5, 255, 256, 65536, i32::MAX, i32::MIN, -1, -2];
+        let mut buf = [0u8; 65536];
+        differential(&mut buf, BR_TABLE_MODULE, 0, INDICES);
+        differential(&mut buf, BR_TABLE_MODULE, 1, INDICES);
+        differential(&mut buf, BR_TABLE_MODULE, 2, &[1, 2, 5, 100]);
+
+        // Spot check the compiled results that the engines agreed on
+        let mut f = Fixture::load(&mut buf, BR_TABLE_MODULE);
+        for &(index, expected) in [(0, 100), (2, 102), (3, 103), (4, 103), (-1, 103), (i32::MIN, 103)].iter() {
+            assert_eq!(f.call(0, &[index]).unwrap(), &[Value::from(expected)], "index {}", index);
+        }
+        assert_eq!(f.call(1, &[-1]).unwrap(), &[Value::from(8)]);
+        assert_eq!(f.call(2, &[5]).unwrap(), &[Value::from(15)]);
+
+        // Short and wide tables
+        for &n in [0, 4000].iter() {
+            let mut module = [0u8; 16384];
+            let module = br_table_default(&mut module, n);
+            let mut buf = [0u8; 2 * 131072];
+            differential(&mut buf, module, 0, INDICES);
+        }
+    }
+
+    #[test]
+    fn test_frame_locals() {
+        // func 0 has 300 locals, more than fit in a short form index. It copies its argument
+        // to locals 299 and 150 and returns local 299 plus func 1 of local 150, which doubles
+        // its argument through a local of its own.
+        let mut module = [0u8; 128];
+        let module = assemble(&mut module, &[
+            &[0x01, 0x06, 0x01, 0x60, 0x01, 0x7f, 0x01, 0x7f],
+            &[0x03, 0x03, 0x02, 0x00, 0x00],
+            &[0x0a, 0x26, 0x02,
+                0x18, 0x01, 0xac, 0x02, 0x7f,
+                    0x20, 0x00, 0x21, 0xab, 0x02, 0x20, 0x00, 0x21, 0x96, 0x01,
+                    0x20, 0x96, 0x01, 0x10, 0x01, 0x20, 0xab, 0x02, 0x6a, 0x0b,
+                0x0b, 0x01, 0x01, 0x7f,
+                    0x20, 0x00, 0x20, 0x00, 0x6a, 0x22, 0x01, 0x0b],
+        ]);
+        for &load in [Load::Compiled, Load::InPlace].iter() {
+            let mut buf = [0u8; 65536];
+            let mut f = Fixture::load_with(&mut buf, module, load, Config::default().value_stack_size(512)).unwrap();
+            assert_eq!(f.call(0, &[7]).unwrap(), &[Value(21)]);
+            assert_eq!(f.interp.stack_len(), 0);
+
+            // The frame does not fit on a smaller value stack
+            let mut interp_buf = [0u8; 16384];
+            let mut interp = Interp::new(&mut interp_buf).unwrap();
+            interp.push(7).unwrap();
+            assert_eq!(interp.call(&f.env, f.mi, 0).unwrap_err().error(), &Error::StackError(stack::Error::Overflow));
+            assert_eq!(interp.stack_len(), 0);
+        }
+    }
+
+    static TRACED_ADDS: AtomicUsize = AtomicUsize::new(0);
+
+    fn trace_adds(_interp: &Interp, _offset: usize, opcode: u8) {
+        if opcode == I32_ADD {
+            TRACED_ADDS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_trace() {
+        // func 0 adds one to its argument
+        let mut module = [0u8; 64];
+        let module = assemble(&mut module, &[
+            &[0x01, 0x06, 0x01, 0x60, 0x01, 0x7f, 0x01, 0x7f],
+            &[0x03, 0x02, 0x01, 0x00],
+            &[0x0a, 0x09, 0x01, 0x07, 0x00, 0x20, 0x00, 0x41, 0x01, 0x6a, 0x0b],
+        ]);
+        for &load in [Load::Compiled, Load::InPlace].iter() {
+            let mut buf = [0u8; 32768];
+            let before = TRACED_ADDS.load(Ordering::SeqCst);
+            let mut f = Fixture::load_with(&mut buf, module, load, Config::default().trace(trace_adds)).unwrap();
+            assert_eq!(f.call(0, &[7]).unwrap(), &[Value(8)]);
+            assert_eq!(TRACED_ADDS.load(Ordering::SeqCst), before + 1);
+
+            // Without a hook nothing is traced
+            let mut interp_buf = [0u8; 16384];
+            let mut interp = Interp::new(&mut interp_buf).unwrap();
+            interp.push(7).unwrap();
+            assert_eq!(interp.call(&f.env, f.mi, 0).unwrap(), &[Value(8)]);
+            assert_eq!(TRACED_ADDS.load(Ordering::SeqCst), before + 1);
+        }
+    }
+}
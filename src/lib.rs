@@ -1,5 +1,9 @@
 #![no_std]
-#![feature(ptr_offset_from, core_intrinsics)]
+#![cfg_attr(any(not(any(feature="soft-float", feature="integer-only")), test, feature="enable-log-off"), feature(core_intrinsics))]
+#![cfg_attr(any(not(any(feature="soft-float", feature="integer-only")), test, feature="enable-log-off"), allow(internal_features))]
+
+#[cfg(feature="std")]
+extern crate std;
 
 extern crate fallible_iterator;
 extern crate byteorder;
@@ -10,22 +14,44 @@ extern crate byteorder;
 
 pub mod error;
 pub mod types;
+pub mod opcode;
 pub mod cursor;
 pub mod reader;
 pub mod writer;
 pub mod stack;
 pub mod small_vec;
+pub mod limits;
+pub mod code;
+pub mod side_table;
+#[cfg(feature="compiler")]
 pub mod compiler;
+#[cfg(feature="compiler")]
 pub mod typeck;
 pub mod interp;
+#[cfg(feature="simd")]
+pub mod simd;
 pub mod memory_inst;
+pub mod table_inst;
 pub mod module_inst;
 pub mod environ;
+pub mod image;
+#[cfg(feature="compiler")]
+pub mod estimate;
 pub mod floathex;
+#[cfg(not(feature="integer-only"))]
+pub mod fp;
+#[cfg(any(feature="soft-float", not(feature="integer-only"), test))]
+pub mod soft_float;
+#[cfg(feature="compiler")]
 pub mod parser;
 pub mod page_table;
+#[cfg(test)]
+mod test_support;
+#[cfg(all(test, feature="simd", feature="compiler"))]
+mod simd_spec;
 
-use parser::opcode as opcode;
+#[cfg(all(feature="integer-only", feature="simd"))]
+compile_error!("the `simd` feature has float lanes and cannot be combined with `integer-only`");
 
 pub use error::*;
 pub use types::*;
@@ -0,0 +1,61 @@
+/// Capacities used when instantiating and compiling a module. Every table is allocated up
+/// front from the buffers passed in, so raising a limit only costs memory when the
+/// embedder provides it.
+///
+/// The defaults are enough for the test suite. Modules produced by rustc usually need more
+/// functions, exports and globals, for example
+/// `ResourceLimits { functions: 1024, exports: 256, ..ResourceLimits::default() }`.
+///
+/// The compiler tracks `operands` as value types, one byte each. At run time operands and
+/// locals are `Value`s on the interpreter's value stack, sized by
+/// `interp::Config::value_stack_size`, and each global holds one. A `Value` is 8 bytes, or
+/// 16 with the `simd` feature, so the interpreter's default 64-entry value stack and
+/// 16-entry exception stack take 640 bytes (1280 with `simd`) and the default 16 globals
+/// take 384 bytes (512 with `simd`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Modules that can be loaded into an `Environment`.
+    pub modules: usize,
+    /// Function types in a module.
+    pub types: usize,
+    /// Functions in a module, counting imported functions.
+    pub functions: usize,
+    /// Globals in a module, counting imported globals. Each takes 24 bytes, or 32 with the
+    /// `simd` feature.
+    pub globals: usize,
+    /// Tables in a module.
+    pub tables: usize,
+    /// Exception tags in a module, counting imported tags.
+    pub tags: usize,
+    /// Exports from a module.
+    pub exports: usize,
+    /// Element segments in a module.
+    pub elements: usize,
+    /// Data segments in a module.
+    pub data: usize,
+    /// Nested blocks, loops and ifs in a function body, including the function itself.
+    pub labels: usize,
+    /// Operands on the type checker's stack while compiling a function body.
+    pub operands: usize,
+    /// Forward branches waiting for their target label to be reached.
+    pub fixups: usize,
+}
+
+impl Default for ResourceLimits {
+    fn default() -> ResourceLimits {
+        ResourceLimits {
+            modules: 4,
+            types: 16,
+            functions: 32,
+            globals: 16,
+            tables: 4,
+            tags: 16,
+            exports: 32,
+            elements: 16,
+            data: 16,
+            labels: 16,
+            operands: 16,
+            fixups: 256,
+        }
+    }
+}
@@ -5,6 +5,7 @@ use byteorder::{ByteOrder, LittleEndian};
 use core::marker::PhantomData;
 use core::cell::{Cell, UnsafeCell};
 use core::slice;
+use core::cmp;
 use core::fmt;
 
 use page_table::PageTable;
@@ -19,15 +20,18 @@ pub struct MemoryInst<'a> {
     num_pages: Cell<usize>,
     min_pages: usize,
     max_pages: usize,
+    max_declared: Cell<Option<usize>>,
     _phantom: PhantomData<&'a [u8]>,
 }
 
 impl<'a> MemoryInst<'a> {
-    pub fn new(buf: &'a mut [u8], min_pages: usize, _max_pages: Option<usize>) -> MemoryInst {        
+    /// Creates a memory backed by `buf`, which the page table addresses in mini-pages. Fails
+    /// with `Error::MemoryTooLarge` if `buf` is larger than 255 mini-pages.
+    pub fn new(buf: &'a mut [u8], min_pages: usize, _max_pages: Option<usize>) -> Result<MemoryInst<'a>, Error> {
         let buf_len = buf.len();
         // let buf_pages = buf.len() / PAGE_SIZE;
         let mini_pages = buf.len() / MINI_SIZE;
-        assert!(mini_pages <= 255);
+        if mini_pages > 255 { return Err(Error::MemoryTooLarge) }
         let buf = buf.as_mut_ptr();
         let page_table = UnsafeCell::new(PageTable::new(mini_pages as u8));
         let num_pages = Cell::new(min_pages);
@@ -38,13 +42,18 @@ impl<'a> MemoryInst<'a> {
         // };
         // Allow 
         let max_pages = 64;
-        MemoryInst { buf: buf as *mut u8, buf_len, page_table, num_pages, min_pages, max_pages, _phantom: PhantomData }
+        let max_declared = Cell::new(None);
+        Ok(MemoryInst { buf, buf_len, page_table, num_pages, min_pages, max_pages, max_declared, _phantom: PhantomData })
     }
 
     pub fn len(&self) -> usize {
         self.num_pages.get() * PAGE_SIZE        
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.num_pages.get() == 0
+    }
+
     pub fn cap(&self) -> usize {
         self.max_pages * PAGE_SIZE
     }
@@ -64,9 +73,10 @@ impl<'a> MemoryInst<'a> {
     pub fn grow_memory(&self, pages: i32) -> i32 {
         info!("grow_memory({})", pages);
         let prev = self.current_memory();
-        let next = prev + pages;
-        if next <= self.max_pages as i32 {
-            self.num_pages.set(next as usize);
+        // The page count is unsigned, so a negative delta is a request for more than 2GiB
+        let next = self.num_pages().saturating_add(pages as u32 as usize);
+        if next <= self.max_pages {
+            self.num_pages.set(next);
             info!("   num_pages: {}", self.num_pages());
             info!("   len: {}", self.len());
             prev
@@ -76,27 +86,78 @@ impl<'a> MemoryInst<'a> {
         }
     }
 
-    pub fn as_ref(&self) -> &[u8] {
-        unsafe { slice::from_raw_parts(self.buf, self.buf_len) }
+    /// Records the maximum number of pages declared by the module that defines this memory.
+    pub fn set_max_declared(&self, max: Option<u32>) {
+        self.max_declared.set(max.map(|max| max as usize));
     }
 
+    /// Grows memory against the maximum declared by the module, or the 65536 pages that
+    /// 32-bit addressing allows if it declared none, so that the result does not depend on
+    /// how much memory the host provides. Growth that is allowed but that the host cannot
+    /// provide is an error rather than a failed grow.
+    pub fn grow_memory_declared(&self, pages: i32) -> Result<i32, Error> {
+        info!("grow_memory_declared({})", pages);
+        let prev = self.num_pages();
+        let next = prev.saturating_add(pages as u32 as usize);
+        if next > self.max_declared.get().unwrap_or(65536) {
+            Ok(-1)
+        } else if next > self.max_pages {
+            Err(Error::Nondeterministic("memory.grow beyond host memory"))
+        } else {
+            self.num_pages.set(next);
+            Ok(prev as i32)
+        }
+    }
+
+    // The memory is shared by the environment and the interpreter, which is single threaded
+    // and never holds the slice across calls, so only one mutable borrow is live at a time.
+    #[allow(clippy::mut_from_ref)]
     pub fn as_mut(&self) -> &mut [u8] {
         unsafe { slice::from_raw_parts_mut(self.buf, self.buf_len) }
     }
 
-    fn check_access(&self, index: usize, len: usize) -> Result<(), Error> {
+    pub fn check_access(&self, index: usize, len: usize) -> Result<(), Error> {
         info!("check_access({}, {}) < {}", index, len, self.len());
-        if index + len <= self.len() { Ok(()) } else { Err(Error::OutOfBounds) }
+        match index.checked_add(len) {
+            Some(end) if end <= self.len() => Ok(()),
+            _ => Err(Error::OutOfBounds),
+        }
     }
 
     fn mini_page(&self, addr: usize) -> u8 {
-        (addr / MINI_SIZE) as u8
+        mini_page(addr)
     }
 
     fn offset(&self, addr: usize) -> usize {
-        (addr % MINI_SIZE)
+        addr % MINI_SIZE
+    }
+
+    // Number of bytes from `addr` to the end of its mini page
+    fn page_rem(&self, addr: usize) -> usize {
+        MINI_SIZE - self.offset(addr)
+    }
+
+    // Number of bytes from the start of a mini page up to `end` (exclusive)
+    fn page_used(&self, end: usize) -> usize {
+        self.offset(end - 1) + 1
     }
 
+    // Maps every mini page touched by `addr .. addr + len` so that bulk operations
+    // fail before modifying memory rather than partway through.
+    fn map_range(&self, addr: usize, len: usize) -> Result<(), Error> {
+        if len == 0 {
+            return Ok(())
+        }
+        let end = addr + len;
+        let mut page = addr - self.offset(addr);
+        while page < end {
+            self.map_addr(page)?;
+            page += MINI_SIZE;
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::mut_from_ref)]
     fn page_table(&self) -> &mut PageTable {
         unsafe { &mut *self.page_table.get() }
     }
@@ -104,14 +165,7 @@ impl<'a> MemoryInst<'a> {
     pub fn map_addr(&self, v_addr: usize) -> Result<usize, Error> {
         info!("map_addr({:08x})", v_addr);
 
-        // NOTE: This is here because LLVM allocates static memory starting at 0x100_000, which
-        // is too large for the page table to handle.
-
-        let a_addr = match v_addr {
-            addr @ _ if addr < 0x00_1000 => addr,
-            addr @ _ if addr >= 0x10_0000 => (addr - 0x10_0000) + 0x1000,
-            _ => return Err(Error::ReservedMemoryArea),
-        };
+        let a_addr = translate(v_addr)?;
 
         info!("   a_addr: {:08x}", a_addr);
 
@@ -128,12 +182,15 @@ impl<'a> MemoryInst<'a> {
         }     
     }
 
-    pub fn get(&self, index: usize) -> u8 {
-        self.as_ref()[self.map_addr(index).unwrap()]
+    pub fn get(&self, index: usize) -> Result<u8, Error> {
+        self.check_access(index, 1)?;
+        Ok(self.as_ref()[self.map_addr(index)?])
     }
 
-    pub fn set(&self, index: usize, value: u8) {
-        self.as_mut()[self.map_addr(index).unwrap()] = value
+    pub fn set(&self, index: usize, value: u8) -> Result<(), Error> {
+        self.check_access(index, 1)?;
+        self.as_mut()[self.map_addr(index)?] = value;
+        Ok(())
     }
 
     pub fn load(&self, index: usize) -> Result<i32, Error> {        
@@ -148,7 +205,7 @@ impl<'a> MemoryInst<'a> {
         Ok({
             self.check_access(index, 2)?;
             let index = self.map_addr(index)?;
-            LittleEndian::read_i16(&self.as_ref()[index..]) as i16 as i32
+            LittleEndian::read_i16(&self.as_ref()[index..]) as i32
         })
     }
 
@@ -165,7 +222,7 @@ impl<'a> MemoryInst<'a> {
         Ok({
             self.check_access(index, 1)?;
             let index = self.map_addr(index)?;
-            self.as_mut()[index] as u8 as i32
+            self.as_mut()[index] as i32
         })
     }    
 
@@ -177,27 +234,128 @@ impl<'a> MemoryInst<'a> {
         })
     }    
 
-    pub fn store(&self, index: usize, value: i32) -> Result<(), Error> {
+    pub fn load64(&self, index: usize) -> Result<i64, Error> {        
         Ok({
-            self.check_access(index, 4)?;
+            self.check_access(index, 8)?;
             let index = self.map_addr(index)?;
-            LittleEndian::write_i32(&mut self.as_mut()[index..], value)
+            LittleEndian::read_i64(&self.as_ref()[index..])
         })
     }
 
-    pub fn store16(&self, index: usize, value: i32) -> Result<(), Error> {
+    pub fn store64(&self, index: usize, value: i64) -> Result<(), Error> {
+        self.check_access(index, 8)?;
+        let index = self.map_addr(index)?;
+        LittleEndian::write_i64(&mut self.as_mut()[index..], value);
+        Ok(())
+    }
+
+    #[cfg(feature="simd")]
+    pub fn load128(&self, index: usize) -> Result<u128, Error> {
         Ok({
-            self.check_access(index, 2)?;
-            let index = self.map_addr(index)?;
-            LittleEndian::write_i16(&mut self.as_mut()[index..], value as i16)
+            self.check_access(index, 16)?;
+            let lo = self.load64(index)? as u64 as u128;
+            let hi = self.load64(index + 8)? as u64 as u128;
+            lo | hi << 64
         })
     }
+
+    #[cfg(feature="simd")]
+    pub fn store128(&self, index: usize, value: u128) -> Result<(), Error> {
+        self.check_access(index, 16)?;
+        self.store64(index, value as i64)?;
+        self.store64(index + 8, (value >> 64) as i64)?;
+        Ok(())
+    }
+
+    pub fn store(&self, index: usize, value: i32) -> Result<(), Error> {
+        self.check_access(index, 4)?;
+        let index = self.map_addr(index)?;
+        LittleEndian::write_i32(&mut self.as_mut()[index..], value);
+        Ok(())
+    }
+
+    pub fn store16(&self, index: usize, value: i32) -> Result<(), Error> {
+        self.check_access(index, 2)?;
+        let index = self.map_addr(index)?;
+        LittleEndian::write_i16(&mut self.as_mut()[index..], value as i16);
+        Ok(())
+    }
     pub fn store8(&self, index: usize, value: i32) -> Result<(), Error> {
-        Ok({
-            self.check_access(index, 1)?;
-            let index = self.map_addr(index)?;
-            self.as_mut()[index] = value as u8;
-        })
+        self.check_access(index, 1)?;
+        let index = self.map_addr(index)?;
+        self.as_mut()[index] = value as u8;
+        Ok(())
+    }
+
+    /// Copies `src` into memory at `dst`, as `memory.init` and active data segments do.
+    pub fn copy_from_slice(&self, dst: usize, src: &[u8]) -> Result<(), Error> {
+        info!("copy_from_slice({:08x}, {})", dst, src.len());
+        self.check_access(dst, src.len())?;
+        self.map_range(dst, src.len())?;
+        let buf = self.as_mut();
+        let mut pos = 0;
+        while pos < src.len() {
+            let n = cmp::min(src.len() - pos, self.page_rem(dst + pos));
+            let p = self.map_addr(dst + pos)?;
+            buf[p..p + n].copy_from_slice(&src[pos..pos + n]);
+            pos += n;
+        }
+        Ok(())
+    }
+
+    /// Copies `len` bytes from `src` to `dst`. Overlapping ranges are handled like `memmove`.
+    pub fn copy(&self, dst: usize, src: usize, len: usize) -> Result<(), Error> {
+        info!("copy({:08x}, {:08x}, {})", dst, src, len);
+        self.check_access(dst, len)?;
+        self.check_access(src, len)?;
+        self.map_range(dst, len)?;
+        self.map_range(src, len)?;
+        let buf = self.as_mut();
+        if dst <= src {
+            let mut pos = 0;
+            while pos < len {
+                let (d, s) = (dst + pos, src + pos);
+                let n = cmp::min(len - pos, cmp::min(self.page_rem(d), self.page_rem(s)));
+                let (d, s) = (self.map_addr(d)?, self.map_addr(s)?);
+                buf.copy_within(s..s + n, d);
+                pos += n;
+            }
+        } else {
+            // Copy from the end so that overlapping bytes are read before they are overwritten.
+            let mut rem = len;
+            while rem > 0 {
+                let (d, s) = (dst + rem, src + rem);
+                let n = cmp::min(rem, cmp::min(self.page_used(d), self.page_used(s)));
+                let (d, s) = (self.map_addr(d - n)?, self.map_addr(s - n)?);
+                buf.copy_within(s..s + n, d);
+                rem -= n;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets `len` bytes starting at `dst` to `value`.
+    pub fn fill(&self, dst: usize, value: u8, len: usize) -> Result<(), Error> {
+        info!("fill({:08x}, {:02x}, {})", dst, value, len);
+        self.check_access(dst, len)?;
+        self.map_range(dst, len)?;
+        let buf = self.as_mut();
+        let mut pos = 0;
+        while pos < len {
+            let n = cmp::min(len - pos, self.page_rem(dst + pos));
+            let p = self.map_addr(dst + pos)?;
+            for b in buf[p..p + n].iter_mut() {
+                *b = value;
+            }
+            pos += n;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> AsRef<[u8]> for MemoryInst<'a> {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.buf, self.buf_len) }
     }
 }
 
@@ -210,14 +368,30 @@ impl<'a> fmt::Debug for MemoryInst<'a> {
 }
 
 
+/// Translates a module address to the address space backed by the page table.
+pub(crate) fn translate(v_addr: usize) -> Result<usize, Error> {
+    // NOTE: This is here because LLVM allocates static memory starting at 0x100_000, which
+    // is too large for the page table to handle.
+    match v_addr {
+        addr if addr < 0x00_1000 => Ok(addr),
+        addr if addr >= 0x10_0000 => Ok((addr - 0x10_0000) + 0x1000),
+        _ => Err(Error::ReservedMemoryArea),
+    }
+}
+
+/// The page table entry for a translated address.
+pub(crate) fn mini_page(a_addr: usize) -> u8 {
+    (a_addr / MINI_SIZE) as u8
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_memory() {
-        let mut buf = [0u8; 256];
-        let mem = MemoryInst::new(&mut buf, 1, Some(4));
+        let mut buf = [0u8; MINI_SIZE];
+        let mem = MemoryInst::new(&mut buf, 1, Some(4)).unwrap();
 
         for i in 0..4 {
             mem.store(i * 4, i as i32).unwrap();
@@ -228,4 +402,86 @@ mod tests {
         }
 
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_memory_too_large() {
+        let mut buf = [0u8; 256 * MINI_SIZE];
+        assert!(MemoryInst::new(&mut buf[..255 * MINI_SIZE], 1, None).is_ok());
+        assert_eq!(MemoryInst::new(&mut buf, 1, None).err(), Some(Error::MemoryTooLarge));
+    }
+
+    #[test]
+    fn test_bulk_memory() {
+        let mut buf = [0u8; 4 * MINI_SIZE];
+        let mem = MemoryInst::new(&mut buf, 17, None).unwrap();
+
+        // Map the second mini page first so that the two pages are not physically adjacent.
+        mem.store8(0x10_1000, 0).unwrap();
+
+        mem.fill(0x10_0ff0, 0xaa, 0x20).unwrap();
+        assert_eq!(mem.get(0x10_0fef), Ok(0x00));
+        assert_eq!(mem.get(0x10_0ff0), Ok(0xaa));
+        assert_eq!(mem.get(0x10_100f), Ok(0xaa));
+        assert_eq!(mem.get(0x10_1010), Ok(0x00));
+
+        let data = [1, 2, 3, 4, 5, 6, 7, 8];
+        mem.copy_from_slice(0x10_0ffc, &data).unwrap();
+        for (i, &d) in data.iter().enumerate() {
+            assert_eq!(mem.get(0x10_0ffc + i), Ok(d));
+        }
+
+        // Overlapping copy towards higher addresses
+        mem.copy(0x10_0ffe, 0x10_0ffc, 8).unwrap();
+        for (i, &d) in data.iter().enumerate() {
+            assert_eq!(mem.get(0x10_0ffe + i), Ok(d));
+        }
+
+        // Overlapping copy towards lower addresses
+        mem.copy(0x10_0ffc, 0x10_0ffe, 8).unwrap();
+        for (i, &d) in data.iter().enumerate() {
+            assert_eq!(mem.get(0x10_0ffc + i), Ok(d));
+        }
+
+        let len = mem.len();
+        assert_eq!(mem.fill(len - 1, 0, 2), Err(Error::OutOfBounds));
+        assert_eq!(mem.copy(0x10_0000, len - 4, 8), Err(Error::OutOfBounds));
+        assert_eq!(mem.copy_from_slice(usize::MAX, &data), Err(Error::OutOfBounds));
+        assert_eq!(mem.fill(len, 0, 0), Ok(()));
+    }
+
+    // (memory 1 200)
+    // (func (result i32) i32.const 1 memory.grow)
+    // (func (result i32) i32.const 100 memory.grow)
+    // (func (result i32) i32.const 200 memory.grow)
+    #[cfg(feature="compiler")]
+    const GROW_MODULE: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+        0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f,
+        0x03, 0x04, 0x03, 0x00, 0x00, 0x00,
+        0x05, 0x05, 0x01, 0x01, 0x01, 0xc8, 0x01,
+        0x0a, 0x18, 0x03,
+            0x06, 0x00, 0x41, 0x01, 0x40, 0x00, 0x0b,
+            0x07, 0x00, 0x41, 0xe4, 0x00, 0x40, 0x00, 0x0b,
+            0x07, 0x00, 0x41, 0xc8, 0x01, 0x40, 0x00, 0x0b,
+    ];
+
+    #[cfg(feature="compiler")]
+    #[test]
+    fn test_deterministic_grow() {
+        use interp::{Config, Interp};
+        use module_inst::Value;
+        use test_support::Fixture;
+
+        // By default growth is limited by the memory the host provides
+        let mut buf = [0u8; 32768];
+        let mut f = Fixture::load(&mut buf, GROW_MODULE);
+        assert_eq!(f.call(0, &[]).unwrap(), &[Value::from(2)]);
+        assert_eq!(f.call(1, &[]).unwrap(), &[Value::from(-1)]);
+
+        let mut interp_buf = [0u8; 8192];
+        let mut interp = Interp::new_with_config(Config::default().deterministic(true), &mut interp_buf).unwrap();
+        assert_eq!(interp.call(&f.env, f.mi, 0).unwrap(), &[Value::from(3)]);
+        assert_eq!(interp.call(&f.env, f.mi, 1).unwrap_err().error(), &Error::Nondeterministic("memory.grow beyond host memory"));
+        assert_eq!(interp.call(&f.env, f.mi, 2).unwrap(), &[Value::from(-1)]);
+    }
+}
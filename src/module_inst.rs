@@ -2,38 +2,97 @@ use error::Error;
 
 
 use types::*;
-use parser::{FallibleIterator};
+#[cfg(feature="compiler")]
+use parser::FallibleIterator;
+#[cfg(feature="compiler")]
 use parser::module::*;
-use compiler::*;
+use code::CompiledCode;
+use side_table::SideTable;
+#[cfg(feature="compiler")]
+use compiler::{Compiler, Config, Spaces};
+use image::{self, ImageReader};
 use core::cell::Cell;
 use environ::{Environment, HostHandler};
 use memory_inst::MemoryInst;
+use table_inst::{TableInst, NULL_REF};
 use small_vec::SmallVec;
 use writer::Writer;
 
-use parser::module::ExportDesc;
-use parser::types::{FunctionType, GlobalType};
-
 use core::fmt;
+use core::ops::Range;
 
 pub struct ModuleInst<'buf> {
     function_types: SmallVec<'buf, FunctionType<'buf>>,
     functions: SmallVec<'buf, FuncInst<'buf>>,
     globals: SmallVec<'buf, GlobalInst>,
     exports: SmallVec<'buf, ExportInst<'buf>>,
-    tables: SmallVec<'buf, SmallVec<'buf, u32>>,
+    tables: SmallVec<'buf, TableInst<'buf>>,
+    tags: SmallVec<'buf, Tag>,
+    elements: SmallVec<'buf, Cell<&'buf [u32]>>,
+    data: SmallVec<'buf, Cell<&'buf [u8]>>,
     code: CompiledCode<'buf>,
+    // Used instead of the code when executing in place
+    side_table: Option<SideTable<'buf>>,
+    // Kept to compile functions as they are called, when compiling lazily
+    #[cfg(feature="compiler")]
+    module: Option<Module<'buf>>,
+}
+
+// How `instantiate` prepares function bodies to be executed
+#[cfg(feature="compiler")]
+enum Bodies<'buf> {
+    Compiled,
+    Lazy(Module<'buf>, usize),
+    InPlace(&'buf [u8]),
 }
 
-impl<'buf, 'env> ModuleInst<'buf> {
+impl<'buf> ModuleInst<'buf> {
+    #[cfg(feature="compiler")]
     pub fn new<H: HostHandler>(buf: &'buf mut [u8], env: &Environment<H>, mem: &MemoryInst, m: Module) -> Result<(&'buf mut [u8], ModuleInst<'buf>), Error> {
+        ModuleInst::new_at(buf, env, mem, m, &mut None)
+    }
+
+    /// Instantiates like `new`, setting `loc` to where a function body failed to compile.
+    #[cfg(feature="compiler")]
+    pub fn new_at<H: HostHandler>(buf: &'buf mut [u8], env: &Environment<H>, mem: &MemoryInst, m: Module, loc: &mut Option<Location>) -> Result<(&'buf mut [u8], ModuleInst<'buf>), Error> {
+        ModuleInst::instantiate(buf, env, mem, m, Bodies::Compiled, loc)
+    }
+
+    /// Instantiates like `new` without compiling any function bodies. Each function is
+    /// compiled when it is first called, into `code_size` bytes taken from `buf`, so `m` is
+    /// kept for as long as the instance.
+    #[cfg(feature="compiler")]
+    pub fn new_lazy<H: HostHandler>(buf: &'buf mut [u8], env: &Environment<H>, mem: &MemoryInst, m: Module<'buf>, code_size: usize) -> Result<(&'buf mut [u8], ModuleInst<'buf>), Error> {
+        let lazy = Bodies::Lazy(m.clone(), code_size);
+        ModuleInst::instantiate(buf, env, mem, m, lazy, &mut None)
+    }
+
+    /// Instantiates like `new`, but instead of compiling the function bodies builds a side
+    /// table for executing them in place from `module_data`, which `m` was read from.
+    #[cfg(feature="compiler")]
+    pub fn new_in_place<H: HostHandler>(buf: &'buf mut [u8], env: &Environment<H>, mem: &MemoryInst, m: Module, module_data: &'buf [u8], loc: &mut Option<Location>) -> Result<(&'buf mut [u8], ModuleInst<'buf>), Error> {
+        ModuleInst::instantiate(buf, env, mem, m, Bodies::InPlace(module_data), loc)
+    }
+
+    #[cfg(feature="compiler")]
+    fn instantiate<H: HostHandler>(buf: &'buf mut [u8], env: &Environment<H>, mem: &MemoryInst, m: Module, bodies: Bodies<'buf>, loc: &mut Option<Location>) -> Result<(&'buf mut [u8], ModuleInst<'buf>), Error> {
+        let limits = *env.limits();
         let mut w = Writer::new(buf);
 
-        let mut function_types = w.alloc_smallvec(16);
-        let mut functions = w.alloc_smallvec(32);
-        let mut globals = w.alloc_smallvec(16);
-        let mut tables = w.alloc_smallvec(16);
-        let mut exports = w.alloc_smallvec(32);
+        // The environment's scratch buffers leave `buf` unaligned
+        w.align_to::<FunctionType>()?;
+        w.split::<()>();
+        let mut function_types = w.alloc_smallvec(limits.types)?;
+        let mut functions = w.alloc_smallvec(limits.functions)?;
+        // Values may be wider than a word, so give globals their own alignment
+        w.align_to::<GlobalInst>()?;
+        w.split::<()>();
+        let mut globals = w.alloc_smallvec(limits.globals)?;
+        let mut tables: SmallVec<TableInst> = w.alloc_smallvec(limits.tables)?;
+        let mut tags: SmallVec<Tag> = w.alloc_smallvec(limits.tags)?;
+        let mut exports = w.alloc_smallvec(limits.exports)?;
+        let mut elements: SmallVec<Cell<&[u32]>> = w.alloc_smallvec(limits.elements)?;
+        let mut data: SmallVec<Cell<&[u8]>> = w.alloc_smallvec(limits.data)?;
         
         info!("function_types:  {:p}", &function_types);
         info!("functions:       {:p}", &functions);
@@ -49,7 +108,7 @@ impl<'buf, 'env> ModuleInst<'buf> {
                         let functype = 0x60;
                         let parameters: &[ValueType] = w.copy_slice(t.parameters)?;
                         let results: &[ValueType] = w.copy_slice(t.results)?;
-                        function_types.push(FunctionType { functype, parameters, results });
+                        function_types.push(FunctionType { functype, parameters, results })?;
                     }
                 },
                 Id::Import => {
@@ -59,6 +118,9 @@ impl<'buf, 'env> ModuleInst<'buf> {
                         info!("Import: {:?}", i);
                         match i.import_desc {
                             ImportDesc::Func(type_index) => {
+                                if type_index as usize >= function_types.len() {
+                                    return Err(Error::InvalidSignature { id: type_index })
+                                }
                                 let type_index = type_index as usize;
                                 let module_bytes = w.copy_slice(i.module.as_bytes())?;
                                 let module = ::core::str::from_utf8(module_bytes)?;
@@ -68,10 +130,10 @@ impl<'buf, 'env> ModuleInst<'buf> {
                                 if module_bytes == b"host" || module_bytes == b"env" {
                                     let host_index = env.import_host_function(module, name, &i.import_desc)?;
                                     info!("Host Function @ {}: {} {} {} {}", functions.len(), type_index, module, name, host_index);
-                                    functions.push(FuncInst::Host { type_index, module, name, host_index });
+                                    functions.push(FuncInst::Host { type_index, module, name, host_index })?;
                                 } else {
                                     info!("Import Function @ {}: {} {} {} {}", functions.len(), type_index, module, name, import_index);
-                                    functions.push(FuncInst::Import { type_index, module, name, module_index, import_index });
+                                    functions.push(FuncInst::Import { type_index, module, name, module_index, import_index })?;
                                 }
                             },
                             ImportDesc::Table(_) => {
@@ -81,7 +143,12 @@ impl<'buf, 'env> ModuleInst<'buf> {
                                 // info!("Import Memory");
                             },
                             ImportDesc::Global(global_type) => {
-                                globals.push(GlobalInst::Import { global_type, import_index});
+                                globals.push(GlobalInst::Import { global_type, import_index})?;
+                            },
+                            ImportDesc::Tag(tag) => {
+                                // Imported tags are not shared with other modules, so they only
+                                // match exceptions thrown by this module.
+                                tags.push(tag)?;
                             }
                         }
                         import_index += 1;
@@ -92,8 +159,11 @@ impl<'buf, 'env> ModuleInst<'buf> {
                     let mut function_index = 0;
                     while let Some(function) = funcs.next()? {                    
                         let type_index = function as usize;
+                        if type_index >= function_types.len() {
+                            return Err(Error::InvalidSignature { id: function })
+                        }
                         info!("Local Func @ {}: {} {}", functions.len(), type_index, function_index);
-                        functions.push(FuncInst::Local { type_index, function_index });
+                        functions.push(FuncInst::Local { type_index, function_index })?;
                         function_index += 1;
                     }
                 },
@@ -102,7 +172,10 @@ impl<'buf, 'env> ModuleInst<'buf> {
                     while let Some(m) = mems.next()? {
                         info!("MEMORY: {:?}", m.limits);
                         info!("growing to {}", m.limits.min);
-                        mem.grow_memory(m.limits.min as i32);
+                        if mem.grow_memory(m.limits.min as i32) < 0 {
+                            return Err(Error::OutOfMemory)
+                        }
+                        mem.set_max_declared(m.limits.max);
                         info!("num_pages: {}", mem.num_pages());
                     }
 
@@ -113,12 +186,16 @@ impl<'buf, 'env> ModuleInst<'buf> {
                     while let Some(global) = globs.next()? {  
                         let global_type = global.global_type;
                         let init = global.init;
-                        let value = if let Some(value) = init.i32_value() {
-                            Cell::new(Value::from(value))
-                        } else {
-                            panic!("Invalid global initializer value");
+                        let value = match init.instr.immediate {
+                            Immediate::I32Const { value } => Cell::new(Value::from(value)),
+                            Immediate::I64Const { value } => Cell::new(Value::from(value)),
+                            Immediate::F32Const { value } => Cell::new(Value::from(value)),
+                            Immediate::F64Const { value } => Cell::new(Value::from(value)),
+                            Immediate::RefNull { reftype: _ } => Cell::new(Value::from(NULL_REF)),
+                            Immediate::RefFunc { index } => Cell::new(Value::from(index)),
+                            _ => return Err(Error::InvalidGlobal { id: global_index as u32 }),
                         };
-                        globals.push(GlobalInst::Local { global_type, global_index, value });
+                        globals.push(GlobalInst::Local { global_type, global_index, value })?;
                         global_index += 1;
                     }
                 },
@@ -127,58 +204,74 @@ impl<'buf, 'env> ModuleInst<'buf> {
                     while let Some(table) = tabs.next()? {                          
                         let TableType { elemtype, limits } = table;
                         info!("Adding table: {} {:?}", elemtype, limits);
-                        let size = if let Some(max) = limits.max {
+                        let cap = if let Some(max) = limits.max {
                             max
                         } else {
                             limits.min
                         };
-                        let mut t: SmallVec<u32> = w.alloc_smallvec(size as usize);
-                        for _ in 0..size {
-                            t.push(0);
-                        }
-                        tables.push(t);
+                        w.align_to::<Cell<u32>>()?;
+                        w.split::<()>();
+                        let t: &[Cell<u32>] = w.alloc_slice(cap as usize)?;
+                        tables.push(TableInst::new(elemtype, t, limits.min))?;
                     }
                 },   
+                Id::Tag => {
+                    let mut items = section.tags();
+                    while let Some(tag) = items.next()? {
+                        tags.push(tag)?;
+                    }
+                },
                 Id::Export => {
                     let mut expts = section.exports();
                     while let Some(export) = expts.next()? {                         
                         let Export { name, export_desc } = export;
                         let bytes = w.copy_slice(name.as_bytes())?;
                         let name = ::core::str::from_utf8(bytes)?;
-                        exports.push(ExportInst { name, export_desc });
+                        exports.push(ExportInst { name, export_desc })?;
                     }
                 }
                 Id::Element => {
-                    let mut elements = section.elements();
-                    while let Some(element) = elements.next()? {                         
-                        let Element { table_index, offset, init } = element;
-                        // use byteorder::{ByteOrder, LittleEndian};
-
-                        info!("Initializing table {}", table_index);
-                        let table = &mut tables[table_index as usize];
-                        let offset = offset.i32_value().unwrap();
+                    let mut segments = section.elements();
+                    while let Some(element) = segments.next()? {                         
+                        // Resolve the segment's items to references
+                        let len = element.iter().count()?;
+                        w.align_to::<u32>()?;
+                        w.split::<()>();
+                        let init: &mut [u32] = w.alloc_slice(len)?;
+                        let mut items = element.iter();
                         let mut i = 0;
-                        let mut o = offset as usize;
-                        while i < init.len() {
-                            // let d = LittleEndian::read_u32(&data[i..]);
-                            let d = init[i] as u32;
-                            info!("{:08x}: {:08x}", o, d);
-                            table[o] = d;
-                            o += 1;
+                        while let Some(item) = items.next()? {
+                            init[i] = item.unwrap_or(NULL_REF);
                             i += 1;
-                        }              
+                        }
+                        match element.mode {
+                            SegmentMode::Active { index: table_index, offset } => {
+                                info!("Initializing table {}", table_index);
+                                if table_index as usize >= tables.len() {
+                                    return Err(Error::InvalidTable { id: table_index })
+                                }
+                                let offset = offset.i32_value().ok_or(Error::InvalidElementSegment { id: elements.len() as u32 })?;
+                                tables[table_index as usize].init(offset as u32, init)?;
+                                // Active and declarative segments are dropped once they have been applied
+                                elements.push(Cell::new(&[]))?;
+                            },
+                            SegmentMode::Passive => elements.push(Cell::new(init))?,
+                            SegmentMode::Declarative => elements.push(Cell::new(&[]))?,
+                        }
                     }
                 },
                 Id::Data => {
-                    let mut data = section.data();
-                    while let Some(data) = data.next()? {                                             
-                        let Data { mem_index: _, offset, init } = data;
-                        let offset = offset.i32_value().unwrap();
-                        for i in 0..init.len() {
-                            let d = init[i];
-                            let o = offset as usize + i;
-                            // info!("{:08x}: {:02x}", o, d);
-                            mem.set(o, d);
+                    let mut segments = section.data();
+                    while let Some(segment) = segments.next()? {                                             
+                        let Data { mode, init } = segment;
+                        if let SegmentMode::Active { index: _, offset } = mode {
+                            let offset = offset.i32_value().ok_or(Error::InvalidDataSegment { id: data.len() as u32 })?;
+                            mem.copy_from_slice(offset as u32 as usize, init)?;
+                            // Active segments are dropped once they have been applied
+                            data.push(Cell::new(&[]))?;
+                        } else {
+                            let init: &[u8] = w.copy_slice(init)?;
+                            data.push(Cell::new(init))?;
                         }
                     }
                 },
@@ -188,22 +281,252 @@ impl<'buf, 'env> ModuleInst<'buf> {
 
         let buf = w.into_slice();
 
+        if let Bodies::Lazy(module, code_size) = bodies {
+            // Each local function's range is filled in when it is compiled
+            let count = functions.as_ref().iter().filter(|f| matches!(f, FuncInst::Local { .. })).count();
+            let mut w = Writer::new(buf);
+            w.align_to::<Cell<(u32, u32)>>()?;
+            w.split::<()>();
+            let ranges = w.alloc_slice(count)?;
+            let code_buf = w.alloc_slice(code_size)?;
+            let code = CompiledCode::new_lazy(code_buf, ranges);
+            let buf = w.into_slice();
+            let module = Some(module);
+            return Ok((buf, ModuleInst { function_types, functions, globals, exports, tables, tags, elements, data, code, side_table: None, module }))
+        }
+
         // Change compiler to use ModuleInst
 
-        let (buf, code) = Compiler::new(&mut [0u8; 4096]).compile(buf, 
-            function_types.as_ref(),
-            functions.as_ref(), 
-            globals.as_ref(),
-        &m)?;
+        let mut compiler_buf = env.compiler_buf()?;
+        let cfg = Config { limits, ..Config::default() };
+        let mut compiler = Compiler::new_with_config(&mut compiler_buf, cfg)?;
+        let spaces = Spaces {
+            types: function_types.as_ref(),
+            functions: functions.as_ref(),
+            globals: globals.as_ref(),
+            tables: tables.as_ref(),
+            tags: tags.as_ref(),
+        };
+
+        if let Bodies::InPlace(module_data) = bodies {
+            let (buf, table) = match compiler.side_table(buf, &spaces, &m) {
+                Ok(built) => built,
+                Err(e) => {
+                    *loc = Some(compiler.location());
+                    return Err(e)
+                }
+            };
+            let code = CompiledCode::new(&[]);
+            let side_table = Some(SideTable::new(module_data, table));
+            return Ok((buf, ModuleInst { function_types, functions, globals, exports, tables, tags, elements, data, code, side_table, module: None }))
+        }
+
+        let (buf, code) = match compiler.compile(buf, &spaces, &m) {
+            Ok(compiled) => compiled,
+            Err(e) => {
+                *loc = Some(compiler.location());
+                return Err(e)
+            }
+        };
 
-        Ok((buf, ModuleInst { function_types, functions, globals, exports, tables, code }))
+        Ok((buf, ModuleInst { function_types, functions, globals, exports, tables, tags, elements, data, code, side_table: None, module: None }))
     }
 
-    pub fn function_types(&self) -> &[FunctionType] {
+    /// Instantiates a module from an image written by `image::write_image`. Function types,
+    /// names, passive data segments and code are used in place from `image`; everything else
+    /// is allocated from `buf` and the rest of `buf` is returned.
+    pub fn from_image<H: HostHandler>(buf: &'buf mut [u8], env: &Environment<H>, mem: &MemoryInst, image: &'buf [u8]) -> Result<(&'buf mut [u8], ModuleInst<'buf>), Error> {
+        let mut r = ImageReader::new(image)?;
+        let mut w = Writer::new(buf);
+
+        w.align_to::<FunctionType>()?;
+        w.split::<()>();
+        let mut function_types = w.alloc_smallvec(r.read_len(8)?)?;
+        for _ in 0..function_types.cap() {
+            let functype = 0x60;
+            let parameters = r.read_value_types()?;
+            let results = r.read_value_types()?;
+            function_types.push(FunctionType { functype, parameters, results })?;
+        }
+
+        w.align_to::<FuncInst>()?;
+        w.split::<()>();
+        let mut functions = w.alloc_smallvec(r.read_len(9)?)?;
+        for _ in 0..functions.cap() {
+            let kind = r.read_u8()?;
+            let type_index = r.read_u32()?;
+            if type_index as usize >= function_types.len() {
+                return Err(Error::InvalidSignature { id: type_index })
+            }
+            let type_index = type_index as usize;
+            functions.push(match kind {
+                image::FUNC_HOST => {
+                    let module = r.read_str()?;
+                    let name = r.read_str()?;
+                    let host_index = env.import_host_function(module, name, &ImportDesc::Func(type_index as u32))?;
+                    FuncInst::Host { type_index, module, name, host_index }
+                },
+                image::FUNC_IMPORT => {
+                    let module = r.read_str()?;
+                    let name = r.read_str()?;
+                    let module_index = r.read_u32()? as usize;
+                    let import_index = r.read_u32()? as usize;
+                    FuncInst::Import { type_index, module, name, module_index, import_index }
+                },
+                image::FUNC_LOCAL => {
+                    let function_index = r.read_u32()? as usize;
+                    FuncInst::Local { type_index, function_index }
+                },
+                _ => return Err(Error::InvalidImage),
+            })?;
+        }
+
+        w.align_to::<GlobalInst>()?;
+        w.split::<()>();
+        let mut globals = w.alloc_smallvec(r.read_len(7)?)?;
+        for _ in 0..globals.cap() {
+            let kind = r.read_u8()?;
+            let valtype = r.read_value_type()?;
+            let mutable = r.read_bool()?;
+            let global_type = GlobalType { valtype, mutable };
+            globals.push(match kind {
+                image::GLOBAL_IMPORT => {
+                    let import_index = r.read_u32()? as usize;
+                    GlobalInst::Import { global_type, import_index }
+                },
+                image::GLOBAL_LOCAL => {
+                    let global_index = r.read_u32()? as usize;
+                    let value = Cell::new(r.read_value()?);
+                    GlobalInst::Local { global_type, global_index, value }
+                },
+                _ => return Err(Error::InvalidImage),
+            })?;
+        }
+
+        w.align_to::<TableInst>()?;
+        w.split::<()>();
+        let mut tables: SmallVec<TableInst> = w.alloc_smallvec(r.read_len(9)?)?;
+        for _ in 0..tables.cap() {
+            let elemtype = r.read_value_type()?;
+            let size = r.read_u32()?;
+            let cap = r.read_u32()?;
+            if size > cap {
+                return Err(Error::InvalidImage)
+            }
+            w.align_to::<Cell<u32>>()?;
+            w.split::<()>();
+            let t: &[Cell<u32>] = w.alloc_slice(cap as usize)?;
+            let table = TableInst::new(elemtype, t, size);
+            for i in 0..size {
+                table.set(i, r.read_u32()?)?;
+            }
+            tables.push(table)?;
+        }
+
+        w.align_to::<Tag>()?;
+        w.split::<()>();
+        let mut tags: SmallVec<Tag> = w.alloc_smallvec(r.read_len(5)?)?;
+        for _ in 0..tags.cap() {
+            let attribute = r.read_u8()?;
+            let type_index = r.read_u32()?;
+            if type_index as usize >= function_types.len() {
+                return Err(Error::InvalidSignature { id: type_index })
+            }
+            tags.push(Tag { attribute, type_index })?;
+        }
+
+        w.align_to::<ExportInst>()?;
+        w.split::<()>();
+        let mut exports = w.alloc_smallvec(r.read_len(9)?)?;
+        for _ in 0..exports.cap() {
+            let name = r.read_str()?;
+            let kind = r.read_u8()?;
+            let index = r.read_u32()?;
+            let export_desc = match kind {
+                0x00 => ExportDesc::Func(index),
+                0x01 => ExportDesc::Table(index),
+                0x02 => ExportDesc::Memory(index),
+                0x03 => ExportDesc::Global(index),
+                0x04 => ExportDesc::Tag(index),
+                _ => return Err(Error::InvalidImage),
+            };
+            let count = match export_desc {
+                ExportDesc::Func(_) => functions.len(),
+                ExportDesc::Table(_) => tables.len(),
+                ExportDesc::Memory(_) => 1,
+                ExportDesc::Global(_) => globals.len(),
+                ExportDesc::Tag(_) => tags.len(),
+            };
+            if index as usize >= count {
+                return Err(Error::InvalidImage)
+            }
+            exports.push(ExportInst { name, export_desc })?;
+        }
+
+        w.align_to::<Cell<&[u32]>>()?;
+        w.split::<()>();
+        let mut elements: SmallVec<Cell<&[u32]>> = w.alloc_smallvec(r.read_len(4)?)?;
+        for _ in 0..elements.cap() {
+            let len = r.read_len(4)?;
+            w.align_to::<u32>()?;
+            w.split::<()>();
+            let init: &mut [u32] = w.alloc_slice(len)?;
+            for item in init.iter_mut() {
+                *item = r.read_u32()?;
+            }
+            elements.push(Cell::new(init))?;
+        }
+
+        w.align_to::<Cell<&[u8]>>()?;
+        w.split::<()>();
+        let mut data: SmallVec<Cell<&[u8]>> = w.alloc_smallvec(r.read_len(4)?)?;
+        for _ in 0..data.cap() {
+            data.push(Cell::new(r.read_bytes()?))?;
+        }
+
+        let has_memory = r.read_bool()?;
+        let min = r.read_u32()?;
+        let max = r.read_u32()?;
+        if has_memory {
+            if mem.grow_memory(min as i32) < 0 {
+                return Err(Error::OutOfMemory)
+            }
+            mem.set_max_declared(if max == image::NO_MAX { None } else { Some(max) });
+        }
+
+        for _ in 0..r.read_len(8)? {
+            let offset = r.read_u32()?;
+            mem.copy_from_slice(offset as usize, r.read_bytes()?)?;
+        }
+
+        let code = CompiledCode::new(r.read_bytes()?);
+        r.finish()?;
+
+        // The code is entered through its index, so the index and every local function's
+        // body are checked before anything can be called.
+        code.check_index()?;
+        for f in functions.as_ref() {
+            if let FuncInst::Local { function_index, .. } = *f {
+                if function_index >= code.body_count() {
+                    return Err(Error::InvalidFunction { id: function_index as u32 })
+                }
+            }
+        }
+
+        let buf = w.into_slice();
+        Ok((buf, ModuleInst {
+            function_types, functions, globals, exports, tables, tags, elements, data, code,
+            side_table: None,
+            #[cfg(feature="compiler")]
+            module: None,
+        }))
+    }
+
+    pub fn function_types(&self) -> &[FunctionType<'_>] {
         self.function_types.as_ref()
     }
 
-    pub fn functions(&self) -> &[FuncInst] {
+    pub fn functions(&self) -> &[FuncInst<'_>] {
         self.functions.as_ref()
     }
 
@@ -211,30 +534,102 @@ impl<'buf, 'env> ModuleInst<'buf> {
         self.globals.as_ref()
     }
 
-    pub fn table(&self, index: usize) -> &SmallVec<u32> {
-        &self.tables[index]
+    pub fn tables(&self) -> &[TableInst<'_>] {
+        self.tables.as_ref()
+    }
+
+    pub fn function(&self, index: u32) -> Result<&FuncInst<'_>, Error> {
+        self.functions.as_ref().get(index as usize).ok_or(Error::InvalidFunction { id: index })
+    }
+
+    pub fn table(&self, index: u32) -> Result<&TableInst<'_>, Error> {
+        if (index as usize) < self.tables.len() {
+            Ok(&self.tables[index as usize])
+        } else {
+            Err(Error::InvalidTable { id: index })
+        }
+    }
+
+    pub fn tags(&self) -> &[Tag] {
+        self.tags.as_ref()
+    }
+
+    /// Returns the payload types of exceptions with tag `index`.
+    pub fn tag_parameters(&self, index: u32) -> Result<&[ValueType], Error> {
+        match self.tags.as_ref().get(index as usize) {
+            Some(tag) => match self.function_types.as_ref().get(tag.type_index as usize) {
+                Some(t) => Ok(t.parameters),
+                None => Err(Error::InvalidTag { id: index }),
+            },
+            None => Err(Error::InvalidTag { id: index }),
+        }
     }
 
-    pub fn exports(&self) -> &[ExportInst] {
+    pub fn exports(&self) -> &[ExportInst<'_>] {
         self.exports.as_ref()
     }
 
-    pub fn indirect_functions_len(&self) -> usize {
-        self.tables[0].len()
+    pub fn data_segment(&self, index: u32) -> Result<&[u8], Error> {
+        if (index as usize) < self.data.len() {
+            Ok(self.data[index as usize].get())
+        } else {
+            Err(Error::InvalidDataSegment { id: index })
+        }
+    }
+
+    pub fn data_segment_count(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn drop_data_segment(&self, index: u32) -> Result<(), Error> {
+        if (index as usize) < self.data.len() {
+            self.data[index as usize].set(&[]);
+            Ok(())
+        } else {
+            Err(Error::InvalidDataSegment { id: index })
+        }
+    }
+
+    pub fn element_segment(&self, index: u32) -> Result<&[u32], Error> {
+        if (index as usize) < self.elements.len() {
+            Ok(self.elements[index as usize].get())
+        } else {
+            Err(Error::InvalidElementSegment { id: index })
+        }
+    }
+
+    pub fn element_segment_count(&self) -> usize {
+        self.elements.len()
     }
 
-    pub fn indirect_function_id(&self, index: usize) -> u32 {
-        self.tables[0][index]
+    pub fn drop_element_segment(&self, index: u32) -> Result<(), Error> {
+        if (index as usize) < self.elements.len() {
+            self.elements[index as usize].set(&[]);
+            Ok(())
+        } else {
+            Err(Error::InvalidElementSegment { id: index })
+        }
     }
 
-    pub fn type_signature(&self, index: usize) -> &FunctionType {
+    pub fn type_signature(&self, index: usize) -> &FunctionType<'_> {
         &self.function_types[index]
     }
 
+    pub fn local_function_type(&self, function_index: usize) -> Option<&FunctionType<'_>> {
+        for f in self.functions() {
+            if let &FuncInst::Local { type_index, function_index: index } = f {
+                if index == function_index {
+                    return Some(&self.function_types[type_index])
+                }
+            }
+        }
+        None
+    }
+
     pub fn global_type(&self, index: u32) -> Result<GlobalType, Error> {
         Ok({
             info!("global_type({})", index);
-            if index as usize > self.globals.len() {
+            if index as usize >= self.globals.len() {
                 return Err(Error::OutOfBounds);
             }
             match self.globals[index as usize] {
@@ -248,45 +643,75 @@ impl<'buf, 'env> ModuleInst<'buf> {
         })         
     }
 
-    pub fn get_global(&self, index: u32) -> Result<i32, Error> {
+    pub fn get_global(&self, index: u32) -> Result<Value, Error> {
         Ok({
             info!("get_global({})", index);
-            if index as usize > self.globals.len() {
+            if index as usize >= self.globals.len() {
                 return Err(Error::OutOfBounds);
             }
             match self.globals[index as usize] {
                 GlobalInst::Local { global_type: _, global_index: _, ref value } => {
-                    let v = value.get().0;
-                    info!("  => {}", v);
+                    let v = value.get();
+                    info!("  => {:?}", v);
                     v
                 },
                 GlobalInst::Import { global_type: _, import_index: _ } => {
-                    unimplemented!()
+                    return Err(Error::Unimplemented("imported globals"))
                 }
             }
         })        
     }
-    pub fn set_global(&self, index: u32, new_value: i32) -> Result<(), Error> {
-        Ok({
-            info!("set_global({}, {})", index, new_value);
-            if index as usize > self.globals.len() {
-                return Err(Error::OutOfBounds);
-            }
-            match self.globals[index as usize] {
-                GlobalInst::Local { global_type: _, global_index: _, ref value } => {
-                    info!("set_value @ {:p} = {}", value, new_value);
-                    value.set(Value(new_value))
-                },
-                GlobalInst::Import { global_type: _, import_index: _ } => {
-                    unimplemented!()
-                }
+    pub fn set_global(&self, index: u32, new_value: Value) -> Result<(), Error> {
+        info!("set_global({}, {:?})", index, new_value);
+        if index as usize >= self.globals.len() {
+            return Err(Error::OutOfBounds);
+        }
+        match self.globals[index as usize] {
+            GlobalInst::Local { global_type: _, global_index: _, ref value } => {
+                info!("set_value @ {:p} = {:?}", value, new_value);
+                value.set(new_value)
+            },
+            GlobalInst::Import { global_type: _, import_index: _ } => {
+                return Err(Error::Unimplemented("imported globals"))
             }
-        })        
+        }
+        Ok(())
     }    
-    pub fn code(&self) -> &CompiledCode {
+    pub fn code(&self) -> &CompiledCode<'_> {
         &self.code
     }
 
+    /// The side table, if the instance executes its module in place rather than compiled code.
+    pub fn side_table(&self) -> Option<&SideTable<'buf>> {
+        self.side_table.as_ref()
+    }
+
+    /// Returns where local function `function_index` is in the code, compiling it first if
+    /// the instance is compiled lazily and it has not been called before.
+    pub fn body_range<H: HostHandler>(&self, env: &Environment<H>, function_index: usize) -> Result<Range<usize>, Error> {
+        #[cfg(feature="compiler")]
+        {
+            if let Some(ref m) = self.module {
+                return self.code.compile_lazily(function_index, |w| {
+                    let mut compiler_buf = env.compiler_buf()?;
+                    let cfg = Config { limits: *env.limits(), ..Config::default() };
+                    let mut compiler = Compiler::new_with_config(&mut compiler_buf, cfg)?;
+                    let spaces = Spaces {
+                        types: self.function_types.as_ref(),
+                        functions: self.functions.as_ref(),
+                        globals: self.globals.as_ref(),
+                        tables: self.tables.as_ref(),
+                        tags: self.tags.as_ref(),
+                    };
+                    compiler.compile_function(w, &spaces, m, function_index as u32)
+                })
+            }
+        }
+        #[cfg(not(feature="compiler"))]
+        let _ = env;
+        self.code.body_range(function_index)
+    }
+
     // pub fn body(&self, index: usize) -> Option<Body> {
     //     self.m.body(index as u32)
     // }
@@ -301,10 +726,10 @@ pub enum FuncInst<'a> {
 
 impl<'a> FuncInst<'a> {
     pub fn type_index(&self) -> usize {
-        match self {
-            &FuncInst::Host { type_index, module: _, name: _, host_index: _ } => type_index,
-            &FuncInst::Import { type_index, module: _, name: _, module_index: _, import_index: _ } => type_index,
-            &FuncInst::Local { type_index, function_index: _ } => type_index,
+        match *self {
+            FuncInst::Host { type_index, module: _, name: _, host_index: _ } => type_index,
+            FuncInst::Import { type_index, module: _, name: _, module_index: _, import_index: _ } => type_index,
+            FuncInst::Local { type_index, function_index: _ } => type_index,
         }
     }
 }
@@ -317,9 +742,9 @@ pub enum GlobalInst {
 
 impl GlobalInst {
     pub fn global_type(&self) -> GlobalType {
-        match self {
-            &GlobalInst::Import { global_type, import_index: _ } => global_type,
-            &GlobalInst::Local { global_type, global_index: _, value: _ } => global_type,
+        match *self {
+            GlobalInst::Import { global_type, import_index: _ } => global_type,
+            GlobalInst::Local { global_type, global_index: _, value: _ } => global_type,
         }
     }
 }
@@ -330,12 +755,40 @@ pub struct ExportInst<'buf> {
     pub export_desc: ExportDesc,
 }
 
+/// The raw bits of a `Value`, widened to hold a `v128` when the `simd` feature is enabled.
+/// Every value stack slot, exception payload and global pays for the widest type enabled.
+#[cfg(not(feature="simd"))]
+pub type RawValue = u64;
+#[cfg(feature="simd")]
+pub type RawValue = u128;
+
+/// An untyped value stack slot. 32-bit values are stored zero-extended and floats
+/// are stored as their bit patterns, so two slots holding the same typed value
+/// always compare equal.
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
-pub struct Value(pub i32);
+pub struct Value(pub RawValue);
+
+// The casts from `RawValue` are only redundant when it is `u64`.
+#[allow(clippy::unnecessary_cast)]
+impl Value {
+    pub fn as_i32(&self) -> i32 { self.0 as u32 as i32 }
+    pub fn as_u32(&self) -> u32 { self.0 as u32 }
+    pub fn as_i64(&self) -> i64 { self.0 as i64 }
+    pub fn as_u64(&self) -> u64 { self.0 as u64 }
+    pub fn as_f32(&self) -> f32 { f32::from_bits(self.0 as u32) }
+    pub fn as_f64(&self) -> f64 { f64::from_bits(self.0 as u64) }
+    #[cfg(feature="simd")]
+    pub fn as_v128(&self) -> u128 { self.0 }
+
+    /// Returns a wrapper that formats this value as `t`.
+    pub fn typed(self, t: ValueType) -> TypedValue {
+        TypedValue(t, self)
+    }
+}
 
 impl fmt::Debug for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "i32:{}", self.0 as u32)
+        write!(f, "Value(0x{:x})", self.0)
     }
 }
 
@@ -345,29 +798,126 @@ impl fmt::Display for Value {
     }
 }
 
-
-
 impl From<i32> for Value {
     fn from(other: i32) -> Value {
-        Value(other)
+        Value(other as u32 as RawValue)
     }
 }
 
 impl From<u32> for Value {
     fn from(other: u32) -> Value {
-        Value(other as i32)
+        Value(other as RawValue)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(other: i64) -> Value {
+        Value(other as u64 as RawValue)
+    }
+}
+
+impl From<u64> for Value {
+    fn from(other: u64) -> Value {
+        Value(other as RawValue)
+    }
+}
+
+#[cfg(feature="simd")]
+impl From<u128> for Value {
+    fn from(other: u128) -> Value {
+        Value(other)
+    }
+}
+
+impl From<f32> for Value {
+    fn from(other: f32) -> Value {
+        Value(other.to_bits() as RawValue)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(other: f64) -> Value {
+        Value(other.to_bits() as RawValue)
     }
 }
 
 impl From<Value> for i32 {
     fn from(other: Value) -> i32 {
-        other.0
+        other.as_i32()
     }
 }
 
 impl From<Value> for u32 {
     fn from(other: Value) -> u32 {
-        other.0 as u32
+        other.as_u32()
+    }
+}
+
+impl From<Value> for i64 {
+    fn from(other: Value) -> i64 {
+        other.as_i64()
+    }
+}
+
+impl From<Value> for u64 {
+    fn from(other: Value) -> u64 {
+        other.as_u64()
+    }
+}
+
+impl From<Value> for f32 {
+    fn from(other: Value) -> f32 {
+        other.as_f32()
+    }
+}
+
+impl From<Value> for f64 {
+    fn from(other: Value) -> f64 {
+        other.as_f64()
+    }
+}
+
+/// A `Value` paired with its type, formatted the way `wasm-interp` prints results
+/// (`i32:42`, `f64:1.500000`).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct TypedValue(pub ValueType, pub Value);
+
+impl fmt::Display for TypedValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let TypedValue(t, v) = *self;
+        match t {
+            ValueType::I32 => write!(f, "i32:{}", v.as_u32()),
+            ValueType::I64 => write!(f, "i64:{}", v.as_u64()),
+            ValueType::F32 => write_float(f, "f32", v.as_f32() as f64),
+            ValueType::F64 => write_float(f, "f64", v.as_f64()),
+            ValueType::AnyFunc | ValueType::ExternRef => if v.as_u32() == NULL_REF {
+                write!(f, "{}:null", t)
+            } else {
+                write!(f, "{}:{}", t, v.as_u32())
+            },
+            ValueType::V128 => {
+                // `RawValue` is only `u128` with the `simd` feature.
+                #[allow(clippy::unnecessary_cast)]
+                let x = v.0 as u128;
+                write!(f, "v128 i32x4:0x{:08x} 0x{:08x} 0x{:08x} 0x{:08x}",
+                    x as u32, (x >> 32) as u32, (x >> 64) as u32, (x >> 96) as u32)
+            },
+            _ => write!(f, "{}:{:?}", t, v),
+        }
+    }
+}
+
+impl fmt::Debug for TypedValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+fn write_float(f: &mut fmt::Formatter, prefix: &str, v: f64) -> fmt::Result {
+    if v.is_nan() {
+        write!(f, "{}:{}nan", prefix, if v.is_sign_negative() { "-" } else { "" })
+    } else {
+        write!(f, "{}:{:.6}", prefix, v)
     }
 }
 
@@ -389,22 +939,23 @@ mod tests {
 
     #[test]
     fn test_copy_types() {
-        use opcode::I32;
+        use ValueType::I32;
 
         let mut buf = [0u8; 1024];
         let mut w = Writer::new(&mut buf);
 
         let t_new = {
-            let parameters = &[I32 as u8, I32 as u8][..];
-            let returns = &[I32 as u8][..];
-            let t = Type { parameters, returns };
-            Type {
+            let parameters = &[I32, I32][..];
+            let results = &[I32][..];
+            let t = FunctionType { functype: 0x60, parameters, results };
+            FunctionType {
+                functype: t.functype,
                 parameters: w.copy_slice(t.parameters).unwrap(),
-                returns: w.copy_slice(t.returns).unwrap(),
+                results: w.copy_slice(t.results).unwrap(),
             }
         };
         assert_eq!(t_new.parameters.len(), 2);
-        assert_eq!(t_new.returns.len(), 1);
+        assert_eq!(t_new.results.len(), 1);
     }
 
 
@@ -423,7 +974,7 @@ mod tests {
             }
         }
 
-        impl<'buf, W, T, E> WriteTo<W, E> for &'buf [T] where T: WriteTo<W, E> {
+        impl<W, T, E> WriteTo<W, E> for &[T] where T: WriteTo<W, E> {
             fn write_to(&self, w: &mut W) -> Result<(), E> {
                 for item in self.iter() {
                     item.write_to(w)?;
@@ -445,4 +996,14 @@ mod tests {
         let _dst: &[i8] = w.split();
 
     }
+
+    // The sizes quoted in the README and `ResourceLimits`
+    #[test]
+    fn test_value_size() {
+        use core::mem::size_of;
+
+        let value = if cfg!(feature="simd") { 16 } else { 8 };
+        assert_eq!(size_of::<Value>(), value);
+        assert_eq!(size_of::<GlobalInst>(), 16 + value);
+    }
 }
\ No newline at end of file
@@ -1,35 +1,35 @@
 #[macro_export]
 macro_rules! debug {
-    (target: $target:expr, $($arg:tt)*) => { };
-    ($($arg:tt)*) => { };
+    (target: $target:expr, $($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
+    ($($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
 }
 
 #[macro_export]
 macro_rules! error {
-    (target: $target:expr, $($arg:tt)*) => { };
-    ($($arg:tt)*) => { };
+    (target: $target:expr, $($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
+    ($($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
 }
 
 #[macro_export]
 macro_rules! info {
-    (target: $target:expr, $($arg:tt)*) => { };
-    ($($arg:tt)*) => { };
+    (target: $target:expr, $($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
+    ($($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
 }
 
 #[macro_export]
 macro_rules! trace {
-    (target: $target:expr, $($arg:tt)*) => { };
-    ($($arg:tt)*) => { };
+    (target: $target:expr, $($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
+    ($($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
 }
 
 #[macro_export]
 macro_rules! warn {
-    (target: $target:expr, $($arg:tt)*) => { };
-    ($($arg:tt)*) => { };
+    (target: $target:expr, $($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
+    ($($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
 }
 
 #[macro_export]
 macro_rules! panic {
-    (target: $target:expr, $($arg:tt)*) => { unsafe { ::core::intrinsics::abort() } };
-    ($($arg:tt)*) => { unsafe { ::core::intrinsics::abort() } };
+    (target: $target:expr, $($arg:tt)*) => { ::core::intrinsics::abort() };
+    ($($arg:tt)*) => { ::core::intrinsics::abort() };
 }
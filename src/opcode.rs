@@ -0,0 +1,1661 @@
+use types::ValueType;
+
+pub const BR_TABLE_ENTRY_SIZE: u32 = 12;
+pub const BR_TABLE_S_ENTRY_SIZE: u32 = 4;
+
+#[derive(Debug)]
+pub struct Op {
+    pub tr: ValueType,
+    pub t1: ValueType,
+    pub t2: ValueType,
+    pub m: u8,
+    pub prefix: u8,
+    pub code: u8,
+    pub text: &'static str,
+}
+
+pub const ___: ValueType = ValueType::Void;
+pub const I32: ValueType = ValueType::I32;
+pub const I64: ValueType = ValueType::I64;
+pub const F32: ValueType = ValueType::F32;
+pub const F64: ValueType = ValueType::F64;
+pub const V128: ValueType = ValueType::V128;
+pub const VOID: ValueType = ValueType::Void;
+
+pub const UNREACHABLE             : u8 = 0x00;
+pub const NOP                     : u8 = 0x01;
+pub const BLOCK                   : u8 = 0x02;
+pub const LOOP                    : u8 = 0x03;
+pub const IF                      : u8 = 0x04;
+pub const ELSE                    : u8 = 0x05;
+pub const TRY                     : u8 = 0x06;
+pub const CATCH                   : u8 = 0x07;
+pub const THROW                   : u8 = 0x08;
+pub const RETHROW                 : u8 = 0x09;
+pub const CATCH_ALL               : u8 = 0x0a;
+pub const END                     : u8 = 0x0b;
+pub const BR                      : u8 = 0x0c;
+pub const BR_IF                   : u8 = 0x0d;
+pub const BR_TABLE                : u8 = 0x0e;
+pub const RETURN                  : u8 = 0x0f;
+pub const CALL                    : u8 = 0x10;
+pub const CALL_INDIRECT           : u8 = 0x11;
+pub const RETURN_CALL             : u8 = 0x12;
+pub const RETURN_CALL_INDIRECT    : u8 = 0x13;
+pub const DROP                    : u8 = 0x1a;
+pub const SELECT                  : u8 = 0x1b;
+pub const SELECT_T                : u8 = 0x1c;
+pub const GET_LOCAL               : u8 = 0x20;
+pub const SET_LOCAL               : u8 = 0x21;
+pub const TEE_LOCAL               : u8 = 0x22;
+pub const GET_GLOBAL              : u8 = 0x23;
+pub const SET_GLOBAL              : u8 = 0x24;
+pub const TABLE_GET               : u8 = 0x25;
+pub const TABLE_SET               : u8 = 0x26;
+pub const I32_LOAD                : u8 = 0x28;
+pub const I64_LOAD                : u8 = 0x29;
+pub const F32_LOAD                : u8 = 0x2a;
+pub const F64_LOAD                : u8 = 0x2b;
+pub const I32_LOAD8_S             : u8 = 0x2c;
+pub const I32_LOAD8_U             : u8 = 0x2d;
+pub const I32_LOAD16_S            : u8 = 0x2e;
+pub const I32_LOAD16_U            : u8 = 0x2f;
+pub const I64_LOAD8_S             : u8 = 0x30;
+pub const I64_LOAD8_U             : u8 = 0x31;
+pub const I64_LOAD16_S            : u8 = 0x32;
+pub const I64_LOAD16_U            : u8 = 0x33;
+pub const I64_LOAD32_S            : u8 = 0x34;
+pub const I64_LOAD32_U            : u8 = 0x35;
+pub const I32_STORE               : u8 = 0x36;
+pub const I64_STORE               : u8 = 0x37;
+pub const F32_STORE               : u8 = 0x38;
+pub const F64_STORE               : u8 = 0x39;
+pub const I32_STORE8              : u8 = 0x3a;
+pub const I32_STORE16             : u8 = 0x3b;
+pub const I64_STORE8              : u8 = 0x3c;
+pub const I64_STORE16             : u8 = 0x3d;
+pub const I64_STORE32             : u8 = 0x3e;
+pub const MEM_SIZE                : u8 = 0x3f;
+pub const MEM_GROW                : u8 = 0x40;
+pub const I32_CONST               : u8 = 0x41;
+pub const I64_CONST               : u8 = 0x42;
+pub const F32_CONST               : u8 = 0x43;
+pub const F64_CONST               : u8 = 0x44;
+pub const I32_EQZ                 : u8 = 0x45;
+pub const I32_EQ                  : u8 = 0x46;
+pub const I32_NE                  : u8 = 0x47;
+pub const I32_LT_S                : u8 = 0x48;
+pub const I32_LT_U                : u8 = 0x49;
+pub const I32_GT_S                : u8 = 0x4a;
+pub const I32_GT_U                : u8 = 0x4b;
+pub const I32_LE_S                : u8 = 0x4c;
+pub const I32_LE_U                : u8 = 0x4d;
+pub const I32_GE_S                : u8 = 0x4e;
+pub const I32_GE_U                : u8 = 0x4f;
+pub const I64_EQZ                 : u8 = 0x50;
+pub const I64_EQ                  : u8 = 0x51;
+pub const I64_NE                  : u8 = 0x52;
+pub const I64_LT_S                : u8 = 0x53;
+pub const I64_LT_U                : u8 = 0x54;
+pub const I64_GT_S                : u8 = 0x55;
+pub const I64_GT_U                : u8 = 0x56;
+pub const I64_LE_S                : u8 = 0x57;
+pub const I64_LE_U                : u8 = 0x58;
+pub const I64_GE_S                : u8 = 0x59;
+pub const I64_GE_U                : u8 = 0x5a;
+pub const F32_EQ                  : u8 = 0x5b;
+pub const F32_NE                  : u8 = 0x5c;
+pub const F32_LT                  : u8 = 0x5d;
+pub const F32_GT                  : u8 = 0x5e;
+pub const F32_LE                  : u8 = 0x5f;
+pub const F32_GE                  : u8 = 0x60;
+pub const F64_EQ                  : u8 = 0x61;
+pub const F64_NE                  : u8 = 0x62;
+pub const F64_LT                  : u8 = 0x63;
+pub const F64_GT                  : u8 = 0x64;
+pub const F64_LE                  : u8 = 0x65;
+pub const F64_GE                  : u8 = 0x66;
+pub const I32_CLZ                 : u8 = 0x67;
+pub const I32_CTZ                 : u8 = 0x68;
+pub const I32_POPCNT              : u8 = 0x69;
+pub const I32_ADD                 : u8 = 0x6a;
+pub const I32_SUB                 : u8 = 0x6b;
+pub const I32_MUL                 : u8 = 0x6c;
+pub const I32_DIV_S               : u8 = 0x6d;
+pub const I32_DIV_U               : u8 = 0x6e;
+pub const I32_REM_S               : u8 = 0x6f;
+pub const I32_REM_U               : u8 = 0x70;
+pub const I32_AND                 : u8 = 0x71;
+pub const I32_OR                  : u8 = 0x72;
+pub const I32_XOR                 : u8 = 0x73;
+pub const I32_SHL                 : u8 = 0x74;
+pub const I32_SHR_S               : u8 = 0x75;
+pub const I32_SHR_U               : u8 = 0x76;
+pub const I32_ROTL                : u8 = 0x77;
+pub const I32_ROTR                : u8 = 0x78;
+pub const I64_CLZ                 : u8 = 0x79;
+pub const I64_CTZ                 : u8 = 0x7a;
+pub const I64_POPCNT              : u8 = 0x7b;
+pub const I64_ADD                 : u8 = 0x7c;
+pub const I64_SUB                 : u8 = 0x7d;
+pub const I64_MUL                 : u8 = 0x7e;
+pub const I64_DIV_S               : u8 = 0x7f;
+pub const I64_DIV_U               : u8 = 0x80;
+pub const I64_REM_S               : u8 = 0x81;
+pub const I64_REM_U               : u8 = 0x82;
+pub const I64_AND                 : u8 = 0x83;
+pub const I64_OR                  : u8 = 0x84;
+pub const I64_XOR                 : u8 = 0x85;
+pub const I64_SHL                 : u8 = 0x86;
+pub const I64_SHR_S               : u8 = 0x87;
+pub const I64_SHR_U               : u8 = 0x88;
+pub const I64_ROTL                : u8 = 0x89;
+pub const I64_ROTR                : u8 = 0x8a;
+pub const F32_ABS                 : u8 = 0x8b;
+pub const F32_NEG                 : u8 = 0x8c;
+pub const F32_CEIL                : u8 = 0x8d;
+pub const F32_FLOOR               : u8 = 0x8e;
+pub const F32_TRUNC               : u8 = 0x8f;
+pub const F32_NEAREST             : u8 = 0x90;
+pub const F32_SQRT                : u8 = 0x91;
+pub const F32_ADD                 : u8 = 0x92;
+pub const F32_SUB                 : u8 = 0x93;
+pub const F32_MUL                 : u8 = 0x94;
+pub const F32_DIV                 : u8 = 0x95;
+pub const F32_MIN                 : u8 = 0x96;
+pub const F32_MAX                 : u8 = 0x97;
+pub const F32_COPYSIGN            : u8 = 0x98;
+pub const F64_ABS                 : u8 = 0x99;
+pub const F64_NEG                 : u8 = 0x9a;
+pub const F64_CEIL                : u8 = 0x9b;
+pub const F64_FLOOR               : u8 = 0x9c;
+pub const F64_TRUNC               : u8 = 0x9d;
+pub const F64_NEAREST             : u8 = 0x9e;
+pub const F64_SQRT                : u8 = 0x9f;
+pub const F64_ADD                 : u8 = 0xa0;
+pub const F64_SUB                 : u8 = 0xa1;
+pub const F64_MUL                 : u8 = 0xa2;
+pub const F64_DIV                 : u8 = 0xa3;
+pub const F64_MIN                 : u8 = 0xa4;
+pub const F64_MAX                 : u8 = 0xa5;
+pub const F64_COPYSIGN            : u8 = 0xa6;
+pub const I32_WRAP_I64            : u8 = 0xa7;
+pub const I32_TRUNC_S_F32         : u8 = 0xa8;
+pub const I32_TRUNC_U_F32         : u8 = 0xa9;
+pub const I32_TRUNC_S_F64         : u8 = 0xaa;
+pub const I32_TRUNC_U_F64         : u8 = 0xab;
+pub const I64_EXTEND_S_I32        : u8 = 0xac;
+pub const I64_EXTEND_U_I32        : u8 = 0xad;
+pub const I64_TRUNC_S_F32         : u8 = 0xae;
+pub const I64_TRUNC_U_F32         : u8 = 0xaf;
+pub const I64_TRUNC_S_F64         : u8 = 0xb0;
+pub const I64_TRUNC_U_F64         : u8 = 0xb1;
+pub const F32_CONVERT_S_I32       : u8 = 0xb2;
+pub const F32_CONVERT_U_I32       : u8 = 0xb3;
+pub const F32_CONVERT_S_I64       : u8 = 0xb4;
+pub const F32_CONVERT_U_I64       : u8 = 0xb5;
+pub const F32_DEMOTE_F64          : u8 = 0xb6;
+pub const F64_CONVERT_S_I32       : u8 = 0xb7;
+pub const F64_CONVERT_U_I32       : u8 = 0xb8;
+pub const F64_CONVERT_S_I64       : u8 = 0xb9;
+pub const F64_CONVERT_U_I64       : u8 = 0xba;
+pub const F64_PROMOTE_F32         : u8 = 0xbb;
+pub const I32_REINTERPRET_F32     : u8 = 0xbc;
+pub const I64_REINTERPRET_F64     : u8 = 0xbd;
+pub const F32_REINTERPRET_I32     : u8 = 0xbe;
+pub const F64_REINTERPRET_I64     : u8 = 0xbf;
+pub const I32_EXTEND8_S           : u8 = 0xC0;
+pub const I32_EXTEND16_S          : u8 = 0xC1;
+pub const I64_EXTEND8_S           : u8 = 0xC2;
+pub const I64_EXTEND16_S          : u8 = 0xC3;
+pub const I64_EXTEND32_S          : u8 = 0xC4;
+pub const REF_NULL                : u8 = 0xd0;
+pub const REF_IS_NULL             : u8 = 0xd1;
+pub const REF_FUNC                : u8 = 0xd2;
+pub const ALLOCA                  : u8 = 0xe0;
+pub const BR_UNLESS               : u8 = 0xe1;
+pub const CALL_HOST               : u8 = 0xe2;
+pub const DATA                    : u8 = 0xe3;
+pub const DROP_KEEP               : u8 = 0xe4;
+pub const HANDLER_POP             : u8 = 0xe5;
+// Short forms of compiled code, with 16-bit relative branch offsets, 8-bit counts and
+// 16-bit constants
+pub const BR_S                    : u8 = 0xe6;
+pub const BR_IF_S                 : u8 = 0xe7;
+pub const BR_UNLESS_S             : u8 = 0xe8;
+pub const BR_TABLE_S              : u8 = 0xe9;
+pub const DROP_KEEP_S             : u8 = 0xea;
+pub const GET_LOCAL_S             : u8 = 0xeb;
+pub const SET_LOCAL_S             : u8 = 0xec;
+pub const TEE_LOCAL_S             : u8 = 0xed;
+pub const I32_CONST_S             : u8 = 0xee;
+pub const ALLOCA_S                : u8 = 0xef;
+pub const MISC_PREFIX             : u8 = 0xfc;
+pub const SIMD_PREFIX             : u8 = 0xfd;
+pub const ATOMIC_PREFIX           : u8 = 0xfe;
+
+// MISC_PREFIX sub-opcodes
+
+pub const I32_TRUNC_SAT_F32_S     : u8 = 0x00;
+pub const I32_TRUNC_SAT_F32_U     : u8 = 0x01;
+pub const I32_TRUNC_SAT_F64_S     : u8 = 0x02;
+pub const I32_TRUNC_SAT_F64_U     : u8 = 0x03;
+pub const I64_TRUNC_SAT_F32_S     : u8 = 0x04;
+pub const I64_TRUNC_SAT_F32_U     : u8 = 0x05;
+pub const I64_TRUNC_SAT_F64_S     : u8 = 0x06;
+pub const I64_TRUNC_SAT_F64_U     : u8 = 0x07;
+pub const MEMORY_INIT             : u8 = 0x08;
+pub const DATA_DROP               : u8 = 0x09;
+pub const MEMORY_COPY             : u8 = 0x0a;
+pub const MEMORY_FILL             : u8 = 0x0b;
+pub const TABLE_INIT              : u8 = 0x0c;
+pub const ELEM_DROP               : u8 = 0x0d;
+pub const TABLE_COPY              : u8 = 0x0e;
+pub const TABLE_GROW              : u8 = 0x0f;
+pub const TABLE_SIZE              : u8 = 0x10;
+pub const TABLE_FILL              : u8 = 0x11;
+
+// ATOMIC_PREFIX sub-opcodes
+
+pub const MEMORY_ATOMIC_NOTIFY    : u8 = 0x00;
+pub const MEMORY_ATOMIC_WAIT32    : u8 = 0x01;
+pub const MEMORY_ATOMIC_WAIT64    : u8 = 0x02;
+pub const ATOMIC_FENCE            : u8 = 0x03;
+pub const I32_ATOMIC_LOAD         : u8 = 0x10;
+pub const I64_ATOMIC_LOAD         : u8 = 0x11;
+pub const I32_ATOMIC_LOAD8_U      : u8 = 0x12;
+pub const I32_ATOMIC_LOAD16_U     : u8 = 0x13;
+pub const I64_ATOMIC_LOAD8_U      : u8 = 0x14;
+pub const I64_ATOMIC_LOAD16_U     : u8 = 0x15;
+pub const I64_ATOMIC_LOAD32_U     : u8 = 0x16;
+pub const I32_ATOMIC_STORE        : u8 = 0x17;
+pub const I64_ATOMIC_STORE        : u8 = 0x18;
+pub const I32_ATOMIC_STORE8       : u8 = 0x19;
+pub const I32_ATOMIC_STORE16      : u8 = 0x1a;
+pub const I64_ATOMIC_STORE8       : u8 = 0x1b;
+pub const I64_ATOMIC_STORE16      : u8 = 0x1c;
+pub const I64_ATOMIC_STORE32      : u8 = 0x1d;
+pub const I32_ATOMIC_RMW_ADD      : u8 = 0x1e;
+pub const I64_ATOMIC_RMW_ADD      : u8 = 0x1f;
+pub const I32_ATOMIC_RMW8_ADD_U   : u8 = 0x20;
+pub const I32_ATOMIC_RMW16_ADD_U  : u8 = 0x21;
+pub const I64_ATOMIC_RMW8_ADD_U   : u8 = 0x22;
+pub const I64_ATOMIC_RMW16_ADD_U  : u8 = 0x23;
+pub const I64_ATOMIC_RMW32_ADD_U  : u8 = 0x24;
+pub const I32_ATOMIC_RMW_SUB      : u8 = 0x25;
+pub const I64_ATOMIC_RMW_SUB      : u8 = 0x26;
+pub const I32_ATOMIC_RMW8_SUB_U   : u8 = 0x27;
+pub const I32_ATOMIC_RMW16_SUB_U  : u8 = 0x28;
+pub const I64_ATOMIC_RMW8_SUB_U   : u8 = 0x29;
+pub const I64_ATOMIC_RMW16_SUB_U  : u8 = 0x2a;
+pub const I64_ATOMIC_RMW32_SUB_U  : u8 = 0x2b;
+pub const I32_ATOMIC_RMW_AND      : u8 = 0x2c;
+pub const I64_ATOMIC_RMW_AND      : u8 = 0x2d;
+pub const I32_ATOMIC_RMW8_AND_U   : u8 = 0x2e;
+pub const I32_ATOMIC_RMW16_AND_U  : u8 = 0x2f;
+pub const I64_ATOMIC_RMW8_AND_U   : u8 = 0x30;
+pub const I64_ATOMIC_RMW16_AND_U  : u8 = 0x31;
+pub const I64_ATOMIC_RMW32_AND_U  : u8 = 0x32;
+pub const I32_ATOMIC_RMW_OR       : u8 = 0x33;
+pub const I64_ATOMIC_RMW_OR       : u8 = 0x34;
+pub const I32_ATOMIC_RMW8_OR_U    : u8 = 0x35;
+pub const I32_ATOMIC_RMW16_OR_U   : u8 = 0x36;
+pub const I64_ATOMIC_RMW8_OR_U    : u8 = 0x37;
+pub const I64_ATOMIC_RMW16_OR_U   : u8 = 0x38;
+pub const I64_ATOMIC_RMW32_OR_U   : u8 = 0x39;
+pub const I32_ATOMIC_RMW_XOR      : u8 = 0x3a;
+pub const I64_ATOMIC_RMW_XOR      : u8 = 0x3b;
+pub const I32_ATOMIC_RMW8_XOR_U   : u8 = 0x3c;
+pub const I32_ATOMIC_RMW16_XOR_U  : u8 = 0x3d;
+pub const I64_ATOMIC_RMW8_XOR_U   : u8 = 0x3e;
+pub const I64_ATOMIC_RMW16_XOR_U  : u8 = 0x3f;
+pub const I64_ATOMIC_RMW32_XOR_U  : u8 = 0x40;
+pub const I32_ATOMIC_RMW_XCHG     : u8 = 0x41;
+pub const I64_ATOMIC_RMW_XCHG     : u8 = 0x42;
+pub const I32_ATOMIC_RMW8_XCHG_U  : u8 = 0x43;
+pub const I32_ATOMIC_RMW16_XCHG_U : u8 = 0x44;
+pub const I64_ATOMIC_RMW8_XCHG_U  : u8 = 0x45;
+pub const I64_ATOMIC_RMW16_XCHG_U : u8 = 0x46;
+pub const I64_ATOMIC_RMW32_XCHG_U : u8 = 0x47;
+pub const I32_ATOMIC_RMW_CMPXCHG  : u8 = 0x48;
+pub const I64_ATOMIC_RMW_CMPXCHG  : u8 = 0x49;
+pub const I32_ATOMIC_RMW8_CMPXCHG_U: u8 = 0x4a;
+pub const I32_ATOMIC_RMW16_CMPXCHG_U: u8 = 0x4b;
+pub const I64_ATOMIC_RMW8_CMPXCHG_U: u8 = 0x4c;
+pub const I64_ATOMIC_RMW16_CMPXCHG_U: u8 = 0x4d;
+pub const I64_ATOMIC_RMW32_CMPXCHG_U: u8 = 0x4e;
+
+// SIMD_PREFIX sub-opcodes
+
+pub const V128_LOAD               : u8 = 0x00;
+pub const V128_LOAD8X8_S          : u8 = 0x01;
+pub const V128_LOAD8X8_U          : u8 = 0x02;
+pub const V128_LOAD16X4_S         : u8 = 0x03;
+pub const V128_LOAD16X4_U         : u8 = 0x04;
+pub const V128_LOAD32X2_S         : u8 = 0x05;
+pub const V128_LOAD32X2_U         : u8 = 0x06;
+pub const V128_LOAD8_SPLAT        : u8 = 0x07;
+pub const V128_LOAD16_SPLAT       : u8 = 0x08;
+pub const V128_LOAD32_SPLAT       : u8 = 0x09;
+pub const V128_LOAD64_SPLAT       : u8 = 0x0a;
+pub const V128_STORE              : u8 = 0x0b;
+pub const V128_CONST              : u8 = 0x0c;
+pub const I8X16_SHUFFLE           : u8 = 0x0d;
+pub const I8X16_SWIZZLE           : u8 = 0x0e;
+pub const I8X16_SPLAT             : u8 = 0x0f;
+pub const I16X8_SPLAT             : u8 = 0x10;
+pub const I32X4_SPLAT             : u8 = 0x11;
+pub const I64X2_SPLAT             : u8 = 0x12;
+pub const F32X4_SPLAT             : u8 = 0x13;
+pub const F64X2_SPLAT             : u8 = 0x14;
+pub const I8X16_EXTRACT_LANE_S    : u8 = 0x15;
+pub const I8X16_EXTRACT_LANE_U    : u8 = 0x16;
+pub const I8X16_REPLACE_LANE      : u8 = 0x17;
+pub const I16X8_EXTRACT_LANE_S    : u8 = 0x18;
+pub const I16X8_EXTRACT_LANE_U    : u8 = 0x19;
+pub const I16X8_REPLACE_LANE      : u8 = 0x1a;
+pub const I32X4_EXTRACT_LANE      : u8 = 0x1b;
+pub const I32X4_REPLACE_LANE      : u8 = 0x1c;
+pub const I64X2_EXTRACT_LANE      : u8 = 0x1d;
+pub const I64X2_REPLACE_LANE      : u8 = 0x1e;
+pub const F32X4_EXTRACT_LANE      : u8 = 0x1f;
+pub const F32X4_REPLACE_LANE      : u8 = 0x20;
+pub const F64X2_EXTRACT_LANE      : u8 = 0x21;
+pub const F64X2_REPLACE_LANE      : u8 = 0x22;
+pub const I8X16_EQ                : u8 = 0x23;
+pub const I8X16_NE                : u8 = 0x24;
+pub const I8X16_LT_S              : u8 = 0x25;
+pub const I8X16_LT_U              : u8 = 0x26;
+pub const I8X16_GT_S              : u8 = 0x27;
+pub const I8X16_GT_U              : u8 = 0x28;
+pub const I8X16_LE_S              : u8 = 0x29;
+pub const I8X16_LE_U              : u8 = 0x2a;
+pub const I8X16_GE_S              : u8 = 0x2b;
+pub const I8X16_GE_U              : u8 = 0x2c;
+pub const I16X8_EQ                : u8 = 0x2d;
+pub const I16X8_NE                : u8 = 0x2e;
+pub const I16X8_LT_S              : u8 = 0x2f;
+pub const I16X8_LT_U              : u8 = 0x30;
+pub const I16X8_GT_S              : u8 = 0x31;
+pub const I16X8_GT_U              : u8 = 0x32;
+pub const I16X8_LE_S              : u8 = 0x33;
+pub const I16X8_LE_U              : u8 = 0x34;
+pub const I16X8_GE_S              : u8 = 0x35;
+pub const I16X8_GE_U              : u8 = 0x36;
+pub const I32X4_EQ                : u8 = 0x37;
+pub const I32X4_NE                : u8 = 0x38;
+pub const I32X4_LT_S              : u8 = 0x39;
+pub const I32X4_LT_U              : u8 = 0x3a;
+pub const I32X4_GT_S              : u8 = 0x3b;
+pub const I32X4_GT_U              : u8 = 0x3c;
+pub const I32X4_LE_S              : u8 = 0x3d;
+pub const I32X4_LE_U              : u8 = 0x3e;
+pub const I32X4_GE_S              : u8 = 0x3f;
+pub const I32X4_GE_U              : u8 = 0x40;
+pub const F32X4_EQ                : u8 = 0x41;
+pub const F32X4_NE                : u8 = 0x42;
+pub const F32X4_LT                : u8 = 0x43;
+pub const F32X4_GT                : u8 = 0x44;
+pub const F32X4_LE                : u8 = 0x45;
+pub const F32X4_GE                : u8 = 0x46;
+pub const F64X2_EQ                : u8 = 0x47;
+pub const F64X2_NE                : u8 = 0x48;
+pub const F64X2_LT                : u8 = 0x49;
+pub const F64X2_GT                : u8 = 0x4a;
+pub const F64X2_LE                : u8 = 0x4b;
+pub const F64X2_GE                : u8 = 0x4c;
+pub const V128_NOT                : u8 = 0x4d;
+pub const V128_AND                : u8 = 0x4e;
+pub const V128_ANDNOT             : u8 = 0x4f;
+pub const V128_OR                 : u8 = 0x50;
+pub const V128_XOR                : u8 = 0x51;
+pub const V128_BITSELECT          : u8 = 0x52;
+pub const V128_ANY_TRUE           : u8 = 0x53;
+pub const V128_LOAD8_LANE         : u8 = 0x54;
+pub const V128_LOAD16_LANE        : u8 = 0x55;
+pub const V128_LOAD32_LANE        : u8 = 0x56;
+pub const V128_LOAD64_LANE        : u8 = 0x57;
+pub const V128_STORE8_LANE        : u8 = 0x58;
+pub const V128_STORE16_LANE       : u8 = 0x59;
+pub const V128_STORE32_LANE       : u8 = 0x5a;
+pub const V128_STORE64_LANE       : u8 = 0x5b;
+pub const V128_LOAD32_ZERO        : u8 = 0x5c;
+pub const V128_LOAD64_ZERO        : u8 = 0x5d;
+pub const F32X4_DEMOTE_F64X2_ZERO : u8 = 0x5e;
+pub const F64X2_PROMOTE_LOW_F32X4 : u8 = 0x5f;
+pub const I8X16_ABS               : u8 = 0x60;
+pub const I8X16_NEG               : u8 = 0x61;
+pub const I8X16_POPCNT            : u8 = 0x62;
+pub const I8X16_ALL_TRUE          : u8 = 0x63;
+pub const I8X16_BITMASK           : u8 = 0x64;
+pub const I8X16_NARROW_I16X8_S    : u8 = 0x65;
+pub const I8X16_NARROW_I16X8_U    : u8 = 0x66;
+pub const F32X4_CEIL              : u8 = 0x67;
+pub const F32X4_FLOOR             : u8 = 0x68;
+pub const F32X4_TRUNC             : u8 = 0x69;
+pub const F32X4_NEAREST           : u8 = 0x6a;
+pub const I8X16_SHL               : u8 = 0x6b;
+pub const I8X16_SHR_S             : u8 = 0x6c;
+pub const I8X16_SHR_U             : u8 = 0x6d;
+pub const I8X16_ADD               : u8 = 0x6e;
+pub const I8X16_ADD_SAT_S         : u8 = 0x6f;
+pub const I8X16_ADD_SAT_U         : u8 = 0x70;
+pub const I8X16_SUB               : u8 = 0x71;
+pub const I8X16_SUB_SAT_S         : u8 = 0x72;
+pub const I8X16_SUB_SAT_U         : u8 = 0x73;
+pub const F64X2_CEIL              : u8 = 0x74;
+pub const F64X2_FLOOR             : u8 = 0x75;
+pub const I8X16_MIN_S             : u8 = 0x76;
+pub const I8X16_MIN_U             : u8 = 0x77;
+pub const I8X16_MAX_S             : u8 = 0x78;
+pub const I8X16_MAX_U             : u8 = 0x79;
+pub const F64X2_TRUNC             : u8 = 0x7a;
+pub const I8X16_AVGR_U            : u8 = 0x7b;
+pub const I16X8_EXTADD_PAIRWISE_I8X16_S: u8 = 0x7c;
+pub const I16X8_EXTADD_PAIRWISE_I8X16_U: u8 = 0x7d;
+pub const I32X4_EXTADD_PAIRWISE_I16X8_S: u8 = 0x7e;
+pub const I32X4_EXTADD_PAIRWISE_I16X8_U: u8 = 0x7f;
+pub const I16X8_ABS               : u8 = 0x80;
+pub const I16X8_NEG               : u8 = 0x81;
+pub const I16X8_Q15MULR_SAT_S     : u8 = 0x82;
+pub const I16X8_ALL_TRUE          : u8 = 0x83;
+pub const I16X8_BITMASK           : u8 = 0x84;
+pub const I16X8_NARROW_I32X4_S    : u8 = 0x85;
+pub const I16X8_NARROW_I32X4_U    : u8 = 0x86;
+pub const I16X8_EXTEND_LOW_I8X16_S: u8 = 0x87;
+pub const I16X8_EXTEND_HIGH_I8X16_S: u8 = 0x88;
+pub const I16X8_EXTEND_LOW_I8X16_U: u8 = 0x89;
+pub const I16X8_EXTEND_HIGH_I8X16_U: u8 = 0x8a;
+pub const I16X8_SHL               : u8 = 0x8b;
+pub const I16X8_SHR_S             : u8 = 0x8c;
+pub const I16X8_SHR_U             : u8 = 0x8d;
+pub const I16X8_ADD               : u8 = 0x8e;
+pub const I16X8_ADD_SAT_S         : u8 = 0x8f;
+pub const I16X8_ADD_SAT_U         : u8 = 0x90;
+pub const I16X8_SUB               : u8 = 0x91;
+pub const I16X8_SUB_SAT_S         : u8 = 0x92;
+pub const I16X8_SUB_SAT_U         : u8 = 0x93;
+pub const F64X2_NEAREST           : u8 = 0x94;
+pub const I16X8_MUL               : u8 = 0x95;
+pub const I16X8_MIN_S             : u8 = 0x96;
+pub const I16X8_MIN_U             : u8 = 0x97;
+pub const I16X8_MAX_S             : u8 = 0x98;
+pub const I16X8_MAX_U             : u8 = 0x99;
+pub const I16X8_AVGR_U            : u8 = 0x9b;
+pub const I16X8_EXTMUL_LOW_I8X16_S: u8 = 0x9c;
+pub const I16X8_EXTMUL_HIGH_I8X16_S: u8 = 0x9d;
+pub const I16X8_EXTMUL_LOW_I8X16_U: u8 = 0x9e;
+pub const I16X8_EXTMUL_HIGH_I8X16_U: u8 = 0x9f;
+pub const I32X4_ABS               : u8 = 0xa0;
+pub const I32X4_NEG               : u8 = 0xa1;
+pub const I32X4_ALL_TRUE          : u8 = 0xa3;
+pub const I32X4_BITMASK           : u8 = 0xa4;
+pub const I32X4_EXTEND_LOW_I16X8_S: u8 = 0xa7;
+pub const I32X4_EXTEND_HIGH_I16X8_S: u8 = 0xa8;
+pub const I32X4_EXTEND_LOW_I16X8_U: u8 = 0xa9;
+pub const I32X4_EXTEND_HIGH_I16X8_U: u8 = 0xaa;
+pub const I32X4_SHL               : u8 = 0xab;
+pub const I32X4_SHR_S             : u8 = 0xac;
+pub const I32X4_SHR_U             : u8 = 0xad;
+pub const I32X4_ADD               : u8 = 0xae;
+pub const I32X4_SUB               : u8 = 0xb1;
+pub const I32X4_MUL               : u8 = 0xb5;
+pub const I32X4_MIN_S             : u8 = 0xb6;
+pub const I32X4_MIN_U             : u8 = 0xb7;
+pub const I32X4_MAX_S             : u8 = 0xb8;
+pub const I32X4_MAX_U             : u8 = 0xb9;
+pub const I32X4_DOT_I16X8_S       : u8 = 0xba;
+pub const I32X4_EXTMUL_LOW_I16X8_S: u8 = 0xbc;
+pub const I32X4_EXTMUL_HIGH_I16X8_S: u8 = 0xbd;
+pub const I32X4_EXTMUL_LOW_I16X8_U: u8 = 0xbe;
+pub const I32X4_EXTMUL_HIGH_I16X8_U: u8 = 0xbf;
+pub const I64X2_ABS               : u8 = 0xc0;
+pub const I64X2_NEG               : u8 = 0xc1;
+pub const I64X2_ALL_TRUE          : u8 = 0xc3;
+pub const I64X2_BITMASK           : u8 = 0xc4;
+pub const I64X2_EXTEND_LOW_I32X4_S: u8 = 0xc7;
+pub const I64X2_EXTEND_HIGH_I32X4_S: u8 = 0xc8;
+pub const I64X2_EXTEND_LOW_I32X4_U: u8 = 0xc9;
+pub const I64X2_EXTEND_HIGH_I32X4_U: u8 = 0xca;
+pub const I64X2_SHL               : u8 = 0xcb;
+pub const I64X2_SHR_S             : u8 = 0xcc;
+pub const I64X2_SHR_U             : u8 = 0xcd;
+pub const I64X2_ADD               : u8 = 0xce;
+pub const I64X2_SUB               : u8 = 0xd1;
+pub const I64X2_MUL               : u8 = 0xd5;
+pub const I64X2_EQ                : u8 = 0xd6;
+pub const I64X2_NE                : u8 = 0xd7;
+pub const I64X2_LT_S              : u8 = 0xd8;
+pub const I64X2_GT_S              : u8 = 0xd9;
+pub const I64X2_LE_S              : u8 = 0xda;
+pub const I64X2_GE_S              : u8 = 0xdb;
+pub const I64X2_EXTMUL_LOW_I32X4_S: u8 = 0xdc;
+pub const I64X2_EXTMUL_HIGH_I32X4_S: u8 = 0xdd;
+pub const I64X2_EXTMUL_LOW_I32X4_U: u8 = 0xde;
+pub const I64X2_EXTMUL_HIGH_I32X4_U: u8 = 0xdf;
+pub const F32X4_ABS               : u8 = 0xe0;
+pub const F32X4_NEG               : u8 = 0xe1;
+pub const F32X4_SQRT              : u8 = 0xe3;
+pub const F32X4_ADD               : u8 = 0xe4;
+pub const F32X4_SUB               : u8 = 0xe5;
+pub const F32X4_MUL               : u8 = 0xe6;
+pub const F32X4_DIV               : u8 = 0xe7;
+pub const F32X4_MIN               : u8 = 0xe8;
+pub const F32X4_MAX               : u8 = 0xe9;
+pub const F32X4_PMIN              : u8 = 0xea;
+pub const F32X4_PMAX              : u8 = 0xeb;
+pub const F64X2_ABS               : u8 = 0xec;
+pub const F64X2_NEG               : u8 = 0xed;
+pub const F64X2_SQRT              : u8 = 0xef;
+pub const F64X2_ADD               : u8 = 0xf0;
+pub const F64X2_SUB               : u8 = 0xf1;
+pub const F64X2_MUL               : u8 = 0xf2;
+pub const F64X2_DIV               : u8 = 0xf3;
+pub const F64X2_MIN               : u8 = 0xf4;
+pub const F64X2_MAX               : u8 = 0xf5;
+pub const F64X2_PMIN              : u8 = 0xf6;
+pub const F64X2_PMAX              : u8 = 0xf7;
+pub const I32X4_TRUNC_SAT_F32X4_S : u8 = 0xf8;
+pub const I32X4_TRUNC_SAT_F32X4_U : u8 = 0xf9;
+pub const F32X4_CONVERT_I32X4_S   : u8 = 0xfa;
+pub const F32X4_CONVERT_I32X4_U   : u8 = 0xfb;
+pub const I32X4_TRUNC_SAT_F64X2_S_ZERO: u8 = 0xfc;
+pub const I32X4_TRUNC_SAT_F64X2_U_ZERO: u8 = 0xfd;
+pub const F64X2_CONVERT_LOW_I32X4_S: u8 = 0xfe;
+pub const F64X2_CONVERT_LOW_I32X4_U: u8 = 0xff;
+
+
+pub const UNREACHABLE_OP          : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x00,  text: "unreachable", };
+pub const NOP_OP                  : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x01,  text: "nop", };
+pub const BLOCK_OP                : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x02,  text: "block", };
+pub const LOOP_OP                 : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x03,  text: "loop", };
+pub const IF_OP                   : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x04,  text: "if", };
+pub const ELSE_OP                 : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x05,  text: "else", };
+pub const TRY_OP                  : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x06,  text: "try", };
+pub const CATCH_OP                : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x07,  text: "catch", };
+pub const THROW_OP                : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x08,  text: "throw", };
+pub const RETHROW_OP              : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x09,  text: "rethrow", };
+pub const CATCH_ALL_OP            : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x0a,  text: "catch_all", };
+pub const END_OP                  : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x0b,  text: "end", };
+pub const BR_OP                   : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x0c,  text: "br", };
+pub const BR_IF_OP                : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x0d,  text: "br_if", };
+pub const BR_TABLE_OP             : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x0e,  text: "br_table", };
+pub const RETURN_OP               : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x0f,  text: "return", };
+pub const CALL_OP                 : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x10,  text: "call", };
+pub const CALL_INDIRECT_OP        : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x11,  text: "call_indirect", };
+pub const RETURN_CALL_OP          : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x12,  text: "return_call", };
+pub const RETURN_CALL_INDIRECT_OP : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x13,  text: "return_call_indirect", };
+pub const DROP_OP                 : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x1a,  text: "drop", };
+pub const SELECT_OP               : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x1b,  text: "select", };
+pub const SELECT_T_OP             : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x1c,  text: "select", };
+pub const GET_LOCAL_OP            : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x20,  text: "local.get", };
+pub const SET_LOCAL_OP            : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x21,  text: "local.set", };
+pub const TEE_LOCAL_OP            : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x22,  text: "local.tee", };
+pub const GET_GLOBAL_OP           : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x23,  text: "global.get", };
+pub const SET_GLOBAL_OP           : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x24,  text: "global.set", };
+pub const TABLE_GET_OP            : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x25,  text: "table.get", };
+pub const TABLE_SET_OP            : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x26,  text: "table.set", };
+pub const I32_LOAD_OP             : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 4,  prefix: 0x00,  code: 0x28,  text: "i32.load", };
+pub const I64_LOAD_OP             : Op = Op { tr: I64,  t1: I32,  t2: ___,  m: 8,  prefix: 0x00,  code: 0x29,  text: "i64.load", };
+pub const F32_LOAD_OP             : Op = Op { tr: F32,  t1: I32,  t2: ___,  m: 4,  prefix: 0x00,  code: 0x2a,  text: "f32.load", };
+pub const F64_LOAD_OP             : Op = Op { tr: F64,  t1: I32,  t2: ___,  m: 8,  prefix: 0x00,  code: 0x2b,  text: "f64.load", };
+pub const I32_LOAD8_S_OP          : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 1,  prefix: 0x00,  code: 0x2c,  text: "i32.load8_s", };
+pub const I32_LOAD8_U_OP          : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 1,  prefix: 0x00,  code: 0x2d,  text: "i32.load8_u", };
+pub const I32_LOAD16_S_OP         : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 2,  prefix: 0x00,  code: 0x2e,  text: "i32.load16_s", };
+pub const I32_LOAD16_U_OP         : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 2,  prefix: 0x00,  code: 0x2f,  text: "i32.load16_u", };
+pub const I64_LOAD8_S_OP          : Op = Op { tr: I64,  t1: I32,  t2: ___,  m: 1,  prefix: 0x00,  code: 0x30,  text: "i64.load8_s", };
+pub const I64_LOAD8_U_OP          : Op = Op { tr: I64,  t1: I32,  t2: ___,  m: 1,  prefix: 0x00,  code: 0x31,  text: "i64.load8_u", };
+pub const I64_LOAD16_S_OP         : Op = Op { tr: I64,  t1: I32,  t2: ___,  m: 2,  prefix: 0x00,  code: 0x32,  text: "i64.load16_s", };
+pub const I64_LOAD16_U_OP         : Op = Op { tr: I64,  t1: I32,  t2: ___,  m: 2,  prefix: 0x00,  code: 0x33,  text: "i64.load16_u", };
+pub const I64_LOAD32_S_OP         : Op = Op { tr: I64,  t1: I32,  t2: ___,  m: 4,  prefix: 0x00,  code: 0x34,  text: "i64.load32_s", };
+pub const I64_LOAD32_U_OP         : Op = Op { tr: I64,  t1: I32,  t2: ___,  m: 4,  prefix: 0x00,  code: 0x35,  text: "i64.load32_u", };
+pub const I32_STORE_OP            : Op = Op { tr: ___,  t1: I32,  t2: I32,  m: 4,  prefix: 0x00,  code: 0x36,  text: "i32.store", };
+pub const I64_STORE_OP            : Op = Op { tr: ___,  t1: I32,  t2: I64,  m: 8,  prefix: 0x00,  code: 0x37,  text: "i64.store", };
+pub const F32_STORE_OP            : Op = Op { tr: ___,  t1: I32,  t2: F32,  m: 4,  prefix: 0x00,  code: 0x38,  text: "f32.store", };
+pub const F64_STORE_OP            : Op = Op { tr: ___,  t1: I32,  t2: F64,  m: 8,  prefix: 0x00,  code: 0x39,  text: "f64.store", };
+pub const I32_STORE8_OP           : Op = Op { tr: ___,  t1: I32,  t2: I32,  m: 1,  prefix: 0x00,  code: 0x3a,  text: "i32.store8", };
+pub const I32_STORE16_OP          : Op = Op { tr: ___,  t1: I32,  t2: I32,  m: 2,  prefix: 0x00,  code: 0x3b,  text: "i32.store16", };
+pub const I64_STORE8_OP           : Op = Op { tr: ___,  t1: I32,  t2: I64,  m: 1,  prefix: 0x00,  code: 0x3c,  text: "i64.store8", };
+pub const I64_STORE16_OP          : Op = Op { tr: ___,  t1: I32,  t2: I64,  m: 2,  prefix: 0x00,  code: 0x3d,  text: "i64.store16", };
+pub const I64_STORE32_OP          : Op = Op { tr: ___,  t1: I32,  t2: I64,  m: 4,  prefix: 0x00,  code: 0x3e,  text: "i64.store32", };
+pub const MEM_SIZE_OP             : Op = Op { tr: I32,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x3f,  text: "mem_size", };
+pub const MEM_GROW_OP             : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x40,  text: "mem_grow", };
+pub const I32_CONST_OP            : Op = Op { tr: I32,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x41,  text: "i32.const", };
+pub const I64_CONST_OP            : Op = Op { tr: I64,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x42,  text: "i64.const", };
+pub const F32_CONST_OP            : Op = Op { tr: F32,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x43,  text: "f32.const", };
+pub const F64_CONST_OP            : Op = Op { tr: F64,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x44,  text: "f64.const", };
+pub const I32_EQZ_OP              : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x45,  text: "i32.eqz", };
+pub const I32_EQ_OP               : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x46,  text: "i32.eq", };
+pub const I32_NE_OP               : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x47,  text: "i32.ne", };
+pub const I32_LT_S_OP             : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x48,  text: "i32.lt_s", };
+pub const I32_LT_U_OP             : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x49,  text: "i32.lt_u", };
+pub const I32_GT_S_OP             : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x4a,  text: "i32.gt_s", };
+pub const I32_GT_U_OP             : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x4b,  text: "i32.gt_u", };
+pub const I32_LE_S_OP             : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x4c,  text: "i32.le_s", };
+pub const I32_LE_U_OP             : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x4d,  text: "i32.le_u", };
+pub const I32_GE_S_OP             : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x4e,  text: "i32.ge_s", };
+pub const I32_GE_U_OP             : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x4f,  text: "i32.ge_u", };
+pub const I64_EQZ_OP              : Op = Op { tr: I32,  t1: I64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x50,  text: "i64.eqz", };
+pub const I64_EQ_OP               : Op = Op { tr: I32,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x51,  text: "i64.eq", };
+pub const I64_NE_OP               : Op = Op { tr: I32,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x52,  text: "i64.ne", };
+pub const I64_LT_S_OP             : Op = Op { tr: I32,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x53,  text: "i64.lt_s", };
+pub const I64_LT_U_OP             : Op = Op { tr: I32,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x54,  text: "i64.lt_u", };
+pub const I64_GT_S_OP             : Op = Op { tr: I32,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x55,  text: "i64.gt_s", };
+pub const I64_GT_U_OP             : Op = Op { tr: I32,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x56,  text: "i64.gt_u", };
+pub const I64_LE_S_OP             : Op = Op { tr: I32,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x57,  text: "i64.le_s", };
+pub const I64_LE_U_OP             : Op = Op { tr: I32,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x58,  text: "i64.le_u", };
+pub const I64_GE_S_OP             : Op = Op { tr: I32,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x59,  text: "i64.ge_s", };
+pub const I64_GE_U_OP             : Op = Op { tr: I32,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x5a,  text: "i64.ge_u", };
+pub const F32_EQ_OP               : Op = Op { tr: I32,  t1: F32,  t2: F32,  m: 0,  prefix: 0x00,  code: 0x5b,  text: "f32.eq", };
+pub const F32_NE_OP               : Op = Op { tr: I32,  t1: F32,  t2: F32,  m: 0,  prefix: 0x00,  code: 0x5c,  text: "f32.ne", };
+pub const F32_LT_OP               : Op = Op { tr: I32,  t1: F32,  t2: F32,  m: 0,  prefix: 0x00,  code: 0x5d,  text: "f32.lt", };
+pub const F32_GT_OP               : Op = Op { tr: I32,  t1: F32,  t2: F32,  m: 0,  prefix: 0x00,  code: 0x5e,  text: "f32.gt", };
+pub const F32_LE_OP               : Op = Op { tr: I32,  t1: F32,  t2: F32,  m: 0,  prefix: 0x00,  code: 0x5f,  text: "f32.le", };
+pub const F32_GE_OP               : Op = Op { tr: I32,  t1: F32,  t2: F32,  m: 0,  prefix: 0x00,  code: 0x60,  text: "f32.ge", };
+pub const F64_EQ_OP               : Op = Op { tr: I32,  t1: F64,  t2: F64,  m: 0,  prefix: 0x00,  code: 0x61,  text: "f64.eq", };
+pub const F64_NE_OP               : Op = Op { tr: I32,  t1: F64,  t2: F64,  m: 0,  prefix: 0x00,  code: 0x62,  text: "f64.ne", };
+pub const F64_LT_OP               : Op = Op { tr: I32,  t1: F64,  t2: F64,  m: 0,  prefix: 0x00,  code: 0x63,  text: "f64.lt", };
+pub const F64_GT_OP               : Op = Op { tr: I32,  t1: F64,  t2: F64,  m: 0,  prefix: 0x00,  code: 0x64,  text: "f64.gt", };
+pub const F64_LE_OP               : Op = Op { tr: I32,  t1: F64,  t2: F64,  m: 0,  prefix: 0x00,  code: 0x65,  text: "f64.le", };
+pub const F64_GE_OP               : Op = Op { tr: I32,  t1: F64,  t2: F64,  m: 0,  prefix: 0x00,  code: 0x66,  text: "f64.ge", };
+pub const I32_CLZ_OP              : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x67,  text: "i32.clz", };
+pub const I32_CTZ_OP              : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x68,  text: "i32.ctz", };
+pub const I32_POPCNT_OP           : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x69,  text: "i32.popcnt", };
+pub const I32_ADD_OP              : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x6a,  text: "i32.add", };
+pub const I32_SUB_OP              : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x6b,  text: "i32.sub", };
+pub const I32_MUL_OP              : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x6c,  text: "i32.mul", };
+pub const I32_DIV_S_OP            : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x6d,  text: "i32.div_s", };
+pub const I32_DIV_U_OP            : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x6e,  text: "i32.div_u", };
+pub const I32_REM_S_OP            : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x6f,  text: "i32.rem_s", };
+pub const I32_REM_U_OP            : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x70,  text: "i32.rem_u", };
+pub const I32_AND_OP              : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x71,  text: "i32.and", };
+pub const I32_OR_OP               : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x72,  text: "i32.or", };
+pub const I32_XOR_OP              : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x73,  text: "i32.xor", };
+pub const I32_SHL_OP              : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x74,  text: "i32.shl", };
+pub const I32_SHR_S_OP            : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x75,  text: "i32.shr_s", };
+pub const I32_SHR_U_OP            : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x76,  text: "i32.shr_u", };
+pub const I32_ROTL_OP             : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x77,  text: "i32.rotl", };
+pub const I32_ROTR_OP             : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 0,  prefix: 0x00,  code: 0x78,  text: "i32.rotr", };
+pub const I64_CLZ_OP              : Op = Op { tr: I64,  t1: I64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x79,  text: "i64.clz", };
+pub const I64_CTZ_OP              : Op = Op { tr: I64,  t1: I64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x7a,  text: "i64.ctz", };
+pub const I64_POPCNT_OP           : Op = Op { tr: I64,  t1: I64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x7b,  text: "i64.popcnt", };
+pub const I64_ADD_OP              : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x7c,  text: "i64.add", };
+pub const I64_SUB_OP              : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x7d,  text: "i64.sub", };
+pub const I64_MUL_OP              : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x7e,  text: "i64.mul", };
+pub const I64_DIV_S_OP            : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x7f,  text: "i64.div_s", };
+pub const I64_DIV_U_OP            : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x80,  text: "i64.div_u", };
+pub const I64_REM_S_OP            : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x81,  text: "i64.rem_s", };
+pub const I64_REM_U_OP            : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x82,  text: "i64.rem_u", };
+pub const I64_AND_OP              : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x83,  text: "i64.and", };
+pub const I64_OR_OP               : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x84,  text: "i64.or", };
+pub const I64_XOR_OP              : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x85,  text: "i64.xor", };
+pub const I64_SHL_OP              : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x86,  text: "i64.shl", };
+pub const I64_SHR_S_OP            : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x87,  text: "i64.shr_s", };
+pub const I64_SHR_U_OP            : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x88,  text: "i64.shr_u", };
+pub const I64_ROTL_OP             : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x89,  text: "i64.rotl", };
+pub const I64_ROTR_OP             : Op = Op { tr: I64,  t1: I64,  t2: I64,  m: 0,  prefix: 0x00,  code: 0x8a,  text: "i64.rotr", };
+pub const F32_ABS_OP              : Op = Op { tr: F32,  t1: F32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x8b,  text: "f32.abs", };
+pub const F32_NEG_OP              : Op = Op { tr: F32,  t1: F32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x8c,  text: "f32.neg", };
+pub const F32_CEIL_OP             : Op = Op { tr: F32,  t1: F32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x8d,  text: "f32.ceil", };
+pub const F32_FLOOR_OP            : Op = Op { tr: F32,  t1: F32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x8e,  text: "f32.floor", };
+pub const F32_TRUNC_OP            : Op = Op { tr: F32,  t1: F32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x8f,  text: "f32.trunc", };
+pub const F32_NEAREST_OP          : Op = Op { tr: F32,  t1: F32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x90,  text: "f32.nearest", };
+pub const F32_SQRT_OP             : Op = Op { tr: F32,  t1: F32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x91,  text: "f32.sqrt", };
+pub const F32_ADD_OP              : Op = Op { tr: F32,  t1: F32,  t2: F32,  m: 0,  prefix: 0x00,  code: 0x92,  text: "f32.add", };
+pub const F32_SUB_OP              : Op = Op { tr: F32,  t1: F32,  t2: F32,  m: 0,  prefix: 0x00,  code: 0x93,  text: "f32.sub", };
+pub const F32_MUL_OP              : Op = Op { tr: F32,  t1: F32,  t2: F32,  m: 0,  prefix: 0x00,  code: 0x94,  text: "f32.mul", };
+pub const F32_DIV_OP              : Op = Op { tr: F32,  t1: F32,  t2: F32,  m: 0,  prefix: 0x00,  code: 0x95,  text: "f32.div", };
+pub const F32_MIN_OP              : Op = Op { tr: F32,  t1: F32,  t2: F32,  m: 0,  prefix: 0x00,  code: 0x96,  text: "f32.min", };
+pub const F32_MAX_OP              : Op = Op { tr: F32,  t1: F32,  t2: F32,  m: 0,  prefix: 0x00,  code: 0x97,  text: "f32.max", };
+pub const F32_COPYSIGN_OP         : Op = Op { tr: F32,  t1: F32,  t2: F32,  m: 0,  prefix: 0x00,  code: 0x98,  text: "f32.copysign", };
+pub const F64_ABS_OP              : Op = Op { tr: F64,  t1: F64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x99,  text: "f64.abs", };
+pub const F64_NEG_OP              : Op = Op { tr: F64,  t1: F64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x9a,  text: "f64.neg", };
+pub const F64_CEIL_OP             : Op = Op { tr: F64,  t1: F64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x9b,  text: "f64.ceil", };
+pub const F64_FLOOR_OP            : Op = Op { tr: F64,  t1: F64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x9c,  text: "f64.floor", };
+pub const F64_TRUNC_OP            : Op = Op { tr: F64,  t1: F64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x9d,  text: "f64.trunc", };
+pub const F64_NEAREST_OP          : Op = Op { tr: F64,  t1: F64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x9e,  text: "f64.nearest", };
+pub const F64_SQRT_OP             : Op = Op { tr: F64,  t1: F64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0x9f,  text: "f64.sqrt", };
+pub const F64_ADD_OP              : Op = Op { tr: F64,  t1: F64,  t2: F64,  m: 0,  prefix: 0x00,  code: 0xa0,  text: "f64.add", };
+pub const F64_SUB_OP              : Op = Op { tr: F64,  t1: F64,  t2: F64,  m: 0,  prefix: 0x00,  code: 0xa1,  text: "f64.sub", };
+pub const F64_MUL_OP              : Op = Op { tr: F64,  t1: F64,  t2: F64,  m: 0,  prefix: 0x00,  code: 0xa2,  text: "f64.mul", };
+pub const F64_DIV_OP              : Op = Op { tr: F64,  t1: F64,  t2: F64,  m: 0,  prefix: 0x00,  code: 0xa3,  text: "f64.div", };
+pub const F64_MIN_OP              : Op = Op { tr: F64,  t1: F64,  t2: F64,  m: 0,  prefix: 0x00,  code: 0xa4,  text: "f64.min", };
+pub const F64_MAX_OP              : Op = Op { tr: F64,  t1: F64,  t2: F64,  m: 0,  prefix: 0x00,  code: 0xa5,  text: "f64.max", };
+pub const F64_COPYSIGN_OP         : Op = Op { tr: F64,  t1: F64,  t2: F64,  m: 0,  prefix: 0x00,  code: 0xa6,  text: "f64.copysign", };
+pub const I32_WRAP_I64_OP         : Op = Op { tr: I32,  t1: I64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xa7,  text: "i32.wrap_i64", };
+pub const I32_TRUNC_S_F32_OP      : Op = Op { tr: I32,  t1: F32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xa8,  text: "i32.trunc_f32_s", };
+pub const I32_TRUNC_U_F32_OP      : Op = Op { tr: I32,  t1: F32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xa9,  text: "i32.trunc_f32_u", };
+pub const I32_TRUNC_S_F64_OP      : Op = Op { tr: I32,  t1: F64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xaa,  text: "i32.trunc_f64_s", };
+pub const I32_TRUNC_U_F64_OP      : Op = Op { tr: I32,  t1: F64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xab,  text: "i32.trunc_f64_u", };
+pub const I64_EXTEND_S_I32_OP     : Op = Op { tr: I64,  t1: I32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xac,  text: "i64.extend_i32_s", };
+pub const I64_EXTEND_U_I32_OP     : Op = Op { tr: I64,  t1: I32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xad,  text: "i64.extend_i32_u", };
+pub const I64_TRUNC_S_F32_OP      : Op = Op { tr: I64,  t1: F32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xae,  text: "i64.trunc_f32_s", };
+pub const I64_TRUNC_U_F32_OP      : Op = Op { tr: I64,  t1: F32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xaf,  text: "i64.trunc_f32_u", };
+pub const I64_TRUNC_S_F64_OP      : Op = Op { tr: I64,  t1: F64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xb0,  text: "i64.trunc_f64_s", };
+pub const I64_TRUNC_U_F64_OP      : Op = Op { tr: I64,  t1: F64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xb1,  text: "i64.trunc_f64_u", };
+pub const F32_CONVERT_S_I32_OP    : Op = Op { tr: F32,  t1: I32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xb2,  text: "f32.convert_i32_s", };
+pub const F32_CONVERT_U_I32_OP    : Op = Op { tr: F32,  t1: I32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xb3,  text: "f32.convert_i32_u", };
+pub const F32_CONVERT_S_I64_OP    : Op = Op { tr: F32,  t1: I64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xb4,  text: "f32.convert_i64_s", };
+pub const F32_CONVERT_U_I64_OP    : Op = Op { tr: F32,  t1: I64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xb5,  text: "f32.convert_i64_u", };
+pub const F32_DEMOTE_F64_OP       : Op = Op { tr: F32,  t1: F64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xb6,  text: "f32.demote_f64", };
+pub const F64_CONVERT_S_I32_OP    : Op = Op { tr: F64,  t1: I32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xb7,  text: "f64.convert_i32_s", };
+pub const F64_CONVERT_U_I32_OP    : Op = Op { tr: F64,  t1: I32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xb8,  text: "f64.convert_i32_u", };
+pub const F64_CONVERT_S_I64_OP    : Op = Op { tr: F64,  t1: I64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xb9,  text: "f64.convert_i64_s", };
+pub const F64_CONVERT_U_I64_OP    : Op = Op { tr: F64,  t1: I64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xba,  text: "f64.convert_i64_u", };
+pub const F64_PROMOTE_F32_OP      : Op = Op { tr: F64,  t1: F32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xbb,  text: "f64.promote_f32", };
+pub const I32_REINTERPRET_F32_OP  : Op = Op { tr: I32,  t1: F32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xbc,  text: "i32.reinterpret_f32", };
+pub const I64_REINTERPRET_F64_OP  : Op = Op { tr: I64,  t1: F64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xbd,  text: "i64.reinterpret_f64", };
+pub const F32_REINTERPRET_I32_OP  : Op = Op { tr: F32,  t1: I32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xbe,  text: "f32.reinterpret_i32", };
+pub const F64_REINTERPRET_I64_OP  : Op = Op { tr: F64,  t1: I64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xbf,  text: "f64.reinterpret_i64", };
+pub const I32_EXTEND8_S_OP        : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xC0,  text: "i32.extend8_s", };
+pub const I32_EXTEND16_S_OP       : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xC1,  text: "i32.extend16_s", };
+pub const I64_EXTEND8_S_OP        : Op = Op { tr: I64,  t1: I64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xC2,  text: "i64.extend8_s", };
+pub const I64_EXTEND16_S_OP       : Op = Op { tr: I64,  t1: I64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xC3,  text: "i64.extend16_s", };
+pub const I64_EXTEND32_S_OP       : Op = Op { tr: I64,  t1: I64,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xC4,  text: "i64.extend32_s", };
+pub const REF_NULL_OP             : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xd0,  text: "ref.null", };
+pub const REF_IS_NULL_OP          : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xd1,  text: "ref.is_null", };
+pub const REF_FUNC_OP             : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xd2,  text: "ref.func", };
+pub const ALLOCA_OP               : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xe0,  text: "alloca", };
+pub const BR_UNLESS_OP            : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xe1,  text: "br_unless", };
+pub const CALL_HOST_OP            : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xe2,  text: "call_host", };
+pub const DATA_OP                 : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xe3,  text: "data", };
+pub const DROP_KEEP_OP            : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xe4,  text: "drop_keep", };
+pub const HANDLER_POP_OP          : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xe5,  text: "handler_pop", };
+pub const BR_S_OP                 : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xe6,  text: "br_s", };
+pub const BR_IF_S_OP              : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xe7,  text: "br_if_s", };
+pub const BR_UNLESS_S_OP          : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xe8,  text: "br_unless_s", };
+pub const BR_TABLE_S_OP           : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xe9,  text: "br_table_s", };
+pub const DROP_KEEP_S_OP          : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xea,  text: "drop_keep_s", };
+pub const GET_LOCAL_S_OP          : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xeb,  text: "local.get_s", };
+pub const SET_LOCAL_S_OP          : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xec,  text: "local.set_s", };
+pub const TEE_LOCAL_S_OP          : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xed,  text: "local.tee_s", };
+pub const I32_CONST_S_OP          : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xee,  text: "i32.const_s", };
+pub const ALLOCA_S_OP             : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0x00,  code: 0xef,  text: "alloca_s", };
+
+pub const I32_TRUNC_SAT_F32_S_OP  : Op = Op { tr: I32,  t1: F32,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x00,  text: "i32.trunc_sat_f32_s", };
+pub const I32_TRUNC_SAT_F32_U_OP  : Op = Op { tr: I32,  t1: F32,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x01,  text: "i32.trunc_sat_f32_u", };
+pub const I32_TRUNC_SAT_F64_S_OP  : Op = Op { tr: I32,  t1: F64,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x02,  text: "i32.trunc_sat_f64_s", };
+pub const I32_TRUNC_SAT_F64_U_OP  : Op = Op { tr: I32,  t1: F64,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x03,  text: "i32.trunc_sat_f64_u", };
+pub const I64_TRUNC_SAT_F32_S_OP  : Op = Op { tr: I64,  t1: F32,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x04,  text: "i64.trunc_sat_f32_s", };
+pub const I64_TRUNC_SAT_F32_U_OP  : Op = Op { tr: I64,  t1: F32,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x05,  text: "i64.trunc_sat_f32_u", };
+pub const I64_TRUNC_SAT_F64_S_OP  : Op = Op { tr: I64,  t1: F64,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x06,  text: "i64.trunc_sat_f64_s", };
+pub const I64_TRUNC_SAT_F64_U_OP  : Op = Op { tr: I64,  t1: F64,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x07,  text: "i64.trunc_sat_f64_u", };
+pub const MEMORY_INIT_OP          : Op = Op { tr: ___,  t1: I32,  t2: I32,  m: 0,  prefix: 0xfc,  code: 0x08,  text: "memory.init", };
+pub const DATA_DROP_OP            : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x09,  text: "data.drop", };
+pub const MEMORY_COPY_OP          : Op = Op { tr: ___,  t1: I32,  t2: I32,  m: 0,  prefix: 0xfc,  code: 0x0a,  text: "memory.copy", };
+pub const MEMORY_FILL_OP          : Op = Op { tr: ___,  t1: I32,  t2: I32,  m: 0,  prefix: 0xfc,  code: 0x0b,  text: "memory.fill", };
+pub const TABLE_INIT_OP           : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x0c,  text: "table.init", };
+pub const ELEM_DROP_OP            : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x0d,  text: "elem.drop", };
+pub const TABLE_COPY_OP           : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x0e,  text: "table.copy", };
+pub const TABLE_GROW_OP           : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x0f,  text: "table.grow", };
+pub const TABLE_SIZE_OP           : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x10,  text: "table.size", };
+pub const TABLE_FILL_OP           : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0xfc,  code: 0x11,  text: "table.fill", };
+
+pub const MEMORY_ATOMIC_NOTIFY_OP : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 4,  prefix: 0xfe,  code: 0x00,  text: "memory.atomic.notify", };
+pub const MEMORY_ATOMIC_WAIT32_OP : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 4,  prefix: 0xfe,  code: 0x01,  text: "memory.atomic.wait32", };
+pub const MEMORY_ATOMIC_WAIT64_OP : Op = Op { tr: I32,  t1: I32,  t2: I64,  m: 8,  prefix: 0xfe,  code: 0x02,  text: "memory.atomic.wait64", };
+pub const ATOMIC_FENCE_OP         : Op = Op { tr: ___,  t1: ___,  t2: ___,  m: 0,  prefix: 0xfe,  code: 0x03,  text: "atomic.fence", };
+pub const I32_ATOMIC_LOAD_OP      : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 4,  prefix: 0xfe,  code: 0x10,  text: "i32.atomic.load", };
+pub const I64_ATOMIC_LOAD_OP      : Op = Op { tr: I64,  t1: I32,  t2: ___,  m: 8,  prefix: 0xfe,  code: 0x11,  text: "i64.atomic.load", };
+pub const I32_ATOMIC_LOAD8_U_OP   : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 1,  prefix: 0xfe,  code: 0x12,  text: "i32.atomic.load8_u", };
+pub const I32_ATOMIC_LOAD16_U_OP  : Op = Op { tr: I32,  t1: I32,  t2: ___,  m: 2,  prefix: 0xfe,  code: 0x13,  text: "i32.atomic.load16_u", };
+pub const I64_ATOMIC_LOAD8_U_OP   : Op = Op { tr: I64,  t1: I32,  t2: ___,  m: 1,  prefix: 0xfe,  code: 0x14,  text: "i64.atomic.load8_u", };
+pub const I64_ATOMIC_LOAD16_U_OP  : Op = Op { tr: I64,  t1: I32,  t2: ___,  m: 2,  prefix: 0xfe,  code: 0x15,  text: "i64.atomic.load16_u", };
+pub const I64_ATOMIC_LOAD32_U_OP  : Op = Op { tr: I64,  t1: I32,  t2: ___,  m: 4,  prefix: 0xfe,  code: 0x16,  text: "i64.atomic.load32_u", };
+pub const I32_ATOMIC_STORE_OP     : Op = Op { tr: ___,  t1: I32,  t2: I32,  m: 4,  prefix: 0xfe,  code: 0x17,  text: "i32.atomic.store", };
+pub const I64_ATOMIC_STORE_OP     : Op = Op { tr: ___,  t1: I32,  t2: I64,  m: 8,  prefix: 0xfe,  code: 0x18,  text: "i64.atomic.store", };
+pub const I32_ATOMIC_STORE8_OP    : Op = Op { tr: ___,  t1: I32,  t2: I32,  m: 1,  prefix: 0xfe,  code: 0x19,  text: "i32.atomic.store8", };
+pub const I32_ATOMIC_STORE16_OP   : Op = Op { tr: ___,  t1: I32,  t2: I32,  m: 2,  prefix: 0xfe,  code: 0x1a,  text: "i32.atomic.store16", };
+pub const I64_ATOMIC_STORE8_OP    : Op = Op { tr: ___,  t1: I32,  t2: I64,  m: 1,  prefix: 0xfe,  code: 0x1b,  text: "i64.atomic.store8", };
+pub const I64_ATOMIC_STORE16_OP   : Op = Op { tr: ___,  t1: I32,  t2: I64,  m: 2,  prefix: 0xfe,  code: 0x1c,  text: "i64.atomic.store16", };
+pub const I64_ATOMIC_STORE32_OP   : Op = Op { tr: ___,  t1: I32,  t2: I64,  m: 4,  prefix: 0xfe,  code: 0x1d,  text: "i64.atomic.store32", };
+pub const I32_ATOMIC_RMW_ADD_OP   : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 4,  prefix: 0xfe,  code: 0x1e,  text: "i32.atomic.rmw.add", };
+pub const I64_ATOMIC_RMW_ADD_OP   : Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 8,  prefix: 0xfe,  code: 0x1f,  text: "i64.atomic.rmw.add", };
+pub const I32_ATOMIC_RMW8_ADD_U_OP: Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 1,  prefix: 0xfe,  code: 0x20,  text: "i32.atomic.rmw8.add_u", };
+pub const I32_ATOMIC_RMW16_ADD_U_OP: Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 2,  prefix: 0xfe,  code: 0x21,  text: "i32.atomic.rmw16.add_u", };
+pub const I64_ATOMIC_RMW8_ADD_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 1,  prefix: 0xfe,  code: 0x22,  text: "i64.atomic.rmw8.add_u", };
+pub const I64_ATOMIC_RMW16_ADD_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 2,  prefix: 0xfe,  code: 0x23,  text: "i64.atomic.rmw16.add_u", };
+pub const I64_ATOMIC_RMW32_ADD_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 4,  prefix: 0xfe,  code: 0x24,  text: "i64.atomic.rmw32.add_u", };
+pub const I32_ATOMIC_RMW_SUB_OP   : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 4,  prefix: 0xfe,  code: 0x25,  text: "i32.atomic.rmw.sub", };
+pub const I64_ATOMIC_RMW_SUB_OP   : Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 8,  prefix: 0xfe,  code: 0x26,  text: "i64.atomic.rmw.sub", };
+pub const I32_ATOMIC_RMW8_SUB_U_OP: Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 1,  prefix: 0xfe,  code: 0x27,  text: "i32.atomic.rmw8.sub_u", };
+pub const I32_ATOMIC_RMW16_SUB_U_OP: Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 2,  prefix: 0xfe,  code: 0x28,  text: "i32.atomic.rmw16.sub_u", };
+pub const I64_ATOMIC_RMW8_SUB_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 1,  prefix: 0xfe,  code: 0x29,  text: "i64.atomic.rmw8.sub_u", };
+pub const I64_ATOMIC_RMW16_SUB_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 2,  prefix: 0xfe,  code: 0x2a,  text: "i64.atomic.rmw16.sub_u", };
+pub const I64_ATOMIC_RMW32_SUB_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 4,  prefix: 0xfe,  code: 0x2b,  text: "i64.atomic.rmw32.sub_u", };
+pub const I32_ATOMIC_RMW_AND_OP   : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 4,  prefix: 0xfe,  code: 0x2c,  text: "i32.atomic.rmw.and", };
+pub const I64_ATOMIC_RMW_AND_OP   : Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 8,  prefix: 0xfe,  code: 0x2d,  text: "i64.atomic.rmw.and", };
+pub const I32_ATOMIC_RMW8_AND_U_OP: Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 1,  prefix: 0xfe,  code: 0x2e,  text: "i32.atomic.rmw8.and_u", };
+pub const I32_ATOMIC_RMW16_AND_U_OP: Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 2,  prefix: 0xfe,  code: 0x2f,  text: "i32.atomic.rmw16.and_u", };
+pub const I64_ATOMIC_RMW8_AND_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 1,  prefix: 0xfe,  code: 0x30,  text: "i64.atomic.rmw8.and_u", };
+pub const I64_ATOMIC_RMW16_AND_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 2,  prefix: 0xfe,  code: 0x31,  text: "i64.atomic.rmw16.and_u", };
+pub const I64_ATOMIC_RMW32_AND_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 4,  prefix: 0xfe,  code: 0x32,  text: "i64.atomic.rmw32.and_u", };
+pub const I32_ATOMIC_RMW_OR_OP    : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 4,  prefix: 0xfe,  code: 0x33,  text: "i32.atomic.rmw.or", };
+pub const I64_ATOMIC_RMW_OR_OP    : Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 8,  prefix: 0xfe,  code: 0x34,  text: "i64.atomic.rmw.or", };
+pub const I32_ATOMIC_RMW8_OR_U_OP : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 1,  prefix: 0xfe,  code: 0x35,  text: "i32.atomic.rmw8.or_u", };
+pub const I32_ATOMIC_RMW16_OR_U_OP: Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 2,  prefix: 0xfe,  code: 0x36,  text: "i32.atomic.rmw16.or_u", };
+pub const I64_ATOMIC_RMW8_OR_U_OP : Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 1,  prefix: 0xfe,  code: 0x37,  text: "i64.atomic.rmw8.or_u", };
+pub const I64_ATOMIC_RMW16_OR_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 2,  prefix: 0xfe,  code: 0x38,  text: "i64.atomic.rmw16.or_u", };
+pub const I64_ATOMIC_RMW32_OR_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 4,  prefix: 0xfe,  code: 0x39,  text: "i64.atomic.rmw32.or_u", };
+pub const I32_ATOMIC_RMW_XOR_OP   : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 4,  prefix: 0xfe,  code: 0x3a,  text: "i32.atomic.rmw.xor", };
+pub const I64_ATOMIC_RMW_XOR_OP   : Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 8,  prefix: 0xfe,  code: 0x3b,  text: "i64.atomic.rmw.xor", };
+pub const I32_ATOMIC_RMW8_XOR_U_OP: Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 1,  prefix: 0xfe,  code: 0x3c,  text: "i32.atomic.rmw8.xor_u", };
+pub const I32_ATOMIC_RMW16_XOR_U_OP: Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 2,  prefix: 0xfe,  code: 0x3d,  text: "i32.atomic.rmw16.xor_u", };
+pub const I64_ATOMIC_RMW8_XOR_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 1,  prefix: 0xfe,  code: 0x3e,  text: "i64.atomic.rmw8.xor_u", };
+pub const I64_ATOMIC_RMW16_XOR_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 2,  prefix: 0xfe,  code: 0x3f,  text: "i64.atomic.rmw16.xor_u", };
+pub const I64_ATOMIC_RMW32_XOR_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 4,  prefix: 0xfe,  code: 0x40,  text: "i64.atomic.rmw32.xor_u", };
+pub const I32_ATOMIC_RMW_XCHG_OP  : Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 4,  prefix: 0xfe,  code: 0x41,  text: "i32.atomic.rmw.xchg", };
+pub const I64_ATOMIC_RMW_XCHG_OP  : Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 8,  prefix: 0xfe,  code: 0x42,  text: "i64.atomic.rmw.xchg", };
+pub const I32_ATOMIC_RMW8_XCHG_U_OP: Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 1,  prefix: 0xfe,  code: 0x43,  text: "i32.atomic.rmw8.xchg_u", };
+pub const I32_ATOMIC_RMW16_XCHG_U_OP: Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 2,  prefix: 0xfe,  code: 0x44,  text: "i32.atomic.rmw16.xchg_u", };
+pub const I64_ATOMIC_RMW8_XCHG_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 1,  prefix: 0xfe,  code: 0x45,  text: "i64.atomic.rmw8.xchg_u", };
+pub const I64_ATOMIC_RMW16_XCHG_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 2,  prefix: 0xfe,  code: 0x46,  text: "i64.atomic.rmw16.xchg_u", };
+pub const I64_ATOMIC_RMW32_XCHG_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 4,  prefix: 0xfe,  code: 0x47,  text: "i64.atomic.rmw32.xchg_u", };
+pub const I32_ATOMIC_RMW_CMPXCHG_OP: Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 4,  prefix: 0xfe,  code: 0x48,  text: "i32.atomic.rmw.cmpxchg", };
+pub const I64_ATOMIC_RMW_CMPXCHG_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 8,  prefix: 0xfe,  code: 0x49,  text: "i64.atomic.rmw.cmpxchg", };
+pub const I32_ATOMIC_RMW8_CMPXCHG_U_OP: Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 1,  prefix: 0xfe,  code: 0x4a,  text: "i32.atomic.rmw8.cmpxchg_u", };
+pub const I32_ATOMIC_RMW16_CMPXCHG_U_OP: Op = Op { tr: I32,  t1: I32,  t2: I32,  m: 2,  prefix: 0xfe,  code: 0x4b,  text: "i32.atomic.rmw16.cmpxchg_u", };
+pub const I64_ATOMIC_RMW8_CMPXCHG_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 1,  prefix: 0xfe,  code: 0x4c,  text: "i64.atomic.rmw8.cmpxchg_u", };
+pub const I64_ATOMIC_RMW16_CMPXCHG_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 2,  prefix: 0xfe,  code: 0x4d,  text: "i64.atomic.rmw16.cmpxchg_u", };
+pub const I64_ATOMIC_RMW32_CMPXCHG_U_OP: Op = Op { tr: I64,  t1: I32,  t2: I64,  m: 4,  prefix: 0xfe,  code: 0x4e,  text: "i64.atomic.rmw32.cmpxchg_u", };
+
+pub const V128_LOAD_OP            : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 16,  prefix: 0xfd,  code: 0x00,  text: "v128.load", };
+pub const V128_LOAD8X8_S_OP       : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 8,  prefix: 0xfd,  code: 0x01,  text: "v128.load8x8_s", };
+pub const V128_LOAD8X8_U_OP       : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 8,  prefix: 0xfd,  code: 0x02,  text: "v128.load8x8_u", };
+pub const V128_LOAD16X4_S_OP      : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 8,  prefix: 0xfd,  code: 0x03,  text: "v128.load16x4_s", };
+pub const V128_LOAD16X4_U_OP      : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 8,  prefix: 0xfd,  code: 0x04,  text: "v128.load16x4_u", };
+pub const V128_LOAD32X2_S_OP      : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 8,  prefix: 0xfd,  code: 0x05,  text: "v128.load32x2_s", };
+pub const V128_LOAD32X2_U_OP      : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 8,  prefix: 0xfd,  code: 0x06,  text: "v128.load32x2_u", };
+pub const V128_LOAD8_SPLAT_OP     : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 1,  prefix: 0xfd,  code: 0x07,  text: "v128.load8_splat", };
+pub const V128_LOAD16_SPLAT_OP    : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 2,  prefix: 0xfd,  code: 0x08,  text: "v128.load16_splat", };
+pub const V128_LOAD32_SPLAT_OP    : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 4,  prefix: 0xfd,  code: 0x09,  text: "v128.load32_splat", };
+pub const V128_LOAD64_SPLAT_OP    : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 8,  prefix: 0xfd,  code: 0x0a,  text: "v128.load64_splat", };
+pub const V128_STORE_OP           : Op = Op { tr: ___,  t1: I32,  t2: V128,  m: 16,  prefix: 0xfd,  code: 0x0b,  text: "v128.store", };
+pub const V128_CONST_OP           : Op = Op { tr: V128,  t1: ___,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x0c,  text: "v128.const", };
+pub const I8X16_SHUFFLE_OP        : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x0d,  text: "i8x16.shuffle", };
+pub const I8X16_SWIZZLE_OP        : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x0e,  text: "i8x16.swizzle", };
+pub const I8X16_SPLAT_OP          : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x0f,  text: "i8x16.splat", };
+pub const I16X8_SPLAT_OP          : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x10,  text: "i16x8.splat", };
+pub const I32X4_SPLAT_OP          : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x11,  text: "i32x4.splat", };
+pub const I64X2_SPLAT_OP          : Op = Op { tr: V128,  t1: I64,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x12,  text: "i64x2.splat", };
+pub const F32X4_SPLAT_OP          : Op = Op { tr: V128,  t1: F32,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x13,  text: "f32x4.splat", };
+pub const F64X2_SPLAT_OP          : Op = Op { tr: V128,  t1: F64,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x14,  text: "f64x2.splat", };
+pub const I8X16_EXTRACT_LANE_S_OP : Op = Op { tr: I32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x15,  text: "i8x16.extract_lane_s", };
+pub const I8X16_EXTRACT_LANE_U_OP : Op = Op { tr: I32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x16,  text: "i8x16.extract_lane_u", };
+pub const I8X16_REPLACE_LANE_OP   : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0x17,  text: "i8x16.replace_lane", };
+pub const I16X8_EXTRACT_LANE_S_OP : Op = Op { tr: I32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x18,  text: "i16x8.extract_lane_s", };
+pub const I16X8_EXTRACT_LANE_U_OP : Op = Op { tr: I32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x19,  text: "i16x8.extract_lane_u", };
+pub const I16X8_REPLACE_LANE_OP   : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0x1a,  text: "i16x8.replace_lane", };
+pub const I32X4_EXTRACT_LANE_OP   : Op = Op { tr: I32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x1b,  text: "i32x4.extract_lane", };
+pub const I32X4_REPLACE_LANE_OP   : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0x1c,  text: "i32x4.replace_lane", };
+pub const I64X2_EXTRACT_LANE_OP   : Op = Op { tr: I64,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x1d,  text: "i64x2.extract_lane", };
+pub const I64X2_REPLACE_LANE_OP   : Op = Op { tr: V128,  t1: V128,  t2: I64,  m: 0,  prefix: 0xfd,  code: 0x1e,  text: "i64x2.replace_lane", };
+pub const F32X4_EXTRACT_LANE_OP   : Op = Op { tr: F32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x1f,  text: "f32x4.extract_lane", };
+pub const F32X4_REPLACE_LANE_OP   : Op = Op { tr: V128,  t1: V128,  t2: F32,  m: 0,  prefix: 0xfd,  code: 0x20,  text: "f32x4.replace_lane", };
+pub const F64X2_EXTRACT_LANE_OP   : Op = Op { tr: F64,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x21,  text: "f64x2.extract_lane", };
+pub const F64X2_REPLACE_LANE_OP   : Op = Op { tr: V128,  t1: V128,  t2: F64,  m: 0,  prefix: 0xfd,  code: 0x22,  text: "f64x2.replace_lane", };
+pub const I8X16_EQ_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x23,  text: "i8x16.eq", };
+pub const I8X16_NE_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x24,  text: "i8x16.ne", };
+pub const I8X16_LT_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x25,  text: "i8x16.lt_s", };
+pub const I8X16_LT_U_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x26,  text: "i8x16.lt_u", };
+pub const I8X16_GT_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x27,  text: "i8x16.gt_s", };
+pub const I8X16_GT_U_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x28,  text: "i8x16.gt_u", };
+pub const I8X16_LE_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x29,  text: "i8x16.le_s", };
+pub const I8X16_LE_U_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x2a,  text: "i8x16.le_u", };
+pub const I8X16_GE_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x2b,  text: "i8x16.ge_s", };
+pub const I8X16_GE_U_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x2c,  text: "i8x16.ge_u", };
+pub const I16X8_EQ_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x2d,  text: "i16x8.eq", };
+pub const I16X8_NE_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x2e,  text: "i16x8.ne", };
+pub const I16X8_LT_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x2f,  text: "i16x8.lt_s", };
+pub const I16X8_LT_U_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x30,  text: "i16x8.lt_u", };
+pub const I16X8_GT_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x31,  text: "i16x8.gt_s", };
+pub const I16X8_GT_U_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x32,  text: "i16x8.gt_u", };
+pub const I16X8_LE_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x33,  text: "i16x8.le_s", };
+pub const I16X8_LE_U_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x34,  text: "i16x8.le_u", };
+pub const I16X8_GE_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x35,  text: "i16x8.ge_s", };
+pub const I16X8_GE_U_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x36,  text: "i16x8.ge_u", };
+pub const I32X4_EQ_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x37,  text: "i32x4.eq", };
+pub const I32X4_NE_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x38,  text: "i32x4.ne", };
+pub const I32X4_LT_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x39,  text: "i32x4.lt_s", };
+pub const I32X4_LT_U_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x3a,  text: "i32x4.lt_u", };
+pub const I32X4_GT_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x3b,  text: "i32x4.gt_s", };
+pub const I32X4_GT_U_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x3c,  text: "i32x4.gt_u", };
+pub const I32X4_LE_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x3d,  text: "i32x4.le_s", };
+pub const I32X4_LE_U_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x3e,  text: "i32x4.le_u", };
+pub const I32X4_GE_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x3f,  text: "i32x4.ge_s", };
+pub const I32X4_GE_U_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x40,  text: "i32x4.ge_u", };
+pub const F32X4_EQ_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x41,  text: "f32x4.eq", };
+pub const F32X4_NE_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x42,  text: "f32x4.ne", };
+pub const F32X4_LT_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x43,  text: "f32x4.lt", };
+pub const F32X4_GT_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x44,  text: "f32x4.gt", };
+pub const F32X4_LE_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x45,  text: "f32x4.le", };
+pub const F32X4_GE_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x46,  text: "f32x4.ge", };
+pub const F64X2_EQ_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x47,  text: "f64x2.eq", };
+pub const F64X2_NE_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x48,  text: "f64x2.ne", };
+pub const F64X2_LT_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x49,  text: "f64x2.lt", };
+pub const F64X2_GT_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x4a,  text: "f64x2.gt", };
+pub const F64X2_LE_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x4b,  text: "f64x2.le", };
+pub const F64X2_GE_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x4c,  text: "f64x2.ge", };
+pub const V128_NOT_OP             : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x4d,  text: "v128.not", };
+pub const V128_AND_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x4e,  text: "v128.and", };
+pub const V128_ANDNOT_OP          : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x4f,  text: "v128.andnot", };
+pub const V128_OR_OP              : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x50,  text: "v128.or", };
+pub const V128_XOR_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x51,  text: "v128.xor", };
+pub const V128_BITSELECT_OP       : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x52,  text: "v128.bitselect", };
+pub const V128_ANY_TRUE_OP        : Op = Op { tr: I32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x53,  text: "v128.any_true", };
+pub const V128_LOAD8_LANE_OP      : Op = Op { tr: V128,  t1: I32,  t2: V128,  m: 1,  prefix: 0xfd,  code: 0x54,  text: "v128.load8_lane", };
+pub const V128_LOAD16_LANE_OP     : Op = Op { tr: V128,  t1: I32,  t2: V128,  m: 2,  prefix: 0xfd,  code: 0x55,  text: "v128.load16_lane", };
+pub const V128_LOAD32_LANE_OP     : Op = Op { tr: V128,  t1: I32,  t2: V128,  m: 4,  prefix: 0xfd,  code: 0x56,  text: "v128.load32_lane", };
+pub const V128_LOAD64_LANE_OP     : Op = Op { tr: V128,  t1: I32,  t2: V128,  m: 8,  prefix: 0xfd,  code: 0x57,  text: "v128.load64_lane", };
+pub const V128_STORE8_LANE_OP     : Op = Op { tr: ___,  t1: I32,  t2: V128,  m: 1,  prefix: 0xfd,  code: 0x58,  text: "v128.store8_lane", };
+pub const V128_STORE16_LANE_OP    : Op = Op { tr: ___,  t1: I32,  t2: V128,  m: 2,  prefix: 0xfd,  code: 0x59,  text: "v128.store16_lane", };
+pub const V128_STORE32_LANE_OP    : Op = Op { tr: ___,  t1: I32,  t2: V128,  m: 4,  prefix: 0xfd,  code: 0x5a,  text: "v128.store32_lane", };
+pub const V128_STORE64_LANE_OP    : Op = Op { tr: ___,  t1: I32,  t2: V128,  m: 8,  prefix: 0xfd,  code: 0x5b,  text: "v128.store64_lane", };
+pub const V128_LOAD32_ZERO_OP     : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 4,  prefix: 0xfd,  code: 0x5c,  text: "v128.load32_zero", };
+pub const V128_LOAD64_ZERO_OP     : Op = Op { tr: V128,  t1: I32,  t2: ___,  m: 8,  prefix: 0xfd,  code: 0x5d,  text: "v128.load64_zero", };
+pub const F32X4_DEMOTE_F64X2_ZERO_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x5e,  text: "f32x4.demote_f64x2_zero", };
+pub const F64X2_PROMOTE_LOW_F32X4_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x5f,  text: "f64x2.promote_low_f32x4", };
+pub const I8X16_ABS_OP            : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x60,  text: "i8x16.abs", };
+pub const I8X16_NEG_OP            : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x61,  text: "i8x16.neg", };
+pub const I8X16_POPCNT_OP         : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x62,  text: "i8x16.popcnt", };
+pub const I8X16_ALL_TRUE_OP       : Op = Op { tr: I32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x63,  text: "i8x16.all_true", };
+pub const I8X16_BITMASK_OP        : Op = Op { tr: I32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x64,  text: "i8x16.bitmask", };
+pub const I8X16_NARROW_I16X8_S_OP : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x65,  text: "i8x16.narrow_i16x8_s", };
+pub const I8X16_NARROW_I16X8_U_OP : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x66,  text: "i8x16.narrow_i16x8_u", };
+pub const F32X4_CEIL_OP           : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x67,  text: "f32x4.ceil", };
+pub const F32X4_FLOOR_OP          : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x68,  text: "f32x4.floor", };
+pub const F32X4_TRUNC_OP          : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x69,  text: "f32x4.trunc", };
+pub const F32X4_NEAREST_OP        : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x6a,  text: "f32x4.nearest", };
+pub const I8X16_SHL_OP            : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0x6b,  text: "i8x16.shl", };
+pub const I8X16_SHR_S_OP          : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0x6c,  text: "i8x16.shr_s", };
+pub const I8X16_SHR_U_OP          : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0x6d,  text: "i8x16.shr_u", };
+pub const I8X16_ADD_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x6e,  text: "i8x16.add", };
+pub const I8X16_ADD_SAT_S_OP      : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x6f,  text: "i8x16.add_sat_s", };
+pub const I8X16_ADD_SAT_U_OP      : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x70,  text: "i8x16.add_sat_u", };
+pub const I8X16_SUB_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x71,  text: "i8x16.sub", };
+pub const I8X16_SUB_SAT_S_OP      : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x72,  text: "i8x16.sub_sat_s", };
+pub const I8X16_SUB_SAT_U_OP      : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x73,  text: "i8x16.sub_sat_u", };
+pub const F64X2_CEIL_OP           : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x74,  text: "f64x2.ceil", };
+pub const F64X2_FLOOR_OP          : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x75,  text: "f64x2.floor", };
+pub const I8X16_MIN_S_OP          : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x76,  text: "i8x16.min_s", };
+pub const I8X16_MIN_U_OP          : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x77,  text: "i8x16.min_u", };
+pub const I8X16_MAX_S_OP          : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x78,  text: "i8x16.max_s", };
+pub const I8X16_MAX_U_OP          : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x79,  text: "i8x16.max_u", };
+pub const F64X2_TRUNC_OP          : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x7a,  text: "f64x2.trunc", };
+pub const I8X16_AVGR_U_OP         : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x7b,  text: "i8x16.avgr_u", };
+pub const I16X8_EXTADD_PAIRWISE_I8X16_S_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x7c,  text: "i16x8.extadd_pairwise_i8x16_s", };
+pub const I16X8_EXTADD_PAIRWISE_I8X16_U_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x7d,  text: "i16x8.extadd_pairwise_i8x16_u", };
+pub const I32X4_EXTADD_PAIRWISE_I16X8_S_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x7e,  text: "i32x4.extadd_pairwise_i16x8_s", };
+pub const I32X4_EXTADD_PAIRWISE_I16X8_U_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x7f,  text: "i32x4.extadd_pairwise_i16x8_u", };
+pub const I16X8_ABS_OP            : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x80,  text: "i16x8.abs", };
+pub const I16X8_NEG_OP            : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x81,  text: "i16x8.neg", };
+pub const I16X8_Q15MULR_SAT_S_OP  : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x82,  text: "i16x8.q15mulr_sat_s", };
+pub const I16X8_ALL_TRUE_OP       : Op = Op { tr: I32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x83,  text: "i16x8.all_true", };
+pub const I16X8_BITMASK_OP        : Op = Op { tr: I32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x84,  text: "i16x8.bitmask", };
+pub const I16X8_NARROW_I32X4_S_OP : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x85,  text: "i16x8.narrow_i32x4_s", };
+pub const I16X8_NARROW_I32X4_U_OP : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x86,  text: "i16x8.narrow_i32x4_u", };
+pub const I16X8_EXTEND_LOW_I8X16_S_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x87,  text: "i16x8.extend_low_i8x16_s", };
+pub const I16X8_EXTEND_HIGH_I8X16_S_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x88,  text: "i16x8.extend_high_i8x16_s", };
+pub const I16X8_EXTEND_LOW_I8X16_U_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x89,  text: "i16x8.extend_low_i8x16_u", };
+pub const I16X8_EXTEND_HIGH_I8X16_U_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x8a,  text: "i16x8.extend_high_i8x16_u", };
+pub const I16X8_SHL_OP            : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0x8b,  text: "i16x8.shl", };
+pub const I16X8_SHR_S_OP          : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0x8c,  text: "i16x8.shr_s", };
+pub const I16X8_SHR_U_OP          : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0x8d,  text: "i16x8.shr_u", };
+pub const I16X8_ADD_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x8e,  text: "i16x8.add", };
+pub const I16X8_ADD_SAT_S_OP      : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x8f,  text: "i16x8.add_sat_s", };
+pub const I16X8_ADD_SAT_U_OP      : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x90,  text: "i16x8.add_sat_u", };
+pub const I16X8_SUB_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x91,  text: "i16x8.sub", };
+pub const I16X8_SUB_SAT_S_OP      : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x92,  text: "i16x8.sub_sat_s", };
+pub const I16X8_SUB_SAT_U_OP      : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x93,  text: "i16x8.sub_sat_u", };
+pub const F64X2_NEAREST_OP        : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0x94,  text: "f64x2.nearest", };
+pub const I16X8_MUL_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x95,  text: "i16x8.mul", };
+pub const I16X8_MIN_S_OP          : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x96,  text: "i16x8.min_s", };
+pub const I16X8_MIN_U_OP          : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x97,  text: "i16x8.min_u", };
+pub const I16X8_MAX_S_OP          : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x98,  text: "i16x8.max_s", };
+pub const I16X8_MAX_U_OP          : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x99,  text: "i16x8.max_u", };
+pub const I16X8_AVGR_U_OP         : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x9b,  text: "i16x8.avgr_u", };
+pub const I16X8_EXTMUL_LOW_I8X16_S_OP: Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x9c,  text: "i16x8.extmul_low_i8x16_s", };
+pub const I16X8_EXTMUL_HIGH_I8X16_S_OP: Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x9d,  text: "i16x8.extmul_high_i8x16_s", };
+pub const I16X8_EXTMUL_LOW_I8X16_U_OP: Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x9e,  text: "i16x8.extmul_low_i8x16_u", };
+pub const I16X8_EXTMUL_HIGH_I8X16_U_OP: Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0x9f,  text: "i16x8.extmul_high_i8x16_u", };
+pub const I32X4_ABS_OP            : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xa0,  text: "i32x4.abs", };
+pub const I32X4_NEG_OP            : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xa1,  text: "i32x4.neg", };
+pub const I32X4_ALL_TRUE_OP       : Op = Op { tr: I32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xa3,  text: "i32x4.all_true", };
+pub const I32X4_BITMASK_OP        : Op = Op { tr: I32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xa4,  text: "i32x4.bitmask", };
+pub const I32X4_EXTEND_LOW_I16X8_S_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xa7,  text: "i32x4.extend_low_i16x8_s", };
+pub const I32X4_EXTEND_HIGH_I16X8_S_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xa8,  text: "i32x4.extend_high_i16x8_s", };
+pub const I32X4_EXTEND_LOW_I16X8_U_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xa9,  text: "i32x4.extend_low_i16x8_u", };
+pub const I32X4_EXTEND_HIGH_I16X8_U_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xaa,  text: "i32x4.extend_high_i16x8_u", };
+pub const I32X4_SHL_OP            : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0xab,  text: "i32x4.shl", };
+pub const I32X4_SHR_S_OP          : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0xac,  text: "i32x4.shr_s", };
+pub const I32X4_SHR_U_OP          : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0xad,  text: "i32x4.shr_u", };
+pub const I32X4_ADD_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xae,  text: "i32x4.add", };
+pub const I32X4_SUB_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xb1,  text: "i32x4.sub", };
+pub const I32X4_MUL_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xb5,  text: "i32x4.mul", };
+pub const I32X4_MIN_S_OP          : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xb6,  text: "i32x4.min_s", };
+pub const I32X4_MIN_U_OP          : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xb7,  text: "i32x4.min_u", };
+pub const I32X4_MAX_S_OP          : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xb8,  text: "i32x4.max_s", };
+pub const I32X4_MAX_U_OP          : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xb9,  text: "i32x4.max_u", };
+pub const I32X4_DOT_I16X8_S_OP    : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xba,  text: "i32x4.dot_i16x8_s", };
+pub const I32X4_EXTMUL_LOW_I16X8_S_OP: Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xbc,  text: "i32x4.extmul_low_i16x8_s", };
+pub const I32X4_EXTMUL_HIGH_I16X8_S_OP: Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xbd,  text: "i32x4.extmul_high_i16x8_s", };
+pub const I32X4_EXTMUL_LOW_I16X8_U_OP: Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xbe,  text: "i32x4.extmul_low_i16x8_u", };
+pub const I32X4_EXTMUL_HIGH_I16X8_U_OP: Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xbf,  text: "i32x4.extmul_high_i16x8_u", };
+pub const I64X2_ABS_OP            : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xc0,  text: "i64x2.abs", };
+pub const I64X2_NEG_OP            : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xc1,  text: "i64x2.neg", };
+pub const I64X2_ALL_TRUE_OP       : Op = Op { tr: I32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xc3,  text: "i64x2.all_true", };
+pub const I64X2_BITMASK_OP        : Op = Op { tr: I32,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xc4,  text: "i64x2.bitmask", };
+pub const I64X2_EXTEND_LOW_I32X4_S_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xc7,  text: "i64x2.extend_low_i32x4_s", };
+pub const I64X2_EXTEND_HIGH_I32X4_S_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xc8,  text: "i64x2.extend_high_i32x4_s", };
+pub const I64X2_EXTEND_LOW_I32X4_U_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xc9,  text: "i64x2.extend_low_i32x4_u", };
+pub const I64X2_EXTEND_HIGH_I32X4_U_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xca,  text: "i64x2.extend_high_i32x4_u", };
+pub const I64X2_SHL_OP            : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0xcb,  text: "i64x2.shl", };
+pub const I64X2_SHR_S_OP          : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0xcc,  text: "i64x2.shr_s", };
+pub const I64X2_SHR_U_OP          : Op = Op { tr: V128,  t1: V128,  t2: I32,  m: 0,  prefix: 0xfd,  code: 0xcd,  text: "i64x2.shr_u", };
+pub const I64X2_ADD_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xce,  text: "i64x2.add", };
+pub const I64X2_SUB_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xd1,  text: "i64x2.sub", };
+pub const I64X2_MUL_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xd5,  text: "i64x2.mul", };
+pub const I64X2_EQ_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xd6,  text: "i64x2.eq", };
+pub const I64X2_NE_OP             : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xd7,  text: "i64x2.ne", };
+pub const I64X2_LT_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xd8,  text: "i64x2.lt_s", };
+pub const I64X2_GT_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xd9,  text: "i64x2.gt_s", };
+pub const I64X2_LE_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xda,  text: "i64x2.le_s", };
+pub const I64X2_GE_S_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xdb,  text: "i64x2.ge_s", };
+pub const I64X2_EXTMUL_LOW_I32X4_S_OP: Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xdc,  text: "i64x2.extmul_low_i32x4_s", };
+pub const I64X2_EXTMUL_HIGH_I32X4_S_OP: Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xdd,  text: "i64x2.extmul_high_i32x4_s", };
+pub const I64X2_EXTMUL_LOW_I32X4_U_OP: Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xde,  text: "i64x2.extmul_low_i32x4_u", };
+pub const I64X2_EXTMUL_HIGH_I32X4_U_OP: Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xdf,  text: "i64x2.extmul_high_i32x4_u", };
+pub const F32X4_ABS_OP            : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xe0,  text: "f32x4.abs", };
+pub const F32X4_NEG_OP            : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xe1,  text: "f32x4.neg", };
+pub const F32X4_SQRT_OP           : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xe3,  text: "f32x4.sqrt", };
+pub const F32X4_ADD_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xe4,  text: "f32x4.add", };
+pub const F32X4_SUB_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xe5,  text: "f32x4.sub", };
+pub const F32X4_MUL_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xe6,  text: "f32x4.mul", };
+pub const F32X4_DIV_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xe7,  text: "f32x4.div", };
+pub const F32X4_MIN_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xe8,  text: "f32x4.min", };
+pub const F32X4_MAX_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xe9,  text: "f32x4.max", };
+pub const F32X4_PMIN_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xea,  text: "f32x4.pmin", };
+pub const F32X4_PMAX_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xeb,  text: "f32x4.pmax", };
+pub const F64X2_ABS_OP            : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xec,  text: "f64x2.abs", };
+pub const F64X2_NEG_OP            : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xed,  text: "f64x2.neg", };
+pub const F64X2_SQRT_OP           : Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xef,  text: "f64x2.sqrt", };
+pub const F64X2_ADD_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xf0,  text: "f64x2.add", };
+pub const F64X2_SUB_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xf1,  text: "f64x2.sub", };
+pub const F64X2_MUL_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xf2,  text: "f64x2.mul", };
+pub const F64X2_DIV_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xf3,  text: "f64x2.div", };
+pub const F64X2_MIN_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xf4,  text: "f64x2.min", };
+pub const F64X2_MAX_OP            : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xf5,  text: "f64x2.max", };
+pub const F64X2_PMIN_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xf6,  text: "f64x2.pmin", };
+pub const F64X2_PMAX_OP           : Op = Op { tr: V128,  t1: V128,  t2: V128,  m: 0,  prefix: 0xfd,  code: 0xf7,  text: "f64x2.pmax", };
+pub const I32X4_TRUNC_SAT_F32X4_S_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xf8,  text: "i32x4.trunc_sat_f32x4_s", };
+pub const I32X4_TRUNC_SAT_F32X4_U_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xf9,  text: "i32x4.trunc_sat_f32x4_u", };
+pub const F32X4_CONVERT_I32X4_S_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xfa,  text: "f32x4.convert_i32x4_s", };
+pub const F32X4_CONVERT_I32X4_U_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xfb,  text: "f32x4.convert_i32x4_u", };
+pub const I32X4_TRUNC_SAT_F64X2_S_ZERO_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xfc,  text: "i32x4.trunc_sat_f64x2_s_zero", };
+pub const I32X4_TRUNC_SAT_F64X2_U_ZERO_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xfd,  text: "i32x4.trunc_sat_f64x2_u_zero", };
+pub const F64X2_CONVERT_LOW_I32X4_S_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xfe,  text: "f64x2.convert_low_i32x4_s", };
+pub const F64X2_CONVERT_LOW_I32X4_U_OP: Op = Op { tr: V128,  t1: V128,  t2: ___,  m: 0,  prefix: 0xfd,  code: 0xff,  text: "f64x2.convert_low_i32x4_u", };
+
+impl Op {
+    pub fn from_opcode(opc: u8) -> Option<Op> {
+        Some(match opc {
+            UNREACHABLE          => UNREACHABLE_OP,
+            NOP                  => NOP_OP,
+            BLOCK                => BLOCK_OP,
+            LOOP                 => LOOP_OP,
+            IF                   => IF_OP,
+            ELSE                 => ELSE_OP,
+            TRY                  => TRY_OP,
+            CATCH                => CATCH_OP,
+            THROW                => THROW_OP,
+            RETHROW              => RETHROW_OP,
+            CATCH_ALL            => CATCH_ALL_OP,
+            END                  => END_OP,
+            BR                   => BR_OP,
+            BR_IF                => BR_IF_OP,
+            BR_TABLE             => BR_TABLE_OP,
+            RETURN               => RETURN_OP,
+            CALL                 => CALL_OP,
+            CALL_INDIRECT        => CALL_INDIRECT_OP,
+            RETURN_CALL          => RETURN_CALL_OP,
+            RETURN_CALL_INDIRECT => RETURN_CALL_INDIRECT_OP,
+            DROP                 => DROP_OP,
+            SELECT               => SELECT_OP,
+            SELECT_T             => SELECT_T_OP,
+            GET_LOCAL            => GET_LOCAL_OP,
+            SET_LOCAL            => SET_LOCAL_OP,
+            TEE_LOCAL            => TEE_LOCAL_OP,
+            GET_GLOBAL           => GET_GLOBAL_OP,
+            SET_GLOBAL           => SET_GLOBAL_OP,
+            TABLE_GET            => TABLE_GET_OP,
+            TABLE_SET            => TABLE_SET_OP,
+            I32_LOAD             => I32_LOAD_OP,
+            I64_LOAD             => I64_LOAD_OP,
+            F32_LOAD             => F32_LOAD_OP,
+            F64_LOAD             => F64_LOAD_OP,
+            I32_LOAD8_S          => I32_LOAD8_S_OP,
+            I32_LOAD8_U          => I32_LOAD8_U_OP,
+            I32_LOAD16_S         => I32_LOAD16_S_OP,
+            I32_LOAD16_U         => I32_LOAD16_U_OP,
+            I64_LOAD8_S          => I64_LOAD8_S_OP,
+            I64_LOAD8_U          => I64_LOAD8_U_OP,
+            I64_LOAD16_S         => I64_LOAD16_S_OP,
+            I64_LOAD16_U         => I64_LOAD16_U_OP,
+            I64_LOAD32_S         => I64_LOAD32_S_OP,
+            I64_LOAD32_U         => I64_LOAD32_U_OP,
+            I32_STORE            => I32_STORE_OP,
+            I64_STORE            => I64_STORE_OP,
+            F32_STORE            => F32_STORE_OP,
+            F64_STORE            => F64_STORE_OP,
+            I32_STORE8           => I32_STORE8_OP,
+            I32_STORE16          => I32_STORE16_OP,
+            I64_STORE8           => I64_STORE8_OP,
+            I64_STORE16          => I64_STORE16_OP,
+            I64_STORE32          => I64_STORE32_OP,
+            MEM_SIZE             => MEM_SIZE_OP,
+            MEM_GROW             => MEM_GROW_OP,
+            I32_CONST            => I32_CONST_OP,
+            I64_CONST            => I64_CONST_OP,
+            F32_CONST            => F32_CONST_OP,
+            F64_CONST            => F64_CONST_OP,
+            I32_EQZ              => I32_EQZ_OP,
+            I32_EQ               => I32_EQ_OP,
+            I32_NE               => I32_NE_OP,
+            I32_LT_S             => I32_LT_S_OP,
+            I32_LT_U             => I32_LT_U_OP,
+            I32_GT_S             => I32_GT_S_OP,
+            I32_GT_U             => I32_GT_U_OP,
+            I32_LE_S             => I32_LE_S_OP,
+            I32_LE_U             => I32_LE_U_OP,
+            I32_GE_S             => I32_GE_S_OP,
+            I32_GE_U             => I32_GE_U_OP,
+            I64_EQZ              => I64_EQZ_OP,
+            I64_EQ               => I64_EQ_OP,
+            I64_NE               => I64_NE_OP,
+            I64_LT_S             => I64_LT_S_OP,
+            I64_LT_U             => I64_LT_U_OP,
+            I64_GT_S             => I64_GT_S_OP,
+            I64_GT_U             => I64_GT_U_OP,
+            I64_LE_S             => I64_LE_S_OP,
+            I64_LE_U             => I64_LE_U_OP,
+            I64_GE_S             => I64_GE_S_OP,
+            I64_GE_U             => I64_GE_U_OP,
+            F32_EQ               => F32_EQ_OP,
+            F32_NE               => F32_NE_OP,
+            F32_LT               => F32_LT_OP,
+            F32_GT               => F32_GT_OP,
+            F32_LE               => F32_LE_OP,
+            F32_GE               => F32_GE_OP,
+            F64_EQ               => F64_EQ_OP,
+            F64_NE               => F64_NE_OP,
+            F64_LT               => F64_LT_OP,
+            F64_GT               => F64_GT_OP,
+            F64_LE               => F64_LE_OP,
+            F64_GE               => F64_GE_OP,
+            I32_CLZ              => I32_CLZ_OP,
+            I32_CTZ              => I32_CTZ_OP,
+            I32_POPCNT           => I32_POPCNT_OP,
+            I32_ADD              => I32_ADD_OP,
+            I32_SUB              => I32_SUB_OP,
+            I32_MUL              => I32_MUL_OP,
+            I32_DIV_S            => I32_DIV_S_OP,
+            I32_DIV_U            => I32_DIV_U_OP,
+            I32_REM_S            => I32_REM_S_OP,
+            I32_REM_U            => I32_REM_U_OP,
+            I32_AND              => I32_AND_OP,
+            I32_OR               => I32_OR_OP,
+            I32_XOR              => I32_XOR_OP,
+            I32_SHL              => I32_SHL_OP,
+            I32_SHR_S            => I32_SHR_S_OP,
+            I32_SHR_U            => I32_SHR_U_OP,
+            I32_ROTL             => I32_ROTL_OP,
+            I32_ROTR             => I32_ROTR_OP,
+            I64_CLZ              => I64_CLZ_OP,
+            I64_CTZ              => I64_CTZ_OP,
+            I64_POPCNT           => I64_POPCNT_OP,
+            I64_ADD              => I64_ADD_OP,
+            I64_SUB              => I64_SUB_OP,
+            I64_MUL              => I64_MUL_OP,
+            I64_DIV_S            => I64_DIV_S_OP,
+            I64_DIV_U            => I64_DIV_U_OP,
+            I64_REM_S            => I64_REM_S_OP,
+            I64_REM_U            => I64_REM_U_OP,
+            I64_AND              => I64_AND_OP,
+            I64_OR               => I64_OR_OP,
+            I64_XOR              => I64_XOR_OP,
+            I64_SHL              => I64_SHL_OP,
+            I64_SHR_S            => I64_SHR_S_OP,
+            I64_SHR_U            => I64_SHR_U_OP,
+            I64_ROTL             => I64_ROTL_OP,
+            I64_ROTR             => I64_ROTR_OP,
+            F32_ABS              => F32_ABS_OP,
+            F32_NEG              => F32_NEG_OP,
+            F32_CEIL             => F32_CEIL_OP,
+            F32_FLOOR            => F32_FLOOR_OP,
+            F32_TRUNC            => F32_TRUNC_OP,
+            F32_NEAREST          => F32_NEAREST_OP,
+            F32_SQRT             => F32_SQRT_OP,
+            F32_ADD              => F32_ADD_OP,
+            F32_SUB              => F32_SUB_OP,
+            F32_MUL              => F32_MUL_OP,
+            F32_DIV              => F32_DIV_OP,
+            F32_MIN              => F32_MIN_OP,
+            F32_MAX              => F32_MAX_OP,
+            F32_COPYSIGN         => F32_COPYSIGN_OP,
+            F64_ABS              => F64_ABS_OP,
+            F64_NEG              => F64_NEG_OP,
+            F64_CEIL             => F64_CEIL_OP,
+            F64_FLOOR            => F64_FLOOR_OP,
+            F64_TRUNC            => F64_TRUNC_OP,
+            F64_NEAREST          => F64_NEAREST_OP,
+            F64_SQRT             => F64_SQRT_OP,
+            F64_ADD              => F64_ADD_OP,
+            F64_SUB              => F64_SUB_OP,
+            F64_MUL              => F64_MUL_OP,
+            F64_DIV              => F64_DIV_OP,
+            F64_MIN              => F64_MIN_OP,
+            F64_MAX              => F64_MAX_OP,
+            F64_COPYSIGN         => F64_COPYSIGN_OP,
+            I32_WRAP_I64         => I32_WRAP_I64_OP,
+            I32_TRUNC_S_F32      => I32_TRUNC_S_F32_OP,
+            I32_TRUNC_U_F32      => I32_TRUNC_U_F32_OP,
+            I32_TRUNC_S_F64      => I32_TRUNC_S_F64_OP,
+            I32_TRUNC_U_F64      => I32_TRUNC_U_F64_OP,
+            I64_EXTEND_S_I32     => I64_EXTEND_S_I32_OP,
+            I64_EXTEND_U_I32     => I64_EXTEND_U_I32_OP,
+            I64_TRUNC_S_F32      => I64_TRUNC_S_F32_OP,
+            I64_TRUNC_U_F32      => I64_TRUNC_U_F32_OP,
+            I64_TRUNC_S_F64      => I64_TRUNC_S_F64_OP,
+            I64_TRUNC_U_F64      => I64_TRUNC_U_F64_OP,
+            F32_CONVERT_S_I32    => F32_CONVERT_S_I32_OP,
+            F32_CONVERT_U_I32    => F32_CONVERT_U_I32_OP,
+            F32_CONVERT_S_I64    => F32_CONVERT_S_I64_OP,
+            F32_CONVERT_U_I64    => F32_CONVERT_U_I64_OP,
+            F32_DEMOTE_F64       => F32_DEMOTE_F64_OP,
+            F64_CONVERT_S_I32    => F64_CONVERT_S_I32_OP,
+            F64_CONVERT_U_I32    => F64_CONVERT_U_I32_OP,
+            F64_CONVERT_S_I64    => F64_CONVERT_S_I64_OP,
+            F64_CONVERT_U_I64    => F64_CONVERT_U_I64_OP,
+            F64_PROMOTE_F32      => F64_PROMOTE_F32_OP,
+            I32_REINTERPRET_F32  => I32_REINTERPRET_F32_OP,
+            I64_REINTERPRET_F64  => I64_REINTERPRET_F64_OP,
+            F32_REINTERPRET_I32  => F32_REINTERPRET_I32_OP,
+            F64_REINTERPRET_I64  => F64_REINTERPRET_I64_OP,
+            I32_EXTEND8_S        => I32_EXTEND8_S_OP,
+            I32_EXTEND16_S       => I32_EXTEND16_S_OP,
+            I64_EXTEND8_S        => I64_EXTEND8_S_OP,
+            I64_EXTEND16_S       => I64_EXTEND16_S_OP,
+            I64_EXTEND32_S       => I64_EXTEND32_S_OP,
+            REF_NULL             => REF_NULL_OP,
+            REF_IS_NULL          => REF_IS_NULL_OP,
+            REF_FUNC             => REF_FUNC_OP,
+            ALLOCA               => ALLOCA_OP,
+            BR_UNLESS            => BR_UNLESS_OP,
+            CALL_HOST            => CALL_HOST_OP,
+            DATA                 => DATA_OP,
+            DROP_KEEP            => DROP_KEEP_OP,
+            HANDLER_POP          => HANDLER_POP_OP,
+            BR_S                 => BR_S_OP,
+            BR_IF_S              => BR_IF_S_OP,
+            BR_UNLESS_S          => BR_UNLESS_S_OP,
+            BR_TABLE_S           => BR_TABLE_S_OP,
+            DROP_KEEP_S          => DROP_KEEP_S_OP,
+            GET_LOCAL_S          => GET_LOCAL_S_OP,
+            SET_LOCAL_S          => SET_LOCAL_S_OP,
+            TEE_LOCAL_S          => TEE_LOCAL_S_OP,
+            I32_CONST_S          => I32_CONST_S_OP,
+            ALLOCA_S             => ALLOCA_S_OP,
+            _                    => return None,
+        })
+    }
+
+    /// Looks up an instruction in the MISC_PREFIX (0xfc) opcode space.
+    pub fn from_misc_opcode(opc: u8) -> Option<Op> {
+        Some(match opc {
+            I32_TRUNC_SAT_F32_S  => I32_TRUNC_SAT_F32_S_OP,
+            I32_TRUNC_SAT_F32_U  => I32_TRUNC_SAT_F32_U_OP,
+            I32_TRUNC_SAT_F64_S  => I32_TRUNC_SAT_F64_S_OP,
+            I32_TRUNC_SAT_F64_U  => I32_TRUNC_SAT_F64_U_OP,
+            I64_TRUNC_SAT_F32_S  => I64_TRUNC_SAT_F32_S_OP,
+            I64_TRUNC_SAT_F32_U  => I64_TRUNC_SAT_F32_U_OP,
+            I64_TRUNC_SAT_F64_S  => I64_TRUNC_SAT_F64_S_OP,
+            I64_TRUNC_SAT_F64_U  => I64_TRUNC_SAT_F64_U_OP,
+            MEMORY_INIT          => MEMORY_INIT_OP,
+            DATA_DROP            => DATA_DROP_OP,
+            MEMORY_COPY          => MEMORY_COPY_OP,
+            MEMORY_FILL          => MEMORY_FILL_OP,
+            TABLE_INIT           => TABLE_INIT_OP,
+            ELEM_DROP            => ELEM_DROP_OP,
+            TABLE_COPY           => TABLE_COPY_OP,
+            TABLE_GROW           => TABLE_GROW_OP,
+            TABLE_SIZE           => TABLE_SIZE_OP,
+            TABLE_FILL           => TABLE_FILL_OP,
+            _                    => return None,
+        })
+    }
+
+    /// Looks up an instruction in the SIMD_PREFIX (0xfd) opcode space.
+    pub fn from_simd_opcode(opc: u8) -> Option<Op> {
+        Some(match opc {
+            V128_LOAD                     => V128_LOAD_OP,
+            V128_LOAD8X8_S                => V128_LOAD8X8_S_OP,
+            V128_LOAD8X8_U                => V128_LOAD8X8_U_OP,
+            V128_LOAD16X4_S               => V128_LOAD16X4_S_OP,
+            V128_LOAD16X4_U               => V128_LOAD16X4_U_OP,
+            V128_LOAD32X2_S               => V128_LOAD32X2_S_OP,
+            V128_LOAD32X2_U               => V128_LOAD32X2_U_OP,
+            V128_LOAD8_SPLAT              => V128_LOAD8_SPLAT_OP,
+            V128_LOAD16_SPLAT             => V128_LOAD16_SPLAT_OP,
+            V128_LOAD32_SPLAT             => V128_LOAD32_SPLAT_OP,
+            V128_LOAD64_SPLAT             => V128_LOAD64_SPLAT_OP,
+            V128_STORE                    => V128_STORE_OP,
+            V128_CONST                    => V128_CONST_OP,
+            I8X16_SHUFFLE                 => I8X16_SHUFFLE_OP,
+            I8X16_SWIZZLE                 => I8X16_SWIZZLE_OP,
+            I8X16_SPLAT                   => I8X16_SPLAT_OP,
+            I16X8_SPLAT                   => I16X8_SPLAT_OP,
+            I32X4_SPLAT                   => I32X4_SPLAT_OP,
+            I64X2_SPLAT                   => I64X2_SPLAT_OP,
+            F32X4_SPLAT                   => F32X4_SPLAT_OP,
+            F64X2_SPLAT                   => F64X2_SPLAT_OP,
+            I8X16_EXTRACT_LANE_S          => I8X16_EXTRACT_LANE_S_OP,
+            I8X16_EXTRACT_LANE_U          => I8X16_EXTRACT_LANE_U_OP,
+            I8X16_REPLACE_LANE            => I8X16_REPLACE_LANE_OP,
+            I16X8_EXTRACT_LANE_S          => I16X8_EXTRACT_LANE_S_OP,
+            I16X8_EXTRACT_LANE_U          => I16X8_EXTRACT_LANE_U_OP,
+            I16X8_REPLACE_LANE            => I16X8_REPLACE_LANE_OP,
+            I32X4_EXTRACT_LANE            => I32X4_EXTRACT_LANE_OP,
+            I32X4_REPLACE_LANE            => I32X4_REPLACE_LANE_OP,
+            I64X2_EXTRACT_LANE            => I64X2_EXTRACT_LANE_OP,
+            I64X2_REPLACE_LANE            => I64X2_REPLACE_LANE_OP,
+            F32X4_EXTRACT_LANE            => F32X4_EXTRACT_LANE_OP,
+            F32X4_REPLACE_LANE            => F32X4_REPLACE_LANE_OP,
+            F64X2_EXTRACT_LANE            => F64X2_EXTRACT_LANE_OP,
+            F64X2_REPLACE_LANE            => F64X2_REPLACE_LANE_OP,
+            I8X16_EQ                      => I8X16_EQ_OP,
+            I8X16_NE                      => I8X16_NE_OP,
+            I8X16_LT_S                    => I8X16_LT_S_OP,
+            I8X16_LT_U                    => I8X16_LT_U_OP,
+            I8X16_GT_S                    => I8X16_GT_S_OP,
+            I8X16_GT_U                    => I8X16_GT_U_OP,
+            I8X16_LE_S                    => I8X16_LE_S_OP,
+            I8X16_LE_U                    => I8X16_LE_U_OP,
+            I8X16_GE_S                    => I8X16_GE_S_OP,
+            I8X16_GE_U                    => I8X16_GE_U_OP,
+            I16X8_EQ                      => I16X8_EQ_OP,
+            I16X8_NE                      => I16X8_NE_OP,
+            I16X8_LT_S                    => I16X8_LT_S_OP,
+            I16X8_LT_U                    => I16X8_LT_U_OP,
+            I16X8_GT_S                    => I16X8_GT_S_OP,
+            I16X8_GT_U                    => I16X8_GT_U_OP,
+            I16X8_LE_S                    => I16X8_LE_S_OP,
+            I16X8_LE_U                    => I16X8_LE_U_OP,
+            I16X8_GE_S                    => I16X8_GE_S_OP,
+            I16X8_GE_U                    => I16X8_GE_U_OP,
+            I32X4_EQ                      => I32X4_EQ_OP,
+            I32X4_NE                      => I32X4_NE_OP,
+            I32X4_LT_S                    => I32X4_LT_S_OP,
+            I32X4_LT_U                    => I32X4_LT_U_OP,
+            I32X4_GT_S                    => I32X4_GT_S_OP,
+            I32X4_GT_U                    => I32X4_GT_U_OP,
+            I32X4_LE_S                    => I32X4_LE_S_OP,
+            I32X4_LE_U                    => I32X4_LE_U_OP,
+            I32X4_GE_S                    => I32X4_GE_S_OP,
+            I32X4_GE_U                    => I32X4_GE_U_OP,
+            F32X4_EQ                      => F32X4_EQ_OP,
+            F32X4_NE                      => F32X4_NE_OP,
+            F32X4_LT                      => F32X4_LT_OP,
+            F32X4_GT                      => F32X4_GT_OP,
+            F32X4_LE                      => F32X4_LE_OP,
+            F32X4_GE                      => F32X4_GE_OP,
+            F64X2_EQ                      => F64X2_EQ_OP,
+            F64X2_NE                      => F64X2_NE_OP,
+            F64X2_LT                      => F64X2_LT_OP,
+            F64X2_GT                      => F64X2_GT_OP,
+            F64X2_LE                      => F64X2_LE_OP,
+            F64X2_GE                      => F64X2_GE_OP,
+            V128_NOT                      => V128_NOT_OP,
+            V128_AND                      => V128_AND_OP,
+            V128_ANDNOT                   => V128_ANDNOT_OP,
+            V128_OR                       => V128_OR_OP,
+            V128_XOR                      => V128_XOR_OP,
+            V128_BITSELECT                => V128_BITSELECT_OP,
+            V128_ANY_TRUE                 => V128_ANY_TRUE_OP,
+            V128_LOAD8_LANE               => V128_LOAD8_LANE_OP,
+            V128_LOAD16_LANE              => V128_LOAD16_LANE_OP,
+            V128_LOAD32_LANE              => V128_LOAD32_LANE_OP,
+            V128_LOAD64_LANE              => V128_LOAD64_LANE_OP,
+            V128_STORE8_LANE              => V128_STORE8_LANE_OP,
+            V128_STORE16_LANE             => V128_STORE16_LANE_OP,
+            V128_STORE32_LANE             => V128_STORE32_LANE_OP,
+            V128_STORE64_LANE             => V128_STORE64_LANE_OP,
+            V128_LOAD32_ZERO              => V128_LOAD32_ZERO_OP,
+            V128_LOAD64_ZERO              => V128_LOAD64_ZERO_OP,
+            F32X4_DEMOTE_F64X2_ZERO       => F32X4_DEMOTE_F64X2_ZERO_OP,
+            F64X2_PROMOTE_LOW_F32X4       => F64X2_PROMOTE_LOW_F32X4_OP,
+            I8X16_ABS                     => I8X16_ABS_OP,
+            I8X16_NEG                     => I8X16_NEG_OP,
+            I8X16_POPCNT                  => I8X16_POPCNT_OP,
+            I8X16_ALL_TRUE                => I8X16_ALL_TRUE_OP,
+            I8X16_BITMASK                 => I8X16_BITMASK_OP,
+            I8X16_NARROW_I16X8_S          => I8X16_NARROW_I16X8_S_OP,
+            I8X16_NARROW_I16X8_U          => I8X16_NARROW_I16X8_U_OP,
+            F32X4_CEIL                    => F32X4_CEIL_OP,
+            F32X4_FLOOR                   => F32X4_FLOOR_OP,
+            F32X4_TRUNC                   => F32X4_TRUNC_OP,
+            F32X4_NEAREST                 => F32X4_NEAREST_OP,
+            I8X16_SHL                     => I8X16_SHL_OP,
+            I8X16_SHR_S                   => I8X16_SHR_S_OP,
+            I8X16_SHR_U                   => I8X16_SHR_U_OP,
+            I8X16_ADD                     => I8X16_ADD_OP,
+            I8X16_ADD_SAT_S               => I8X16_ADD_SAT_S_OP,
+            I8X16_ADD_SAT_U               => I8X16_ADD_SAT_U_OP,
+            I8X16_SUB                     => I8X16_SUB_OP,
+            I8X16_SUB_SAT_S               => I8X16_SUB_SAT_S_OP,
+            I8X16_SUB_SAT_U               => I8X16_SUB_SAT_U_OP,
+            F64X2_CEIL                    => F64X2_CEIL_OP,
+            F64X2_FLOOR                   => F64X2_FLOOR_OP,
+            I8X16_MIN_S                   => I8X16_MIN_S_OP,
+            I8X16_MIN_U                   => I8X16_MIN_U_OP,
+            I8X16_MAX_S                   => I8X16_MAX_S_OP,
+            I8X16_MAX_U                   => I8X16_MAX_U_OP,
+            F64X2_TRUNC                   => F64X2_TRUNC_OP,
+            I8X16_AVGR_U                  => I8X16_AVGR_U_OP,
+            I16X8_EXTADD_PAIRWISE_I8X16_S => I16X8_EXTADD_PAIRWISE_I8X16_S_OP,
+            I16X8_EXTADD_PAIRWISE_I8X16_U => I16X8_EXTADD_PAIRWISE_I8X16_U_OP,
+            I32X4_EXTADD_PAIRWISE_I16X8_S => I32X4_EXTADD_PAIRWISE_I16X8_S_OP,
+            I32X4_EXTADD_PAIRWISE_I16X8_U => I32X4_EXTADD_PAIRWISE_I16X8_U_OP,
+            I16X8_ABS                     => I16X8_ABS_OP,
+            I16X8_NEG                     => I16X8_NEG_OP,
+            I16X8_Q15MULR_SAT_S           => I16X8_Q15MULR_SAT_S_OP,
+            I16X8_ALL_TRUE                => I16X8_ALL_TRUE_OP,
+            I16X8_BITMASK                 => I16X8_BITMASK_OP,
+            I16X8_NARROW_I32X4_S          => I16X8_NARROW_I32X4_S_OP,
+            I16X8_NARROW_I32X4_U          => I16X8_NARROW_I32X4_U_OP,
+            I16X8_EXTEND_LOW_I8X16_S      => I16X8_EXTEND_LOW_I8X16_S_OP,
+            I16X8_EXTEND_HIGH_I8X16_S     => I16X8_EXTEND_HIGH_I8X16_S_OP,
+            I16X8_EXTEND_LOW_I8X16_U      => I16X8_EXTEND_LOW_I8X16_U_OP,
+            I16X8_EXTEND_HIGH_I8X16_U     => I16X8_EXTEND_HIGH_I8X16_U_OP,
+            I16X8_SHL                     => I16X8_SHL_OP,
+            I16X8_SHR_S                   => I16X8_SHR_S_OP,
+            I16X8_SHR_U                   => I16X8_SHR_U_OP,
+            I16X8_ADD                     => I16X8_ADD_OP,
+            I16X8_ADD_SAT_S               => I16X8_ADD_SAT_S_OP,
+            I16X8_ADD_SAT_U               => I16X8_ADD_SAT_U_OP,
+            I16X8_SUB                     => I16X8_SUB_OP,
+            I16X8_SUB_SAT_S               => I16X8_SUB_SAT_S_OP,
+            I16X8_SUB_SAT_U               => I16X8_SUB_SAT_U_OP,
+            F64X2_NEAREST                 => F64X2_NEAREST_OP,
+            I16X8_MUL                     => I16X8_MUL_OP,
+            I16X8_MIN_S                   => I16X8_MIN_S_OP,
+            I16X8_MIN_U                   => I16X8_MIN_U_OP,
+            I16X8_MAX_S                   => I16X8_MAX_S_OP,
+            I16X8_MAX_U                   => I16X8_MAX_U_OP,
+            I16X8_AVGR_U                  => I16X8_AVGR_U_OP,
+            I16X8_EXTMUL_LOW_I8X16_S      => I16X8_EXTMUL_LOW_I8X16_S_OP,
+            I16X8_EXTMUL_HIGH_I8X16_S     => I16X8_EXTMUL_HIGH_I8X16_S_OP,
+            I16X8_EXTMUL_LOW_I8X16_U      => I16X8_EXTMUL_LOW_I8X16_U_OP,
+            I16X8_EXTMUL_HIGH_I8X16_U     => I16X8_EXTMUL_HIGH_I8X16_U_OP,
+            I32X4_ABS                     => I32X4_ABS_OP,
+            I32X4_NEG                     => I32X4_NEG_OP,
+            I32X4_ALL_TRUE                => I32X4_ALL_TRUE_OP,
+            I32X4_BITMASK                 => I32X4_BITMASK_OP,
+            I32X4_EXTEND_LOW_I16X8_S      => I32X4_EXTEND_LOW_I16X8_S_OP,
+            I32X4_EXTEND_HIGH_I16X8_S     => I32X4_EXTEND_HIGH_I16X8_S_OP,
+            I32X4_EXTEND_LOW_I16X8_U      => I32X4_EXTEND_LOW_I16X8_U_OP,
+            I32X4_EXTEND_HIGH_I16X8_U     => I32X4_EXTEND_HIGH_I16X8_U_OP,
+            I32X4_SHL                     => I32X4_SHL_OP,
+            I32X4_SHR_S                   => I32X4_SHR_S_OP,
+            I32X4_SHR_U                   => I32X4_SHR_U_OP,
+            I32X4_ADD                     => I32X4_ADD_OP,
+            I32X4_SUB                     => I32X4_SUB_OP,
+            I32X4_MUL                     => I32X4_MUL_OP,
+            I32X4_MIN_S                   => I32X4_MIN_S_OP,
+            I32X4_MIN_U                   => I32X4_MIN_U_OP,
+            I32X4_MAX_S                   => I32X4_MAX_S_OP,
+            I32X4_MAX_U                   => I32X4_MAX_U_OP,
+            I32X4_DOT_I16X8_S             => I32X4_DOT_I16X8_S_OP,
+            I32X4_EXTMUL_LOW_I16X8_S      => I32X4_EXTMUL_LOW_I16X8_S_OP,
+            I32X4_EXTMUL_HIGH_I16X8_S     => I32X4_EXTMUL_HIGH_I16X8_S_OP,
+            I32X4_EXTMUL_LOW_I16X8_U      => I32X4_EXTMUL_LOW_I16X8_U_OP,
+            I32X4_EXTMUL_HIGH_I16X8_U     => I32X4_EXTMUL_HIGH_I16X8_U_OP,
+            I64X2_ABS                     => I64X2_ABS_OP,
+            I64X2_NEG                     => I64X2_NEG_OP,
+            I64X2_ALL_TRUE                => I64X2_ALL_TRUE_OP,
+            I64X2_BITMASK                 => I64X2_BITMASK_OP,
+            I64X2_EXTEND_LOW_I32X4_S      => I64X2_EXTEND_LOW_I32X4_S_OP,
+            I64X2_EXTEND_HIGH_I32X4_S     => I64X2_EXTEND_HIGH_I32X4_S_OP,
+            I64X2_EXTEND_LOW_I32X4_U      => I64X2_EXTEND_LOW_I32X4_U_OP,
+            I64X2_EXTEND_HIGH_I32X4_U     => I64X2_EXTEND_HIGH_I32X4_U_OP,
+            I64X2_SHL                     => I64X2_SHL_OP,
+            I64X2_SHR_S                   => I64X2_SHR_S_OP,
+            I64X2_SHR_U                   => I64X2_SHR_U_OP,
+            I64X2_ADD                     => I64X2_ADD_OP,
+            I64X2_SUB                     => I64X2_SUB_OP,
+            I64X2_MUL                     => I64X2_MUL_OP,
+            I64X2_EQ                      => I64X2_EQ_OP,
+            I64X2_NE                      => I64X2_NE_OP,
+            I64X2_LT_S                    => I64X2_LT_S_OP,
+            I64X2_GT_S                    => I64X2_GT_S_OP,
+            I64X2_LE_S                    => I64X2_LE_S_OP,
+            I64X2_GE_S                    => I64X2_GE_S_OP,
+            I64X2_EXTMUL_LOW_I32X4_S      => I64X2_EXTMUL_LOW_I32X4_S_OP,
+            I64X2_EXTMUL_HIGH_I32X4_S     => I64X2_EXTMUL_HIGH_I32X4_S_OP,
+            I64X2_EXTMUL_LOW_I32X4_U      => I64X2_EXTMUL_LOW_I32X4_U_OP,
+            I64X2_EXTMUL_HIGH_I32X4_U     => I64X2_EXTMUL_HIGH_I32X4_U_OP,
+            F32X4_ABS                     => F32X4_ABS_OP,
+            F32X4_NEG                     => F32X4_NEG_OP,
+            F32X4_SQRT                    => F32X4_SQRT_OP,
+            F32X4_ADD                     => F32X4_ADD_OP,
+            F32X4_SUB                     => F32X4_SUB_OP,
+            F32X4_MUL                     => F32X4_MUL_OP,
+            F32X4_DIV                     => F32X4_DIV_OP,
+            F32X4_MIN                     => F32X4_MIN_OP,
+            F32X4_MAX                     => F32X4_MAX_OP,
+            F32X4_PMIN                    => F32X4_PMIN_OP,
+            F32X4_PMAX                    => F32X4_PMAX_OP,
+            F64X2_ABS                     => F64X2_ABS_OP,
+            F64X2_NEG                     => F64X2_NEG_OP,
+            F64X2_SQRT                    => F64X2_SQRT_OP,
+            F64X2_ADD                     => F64X2_ADD_OP,
+            F64X2_SUB                     => F64X2_SUB_OP,
+            F64X2_MUL                     => F64X2_MUL_OP,
+            F64X2_DIV                     => F64X2_DIV_OP,
+            F64X2_MIN                     => F64X2_MIN_OP,
+            F64X2_MAX                     => F64X2_MAX_OP,
+            F64X2_PMIN                    => F64X2_PMIN_OP,
+            F64X2_PMAX                    => F64X2_PMAX_OP,
+            I32X4_TRUNC_SAT_F32X4_S       => I32X4_TRUNC_SAT_F32X4_S_OP,
+            I32X4_TRUNC_SAT_F32X4_U       => I32X4_TRUNC_SAT_F32X4_U_OP,
+            F32X4_CONVERT_I32X4_S         => F32X4_CONVERT_I32X4_S_OP,
+            F32X4_CONVERT_I32X4_U         => F32X4_CONVERT_I32X4_U_OP,
+            I32X4_TRUNC_SAT_F64X2_S_ZERO  => I32X4_TRUNC_SAT_F64X2_S_ZERO_OP,
+            I32X4_TRUNC_SAT_F64X2_U_ZERO  => I32X4_TRUNC_SAT_F64X2_U_ZERO_OP,
+            F64X2_CONVERT_LOW_I32X4_S     => F64X2_CONVERT_LOW_I32X4_S_OP,
+            F64X2_CONVERT_LOW_I32X4_U     => F64X2_CONVERT_LOW_I32X4_U_OP,
+            _                             => return None,
+        })
+    }
+
+    /// Looks up an instruction in the ATOMIC_PREFIX (0xfe) opcode space.
+    pub fn from_atomic_opcode(opc: u8) -> Option<Op> {
+        Some(match opc {
+            MEMORY_ATOMIC_NOTIFY       => MEMORY_ATOMIC_NOTIFY_OP,
+            MEMORY_ATOMIC_WAIT32       => MEMORY_ATOMIC_WAIT32_OP,
+            MEMORY_ATOMIC_WAIT64       => MEMORY_ATOMIC_WAIT64_OP,
+            ATOMIC_FENCE               => ATOMIC_FENCE_OP,
+            I32_ATOMIC_LOAD            => I32_ATOMIC_LOAD_OP,
+            I64_ATOMIC_LOAD            => I64_ATOMIC_LOAD_OP,
+            I32_ATOMIC_LOAD8_U         => I32_ATOMIC_LOAD8_U_OP,
+            I32_ATOMIC_LOAD16_U        => I32_ATOMIC_LOAD16_U_OP,
+            I64_ATOMIC_LOAD8_U         => I64_ATOMIC_LOAD8_U_OP,
+            I64_ATOMIC_LOAD16_U        => I64_ATOMIC_LOAD16_U_OP,
+            I64_ATOMIC_LOAD32_U        => I64_ATOMIC_LOAD32_U_OP,
+            I32_ATOMIC_STORE           => I32_ATOMIC_STORE_OP,
+            I64_ATOMIC_STORE           => I64_ATOMIC_STORE_OP,
+            I32_ATOMIC_STORE8          => I32_ATOMIC_STORE8_OP,
+            I32_ATOMIC_STORE16         => I32_ATOMIC_STORE16_OP,
+            I64_ATOMIC_STORE8          => I64_ATOMIC_STORE8_OP,
+            I64_ATOMIC_STORE16         => I64_ATOMIC_STORE16_OP,
+            I64_ATOMIC_STORE32         => I64_ATOMIC_STORE32_OP,
+            I32_ATOMIC_RMW_ADD         => I32_ATOMIC_RMW_ADD_OP,
+            I64_ATOMIC_RMW_ADD         => I64_ATOMIC_RMW_ADD_OP,
+            I32_ATOMIC_RMW8_ADD_U      => I32_ATOMIC_RMW8_ADD_U_OP,
+            I32_ATOMIC_RMW16_ADD_U     => I32_ATOMIC_RMW16_ADD_U_OP,
+            I64_ATOMIC_RMW8_ADD_U      => I64_ATOMIC_RMW8_ADD_U_OP,
+            I64_ATOMIC_RMW16_ADD_U     => I64_ATOMIC_RMW16_ADD_U_OP,
+            I64_ATOMIC_RMW32_ADD_U     => I64_ATOMIC_RMW32_ADD_U_OP,
+            I32_ATOMIC_RMW_SUB         => I32_ATOMIC_RMW_SUB_OP,
+            I64_ATOMIC_RMW_SUB         => I64_ATOMIC_RMW_SUB_OP,
+            I32_ATOMIC_RMW8_SUB_U      => I32_ATOMIC_RMW8_SUB_U_OP,
+            I32_ATOMIC_RMW16_SUB_U     => I32_ATOMIC_RMW16_SUB_U_OP,
+            I64_ATOMIC_RMW8_SUB_U      => I64_ATOMIC_RMW8_SUB_U_OP,
+            I64_ATOMIC_RMW16_SUB_U     => I64_ATOMIC_RMW16_SUB_U_OP,
+            I64_ATOMIC_RMW32_SUB_U     => I64_ATOMIC_RMW32_SUB_U_OP,
+            I32_ATOMIC_RMW_AND         => I32_ATOMIC_RMW_AND_OP,
+            I64_ATOMIC_RMW_AND         => I64_ATOMIC_RMW_AND_OP,
+            I32_ATOMIC_RMW8_AND_U      => I32_ATOMIC_RMW8_AND_U_OP,
+            I32_ATOMIC_RMW16_AND_U     => I32_ATOMIC_RMW16_AND_U_OP,
+            I64_ATOMIC_RMW8_AND_U      => I64_ATOMIC_RMW8_AND_U_OP,
+            I64_ATOMIC_RMW16_AND_U     => I64_ATOMIC_RMW16_AND_U_OP,
+            I64_ATOMIC_RMW32_AND_U     => I64_ATOMIC_RMW32_AND_U_OP,
+            I32_ATOMIC_RMW_OR          => I32_ATOMIC_RMW_OR_OP,
+            I64_ATOMIC_RMW_OR          => I64_ATOMIC_RMW_OR_OP,
+            I32_ATOMIC_RMW8_OR_U       => I32_ATOMIC_RMW8_OR_U_OP,
+            I32_ATOMIC_RMW16_OR_U      => I32_ATOMIC_RMW16_OR_U_OP,
+            I64_ATOMIC_RMW8_OR_U       => I64_ATOMIC_RMW8_OR_U_OP,
+            I64_ATOMIC_RMW16_OR_U      => I64_ATOMIC_RMW16_OR_U_OP,
+            I64_ATOMIC_RMW32_OR_U      => I64_ATOMIC_RMW32_OR_U_OP,
+            I32_ATOMIC_RMW_XOR         => I32_ATOMIC_RMW_XOR_OP,
+            I64_ATOMIC_RMW_XOR         => I64_ATOMIC_RMW_XOR_OP,
+            I32_ATOMIC_RMW8_XOR_U      => I32_ATOMIC_RMW8_XOR_U_OP,
+            I32_ATOMIC_RMW16_XOR_U     => I32_ATOMIC_RMW16_XOR_U_OP,
+            I64_ATOMIC_RMW8_XOR_U      => I64_ATOMIC_RMW8_XOR_U_OP,
+            I64_ATOMIC_RMW16_XOR_U     => I64_ATOMIC_RMW16_XOR_U_OP,
+            I64_ATOMIC_RMW32_XOR_U     => I64_ATOMIC_RMW32_XOR_U_OP,
+            I32_ATOMIC_RMW_XCHG        => I32_ATOMIC_RMW_XCHG_OP,
+            I64_ATOMIC_RMW_XCHG        => I64_ATOMIC_RMW_XCHG_OP,
+            I32_ATOMIC_RMW8_XCHG_U     => I32_ATOMIC_RMW8_XCHG_U_OP,
+            I32_ATOMIC_RMW16_XCHG_U    => I32_ATOMIC_RMW16_XCHG_U_OP,
+            I64_ATOMIC_RMW8_XCHG_U     => I64_ATOMIC_RMW8_XCHG_U_OP,
+            I64_ATOMIC_RMW16_XCHG_U    => I64_ATOMIC_RMW16_XCHG_U_OP,
+            I64_ATOMIC_RMW32_XCHG_U    => I64_ATOMIC_RMW32_XCHG_U_OP,
+            I32_ATOMIC_RMW_CMPXCHG     => I32_ATOMIC_RMW_CMPXCHG_OP,
+            I64_ATOMIC_RMW_CMPXCHG     => I64_ATOMIC_RMW_CMPXCHG_OP,
+            I32_ATOMIC_RMW8_CMPXCHG_U  => I32_ATOMIC_RMW8_CMPXCHG_U_OP,
+            I32_ATOMIC_RMW16_CMPXCHG_U => I32_ATOMIC_RMW16_CMPXCHG_U_OP,
+            I64_ATOMIC_RMW8_CMPXCHG_U  => I64_ATOMIC_RMW8_CMPXCHG_U_OP,
+            I64_ATOMIC_RMW16_CMPXCHG_U => I64_ATOMIC_RMW16_CMPXCHG_U_OP,
+            I64_ATOMIC_RMW32_CMPXCHG_U => I64_ATOMIC_RMW32_CMPXCHG_U_OP,
+            _                          => return None,
+        })
+    }
+
+    pub fn is_unop(&self) -> bool {
+        self.t1 != ValueType::Void && self.t2 == ValueType::Void
+    }
+
+    pub fn is_binop(&self) -> bool {
+        self.t1 != ValueType::Void && self.t2 != ValueType::Void
+    }
+}
@@ -47,7 +47,7 @@ impl PageTable {
             } else {
                 None
             },
-            p @ _ => Some(p)
+            p => Some(p)
         }
     }
 
@@ -82,7 +82,7 @@ impl<'a> Memory<'a> {
     }
 
     fn offset(&self, addr: usize) -> usize {
-        (addr % PAGE_SIZE)
+        addr % PAGE_SIZE
     }
 
     pub fn pages(&self) -> usize {
@@ -107,7 +107,8 @@ impl<'a> Memory<'a> {
     }
 
     pub fn set_u8(&mut self, v_addr: usize, value: u8) -> Result<(), Error> {      
-        Ok(self.buf[self.map_addr(v_addr)?] = value)
+        self.buf[self.map_addr(v_addr)?] = value;
+        Ok(())
     }
 
     pub fn get_u16(&mut self, v_addr: usize) -> Result<u16, Error> {
@@ -119,7 +120,8 @@ impl<'a> Memory<'a> {
     pub fn set_u16(&mut self, v_addr: usize, value: u16) -> Result<(), Error> {      
         if v_addr & 0b1 != 0 { return Err(Error::InvalidAlignment) }
         let p_addr = self.map_addr(v_addr)?;
-        Ok(LittleEndian::write_u16(&mut self.buf[p_addr..], value))
+        LittleEndian::write_u16(&mut self.buf[p_addr..], value);
+        Ok(())
     }
 
     pub fn get_u32(&mut self, v_addr: usize) -> Result<u32, Error> {
@@ -131,7 +133,8 @@ impl<'a> Memory<'a> {
     pub fn set_u32(&mut self, v_addr: usize, value: u32) -> Result<(), Error> {      
         if v_addr & 0b11 != 0 { return Err(Error::InvalidAlignment) }
         let p_addr = self.map_addr(v_addr)?;
-        Ok(LittleEndian::write_u32(&mut self.buf[p_addr..], value))
+        LittleEndian::write_u32(&mut self.buf[p_addr..], value);
+        Ok(())
     }
 
 }
@@ -183,7 +186,7 @@ mod tests {
         mem.set_u32(PAGE_SIZE, 0xabcd0123).unwrap();
         assert_eq!(mem.get_u32(PAGE_SIZE).unwrap(), 0xabcd0123);
 
-        assert_eq!(mem.get_u8(PAGE_SIZE + 0).unwrap(), 0x23);
+        assert_eq!(mem.get_u8(PAGE_SIZE).unwrap(), 0x23);
         assert_eq!(mem.get_u8(PAGE_SIZE + 1).unwrap(), 0x01);
         assert_eq!(mem.get_u8(PAGE_SIZE + 2).unwrap(), 0xcd);
         assert_eq!(mem.get_u8(PAGE_SIZE + 3).unwrap(), 0xab);
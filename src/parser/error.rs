@@ -1,3 +1,7 @@
+pub use types::Location;
+
+use core::fmt;
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Error {
     UnexpectedEof,
@@ -15,10 +19,97 @@ pub enum Error {
     InvalidBlockType,
     InvalidFunctionType,
     InvalidTableType,
+    InvalidLimits,
     InvalidSectionId,
     InvalidImportDesc,
     InvalidExportDesc,
+    InvalidSegmentFlags,
+    InvalidElemKind,
+    InvalidInitializer,
+    InvalidTagAttribute,
+
+    SectionOutOfOrder,
+    SectionSizeMismatch,
+    UnknownType,
+    UnknownFunction,
+    UnknownTable,
+    UnknownMemory,
+    UnknownGlobal,
+    UnknownTag,
+    UnknownLocal,
+    UnknownLabel,
+    UnknownElementSegment,
+    UnknownDataSegment,
+    MultipleMemories,
+    MemorySizeTooLarge,
+    TooManyLocals,
+    ConstantExpressionRequired,
+    TypeMismatch,
+    InvalidTagType,
+    InvalidStartFunction,
+    DuplicateExportName,
+    InconsistentFunctionCount,
+    InconsistentDataCount,
+    DataCountRequired,
+    OutOfMemory,
 
     InvalidMagic,
     InvalidVersion,
-}
\ No newline at end of file
+}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::Error::*;
+        write!(f, "{}", match *self {
+            UnexpectedEof => "unexpected end",
+            InvalidU0 => "zero byte expected",
+            InvalidU1 => "malformed varuint1",
+            InvalidU7 => "malformed varuint7",
+            InvalidU32 | InvalidI32 | InvalidU64 | InvalidI64 => "integer too large",
+            InvalidUtf8 => "malformed UTF-8 encoding",
+            InvalidOpcode => "illegal opcode",
+            InvalidEnd => "END opcode expected",
+            InvalidValueType => "malformed value type",
+            InvalidBlockType => "malformed block type",
+            InvalidFunctionType => "malformed function type",
+            InvalidTableType => "malformed table type",
+            InvalidLimits => "malformed limits",
+            InvalidSectionId => "malformed section id",
+            InvalidImportDesc => "malformed import kind",
+            InvalidExportDesc => "malformed export kind",
+            InvalidSegmentFlags => "malformed segment flags",
+            InvalidElemKind => "malformed element kind",
+            InvalidInitializer => "malformed constant expression",
+            InvalidTagAttribute => "malformed tag attribute",
+            SectionOutOfOrder => "section out of order",
+            SectionSizeMismatch => "section size mismatch",
+            UnknownType => "unknown type",
+            UnknownFunction => "unknown function",
+            UnknownTable => "unknown table",
+            UnknownMemory => "unknown memory",
+            UnknownGlobal => "unknown global",
+            UnknownTag => "unknown tag",
+            UnknownLocal => "unknown local",
+            UnknownLabel => "unknown label",
+            UnknownElementSegment => "unknown elem segment",
+            UnknownDataSegment => "unknown data segment",
+            MultipleMemories => "multiple memories",
+            MemorySizeTooLarge => "memory size must be at most 65536 pages (4GiB)",
+            TooManyLocals => "too many locals",
+            ConstantExpressionRequired => "constant expression required",
+            TypeMismatch => "type mismatch",
+            InvalidTagType => "non-empty tag result type",
+            InvalidStartFunction => "start function",
+            DuplicateExportName => "duplicate export name",
+            InconsistentFunctionCount => "function and code section have inconsistent lengths",
+            InconsistentDataCount => "data count and data section have inconsistent lengths",
+            DataCountRequired => "data count section required",
+            OutOfMemory => "out of memory",
+            InvalidMagic => "magic header not detected",
+            InvalidVersion => "unknown binary version",
+        })
+    }
+}
+
+#[cfg(feature="std")]
+impl ::std::error::Error for Error {}
+
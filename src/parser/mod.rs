@@ -5,11 +5,9 @@ pub mod types;
 pub mod section;
 pub mod module;
 pub mod validator;
-pub mod opcode;
 
 pub use self::error::*;
 pub use self::reader::*;
 pub use self::types::*;
-pub use self::section::*;
 pub use self::module::*;
 pub use self::validator::*;
\ No newline at end of file
@@ -1,28 +1,13 @@
 use parser::error::Error;
 use parser::reader::{Reader, Read, ReadIterator, SectionReadIterator, FallibleIterator};
 use parser::types::*;
-use parser::opcode::*;
+pub use types::{Id, ImportDesc, Tag, ExportDesc};
+use opcode::*;
 use floathex;
 
 use core::str;
 use core::fmt;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub enum Id {
-    Custom = 0,
-    Type = 1,
-    Import = 2,
-    Function = 3,
-    Table = 4,
-    Memory = 5,
-    Global = 6,
-    Export = 7,
-    Start = 8,
-    Element = 9,
-    Code = 10,
-    Data = 11,
-}
-
 impl<'a> Read<Id> for Reader<'a> {
     fn read(&mut self) -> Result<Id, Error> {
         Ok(match self.read_u8()? {
@@ -38,52 +23,14 @@ impl<'a> Read<Id> for Reader<'a> {
             9 => Id::Element,
             10 => Id::Code,
             11 => Id::Data,
+            12 => Id::DataCount,
+            13 => Id::Tag,
             _ => return Err(Error::InvalidSectionId)
         })
     }
 }
 
-impl From<u8> for Id {
-    fn from(other: u8) -> Self {
-        match other {
-            0 => Id::Custom,
-            1 => Id::Type,
-            2 => Id::Import,
-            3 => Id::Function,
-            4 => Id::Table,
-            5 => Id::Memory,
-            6 => Id::Global,
-            7 => Id::Export,
-            8 => Id::Start,
-            9 => Id::Element,
-            10 => Id::Code,
-            11 => Id::Data,    
-            _ => panic!("Invalid ID: {}", other)
-        }        
-    }
-}
-
-impl Id {
-    pub fn as_str(&self) -> &'static str {
-        use self::Id::*;
-        match *self {
-            Custom => "Custom",
-            Type => "Type",
-            Import => "Import",
-            Function => "Function",
-            Table => "Table",
-            Memory => "Memory",
-            Global => "Global",
-            Export => "Export",
-            Start => "Start",
-            Element => "Elem",
-            Code => "Code",
-            Data => "Data",            
-        }
-    }    
-}
-
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Module<'a> {
     pub magic: u32,
     pub version: u32,    
@@ -114,7 +61,7 @@ impl<'a> Module<'a> {
         self.version
     }
 
-    pub fn sections(&self) -> ReadIterator<Section<'a>> {
+    pub fn sections(&self) -> ReadIterator<'_, Section<'a>> {
         ReadIterator::new(Reader::new(self.buf))
     }
 
@@ -138,7 +85,7 @@ impl<'a> Module<'a> {
         Ok(None)
     }
 
-    pub fn signature_type(&self, index: Index) -> Result<Option<FunctionType>, Error> {
+    pub fn signature_type(&self, index: Index) -> Result<Option<FunctionType<'_>>, Error> {
         let mut sections = self.sections();
         while let Some(section) = sections.next()? {
             if section.id() != Id::Type { continue }
@@ -197,7 +144,7 @@ impl<'a> Section<'a> {
         self.iter_for_section_id(Id::Table)
     }
 
-    pub fn globals(&self) -> SectionReadIterator<'a, Global> {
+    pub fn globals(&self) -> SectionReadIterator<'a, Global<'_>> {
         self.iter_for_section_id(Id::Global)
     }
 
@@ -205,7 +152,7 @@ impl<'a> Section<'a> {
         self.iter_for_section_id(Id::Memory)
     }
 
-    pub fn elements(&self) -> SectionReadIterator<'a, Element> {
+    pub fn elements(&self) -> SectionReadIterator<'a, Element<'_>> {
         self.iter_for_section_id(Id::Element)
     }
 
@@ -232,6 +179,14 @@ impl<'a> Section<'a> {
     pub fn start(&self) -> Result<Start, Error> {
         Reader::new(self.buf).read()
     }
+
+    pub fn data_count(&self) -> Result<u32, Error> {
+        Reader::new(self.buf).read()
+    }
+
+    pub fn tags(&self) -> SectionReadIterator<'a, Tag> {
+        self.iter_for_section_id(Id::Tag)
+    }
 }
 
 impl<'a> Read<Section<'a>> for Reader<'a> {
@@ -256,13 +211,6 @@ impl<'a> Read<Custom<'a>> for Reader<'a> {
     }
 }
 
-#[derive(Debug)]
-pub enum ImportDesc {
-    Func(Index),
-    Table(TableType),
-    Memory(MemoryType),
-    Global(GlobalType),
-}
 impl<'a> Read<ImportDesc> for Reader<'a> {
     fn read(&mut self) -> Result<ImportDesc, Error> {
         match self.read_u8()? {
@@ -270,6 +218,7 @@ impl<'a> Read<ImportDesc> for Reader<'a> {
             0x01 => self.read().map(ImportDesc::Table),
             0x02 => self.read().map(ImportDesc::Memory),
             0x03 => self.read().map(ImportDesc::Global),
+            0x04 => self.read().map(ImportDesc::Tag),
             _ => Err(Error::InvalidImportDesc),
         }
     }
@@ -293,6 +242,19 @@ impl<'a> Read<Import<'a>> for Reader<'a> {
     }
 }
 
+impl<'a> Read<Tag> for Reader<'a> {
+    fn read(&mut self) -> Result<Tag, Error> {
+        Ok({
+            let attribute = self.read_u8()?;
+            if attribute != 0 {
+                return Err(Error::InvalidTagAttribute)
+            }
+            let type_index = self.read()?;
+            Tag { attribute, type_index }
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct Global<'a> {
     pub global_type: GlobalType,
@@ -309,13 +271,6 @@ impl<'a> Read<Global<'a>> for Reader<'a> {
     }
 }
 
-#[derive(Debug)]
-pub enum ExportDesc {
-    Func(Index),
-    Table(Index),
-    Memory(Index),
-    Global(Index),
-}
 impl<'a> Read<ExportDesc> for Reader<'a> {
     fn read(&mut self) -> Result<ExportDesc, Error> {
         match self.read_u8()? {
@@ -323,6 +278,7 @@ impl<'a> Read<ExportDesc> for Reader<'a> {
             0x01 => self.read().map(ExportDesc::Table),
             0x02 => self.read().map(ExportDesc::Memory),
             0x03 => self.read().map(ExportDesc::Global),
+            0x04 => self.read().map(ExportDesc::Tag),
             _ => Err(Error::InvalidExportDesc),
         }
     }
@@ -358,33 +314,106 @@ impl<'a> Read<Start> for Reader<'a> {
     }
 }
 
+/// How a data or element segment is applied. Active segments are copied into
+/// their memory or table at instantiation, passive segments are only used by
+/// `memory.init` / `table.init` and declarative segments are never applied.
+#[derive(Debug)]
+pub enum SegmentMode<'a> {
+    Active { index: Index, offset: Initializer<'a> },
+    Passive,
+    Declarative,
+}
+
 #[derive(Debug)]
 pub struct Element<'a> {
-    pub table_index: Index,
-    pub offset: Initializer<'a>,
+    pub mode: SegmentMode<'a>,
+    pub elemtype: ValueType,
+    /// True if `init` holds constant expressions rather than function indices.
+    pub exprs: bool,
     pub init: &'a [u8],
 }
 
 impl<'a> Read<Element<'a>> for Reader<'a> {
     fn read(&mut self) -> Result<Element<'a>, Error> {
         Ok({            
-            let table_index = self.read()?;
-            let offset = self.read()?;            
-            let count: u32 = self.read()?;
+            let flags: u32 = self.read()?;
+            if flags > 7 {
+                return Err(Error::InvalidSegmentFlags)
+            }
+            // Bit 0: passive or declarative, bit 1: explicit table index or declarative,
+            // bit 2: items are expressions
+            let mode = match flags & 0b011 {
+                0 => SegmentMode::Active { index: 0, offset: self.read()? },
+                1 => SegmentMode::Passive,
+                2 => SegmentMode::Active { index: self.read()?, offset: self.read()? },
+                _ => SegmentMode::Declarative,
+            };
+            let exprs = flags & 0b100 != 0;
+            let elemtype = match (flags & 0b011 != 0, exprs) {
+                (false, _) => ValueType::AnyFunc,
+                (true, false) => if self.read_u8()? == 0x00 {
+                    ValueType::AnyFunc
+                } else {
+                    return Err(Error::InvalidElemKind)
+                },
+                (true, true) => {
+                    let elemtype: ValueType = self.read()?;
+                    if !elemtype.is_ref() {
+                        return Err(Error::InvalidElemKind)
+                    }
+                    elemtype
+                },
+            };
             let base = self.clone();
+            let count: u32 = self.read()?;
             for _ in 0..count {
-                let _: Index = self.read()?;
+                if exprs {
+                    let _: Initializer = self.read()?;
+                } else {
+                    let _: Index = self.read()?;
+                }
             }
             let len = self.offset_from(&base);
             let init = &base.into_slice()[..len];            
-            Element { table_index, offset, init }
+            Element { mode, elemtype, exprs, init }
         })
     }
 }
 
 impl<'a> Element<'a> {
-    pub fn iter(&self) -> SectionReadIterator<'a, Index> {
-        SectionReadIterator::new(Reader::new(self.init))
+    /// Iterates over the segment's items: `Some(function index)` or `None` for a null reference.
+    pub fn iter(&self) -> ElementItemIterator<'a> {
+        ElementItemIterator { r: Reader::new(self.init), count: 0, exprs: self.exprs }
+    }
+}
+
+pub struct ElementItemIterator<'a> {
+    r: Reader<'a>,
+    count: u32,
+    exprs: bool,
+}
+
+impl<'a> FallibleIterator for ElementItemIterator<'a> {
+    type Item = Option<Index>;
+    type Error = Error;
+    fn next(&mut self) -> Result<Option<Self::Item>, Self::Error> {
+        if self.count == 0 {
+            self.r.read_var_u32()?;
+        }
+        if self.r.is_empty() {
+            return Ok(None)
+        }
+        self.count += 1;
+        if self.exprs {
+            let init: Initializer = self.r.read()?;
+            match init.instr.immediate {
+                Immediate::RefFunc { index } => Ok(Some(Some(index))),
+                Immediate::RefNull { reftype: _ } => Ok(Some(None)),
+                _ => Err(Error::InvalidInitializer),
+            }
+        } else {
+            Ok(Some(Some(self.r.read()?)))
+        }
     }
 }
 
@@ -435,7 +464,7 @@ impl<'a> Read<Local> for Reader<'a> {
         Ok({
             let n = self.read()?;
             let t = self.read()?;
-            Local { n: n, t: t }
+            Local { n, t }
         })
     }
 }
@@ -462,6 +491,19 @@ impl<'a> FuncItemIterator<'a> {
         use self::FuncItemIteratorState as State;
         FuncItemIterator { r, state: State::Start }
     }    
+
+    /// The bytes of the items that have not been read yet, which start after the local
+    /// declaration count.
+    pub fn rest(&self) -> &'a [u8] {
+        use self::FuncItemIteratorState as State;
+        let mut r = Reader::new(self.r.rest());
+        if let State::Start = self.state {
+            if r.read_var_u32().is_err() {
+                return self.r.rest()
+            }
+        }
+        r.rest()
+    }
 }
 
 impl<'a> FallibleIterator for FuncItemIterator<'a> {
@@ -489,7 +531,7 @@ impl<'a> FallibleIterator for FuncItemIterator<'a> {
                     return Ok(Some(FuncItem::Local(local)));
                 },
                 State::Instr => {
-                    if self.r.len() > 0 {
+                    if !self.r.is_empty() {
                         let instr = self.r.read()?;
                         return Ok(Some(FuncItem::Instr(instr)));
                     } else {
@@ -503,18 +545,22 @@ impl<'a> FallibleIterator for FuncItemIterator<'a> {
 
 #[derive(Debug)]
 pub struct Data<'a> {
-    pub mem_index: Index,
-    pub offset: Initializer<'a>,
+    pub mode: SegmentMode<'a>,
     pub init: &'a [u8],
 }
 
 impl<'a> Read<Data<'a>> for Reader<'a> {
     fn read(&mut self) -> Result<Data<'a>, Error> {
         Ok({
-            let mem_index = self.read()?;
-            let offset = self.read()?;
+            let flags: u32 = self.read()?;
+            let mode = match flags {
+                0 => SegmentMode::Active { index: 0, offset: self.read()? },
+                1 => SegmentMode::Passive,
+                2 => SegmentMode::Active { index: self.read()?, offset: self.read()? },
+                _ => return Err(Error::InvalidSegmentFlags),
+            };
             let init = self.read()?;
-            Data { mem_index, offset, init }
+            Data { mode, init }
         })
     }
 }
@@ -550,21 +596,100 @@ impl<'a> Initializer<'a> {
 
 pub struct Instr<'a> {
     pub opcode: u8,
+    /// The sub-opcode of a prefixed instruction, or 0 if `opcode` is not a prefix.
+    pub sub_opcode: u8,
     pub immediate: Immediate<'a>,
     pub data: &'a [u8],
 }
 
+impl<'a> Instr<'a> {
+    pub fn op(&self) -> Option<Op> {
+        match self.opcode {
+            MISC_PREFIX => Op::from_misc_opcode(self.sub_opcode),
+            #[cfg(feature="simd")]
+            SIMD_PREFIX => Op::from_simd_opcode(self.sub_opcode),
+            ATOMIC_PREFIX => Op::from_atomic_opcode(self.sub_opcode),
+            opc => Op::from_opcode(opc),
+        }
+    }
+}
+
 impl<'a> Read<Instr<'a>> for Reader<'a> {
     fn read(&mut self) -> Result<Instr<'a>, Error> {
         let base = self.clone();
         let opcode = self.read()?;
+        let mut sub_opcode = 0;
         let immediate = match opcode {
-            BLOCK | LOOP | IF => {
+            MISC_PREFIX => {
+                let sub: u32 = self.read()?;
+                if sub > 0xff {
+                    return Err(Error::InvalidOpcode)
+                }
+                sub_opcode = sub as u8;
+                match sub_opcode {
+                    MEMORY_INIT => Immediate::MemoryInit { index: self.read()?, reserved: self.read()? },
+                    DATA_DROP => Immediate::DataDrop { index: self.read()? },
+                    MEMORY_COPY => Immediate::MemoryCopy { dst: self.read()?, src: self.read()? },
+                    MEMORY_FILL => Immediate::Memory { reserved: self.read()? },
+                    TABLE_INIT => Immediate::TableInit { index: self.read()?, table: self.read()? },
+                    ELEM_DROP => Immediate::ElemDrop { index: self.read()? },
+                    TABLE_COPY => Immediate::TableCopy { dst: self.read()?, src: self.read()? },
+                    TABLE_GROW | TABLE_SIZE | TABLE_FILL => Immediate::Table { index: self.read()? },
+                    _ => Immediate::None,
+                }
+            },
+            #[cfg(feature="simd")]
+            SIMD_PREFIX => {
+                let sub: u32 = self.read()?;
+                if sub > 0xff {
+                    return Err(Error::InvalidOpcode)
+                }
+                sub_opcode = sub as u8;
+                match sub_opcode {
+                    V128_LOAD ..= V128_STORE | V128_LOAD32_ZERO | V128_LOAD64_ZERO => {
+                        Immediate::LoadStore { align: self.read()?, offset: self.read()? }
+                    },
+                    V128_LOAD8_LANE ..= V128_STORE64_LANE => {
+                        Immediate::LoadStoreLane { align: self.read()?, offset: self.read()?, lane: self.read()? }
+                    },
+                    V128_CONST => Immediate::V128Const { value: self.read_slice(16)? },
+                    I8X16_SHUFFLE => Immediate::Shuffle { lanes: self.read_slice(16)? },
+                    I8X16_EXTRACT_LANE_S ..= F64X2_REPLACE_LANE => Immediate::Lane { index: self.read()? },
+                    _ => Immediate::None,
+                }
+            },
+            ATOMIC_PREFIX => {
+                let sub: u32 = self.read()?;
+                if sub > 0xff {
+                    return Err(Error::InvalidOpcode)
+                }
+                sub_opcode = sub as u8;
+                match sub_opcode {
+                    ATOMIC_FENCE => Immediate::Memory { reserved: self.read()? },
+                    _ => Immediate::LoadStore { align: self.read()?, offset: self.read()? },
+                }
+            },
+            BLOCK | LOOP | IF | TRY => {
                 Immediate::Block { signature: self.read()? }
             },
-            BR | BR_IF => {
+            SELECT_T => {
+                Immediate::SelectT { types: self.read()? }
+            },
+            TABLE_GET | TABLE_SET => {
+                Immediate::Table { index: self.read()? }
+            },
+            REF_NULL => {
+                Immediate::RefNull { reftype: self.read()? }
+            },
+            REF_FUNC => {
+                Immediate::RefFunc { index: self.read()? }
+            },
+            BR | BR_IF | RETHROW => {
                 Immediate::Branch { depth: self.read()? }
             },
+            CATCH | THROW => {
+                Immediate::Tag { index: self.read()? }
+            },
             BR_TABLE => {
                 let count: u32 = self.read()?;
                 let mut base = self.clone();
@@ -582,11 +707,11 @@ impl<'a> Read<Instr<'a>> for Reader<'a> {
             GET_GLOBAL | SET_GLOBAL => {
                 Immediate::Global { index: self.read()? }
             },
-            CALL => {
+            CALL | RETURN_CALL => {
                 Immediate::Call { index: self.read()? }
             },
-            CALL_INDIRECT => {
-                Immediate::CallIndirect { index: self.read()?, reserved: self.read()? }
+            CALL_INDIRECT | RETURN_CALL_INDIRECT => {
+                Immediate::CallIndirect { index: self.read()?, table: self.read()? }
             },
             I32_CONST => {
                 Immediate::I32Const { value: self.read()? }
@@ -601,8 +726,8 @@ impl<'a> Read<Instr<'a>> for Reader<'a> {
                 Immediate::F64Const { value: self.read()? }
             },
             I32_LOAD | I32_STORE |
-            I32_LOAD8_S ... I32_LOAD16_U |
-            I32_STORE8 ... I32_STORE16 => {
+            I32_LOAD8_S ..= I32_LOAD16_U |
+            I32_STORE8 ..= I32_STORE16 => {
                 Immediate::LoadStore { align: self.read()?, offset: self.read()? }
             },
 
@@ -611,8 +736,8 @@ impl<'a> Read<Instr<'a>> for Reader<'a> {
             },
 
             I64_LOAD | I64_STORE |
-            I64_LOAD8_S ... I64_LOAD32_U |
-            I64_STORE8 ... I64_STORE32 => {
+            I64_LOAD8_S ..= I64_LOAD32_U |
+            I64_STORE8 ..= I64_STORE32 => {
                 Immediate::LoadStore { align: self.read()?, offset: self.read()? }
             },
 
@@ -627,39 +752,57 @@ impl<'a> Read<Instr<'a>> for Reader<'a> {
         };
         let len = self.offset_from(&base);
         let data = &base.into_slice()[..len];
-        Ok(Instr { opcode, immediate, data })
+        Ok(Instr { opcode, sub_opcode, immediate, data })
     }
 }
 
 impl<'a> fmt::Debug for Instr<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        Ok({
-            if let Some(op) = Op::from_opcode(self.opcode) {
-                match self.immediate {
-                    Immediate::None => write!(f, "{}", op.text)?,
-                    _ => write!(f, "{} {:?}", op.text, self.immediate)?,
-                }
-            } else {
-                write!(f, "unknown")?;
+        if let Some(op) = self.op() {
+            match self.immediate {
+                Immediate::None => write!(f, "{}", op.text)?,
+                _ => write!(f, "{} {:?}", op.text, self.immediate)?,
             }
-        })
+        } else {
+            write!(f, "unknown")?;
+        }
+        Ok(())
     }
 }
 pub enum Immediate<'a> {
     None,
-    Block { signature: ValueType },
+    Block { signature: BlockType },
     Branch { depth: Depth },
     BranchTable { table: &'a [u8] },
     Local { index: Index },
     Global { index: Index },
     Call { index: Index },
-    CallIndirect { index: Index, reserved: Reserved },
+    CallIndirect { index: Index, table: Index },
     I32Const { value: i32 },
     F32Const { value: f32 },
     I64Const { value: i64 },
     F64Const { value: f64 },
     LoadStore { align: u32, offset: u32 },
     Memory { reserved: Reserved },
+    MemoryInit { index: Index, reserved: Reserved },
+    DataDrop { index: Index },
+    MemoryCopy { dst: Reserved, src: Reserved },
+    SelectT { types: &'a [ValueType] },
+    Table { index: Index },
+    TableInit { index: Index, table: Index },
+    ElemDrop { index: Index },
+    TableCopy { dst: Index, src: Index },
+    RefNull { reftype: ValueType },
+    RefFunc { index: Index },
+    Tag { index: Index },
+    #[cfg(feature="simd")]
+    LoadStoreLane { align: u32, offset: u32, lane: u8 },
+    #[cfg(feature="simd")]
+    V128Const { value: &'a [u8] },
+    #[cfg(feature="simd")]
+    Shuffle { lanes: &'a [u8] },
+    #[cfg(feature="simd")]
+    Lane { index: u8 },
 }
 
 
@@ -668,17 +811,13 @@ impl<'a> fmt::Debug for Immediate<'a> {
         use self::Immediate::*;
         match *self {
             None => Ok(()),
-            Block { signature } => if signature != ValueType::Void {
-                write!(f, "{}", signature)
-            } else {
-                Ok(())
-            },
+            Block { signature } => write!(f, "{}", signature),
             Branch { depth } => write!(f, "{}", depth),
             BranchTable { table: _ } => Ok(()),
             Local { ref index } => write!(f, "{}", index),
             Global { ref index } => write!(f, "{}", index),
             Call { ref index } => write!(f, "{}", index),
-            CallIndirect { ref index, reserved } => write!(f, "{} {}", index, reserved),
+            CallIndirect { ref index, table } => write!(f, "{} {}", index, table),
             I32Const { value } => write!(f, "{}", value as u32),
             F32Const { value } => {
                 floathex::f32_hex(f, value)
@@ -689,6 +828,43 @@ impl<'a> fmt::Debug for Immediate<'a> {
             },
             LoadStore { align, offset } => write!(f, "{} {}", align, offset),
             Memory { reserved: _ } => Ok(()),
+            MemoryInit { index, reserved } => write!(f, "{} {}", index, reserved),
+            DataDrop { index } => write!(f, "{}", index),
+            MemoryCopy { dst, src } => write!(f, "{} {}", dst, src),
+            SelectT { types } => {
+                for (i, t) in types.iter().enumerate() {
+                    if i > 0 { write!(f, " ")?; }
+                    write!(f, "{}", t)?;
+                }
+                Ok(())
+            },
+            Table { index } => write!(f, "{}", index),
+            TableInit { index, table } => write!(f, "{} {}", table, index),
+            ElemDrop { index } => write!(f, "{}", index),
+            TableCopy { dst, src } => write!(f, "{} {}", dst, src),
+            RefNull { reftype } => write!(f, "{}", reftype),
+            RefFunc { index } => write!(f, "{}", index),
+            Tag { index } => write!(f, "{}", index),
+            #[cfg(feature="simd")]
+            LoadStoreLane { align, offset, lane } => write!(f, "{} {} {}", align, offset, lane),
+            #[cfg(feature="simd")]
+            V128Const { value } => {
+                write!(f, "i32x4")?;
+                for c in value.chunks(4) {
+                    write!(f, " 0x{:02x}{:02x}{:02x}{:02x}", c[3], c[2], c[1], c[0])?;
+                }
+                Ok(())
+            },
+            #[cfg(feature="simd")]
+            Shuffle { lanes } => {
+                for (i, l) in lanes.iter().enumerate() {
+                    if i > 0 { write!(f, " ")?; }
+                    write!(f, "{}", l)?;
+                }
+                Ok(())
+            },
+            #[cfg(feature="simd")]
+            Lane { index } => write!(f, "{}", index),
         }
 
     }
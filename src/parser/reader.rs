@@ -1,10 +1,8 @@
 use parser::Error;
-use parser::util::*;
 use byteorder::{ByteOrder, LittleEndian};
 pub use fallible_iterator::FallibleIterator;
 
 use core::str;
-use core::mem;
 use core::marker::PhantomData;
 
 pub struct Reader<'a> {
@@ -20,6 +18,10 @@ impl<'a> Reader<'a> {
         self.buf.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
     pub fn into_slice(self) -> &'a [u8] {
         self.buf
     }
@@ -29,8 +31,8 @@ impl<'a> Reader<'a> {
     }
 
     pub fn offset_from(&self, base: &Reader<'a>) -> usize {
-        let base_ptr = base.buf.as_ptr() as *const u8 as usize;
-        let ptr = self.buf.as_ptr() as *const u8 as usize;
+        let base_ptr = base.buf.as_ptr() as usize;
+        let ptr = self.buf.as_ptr() as usize;
         debug_assert!(base_ptr <= ptr);
         debug_assert!(ptr + self.buf.len() <= base_ptr + base.buf.len());
         ptr - base_ptr        
@@ -54,7 +56,7 @@ impl<'a> Reader<'a> {
     pub fn read_u32(&mut self) -> Result<u32, Error> {
         let slice = self.read_slice(4)?;
         Ok(
-            (slice[0] as u32) << 0 |
+            (slice[0] as u32) |
             (slice[1] as u32) << 8 |
             (slice[2] as u32) << 16 |
             (slice[3] as u32) << 24
@@ -94,6 +96,10 @@ impl<'a> Reader<'a> {
             let mut shift = 7;            
             loop {
                 byte = self.read_u8()?;
+                // The last byte may only hold the remaining 4 bits
+                if shift == 28 && byte & 0xf0 != 0 {
+                    return Err(Error::InvalidU32)
+                }
                 value |= ((byte & 0x7f) as u32) << shift;
                 if byte & 0x80 == 0 { break }
                 shift += 7;
@@ -113,6 +119,9 @@ impl<'a> Reader<'a> {
             let mut shift = 7;            
             loop {
                 byte = self.read_u8()?;
+                if shift == 63 && byte & 0xfe != 0 {
+                    return Err(Error::InvalidU64)
+                }
                 value |= ((byte & 0x7f) as u64) << shift;
                 if byte & 0x80 == 0 { break }
                 shift += 7;
@@ -135,6 +144,10 @@ impl<'a> Reader<'a> {
             let mut shift = 7;
             loop {
                 byte = self.read_u8()?;
+                // The unused bits of the last byte must match the sign bit
+                if shift == 28 && byte & 0xf8 != 0 && byte & 0xf8 != 0x78 {
+                    return Err(Error::InvalidI32)
+                }
                 value |= ((byte & 0x7f) as u32) << shift;
                 if byte & 0x80 == 0 {
                     if byte & 0x40 != 0 {
@@ -162,6 +175,9 @@ impl<'a> Reader<'a> {
             let mut shift = 7;
             loop {
                 byte = self.read_u8()?;
+                if shift == 63 && byte != 0x00 && byte != 0x7f {
+                    return Err(Error::InvalidI64)
+                }
                 value |= ((byte & 0x7f) as u64) << shift;
                 if byte & 0x80 == 0 {
                     if byte & 0x40 != 0 {
@@ -239,11 +255,10 @@ impl<'a> Read<f64> for Reader<'a> {
     }
 }
 
-impl<'a, T> Read<&'a [T]> for Reader<'a> {
-    fn read(&mut self) -> Result<&'a [T], Error> {        
+impl<'a> Read<&'a [u8]> for Reader<'a> {
+    fn read(&mut self) -> Result<&'a [u8], Error> {
         let len = self.read_var_u32()? as usize;
-        let t_len = len * mem::size_of::<T>();
-        self.read_slice(t_len).map(from_byte_slice)
+        self.read_slice(len)
     }
 }
 
@@ -265,6 +280,11 @@ impl<'a, T> ReadIterator<'a, T> {
     pub fn new(r: Reader<'a>) -> Self {
         ReadIterator { r, _phantom: PhantomData }
     }
+
+    /// The bytes that have not been read yet.
+    pub fn rest(&self) -> &'a [u8] {
+        self.r.rest()
+    }
 }
 
 impl<'a, T> FallibleIterator for ReadIterator<'a, T> 
@@ -272,7 +292,7 @@ where Reader<'a>: Read<T> {
     type Item = T;
     type Error = Error;
     fn next(&mut self) -> Result<Option<Self::Item>, Self::Error> {
-        if self.r.len() > 0 {
+        if !self.r.is_empty() {
             Ok(Some(self.r.read()?))
         } else {
             Ok(None)
@@ -291,6 +311,15 @@ impl<'a, T> SectionReadIterator<'a, T> {
     pub fn new(r: Reader<'a>) -> Self {
         SectionReadIterator { r, count: 0, _phantom: PhantomData }
     }
+
+    /// The bytes of the items that have not been read yet, which start after the item count.
+    pub fn rest(&self) -> &'a [u8] {
+        let mut r = Reader::new(self.r.rest());
+        if self.count == 0 && r.read_var_u32().is_err() {
+            return self.r.rest()
+        }
+        r.rest()
+    }
 }
 
 impl<'a, T> FallibleIterator for SectionReadIterator<'a, T> 
@@ -301,7 +330,7 @@ where Reader<'a>: Read<T> {
         if self.count == 0 {
             self.r.read_var_u32()?;
         }
-        if self.r.len() > 0 {
+        if !self.r.is_empty() {
             self.count += 1;
             Ok(Some(self.r.read()?))
         } else {
@@ -1,8 +1,10 @@
 use parser::error::Error;
 use parser::reader::{Reader, Read};
+pub use types::{Index, ValueType, FunctionType, Limits, MemoryType, TableType, GlobalType};
+
 use core::fmt;
+use core::slice;
 
-pub type Index = u32;
 pub type Depth = u32;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -23,71 +25,54 @@ impl fmt::Display for Reserved {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(u8)]
-pub enum ValueType {
-    Any = 0x00,
-    Void = 0x40,    
-    Func = 0x60,
-    AnyFunc = 0x70,
-    I32 = 0x7f,
-    I64 = 0x7e,
-    F32 = 0x7d,
-    F64 = 0x7c,
-}
-
 impl<'a> Read<ValueType> for Reader<'a> {
     fn read(&mut self) -> Result<ValueType, Error> {
-        Ok(match self.read_u8()? {
-            0x00 => ValueType::Any,
-            0x40 => ValueType::Void,
-            0x60 => ValueType::Func,
-            0x70 => ValueType::AnyFunc,
-            0x7f => ValueType::I32,
-            0x7e => ValueType::I64,
-            0x7d => ValueType::F32,
-            0x7c => ValueType::F64,
-            _ => return Err(Error::InvalidValueType)
-        })
+        ValueType::from_u8(self.read_u8()?).ok_or(Error::InvalidValueType)
     }
 }
 
-impl From<u8> for ValueType {
-    fn from(other: u8) -> Self {
-        match other {
-            0x00 => ValueType::Any,
-            0x40 => ValueType::Void,
-            0x60 => ValueType::Func,
-            0x70 => ValueType::AnyFunc,
-            0x7f => ValueType::I32,
-            0x7e  => ValueType::I64,
-            0x7d => ValueType::F32,
-            0x7c => ValueType::F64,
-            _ => panic!("Unrecognized ValueType: 0x{:02x}", other)
+impl<'a> Read<&'a [ValueType]> for Reader<'a> {
+    fn read(&mut self) -> Result<&'a [ValueType], Error> {
+        // The types are used in place, so every byte must be checked before it is
+        // reinterpreted as a ValueType.
+        let buf: &'a [u8] = self.read()?;
+        for &b in buf {
+            ValueType::from_u8(b).ok_or(Error::InvalidValueType)?;
         }
+        Ok(unsafe { slice::from_raw_parts(buf.as_ptr() as *const ValueType, buf.len()) })
     }
 }
 
-impl fmt::Display for ValueType {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use self::ValueType::*;
-        write!(f, "{}", match *self {
-            Any => "any",
-            I32 => "i32",
-            I64 => "i64",
-            F32 => "f32",
-            F64 => "f64",
-            AnyFunc => "anyfunc",
-            Func => "func",
-            Void => "void",
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockType {
+    Value(ValueType),
+    Index(Index),
+}
+
+impl<'a> Read<BlockType> for Reader<'a> {
+    fn read(&mut self) -> Result<BlockType, Error> {
+        Ok(match self.clone().read_u8()? {
+            0x40 | 0x6f | 0x70 | 0x7b | 0x7c | 0x7d | 0x7e | 0x7f => BlockType::Value(self.read()?),
+            _ => {
+                // Type indices are encoded as positive s33 values
+                let index = self.read_var_i64()?;
+                if !(0..=0xffff_ffff).contains(&index) {
+                    return Err(Error::InvalidBlockType)
+                }
+                BlockType::Index(index as Index)
+            }
         })
     }
 }
-#[derive(Debug)]
-pub struct FunctionType<'a> {
-    pub functype: u8,
-    pub parameters: &'a [ValueType],
-    pub results: &'a [ValueType],
+
+impl fmt::Display for BlockType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BlockType::Value(ValueType::Void) => Ok(()),
+            BlockType::Value(t) => write!(f, "{}", t),
+            BlockType::Index(index) => write!(f, "type[{}]", index),
+        }
+    }
 }
 
 impl<'a> Read<FunctionType<'a>> for Reader<'a> {
@@ -104,33 +89,32 @@ impl<'a> Read<FunctionType<'a>> for Reader<'a> {
     }
 }
 
-#[derive(Debug)]
-pub struct Limits {
-    pub flag: bool,
-    pub min: u32,
-    pub max: Option<u32>,
-}
-
 impl<'a> Read<Limits> for Reader<'a> {
     fn read(&mut self) -> Result<Limits, Error> {
         Ok({
-            let flag = self.read()?;
+            let flags: u8 = self.read()?;
+            if flags > 3 {
+                return Err(Error::InvalidLimits)
+            }
+            let flag = flags & 0b01 != 0;
+            let shared = flags & 0b10 != 0;
+            if shared && !flag {
+                return Err(Error::InvalidLimits)
+            }
             let min = self.read()?;
             let max = if flag {
                 self.read().map(Some)?
             } else {
                 None
             };
-            Limits { flag, min, max }
+            if max.is_some_and(|max| max < min) {
+                return Err(Error::InvalidLimits)
+            }
+            Limits { flag, shared, min, max }
         })
     }
 }
 
-#[derive(Debug)]
-pub struct MemoryType {
-    pub limits: Limits,
-}
-
 impl<'a> Read<MemoryType> for Reader<'a> {
     fn read(&mut self) -> Result<MemoryType, Error> {
         Ok({
@@ -140,31 +124,22 @@ impl<'a> Read<MemoryType> for Reader<'a> {
     }
 }
 
-#[derive(Debug)]
-pub struct TableType {
-    pub elemtype: ValueType,
-    pub limits: Limits,
-}
-
 impl<'a> Read<TableType> for Reader<'a> {
     fn read(&mut self) -> Result<TableType, Error> {
         Ok({
-            let elemtype = self.read()?;
-            if elemtype != ValueType::AnyFunc {
+            let elemtype: ValueType = self.read()?;
+            if !elemtype.is_ref() {
+                return Err(Error::InvalidTableType)
+            }
+            let limits: Limits = self.read()?;
+            if limits.shared {
                 return Err(Error::InvalidTableType)
             }
-            let limits = self.read()?;
             TableType { elemtype, limits }
         })
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct GlobalType {
-    pub valtype: ValueType,
-    pub mutable: bool,
-}
-
 impl<'a> Read<GlobalType> for Reader<'a> {
     fn read(&mut self) -> Result<GlobalType, Error> {
         Ok({
@@ -3,11 +3,16 @@ use core::slice;
 
 pub fn from_byte_slice<T>(buf: &[u8]) -> &[T] {
     let size = mem::size_of::<T>();
-    assert!(buf.len() % size == 0);
+    assert!(buf.len().is_multiple_of(size));
     let t_len = buf.len() / size;
     unsafe { slice::from_raw_parts(buf.as_ptr() as *const T, t_len) }
 }
 
+/// The aligned part of `buf` as a `u32` slice, for scratch space.
+pub fn u32_slice_mut(buf: &mut [u8]) -> &mut [u32] {
+    unsafe { buf.align_to_mut::<u32>().1 }
+}
+
 // pub fn into_byte_slice<T>(buf: &[T]) -> &[u8] {
 //     debug_assert!(mem::size_of::<T>() == 1);
 //     unsafe { slice::from_raw_parts(buf.as_ptr() as *const u8, buf.len()) }
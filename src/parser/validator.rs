@@ -1,8 +1,43 @@
-use parser::error::Error;
-use parser::reader::FallibleIterator;
-use parser::module::{Module, Id, FuncItem};
+use parser::error::{Error, Location};
+use parser::reader::{Reader, Read, ReadIterator, SectionReadIterator, FallibleIterator};
+use parser::module::{Module, Section, Id, FuncItem, FuncItemIterator, ImportDesc, ExportDesc, SegmentMode, Initializer, Immediate};
+use parser::util::u32_slice_mut;
+use parser::types::{Index, ValueType, BlockType, GlobalType, Limits};
+use opcode::*;
 
-pub fn validate(m: &Module) -> Result<(), Error> {
+// The largest number of 64KiB pages that 32-bit addressing allows.
+const MAX_PAGES: u32 = 65536;
+
+/// The size of each index space, with imports counted first. Counts come from the
+/// section headers and are checked against the section contents as they are read.
+#[derive(Debug, Default)]
+struct Counts {
+    types: u32,
+    functions: u32,
+    imported_functions: u32,
+    tables: u32,
+    memories: u32,
+    globals: u32,
+    imported_globals: u32,
+    tags: u32,
+    elements: u32,
+    data: u32,
+    data_count: Option<u32>,
+    code: u32,
+}
+
+/// Checks the structure of a module without instantiating it: the header, section
+/// order and sizes, index bounds, limits, constant expressions, exports, the start
+/// function and that every function body decodes. Operand types within function
+/// bodies are left to the type checker. `buf` holds four bytes for each export while
+/// their names are compared.
+pub fn validate(buf: &mut [u8], m: &Module) -> Result<(), Error> {
+    validate_at(buf, m, &mut Location::default())
+}
+
+/// Validates like `validate`, leaving `loc` at the item that failed on error.
+pub fn validate_at(buf: &mut [u8], m: &Module, loc: &mut Location) -> Result<(), Error> {
+    *loc = Location::default();
     if m.magic != 0x6d73_6100 {
         return Err(Error::InvalidMagic)
     }
@@ -11,72 +46,687 @@ pub fn validate(m: &Module) -> Result<(), Error> {
         return Err(Error::InvalidVersion)
     }
 
+    let counts = count(m, loc)?;
+
     let mut sections = m.sections();
-    while let Some(section) = sections.next()? {
+    while let Some(section) = next(m, loc, &mut sections)? {
+        *loc = Location { section: Some(section.id), ..*loc };
         match section.id {
             Id::Type => {
+                let mut n = 0;
                 let mut function_types = section.function_types();
-                while let Some(t) = function_types.next()? {
-                    for _ in t.parameters {
-                    }
-                    for _ in t.results {
+                while next(m, loc, &mut function_types)?.is_some() {
+                    n += 1;
+                }
+                check_count(&section, n)?;
+            },
+            Id::Import => {
+                let mut n = 0;
+                let mut imports = section.imports();
+                while let Some(import) = next(m, loc, &mut imports)? {
+                    match import.import_desc {
+                        ImportDesc::Func(type_index) => check_type(&counts, type_index)?,
+                        ImportDesc::Table(_) => {},
+                        ImportDesc::Memory(memory) => check_memory(&memory.limits)?,
+                        ImportDesc::Global(_) => {},
+                        ImportDesc::Tag(tag) => check_tag(m, tag.type_index)?,
                     }
+                    n += 1;
                 }
+                check_count(&section, n)?;
             },
             Id::Function => {
+                let mut n = 0;
                 let mut functions = section.functions();
-                while let Some(_) = functions.next()? {
+                while let Some(type_index) = next(m, loc, &mut functions)? {
+                    check_type(&counts, type_index)?;
+                    n += 1;
                 }
-            },     
+                check_count(&section, n)?;
+            },
             Id::Table => {
+                let mut n = 0;
                 let mut tables = section.tables();
-                while let Some(_) = tables.next()? {
+                while next(m, loc, &mut tables)?.is_some() {
+                    n += 1;
                 }
-            },     
+                check_count(&section, n)?;
+            },
             Id::Memory => {
+                let mut n = 0;
                 let mut memory = section.memory();
-                while let Some(_) = memory.next()? {
+                while let Some(memory) = next(m, loc, &mut memory)? {
+                    check_memory(&memory.limits)?;
+                    n += 1;
                 }
-            },                                 
+                check_count(&section, n)?;
+            },
+            Id::Tag => {
+                let mut n = 0;
+                let mut tags = section.tags();
+                while let Some(tag) = next(m, loc, &mut tags)? {
+                    check_tag(m, tag.type_index)?;
+                    n += 1;
+                }
+                check_count(&section, n)?;
+            },
+            Id::Global => {
+                let mut n = 0;
+                let mut globals = section.globals();
+                while let Some(global) = next(m, loc, &mut globals)? {
+                    check_initializer(m, &counts, &global.init, global.global_type.valtype)?;
+                    n += 1;
+                }
+                check_count(&section, n)?;
+            },
             Id::Export => {
+                let start = m.offset_to(section.buf) as u32;
+                let offsets = u32_slice_mut(buf);
+                let mut n = 0;
                 let mut exports = section.exports();
-                while let Some(_) = exports.next()? {
+                while let Some(export) = next(m, loc, &mut exports)? {
+                    let (index, len) = match export.export_desc {
+                        ExportDesc::Func(index) => (index, counts.functions),
+                        ExportDesc::Table(index) => (index, counts.tables),
+                        ExportDesc::Memory(index) => (index, counts.memories),
+                        ExportDesc::Global(index) => (index, counts.globals),
+                        ExportDesc::Tag(index) => (index, counts.tags),
+                    };
+                    if index >= len {
+                        return Err(match export.export_desc {
+                            ExportDesc::Func(_) => Error::UnknownFunction,
+                            ExportDesc::Table(_) => Error::UnknownTable,
+                            ExportDesc::Memory(_) => Error::UnknownMemory,
+                            ExportDesc::Global(_) => Error::UnknownGlobal,
+                            ExportDesc::Tag(_) => Error::UnknownTag,
+                        })
+                    }
+                    *offsets.get_mut(n as usize).ok_or(Error::OutOfMemory)? = loc.offset - start;
+                    n += 1;
+                }
+                check_count(&section, n)?;
+                check_export_names(&section, &mut offsets[..n as usize], start, loc)?;
+            },
+            Id::Start => {
+                let mut r = Reader::new(section.buf);
+                let func_index: Index = r.read()?;
+                if !r.is_empty() {
+                    return Err(Error::SectionSizeMismatch)
+                }
+                let type_index = function_type_index(m, func_index)?.ok_or(Error::UnknownFunction)?;
+                let signature = m.signature_type(type_index)?.ok_or(Error::UnknownType)?;
+                if !signature.parameters.is_empty() || !signature.results.is_empty() {
+                    return Err(Error::InvalidStartFunction)
                 }
             },
             Id::Element => {
+                let mut n = 0;
                 let mut elements = section.elements();
-                while let Some(element) = elements.next()? {
-                    let mut items = element.iter();
-                    while let Some(_) = items.next()? {
+                while let Some(element) = next(m, loc, &mut elements)? {
+                    if let SegmentMode::Active { index, ref offset } = element.mode {
+                        let elemtype = table_type(m, index)?.ok_or(Error::UnknownTable)?;
+                        if elemtype != element.elemtype {
+                            return Err(Error::TypeMismatch)
+                        }
+                        check_initializer(m, &counts, offset, ValueType::I32)?;
                     }
+                    let mut r = Reader::new(element.init);
+                    let items: u32 = r.read()?;
+                    for _ in 0..items {
+                        if element.exprs {
+                            let init: Initializer = r.read()?;
+                            check_initializer(m, &counts, &init, element.elemtype)?;
+                        } else {
+                            let index: Index = r.read()?;
+                            if index >= counts.functions {
+                                return Err(Error::UnknownFunction)
+                            }
+                        }
+                    }
+                    n += 1;
                 }
-            },      
+                check_count(&section, n)?;
+            },
+            Id::DataCount => {
+                let mut r = Reader::new(section.buf);
+                let _: u32 = r.read()?;
+                if !r.is_empty() {
+                    return Err(Error::SectionSizeMismatch)
+                }
+            },
             Id::Code => {
+                let mut n = 0;
+                let mut type_indices = function_section(m)?;
                 let mut code_section = section.code();
-                while let Some(code) = code_section.next()? {
+                while let Some(code) = next(m, loc, &mut code_section)? {
+                    let body = m.offset_to(code.func.buf) as u32;
+                    loc.function = Some(counts.imported_functions + n);
+                    let type_index = match type_indices.as_mut() {
+                        Some(type_indices) => type_indices.next()?,
+                        None => None,
+                    };
+                    let type_index = type_index.ok_or(Error::InconsistentFunctionCount)?;
+                    let signature = m.signature_type(type_index)?.ok_or(Error::UnknownType)?;
+                    let mut locals = signature.parameters.len() as u64;
+                    let mut labels: u32 = 0;
+                    let mut ended = false;
                     let mut item_iter = code.func.iter();
-                    while let Some(item) = item_iter.next()? {
+                    while let Some(item) = next_item(m, loc, body, &mut item_iter)? {
                         match item {
-                            FuncItem::Local(_local) => {
+                            FuncItem::Local(local) => {
+                                locals += local.n as u64;
+                                if locals > 0xffff_ffff {
+                                    return Err(Error::TooManyLocals)
+                                }
                             },
-                            FuncItem::Instr(_instr) => {
+                            FuncItem::Instr(instr) => {
+                                if ended {
+                                    return Err(Error::InvalidEnd)
+                                }
+                                if instr.op().is_none() {
+                                    return Err(Error::InvalidOpcode)
+                                }
+                                check_immediate(&counts, locals, labels, instr.opcode, &instr.immediate)?;
+                                match instr.opcode {
+                                    BLOCK | LOOP | IF | TRY => labels += 1,
+                                    END if labels == 0 => ended = true,
+                                    END => labels -= 1,
+                                    _ => {},
+                                }
                             }
                         }
                     }
+                    if !ended {
+                        return Err(Error::InvalidEnd)
+                    }
+                    loc.function = None;
+                    loc.instruction = None;
+                    n += 1;
                 }
-            },                    
+                check_count(&section, n)?;
+            },
             Id::Data => {
+                let mut n = 0;
                 let mut data = section.data();
-                while let Some(_d) = data.next()? {
+                while let Some(d) = next(m, loc, &mut data)? {
+                    if let SegmentMode::Active { index, ref offset } = d.mode {
+                        if index >= counts.memories {
+                            return Err(Error::UnknownMemory)
+                        }
+                        check_initializer(m, &counts, offset, ValueType::I32)?;
+                    }
+                    n += 1;
                 }
-            },  
+                check_count(&section, n)?;
+            },
             Id::Custom => {
                 let _custom = section.custom()?;
-            },                          
-            _ => {},
+            },
         }
+        loc.section = None;
+    }
+
+    *loc = Location { offset: (m.offset_to(m.buf) + m.buf.len()) as u32, ..Location::default() };
+    if counts.functions - counts.imported_functions != counts.code {
+        return Err(Error::InconsistentFunctionCount)
+    }
+    if counts.data_count.is_some_and(|data_count| data_count != counts.data) {
+        return Err(Error::InconsistentDataCount)
+    }
+
+    Ok(())
+}
+
+// Position of each section in the order the binary format requires. The tag section
+// sits between the memory and global sections.
+fn section_order(id: Id) -> u8 {
+    match id {
+        Id::Custom => 0,
+        Id::Type => 1,
+        Id::Import => 2,
+        Id::Function => 3,
+        Id::Table => 4,
+        Id::Memory => 5,
+        Id::Tag => 6,
+        Id::Global => 7,
+        Id::Export => 8,
+        Id::Start => 9,
+        Id::Element => 10,
+        Id::DataCount => 11,
+        Id::Code => 12,
+        Id::Data => 13,
+    }
+}
+
+// Checks the section order and counts the entries in each index space.
+fn count(m: &Module, loc: &mut Location) -> Result<Counts, Error> {
+    let mut counts = Counts::default();
+    let mut last = 0;
+    let mut sections = m.sections();
+    while let Some(section) = next(m, loc, &mut sections)? {
+        loc.section = Some(section.id);
+        if section.id != Id::Custom {
+            let order = section_order(section.id);
+            if order <= last {
+                return Err(Error::SectionOutOfOrder)
+            }
+            last = order;
+        }
+        match section.id {
+            Id::Type => counts.types = section_count(&section)?,
+            Id::Import => {
+                let mut imports = section.imports();
+                while let Some(import) = next(m, loc, &mut imports)? {
+                    match import.import_desc {
+                        ImportDesc::Func(_) => counts.imported_functions += 1,
+                        ImportDesc::Table(_) => counts.tables += 1,
+                        ImportDesc::Memory(_) => counts.memories += 1,
+                        ImportDesc::Global(_) => counts.imported_globals += 1,
+                        ImportDesc::Tag(_) => counts.tags += 1,
+                    }
+                }
+                counts.functions = counts.imported_functions;
+                counts.globals = counts.imported_globals;
+            },
+            Id::Function => counts.functions += section_count(&section)?,
+            Id::Table => counts.tables += section_count(&section)?,
+            Id::Memory => counts.memories += section_count(&section)?,
+            Id::Tag => counts.tags += section_count(&section)?,
+            Id::Global => counts.globals += section_count(&section)?,
+            Id::Element => counts.elements = section_count(&section)?,
+            Id::DataCount => counts.data_count = Some(section.data_count()?),
+            Id::Code => counts.code = section_count(&section)?,
+            Id::Data => counts.data = section_count(&section)?,
+            Id::Custom | Id::Export | Id::Start => {},
+        }
+        loc.section = None;
+    }
+    if counts.memories > 1 {
+        return Err(Error::MultipleMemories)
+    }
+    if counts.functions < counts.imported_functions {
+        return Err(Error::InconsistentFunctionCount)
+    }
+    Ok(counts)
+}
+
+// Iterators that know where their next item starts.
+trait Items<'a>: FallibleIterator<Error = Error> {
+    fn rest(&self) -> &'a [u8];
+}
+
+impl<'a, T> Items<'a> for ReadIterator<'a, T> where Reader<'a>: Read<T> {
+    fn rest(&self) -> &'a [u8] { ReadIterator::rest(self) }
+}
+
+impl<'a, T> Items<'a> for SectionReadIterator<'a, T> where Reader<'a>: Read<T> {
+    fn rest(&self) -> &'a [u8] { SectionReadIterator::rest(self) }
+}
+
+impl<'a> Items<'a> for FuncItemIterator<'a> {
+    fn rest(&self) -> &'a [u8] { FuncItemIterator::rest(self) }
+}
+
+// Reads the next item, first moving `loc` to where it starts.
+// Sorts the exports at `offsets` within `section` by name and fails at the first
+// export in the section whose name appeared before it.
+fn check_export_names(section: &Section, offsets: &mut [u32], start: u32, loc: &mut Location) -> Result<(), Error> {
+    let name = |offset: u32| -> &str {
+        Reader::new(&section.buf[offset as usize..]).read().unwrap_or_default()
+    };
+    offsets.sort_unstable_by(|a, b| name(*a).cmp(name(*b)).then(a.cmp(b)));
+    let duplicate = offsets.windows(2)
+        .filter(|pair| name(pair[0]) == name(pair[1]))
+        .map(|pair| pair[1])
+        .min();
+    if let Some(offset) = duplicate {
+        loc.offset = start + offset;
+        return Err(Error::DuplicateExportName)
+    }
+    Ok(())
+}
+
+fn next<'a, I: Items<'a>>(m: &Module, loc: &mut Location, items: &mut I) -> Result<Option<I::Item>, Error> {
+    loc.offset = m.offset_to(items.rest()) as u32;
+    items.next()
+}
+
+// Reads the next item of a function body starting at module offset `body`.
+fn next_item<'a>(m: &Module, loc: &mut Location, body: u32, items: &mut FuncItemIterator<'a>) -> Result<Option<FuncItem<'a>>, Error> {
+    loc.instruction = Some(m.offset_to(items.rest()) as u32 - body);
+    next(m, loc, items)
+}
+
+fn section_count(section: &Section) -> Result<u32, Error> {
+    Reader::new(section.buf).read_var_u32()
+}
+
+fn check_count(section: &Section, n: u32) -> Result<(), Error> {
+    if section_count(section)? != n {
+        return Err(Error::SectionSizeMismatch)
+    }
+    Ok(())
+}
+
+fn check_type(counts: &Counts, type_index: Index) -> Result<(), Error> {
+    if type_index >= counts.types {
+        return Err(Error::UnknownType)
+    }
+    Ok(())
+}
+
+fn check_memory(limits: &Limits) -> Result<(), Error> {
+    if limits.min > MAX_PAGES || limits.max.is_some_and(|max| max > MAX_PAGES) {
+        return Err(Error::MemorySizeTooLarge)
+    }
+    Ok(())
+}
+
+fn check_tag(m: &Module, type_index: Index) -> Result<(), Error> {
+    let signature = m.signature_type(type_index)?.ok_or(Error::UnknownType)?;
+    if !signature.results.is_empty() {
+        return Err(Error::InvalidTagType)
+    }
+    Ok(())
+}
+
+// Constant expressions are a single constant, `ref.null`, `ref.func` or a `global.get`
+// of an immutable imported global, and must produce `t`.
+fn check_initializer(m: &Module, counts: &Counts, init: &Initializer, t: ValueType) -> Result<(), Error> {
+    let found = match init.instr.immediate {
+        Immediate::I32Const { value: _ } => ValueType::I32,
+        Immediate::I64Const { value: _ } => ValueType::I64,
+        Immediate::F32Const { value: _ } => ValueType::F32,
+        Immediate::F64Const { value: _ } => ValueType::F64,
+        #[cfg(feature="simd")]
+        Immediate::V128Const { value: _ } => ValueType::V128,
+        Immediate::RefNull { reftype } if reftype.is_ref() => reftype,
+        Immediate::RefFunc { index } => {
+            if index >= counts.functions {
+                return Err(Error::UnknownFunction)
+            }
+            ValueType::AnyFunc
+        },
+        Immediate::Global { index } if init.instr.opcode == GET_GLOBAL => {
+            if index >= counts.globals {
+                return Err(Error::UnknownGlobal)
+            }
+            match global_type(m, index)? {
+                Some(GlobalType { valtype, mutable: false }) if index < counts.imported_globals => valtype,
+                _ => return Err(Error::ConstantExpressionRequired),
+            }
+        },
+        _ => return Err(Error::ConstantExpressionRequired),
+    };
+    if found != t {
+        return Err(Error::TypeMismatch)
     }
+    Ok(())
+}
 
+// Checks the indices an instruction refers to. `locals` includes the parameters and
+// `labels` is the number of enclosing blocks, not counting the function body.
+fn check_immediate(counts: &Counts, locals: u64, labels: u32, opcode: u8, immediate: &Immediate) -> Result<(), Error> {
+    use self::Immediate::*;
 
+    let memory = || if counts.memories == 0 { Err(Error::UnknownMemory) } else { Ok(()) };
+    let table = |index: Index| if index >= counts.tables { Err(Error::UnknownTable) } else { Ok(()) };
+    let element = |index: Index| if index >= counts.elements { Err(Error::UnknownElementSegment) } else { Ok(()) };
+    let data = |index: Index| match counts.data_count {
+        Some(data_count) if index < data_count => Ok(()),
+        Some(_) => Err(Error::UnknownDataSegment),
+        _ => Err(Error::DataCountRequired),
+    };
+
+    match *immediate {
+        Block { signature: BlockType::Index(index) } => check_type(counts, index)?,
+        Branch { depth } => if depth > labels {
+            return Err(Error::UnknownLabel)
+        },
+        BranchTable { table } => {
+            let mut r = Reader::new(table);
+            while !r.is_empty() {
+                let depth: u32 = r.read()?;
+                if depth > labels {
+                    return Err(Error::UnknownLabel)
+                }
+            }
+        },
+        Local { index } => if index as u64 >= locals {
+            return Err(Error::UnknownLocal)
+        },
+        Global { index } => if index >= counts.globals {
+            return Err(Error::UnknownGlobal)
+        },
+        Call { index } | RefFunc { index } => if index >= counts.functions {
+            return Err(Error::UnknownFunction)
+        },
+        CallIndirect { index, table: t } => {
+            check_type(counts, index)?;
+            table(t)?;
+        },
+        LoadStore { align: _, offset: _ } => memory()?,
+        // `atomic.fence` has a reserved byte but no memory
+        Memory { reserved: _ } => if opcode != ATOMIC_PREFIX {
+            memory()?
+        },
+        MemoryInit { index, reserved: _ } => {
+            memory()?;
+            data(index)?;
+        },
+        DataDrop { index } => data(index)?,
+        MemoryCopy { dst: _, src: _ } => memory()?,
+        Table { index } => table(index)?,
+        TableInit { index, table: t } => {
+            element(index)?;
+            table(t)?;
+        },
+        ElemDrop { index } => element(index)?,
+        TableCopy { dst, src } => {
+            table(dst)?;
+            table(src)?;
+        },
+        Tag { index } if index >= counts.tags => return Err(Error::UnknownTag),
+        #[cfg(feature="simd")]
+        LoadStoreLane { align: _, offset: _, lane: _ } => memory()?,
+        _ => {},
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+// The function section's type indices, if the module has one.
+fn function_section<'a>(m: &'a Module<'a>) -> Result<Option<SectionReadIterator<'a, Index>>, Error> {
+    let mut sections = m.sections();
+    while let Some(section) = sections.next()? {
+        if section.id == Id::Function {
+            return Ok(Some(section.functions()))
+        }
+    }
+    Ok(None)
+}
+
+// The type index of function `index`, counting imported functions first.
+fn function_type_index(m: &Module, index: Index) -> Result<Option<Index>, Error> {
+    let mut n = 0;
+    let mut sections = m.sections();
+    while let Some(section) = sections.next()? {
+        match section.id {
+            Id::Import => {
+                let mut imports = section.imports();
+                while let Some(import) = imports.next()? {
+                    if let ImportDesc::Func(type_index) = import.import_desc {
+                        if n == index {
+                            return Ok(Some(type_index))
+                        }
+                        n += 1;
+                    }
+                }
+            },
+            Id::Function => {
+                let mut functions = section.functions();
+                while let Some(type_index) = functions.next()? {
+                    if n == index {
+                        return Ok(Some(type_index))
+                    }
+                    n += 1;
+                }
+            },
+            _ => {},
+        }
+    }
+    Ok(None)
+}
+
+// The type of global `index`, counting imported globals first.
+fn global_type(m: &Module, index: Index) -> Result<Option<GlobalType>, Error> {
+    let mut n = 0;
+    let mut sections = m.sections();
+    while let Some(section) = sections.next()? {
+        match section.id {
+            Id::Import => {
+                let mut imports = section.imports();
+                while let Some(import) = imports.next()? {
+                    if let ImportDesc::Global(global_type) = import.import_desc {
+                        if n == index {
+                            return Ok(Some(global_type))
+                        }
+                        n += 1;
+                    }
+                }
+            },
+            Id::Global => {
+                let mut globals = section.globals();
+                while let Some(global) = globals.next()? {
+                    if n == index {
+                        return Ok(Some(global.global_type))
+                    }
+                    n += 1;
+                }
+            },
+            _ => {},
+        }
+    }
+    Ok(None)
+}
+
+// The element type of table `index`, counting imported tables first.
+fn table_type(m: &Module, index: Index) -> Result<Option<ValueType>, Error> {
+    let mut n = 0;
+    let mut sections = m.sections();
+    while let Some(section) = sections.next()? {
+        match section.id {
+            Id::Import => {
+                let mut imports = section.imports();
+                while let Some(import) = imports.next()? {
+                    if let ImportDesc::Table(table_type) = import.import_desc {
+                        if n == index {
+                            return Ok(Some(table_type.elemtype))
+                        }
+                        n += 1;
+                    }
+                }
+            },
+            Id::Table => {
+                let mut tables = section.tables();
+                while let Some(table_type) = tables.next()? {
+                    if n == index {
+                        return Ok(Some(table_type.elemtype))
+                    }
+                    n += 1;
+                }
+            },
+            _ => {},
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &[u8] = &[0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+    // (type (func (result i32))) (type (func))
+    const TYPES: &[u8] = &[0x01, 0x08, 0x02, 0x60, 0x00, 0x01, 0x7f, 0x60, 0x00, 0x00];
+    const FUNCS: &[u8] = &[0x03, 0x03, 0x02, 0x00, 0x01];
+    const CODE: &[u8] = &[0x0a, 0x09, 0x02, 0x04, 0x00, 0x41, 0x01, 0x0b, 0x02, 0x00, 0x0b];
+
+    fn check(sections: &[&[u8]]) -> Result<(), Error> {
+        let mut buf = [0u8; 128];
+        let mut len = 0;
+        for s in [HEADER].iter().chain(sections) {
+            buf[len..len + s.len()].copy_from_slice(s);
+            len += s.len();
+        }
+        validate(&mut [0; 64], &Module::new(&buf[..len])?)
+    }
+
+    #[test]
+    fn test_validate_sections() {
+        assert_eq!(check(&[TYPES, FUNCS, CODE]), Ok(()));
+        assert_eq!(check(&[FUNCS, TYPES, CODE]), Err(Error::SectionOutOfOrder));
+        assert_eq!(check(&[TYPES, TYPES, FUNCS, CODE]), Err(Error::SectionOutOfOrder));
+        assert_eq!(check(&[&[0x01, 0x08, 0x03, 0x60, 0x00, 0x01, 0x7f, 0x60, 0x00, 0x00], FUNCS, CODE]),
+            Err(Error::SectionSizeMismatch));
+        assert_eq!(check(&[TYPES, FUNCS]), Err(Error::InconsistentFunctionCount));
+        assert_eq!(check(&[TYPES, &[0x03, 0x03, 0x02, 0x00, 0x02], CODE]), Err(Error::UnknownType));
+    }
+
+    #[test]
+    fn test_validate_leb128() {
+        // Padding is allowed up to the maximum length
+        assert_eq!(check(&[TYPES, &[0x03, 0x07, 0x02, 0x80, 0x80, 0x80, 0x80, 0x00, 0x01], CODE]), Ok(()));
+        assert_eq!(check(&[TYPES, &[0x03, 0x07, 0x02, 0x80, 0x80, 0x80, 0x80, 0x10, 0x01], CODE]),
+            Err(Error::InvalidU32));
+        assert_eq!(check(&[TYPES, &[0x03, 0x08, 0x02, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00, 0x01], CODE]),
+            Err(Error::InvalidU32));
+    }
+
+    #[test]
+    fn test_validate_module() {
+        // (export "a" (func 0)) (export "a" (func 1))
+        assert_eq!(check(&[TYPES, FUNCS, &[0x07, 0x09, 0x02, 0x01, 0x61, 0x00, 0x00, 0x01, 0x61, 0x00, 0x01], CODE]),
+            Err(Error::DuplicateExportName));
+        assert_eq!(check(&[TYPES, FUNCS, &[0x07, 0x05, 0x01, 0x01, 0x61, 0x00, 0x02], CODE]),
+            Err(Error::UnknownFunction));
+        // (start 1) is [] -> [], (start 0) returns i32
+        assert_eq!(check(&[TYPES, FUNCS, &[0x08, 0x01, 0x01], CODE]), Ok(()));
+        assert_eq!(check(&[TYPES, FUNCS, &[0x08, 0x01, 0x00], CODE]), Err(Error::InvalidStartFunction));
+        // (memory 65537), (memory 1) (memory 1)
+        assert_eq!(check(&[&[0x05, 0x05, 0x01, 0x00, 0x81, 0x80, 0x04]]), Err(Error::MemorySizeTooLarge));
+        assert_eq!(check(&[&[0x05, 0x05, 0x02, 0x00, 0x01, 0x00, 0x01]]), Err(Error::MultipleMemories));
+        // (global i32 (i32.add)), (global i32 (i64.const 0))
+        assert_eq!(check(&[&[0x06, 0x05, 0x01, 0x7f, 0x00, 0x6a, 0x0b]]), Err(Error::ConstantExpressionRequired));
+        assert_eq!(check(&[&[0x06, 0x06, 0x01, 0x7f, 0x00, 0x42, 0x00, 0x0b]]), Err(Error::TypeMismatch));
+    }
+
+    #[test]
+    fn test_validate_export_names() {
+        // (export "a" (func 0)) (export "b" (func 1)) (export "a" (func 0))
+        let exports: &[u8] = &[0x07, 0x0d, 0x03, 0x01, 0x61, 0x00, 0x00, 0x01, 0x62, 0x00, 0x01, 0x01, 0x61, 0x00, 0x00];
+        let mut buf = [0u8; 128];
+        let mut len = 0;
+        for s in &[HEADER, TYPES, FUNCS, exports, CODE] {
+            buf[len..len + s.len()].copy_from_slice(s);
+            len += s.len();
+        }
+        let m = Module::new(&buf[..len]).unwrap();
+        let mut loc = Location::default();
+        assert_eq!(validate_at(&mut [0; 64], &m, &mut loc), Err(Error::DuplicateExportName));
+        assert_eq!(loc.offset, 34);
+        assert_eq!(validate(&mut [0; 8], &m), Err(Error::OutOfMemory));
+    }
+
+    #[test]
+    fn test_validate_code() {
+        // (func (result i32) br 1)
+        assert_eq!(check(&[TYPES, FUNCS, &[0x0a, 0x09, 0x02, 0x04, 0x00, 0x0c, 0x01, 0x0b, 0x02, 0x00, 0x0b]]),
+            Err(Error::UnknownLabel));
+        // (func data.drop 0) without a data count section
+        assert_eq!(check(&[TYPES, FUNCS, &[0x0a, 0x0c, 0x02, 0x04, 0x00, 0x41, 0x01, 0x0b, 0x05, 0x00, 0xfc, 0x09, 0x00, 0x0b]]),
+            Err(Error::DataCountRequired));
+        // A body without its final end
+        assert_eq!(check(&[TYPES, FUNCS, &[0x0a, 0x08, 0x02, 0x04, 0x00, 0x41, 0x01, 0x0b, 0x01, 0x00]]),
+            Err(Error::InvalidEnd));
+    }
+}
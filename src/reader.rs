@@ -8,11 +8,18 @@ pub type ReaderResult<T> = Result<T, Error>;
 pub struct Reader<'a> {
     buf: &'a [u8],
     pos: usize,
+    leb: bool,
 }
 
 impl<'a> Reader<'a> {
     pub fn new(buf: &'a [u8]) -> Self {
-        Reader { buf: buf, pos: 0 }
+        Reader { buf, pos: 0, leb: false }
+    }
+
+    /// Creates a reader for original WASM code, whose integer immediates are LEB128 encoded
+    /// and whose sub-opcodes and reserved bytes are read as they are found in the module.
+    pub fn new_leb(buf: &'a [u8]) -> Self {
+        Reader { buf, pos: 0, leb: true }
     }
 
     #[inline]
@@ -20,6 +27,11 @@ impl<'a> Reader<'a> {
         self.buf.len()
     }
 
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
     #[inline]
     pub fn pos(&self) -> usize {
         self.pos
@@ -70,6 +82,7 @@ impl<'a> Reader<'a> {
 
     #[inline]
     pub fn read_u32(&mut self) -> ReaderResult<u32> { 
+        if self.leb { return self.read_var_u32() }
         self.read(4, LittleEndian::read_u32)
     }
 
@@ -90,14 +103,45 @@ impl<'a> Reader<'a> {
 
     #[inline]
     pub fn read_i32(&mut self) -> ReaderResult<i32> { 
+        if self.leb { return self.read_var_i32() }
         self.read(4, LittleEndian::read_i32)
     }
 
     #[inline]
     pub fn read_i64(&mut self) -> ReaderResult<i64> { 
+        if self.leb { return self.read_var_i64() }
         self.read(8, LittleEndian::read_i64)
     }
 
+    /// Reads the sub-opcode following a prefix opcode.
+    #[inline]
+    pub fn read_sub_opcode(&mut self) -> ReaderResult<u8> {
+        if self.leb {
+            // Validation has already rejected sub-opcodes that do not fit
+            return Ok(self.read_var_u32()? as u8)
+        }
+        self.read_u8()
+    }
+
+    /// Skips the reserved memory index byte of original code, which compiled code leaves out.
+    #[inline]
+    pub fn read_reserved(&mut self) -> ReaderResult<()> {
+        if self.leb {
+            self.read_u8()?;
+        }
+        Ok(())
+    }
+
+    /// Skips the alignment hint of a memory access in original code, which compiled code
+    /// leaves out.
+    #[inline]
+    pub fn read_align(&mut self) -> ReaderResult<()> {
+        if self.leb {
+            self.read_var_u32()?;
+        }
+        Ok(())
+    }
+
     #[inline]
     pub fn read_f32(&mut self) -> ReaderResult<f32> { 
         self.read(4, LittleEndian::read_f32)
@@ -114,7 +158,7 @@ impl<'a> Reader<'a> {
         if value <= 1 {
             Ok(value)
         } else {
-            return Err(Error::Leb128Overflow)
+            Err(Error::Leb128Overflow)
         }
     }
 
@@ -124,7 +168,7 @@ impl<'a> Reader<'a> {
         if byte & 0x80 == 0 {
             Ok(byte)
         } else {
-            return Err(Error::Leb128Overflow)
+            Err(Error::Leb128Overflow)
         }
     }
 
@@ -174,7 +218,7 @@ impl<'a> Reader<'a> {
             }            
             Ok(byte as i8)
         } else {
-            return Err(Error::Leb128Overflow)
+            Err(Error::Leb128Overflow)
         }
     }
 
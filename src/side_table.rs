@@ -0,0 +1,147 @@
+//! Side tables for executing a module's original code in place.
+//!
+//! Instead of compiled code, a module loaded with `Environment::load_module_in_place` keeps
+//! the module bytes and a side table, which holds what the interpreter cannot cheaply find
+//! out from the bytecode: where each function body starts and how many locals it has, and
+//! for each branch its target and how many values it drops and keeps. All integers are
+//! little endian `u32`s and offsets are from the start of the module:
+//!
+//! ```text
+//! index    count, then for each local function: start of the first instruction, end of
+//!          the body, parameter count, local count
+//! entries  for each branch in order: offset, target, drop, keep
+//! ```
+//!
+//! `if`, `else`, `br`, `br_if`, `return`, the tail calls and the final `end` of each body
+//! have one entry, and `br_table` has one for each target followed by the default. `if`
+//! branches to its `else` or `end` and `else` to its `end`; those entries keep nothing.
+//!
+//! `try`, `catch` and `catch_all` have two entries. The first entry of `try` targets its
+//! first catch clause and keeps its parameters, and the second targets the end of the try
+//! block, just past its `end`. The first entry of a catch clause branches to the end of the
+//! try block when the previous part falls into it, and the second links to the next clause,
+//! or to the end after the last one. `rethrow` has one entry, whose target is the number of
+//! catch clauses between it and the clause whose exception it rethrows.
+
+use error::Error;
+use cursor::Cursor;
+
+use core::ops::Range;
+
+/// The size of each entry.
+pub const ENTRY_SIZE: usize = 16;
+
+const BODY_SIZE: usize = 16;
+
+/// A local function body as recorded in the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Body {
+    pub start: u32,
+    pub end: u32,
+    pub parameters: u32,
+    pub locals: u32,
+}
+
+/// A branch as recorded in the side table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub target: u32,
+    pub drop: u32,
+    pub keep: u32,
+}
+
+pub struct SideTable<'a> {
+    module: &'a [u8],
+    buf: &'a [u8],
+}
+
+impl<'a> SideTable<'a> {
+    /// Creates a side table for `module` from `buf`, which was built by
+    /// `Compiler::side_table` for it.
+    pub fn new(module: &'a [u8], buf: &'a [u8]) -> Self {
+        SideTable { module, buf }
+    }
+
+    /// The original module, which is executed in place.
+    pub fn module(&self) -> &'a [u8] {
+        self.module
+    }
+
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    pub fn body_count(&self) -> usize {
+        if self.buf.len() < 4 { return 0 }
+        Cursor::new(self.buf).read_u32() as usize
+    }
+
+    pub fn body(&self, index: usize) -> Result<Body, Error> {
+        if index >= self.body_count() {
+            return Err(Error::InvalidFunction { id: index as u32 })
+        }
+        let mut cur = Cursor::new(self.buf);
+        cur.advance(4 + index * BODY_SIZE);
+        Ok(Body {
+            start: cur.read_u32(),
+            end: cur.read_u32(),
+            parameters: cur.read_u32(),
+            locals: cur.read_u32(),
+        })
+    }
+
+    /// Returns where body `index` is in the module.
+    pub fn body_range(&self, index: usize) -> Result<Range<usize>, Error> {
+        let body = self.body(index)?;
+        Ok(body.start as usize .. body.end as usize)
+    }
+
+    fn entries(&self) -> &'a [u8] {
+        let start = 4 + self.body_count() * BODY_SIZE;
+        if start > self.buf.len() { return &[] }
+        &self.buf[start..]
+    }
+
+    fn entry_at<'b>(entries: &'b [u8], i: usize) -> Cursor<'b> {
+        let mut cur = Cursor::new(entries);
+        cur.advance(i * ENTRY_SIZE);
+        cur
+    }
+
+    /// Returns entry `n` of the branch at module offset `pc`, where `n` is only non-zero
+    /// for the targets of a `br_table`.
+    pub fn entry(&self, pc: usize, n: usize) -> Result<Entry, Error> {
+        let missing = || Error::InvalidOpcode(self.module.get(pc).cloned().unwrap_or(0));
+        let entries = self.entries();
+        let count = entries.len() / ENTRY_SIZE;
+        let pc = pc as u32;
+        // Entries are sorted by offset, so find the first one for `pc`
+        let (mut lo, mut hi) = (0, count);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if SideTable::entry_at(entries, mid).read_u32() < pc {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        let i = lo + n;
+        if i >= count {
+            return Err(missing())
+        }
+        let mut cur = SideTable::entry_at(entries, i);
+        if cur.read_u32() != pc {
+            return Err(missing())
+        }
+        Ok(Entry { target: cur.read_u32(), drop: cur.read_u32(), keep: cur.read_u32() })
+    }
+
+    /// Returns whether there is an entry for the instruction at `pc`.
+    pub fn has_entry(&self, pc: usize) -> bool {
+        self.entry(pc, 0).is_ok()
+    }
+}
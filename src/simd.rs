@@ -0,0 +1,658 @@
+//! Portable scalar implementations of the 128-bit SIMD lane operations.
+//!
+//! A `v128` is held as a `u128` with lane 0 in the least significant bits, matching the
+//! little-endian memory layout. Scalar operands and results use the same raw encoding as
+//! `Value`.
+
+use opcode::*;
+use interp::{f32_min, f32_max, f64_min, f64_max};
+use fp::{single, double, promote, demote, canonical_f32, canonical_f64};
+
+macro_rules! int_lanes {
+    ($get:ident, $set:ident, $t:ty, $u:ty, $n:expr) => {
+        fn $get(v: u128) -> [$t; $n] {
+            let mut out = [0; $n];
+            for i in 0..$n {
+                out[i] = (v >> (i * (128 / $n))) as $u as $t;
+            }
+            out
+        }
+
+        fn $set(lanes: [$t; $n]) -> u128 {
+            let mut v = 0;
+            for i in 0..$n {
+                v |= (lanes[i] as $u as u128) << (i * (128 / $n));
+            }
+            v
+        }
+    }
+}
+
+int_lanes!(i8x16, from_i8x16, i8, u8, 16);
+int_lanes!(u8x16, from_u8x16, u8, u8, 16);
+int_lanes!(i16x8, from_i16x8, i16, u16, 8);
+int_lanes!(u16x8, from_u16x8, u16, u16, 8);
+int_lanes!(i32x4, from_i32x4, i32, u32, 4);
+int_lanes!(u32x4, from_u32x4, u32, u32, 4);
+int_lanes!(i64x2, from_i64x2, i64, u64, 2);
+int_lanes!(u64x2, from_u64x2, u64, u64, 2);
+
+fn f32x4(v: u128) -> [f32; 4] {
+    let mut out = [0.0; 4];
+    for (o, b) in out.iter_mut().zip(u32x4(v).iter()) {
+        *o = f32::from_bits(*b);
+    }
+    out
+}
+
+fn from_f32x4(lanes: [f32; 4]) -> u128 {
+    let mut out = [0; 4];
+    for (o, f) in out.iter_mut().zip(lanes.iter()) {
+        *o = f.to_bits();
+    }
+    from_u32x4(out)
+}
+
+fn f64x2(v: u128) -> [f64; 2] {
+    let b = u64x2(v);
+    [f64::from_bits(b[0]), f64::from_bits(b[1])]
+}
+
+fn from_f64x2(lanes: [f64; 2]) -> u128 {
+    from_u64x2([lanes[0].to_bits(), lanes[1].to_bits()])
+}
+
+// Applies `$e` to each lane of `$a`.
+macro_rules! map {
+    ($get:ident, $set:ident, $a:expr, |$x:ident| $e:expr) => {{
+        let mut r = $get($a);
+        for l in r.iter_mut() {
+            let $x = *l;
+            *l = $e;
+        }
+        $set(r)
+    }}
+}
+
+// Applies `$e` to each pair of lanes of `$a` and `$b`.
+macro_rules! zip {
+    ($get:ident, $set:ident, $a:expr, $b:expr, |$x:ident, $y:ident| $e:expr) => {{
+        let (mut r, b) = ($get($a), $get($b));
+        for i in 0..r.len() {
+            let ($x, $y) = (r[i], b[i]);
+            r[i] = $e;
+        }
+        $set(r)
+    }}
+}
+
+// Compares each pair of lanes, producing all ones where `$e` holds. `$mask` reads the
+// unsigned lanes of the same width as the operands.
+macro_rules! cmp {
+    ($get:ident, $mask:ident, $set:ident, $a:expr, $b:expr, |$x:ident, $y:ident| $e:expr) => {{
+        let (a, b) = ($get($a), $get($b));
+        let mut r = $mask(0);
+        for i in 0..r.len() {
+            let ($x, $y) = (a[i], b[i]);
+            r[i] = if $e { !0 } else { 0 };
+        }
+        $set(r)
+    }}
+}
+
+// Widens the lanes of `$a` starting at `$from` to the output lane type.
+macro_rules! extend {
+    ($get:ident, $out:ident, $set:ident, $a:expr, $from:expr) => {{
+        let a = $get($a);
+        let mut r = $out(0);
+        for i in 0..r.len() {
+            r[i] = a[$from + i].into();
+        }
+        $set(r)
+    }}
+}
+
+// Multiplies the widened lanes of `$a` and `$b` starting at `$from`.
+macro_rules! extmul {
+    ($get:ident, $out:ident, $set:ident, $a:expr, $b:expr, $from:expr) => {{
+        let (a, b) = ($get($a), $get($b));
+        let mut r = $out(0);
+        for i in 0..r.len() {
+            r[i] = a[$from + i] as _;
+            r[i] = r[i].wrapping_mul(b[$from + i] as _);
+        }
+        $set(r)
+    }}
+}
+
+// Adds adjacent pairs of lanes of `$a`, widened.
+macro_rules! extadd {
+    ($get:ident, $out:ident, $set:ident, $a:expr) => {{
+        let a = $get($a);
+        let mut r = $out(0);
+        for i in 0..r.len() {
+            r[i] = a[2 * i] as _;
+            r[i] = r[i].wrapping_add(a[2 * i + 1] as _);
+        }
+        $set(r)
+    }}
+}
+
+// Narrows the lanes of `$a` followed by `$b`, saturating to `$lo..=$hi`.
+macro_rules! narrow {
+    ($get:ident, $out:ident, $set:ident, $a:expr, $b:expr, $lo:expr, $hi:expr) => {{
+        let (a, b) = ($get($a), $get($b));
+        let n = a.len();
+        let mut r = $out(0);
+        for i in 0..r.len() {
+            let x = if i < n { a[i] } else { b[i - n] };
+            r[i] = if x < $lo { $lo } else if x > $hi { $hi } else { x } as _;
+        }
+        $set(r)
+    }}
+}
+
+// Collects the top bit of each lane.
+macro_rules! bitmask {
+    ($get:ident, $a:expr) => {{
+        let mut m = 0;
+        for (i, x) in $get($a).iter().enumerate() {
+            if *x < 0 {
+                m |= 1 << i;
+            }
+        }
+        m
+    }}
+}
+
+macro_rules! all_true {
+    ($get:ident, $a:expr) => {
+        $get($a).iter().all(|x| *x != 0) as u128
+    }
+}
+
+fn f32_pmin(x: f32, y: f32) -> f32 { if single::lt(y, x) { y } else { x } }
+fn f32_pmax(x: f32, y: f32) -> f32 { if single::lt(x, y) { y } else { x } }
+fn f64_pmin(x: f64, y: f64) -> f64 { if double::lt(y, x) { y } else { x } }
+fn f64_pmax(x: f64, y: f64) -> f64 { if double::lt(x, y) { y } else { x } }
+
+fn avgr_u8(x: u8, y: u8) -> u8 { (x as u16 + y as u16).div_ceil(2) as u8 }
+fn avgr_u16(x: u16, y: u16) -> u16 { (x as u32 + y as u32).div_ceil(2) as u16 }
+
+fn q15mulr_sat(x: i16, y: i16) -> i16 {
+    let r = (x as i32 * y as i32 + 0x4000) >> 15;
+    if r > i16::MAX as i32 { i16::MAX } else { r as i16 }
+}
+
+/// Executes a single-operand instruction. `a` holds a `v128`, or the scalar operand of a
+/// `splat`. Returns `None` if `sub` is not a single-operand instruction.
+pub fn unary(sub: u8, a: u128) -> Option<u128> {
+    Some(match sub {
+        I8X16_SPLAT => from_u8x16([a as u8; 16]),
+        I16X8_SPLAT => from_u16x8([a as u16; 8]),
+        I32X4_SPLAT | F32X4_SPLAT => from_u32x4([a as u32; 4]),
+        I64X2_SPLAT | F64X2_SPLAT => from_u64x2([a as u64; 2]),
+
+        V128_NOT => !a,
+        V128_ANY_TRUE => (a != 0) as u128,
+
+        I8X16_ABS => map!(i8x16, from_i8x16, a, |x| x.wrapping_abs()),
+        I8X16_NEG => map!(i8x16, from_i8x16, a, |x| x.wrapping_neg()),
+        I8X16_POPCNT => map!(u8x16, from_u8x16, a, |x| x.count_ones() as u8),
+        I8X16_ALL_TRUE => all_true!(u8x16, a),
+        I8X16_BITMASK => bitmask!(i8x16, a),
+        I16X8_ABS => map!(i16x8, from_i16x8, a, |x| x.wrapping_abs()),
+        I16X8_NEG => map!(i16x8, from_i16x8, a, |x| x.wrapping_neg()),
+        I16X8_ALL_TRUE => all_true!(u16x8, a),
+        I16X8_BITMASK => bitmask!(i16x8, a),
+        I32X4_ABS => map!(i32x4, from_i32x4, a, |x| x.wrapping_abs()),
+        I32X4_NEG => map!(i32x4, from_i32x4, a, |x| x.wrapping_neg()),
+        I32X4_ALL_TRUE => all_true!(u32x4, a),
+        I32X4_BITMASK => bitmask!(i32x4, a),
+        I64X2_ABS => map!(i64x2, from_i64x2, a, |x| x.wrapping_abs()),
+        I64X2_NEG => map!(i64x2, from_i64x2, a, |x| x.wrapping_neg()),
+        I64X2_ALL_TRUE => all_true!(u64x2, a),
+        I64X2_BITMASK => bitmask!(i64x2, a),
+
+        I16X8_EXTADD_PAIRWISE_I8X16_S => extadd!(i8x16, i16x8, from_i16x8, a),
+        I16X8_EXTADD_PAIRWISE_I8X16_U => extadd!(u8x16, u16x8, from_u16x8, a),
+        I32X4_EXTADD_PAIRWISE_I16X8_S => extadd!(i16x8, i32x4, from_i32x4, a),
+        I32X4_EXTADD_PAIRWISE_I16X8_U => extadd!(u16x8, u32x4, from_u32x4, a),
+
+        I16X8_EXTEND_LOW_I8X16_S => extend!(i8x16, i16x8, from_i16x8, a, 0),
+        I16X8_EXTEND_HIGH_I8X16_S => extend!(i8x16, i16x8, from_i16x8, a, 8),
+        I16X8_EXTEND_LOW_I8X16_U => extend!(u8x16, u16x8, from_u16x8, a, 0),
+        I16X8_EXTEND_HIGH_I8X16_U => extend!(u8x16, u16x8, from_u16x8, a, 8),
+        I32X4_EXTEND_LOW_I16X8_S => extend!(i16x8, i32x4, from_i32x4, a, 0),
+        I32X4_EXTEND_HIGH_I16X8_S => extend!(i16x8, i32x4, from_i32x4, a, 4),
+        I32X4_EXTEND_LOW_I16X8_U => extend!(u16x8, u32x4, from_u32x4, a, 0),
+        I32X4_EXTEND_HIGH_I16X8_U => extend!(u16x8, u32x4, from_u32x4, a, 4),
+        I64X2_EXTEND_LOW_I32X4_S => extend!(i32x4, i64x2, from_i64x2, a, 0),
+        I64X2_EXTEND_HIGH_I32X4_S => extend!(i32x4, i64x2, from_i64x2, a, 2),
+        I64X2_EXTEND_LOW_I32X4_U => extend!(u32x4, u64x2, from_u64x2, a, 0),
+        I64X2_EXTEND_HIGH_I32X4_U => extend!(u32x4, u64x2, from_u64x2, a, 2),
+
+        F32X4_ABS => map!(f32x4, from_f32x4, a, |x| x.abs()),
+        F32X4_NEG => map!(f32x4, from_f32x4, a, |x| -x),
+        F32X4_SQRT => map!(f32x4, from_f32x4, a, |x| single::sqrt(x)),
+        F32X4_CEIL => map!(f32x4, from_f32x4, a, |x| single::ceil(x)),
+        F32X4_FLOOR => map!(f32x4, from_f32x4, a, |x| single::floor(x)),
+        F32X4_TRUNC => map!(f32x4, from_f32x4, a, |x| single::trunc(x)),
+        F32X4_NEAREST => map!(f32x4, from_f32x4, a, |x| single::nearest(x)),
+        F64X2_ABS => map!(f64x2, from_f64x2, a, |x| x.abs()),
+        F64X2_NEG => map!(f64x2, from_f64x2, a, |x| -x),
+        F64X2_SQRT => map!(f64x2, from_f64x2, a, |x| double::sqrt(x)),
+        F64X2_CEIL => map!(f64x2, from_f64x2, a, |x| double::ceil(x)),
+        F64X2_FLOOR => map!(f64x2, from_f64x2, a, |x| double::floor(x)),
+        F64X2_TRUNC => map!(f64x2, from_f64x2, a, |x| double::trunc(x)),
+        F64X2_NEAREST => map!(f64x2, from_f64x2, a, |x| double::nearest(x)),
+
+        // Conversions to integers saturate and map NaN to 0, as trunc_sat requires.
+        I32X4_TRUNC_SAT_F32X4_S => {
+            let f = f32x4(a);
+            from_i32x4([single::to_i32(f[0]), single::to_i32(f[1]), single::to_i32(f[2]), single::to_i32(f[3])])
+        },
+        I32X4_TRUNC_SAT_F32X4_U => {
+            let f = f32x4(a);
+            from_u32x4([single::to_u32(f[0]), single::to_u32(f[1]), single::to_u32(f[2]), single::to_u32(f[3])])
+        },
+        I32X4_TRUNC_SAT_F64X2_S_ZERO => {
+            let f = f64x2(a);
+            from_i32x4([double::to_i32(f[0]), double::to_i32(f[1]), 0, 0])
+        },
+        I32X4_TRUNC_SAT_F64X2_U_ZERO => {
+            let f = f64x2(a);
+            from_u32x4([double::to_u32(f[0]), double::to_u32(f[1]), 0, 0])
+        },
+        F32X4_CONVERT_I32X4_S => {
+            let i = i32x4(a);
+            from_f32x4([single::from_i32(i[0]), single::from_i32(i[1]), single::from_i32(i[2]), single::from_i32(i[3])])
+        },
+        F32X4_CONVERT_I32X4_U => {
+            let i = u32x4(a);
+            from_f32x4([single::from_u32(i[0]), single::from_u32(i[1]), single::from_u32(i[2]), single::from_u32(i[3])])
+        },
+        F64X2_CONVERT_LOW_I32X4_S => {
+            let i = i32x4(a);
+            from_f64x2([double::from_i32(i[0]), double::from_i32(i[1])])
+        },
+        F64X2_CONVERT_LOW_I32X4_U => {
+            let i = u32x4(a);
+            from_f64x2([double::from_u32(i[0]), double::from_u32(i[1])])
+        },
+        F32X4_DEMOTE_F64X2_ZERO => {
+            let f = f64x2(a);
+            from_f32x4([demote(f[0]), demote(f[1]), 0.0, 0.0])
+        },
+        F64X2_PROMOTE_LOW_F32X4 => {
+            let f = f32x4(a);
+            from_f64x2([promote(f[0]), promote(f[1])])
+        },
+        _ => return None,
+    })
+}
+
+/// Executes a two-operand instruction. `b` holds a `v128`, or the `i32` shift count of a
+/// shift. Returns `None` if `sub` is not a two-operand instruction.
+pub fn binary(sub: u8, a: u128, b: u128) -> Option<u128> {
+    Some(match sub {
+        V128_AND => a & b,
+        V128_ANDNOT => a & !b,
+        V128_OR => a | b,
+        V128_XOR => a ^ b,
+
+        I8X16_SWIZZLE => {
+            let (a, b) = (u8x16(a), u8x16(b));
+            let mut r = [0; 16];
+            for i in 0..16 {
+                if b[i] < 16 {
+                    r[i] = a[b[i] as usize];
+                }
+            }
+            from_u8x16(r)
+        },
+
+        I8X16_EQ => cmp!(u8x16, u8x16, from_u8x16, a, b, |x, y| x == y),
+        I8X16_NE => cmp!(u8x16, u8x16, from_u8x16, a, b, |x, y| x != y),
+        I8X16_LT_S => cmp!(i8x16, u8x16, from_u8x16, a, b, |x, y| x < y),
+        I8X16_LT_U => cmp!(u8x16, u8x16, from_u8x16, a, b, |x, y| x < y),
+        I8X16_GT_S => cmp!(i8x16, u8x16, from_u8x16, a, b, |x, y| x > y),
+        I8X16_GT_U => cmp!(u8x16, u8x16, from_u8x16, a, b, |x, y| x > y),
+        I8X16_LE_S => cmp!(i8x16, u8x16, from_u8x16, a, b, |x, y| x <= y),
+        I8X16_LE_U => cmp!(u8x16, u8x16, from_u8x16, a, b, |x, y| x <= y),
+        I8X16_GE_S => cmp!(i8x16, u8x16, from_u8x16, a, b, |x, y| x >= y),
+        I8X16_GE_U => cmp!(u8x16, u8x16, from_u8x16, a, b, |x, y| x >= y),
+        I16X8_EQ => cmp!(u16x8, u16x8, from_u16x8, a, b, |x, y| x == y),
+        I16X8_NE => cmp!(u16x8, u16x8, from_u16x8, a, b, |x, y| x != y),
+        I16X8_LT_S => cmp!(i16x8, u16x8, from_u16x8, a, b, |x, y| x < y),
+        I16X8_LT_U => cmp!(u16x8, u16x8, from_u16x8, a, b, |x, y| x < y),
+        I16X8_GT_S => cmp!(i16x8, u16x8, from_u16x8, a, b, |x, y| x > y),
+        I16X8_GT_U => cmp!(u16x8, u16x8, from_u16x8, a, b, |x, y| x > y),
+        I16X8_LE_S => cmp!(i16x8, u16x8, from_u16x8, a, b, |x, y| x <= y),
+        I16X8_LE_U => cmp!(u16x8, u16x8, from_u16x8, a, b, |x, y| x <= y),
+        I16X8_GE_S => cmp!(i16x8, u16x8, from_u16x8, a, b, |x, y| x >= y),
+        I16X8_GE_U => cmp!(u16x8, u16x8, from_u16x8, a, b, |x, y| x >= y),
+        I32X4_EQ => cmp!(u32x4, u32x4, from_u32x4, a, b, |x, y| x == y),
+        I32X4_NE => cmp!(u32x4, u32x4, from_u32x4, a, b, |x, y| x != y),
+        I32X4_LT_S => cmp!(i32x4, u32x4, from_u32x4, a, b, |x, y| x < y),
+        I32X4_LT_U => cmp!(u32x4, u32x4, from_u32x4, a, b, |x, y| x < y),
+        I32X4_GT_S => cmp!(i32x4, u32x4, from_u32x4, a, b, |x, y| x > y),
+        I32X4_GT_U => cmp!(u32x4, u32x4, from_u32x4, a, b, |x, y| x > y),
+        I32X4_LE_S => cmp!(i32x4, u32x4, from_u32x4, a, b, |x, y| x <= y),
+        I32X4_LE_U => cmp!(u32x4, u32x4, from_u32x4, a, b, |x, y| x <= y),
+        I32X4_GE_S => cmp!(i32x4, u32x4, from_u32x4, a, b, |x, y| x >= y),
+        I32X4_GE_U => cmp!(u32x4, u32x4, from_u32x4, a, b, |x, y| x >= y),
+        I64X2_EQ => cmp!(u64x2, u64x2, from_u64x2, a, b, |x, y| x == y),
+        I64X2_NE => cmp!(u64x2, u64x2, from_u64x2, a, b, |x, y| x != y),
+        I64X2_LT_S => cmp!(i64x2, u64x2, from_u64x2, a, b, |x, y| x < y),
+        I64X2_GT_S => cmp!(i64x2, u64x2, from_u64x2, a, b, |x, y| x > y),
+        I64X2_LE_S => cmp!(i64x2, u64x2, from_u64x2, a, b, |x, y| x <= y),
+        I64X2_GE_S => cmp!(i64x2, u64x2, from_u64x2, a, b, |x, y| x >= y),
+        F32X4_EQ => cmp!(f32x4, u32x4, from_u32x4, a, b, |x, y| single::eq(x, y)),
+        F32X4_NE => cmp!(f32x4, u32x4, from_u32x4, a, b, |x, y| !single::eq(x, y)),
+        F32X4_LT => cmp!(f32x4, u32x4, from_u32x4, a, b, |x, y| single::lt(x, y)),
+        F32X4_GT => cmp!(f32x4, u32x4, from_u32x4, a, b, |x, y| single::lt(y, x)),
+        F32X4_LE => cmp!(f32x4, u32x4, from_u32x4, a, b, |x, y| single::le(x, y)),
+        F32X4_GE => cmp!(f32x4, u32x4, from_u32x4, a, b, |x, y| single::le(y, x)),
+        F64X2_EQ => cmp!(f64x2, u64x2, from_u64x2, a, b, |x, y| double::eq(x, y)),
+        F64X2_NE => cmp!(f64x2, u64x2, from_u64x2, a, b, |x, y| !double::eq(x, y)),
+        F64X2_LT => cmp!(f64x2, u64x2, from_u64x2, a, b, |x, y| double::lt(x, y)),
+        F64X2_GT => cmp!(f64x2, u64x2, from_u64x2, a, b, |x, y| double::lt(y, x)),
+        F64X2_LE => cmp!(f64x2, u64x2, from_u64x2, a, b, |x, y| double::le(x, y)),
+        F64X2_GE => cmp!(f64x2, u64x2, from_u64x2, a, b, |x, y| double::le(y, x)),
+
+        // Shift counts are taken modulo the lane width.
+        I8X16_SHL => map!(u8x16, from_u8x16, a, |x| x.wrapping_shl(b as u32)),
+        I8X16_SHR_S => map!(i8x16, from_i8x16, a, |x| x.wrapping_shr(b as u32)),
+        I8X16_SHR_U => map!(u8x16, from_u8x16, a, |x| x.wrapping_shr(b as u32)),
+        I16X8_SHL => map!(u16x8, from_u16x8, a, |x| x.wrapping_shl(b as u32)),
+        I16X8_SHR_S => map!(i16x8, from_i16x8, a, |x| x.wrapping_shr(b as u32)),
+        I16X8_SHR_U => map!(u16x8, from_u16x8, a, |x| x.wrapping_shr(b as u32)),
+        I32X4_SHL => map!(u32x4, from_u32x4, a, |x| x.wrapping_shl(b as u32)),
+        I32X4_SHR_S => map!(i32x4, from_i32x4, a, |x| x.wrapping_shr(b as u32)),
+        I32X4_SHR_U => map!(u32x4, from_u32x4, a, |x| x.wrapping_shr(b as u32)),
+        I64X2_SHL => map!(u64x2, from_u64x2, a, |x| x.wrapping_shl(b as u32)),
+        I64X2_SHR_S => map!(i64x2, from_i64x2, a, |x| x.wrapping_shr(b as u32)),
+        I64X2_SHR_U => map!(u64x2, from_u64x2, a, |x| x.wrapping_shr(b as u32)),
+
+        I8X16_NARROW_I16X8_S => narrow!(i16x8, i8x16, from_i8x16, a, b, -0x80, 0x7f),
+        I8X16_NARROW_I16X8_U => narrow!(i16x8, u8x16, from_u8x16, a, b, 0, 0xff),
+        I16X8_NARROW_I32X4_S => narrow!(i32x4, i16x8, from_i16x8, a, b, -0x8000, 0x7fff),
+        I16X8_NARROW_I32X4_U => narrow!(i32x4, u16x8, from_u16x8, a, b, 0, 0xffff),
+
+        I8X16_ADD => zip!(u8x16, from_u8x16, a, b, |x, y| x.wrapping_add(y)),
+        I8X16_ADD_SAT_S => zip!(i8x16, from_i8x16, a, b, |x, y| x.saturating_add(y)),
+        I8X16_ADD_SAT_U => zip!(u8x16, from_u8x16, a, b, |x, y| x.saturating_add(y)),
+        I8X16_SUB => zip!(u8x16, from_u8x16, a, b, |x, y| x.wrapping_sub(y)),
+        I8X16_SUB_SAT_S => zip!(i8x16, from_i8x16, a, b, |x, y| x.saturating_sub(y)),
+        I8X16_SUB_SAT_U => zip!(u8x16, from_u8x16, a, b, |x, y| x.saturating_sub(y)),
+        I8X16_MIN_S => zip!(i8x16, from_i8x16, a, b, |x, y| if x < y { x } else { y }),
+        I8X16_MIN_U => zip!(u8x16, from_u8x16, a, b, |x, y| if x < y { x } else { y }),
+        I8X16_MAX_S => zip!(i8x16, from_i8x16, a, b, |x, y| if x > y { x } else { y }),
+        I8X16_MAX_U => zip!(u8x16, from_u8x16, a, b, |x, y| if x > y { x } else { y }),
+        I8X16_AVGR_U => zip!(u8x16, from_u8x16, a, b, |x, y| avgr_u8(x, y)),
+
+        I16X8_ADD => zip!(u16x8, from_u16x8, a, b, |x, y| x.wrapping_add(y)),
+        I16X8_ADD_SAT_S => zip!(i16x8, from_i16x8, a, b, |x, y| x.saturating_add(y)),
+        I16X8_ADD_SAT_U => zip!(u16x8, from_u16x8, a, b, |x, y| x.saturating_add(y)),
+        I16X8_SUB => zip!(u16x8, from_u16x8, a, b, |x, y| x.wrapping_sub(y)),
+        I16X8_SUB_SAT_S => zip!(i16x8, from_i16x8, a, b, |x, y| x.saturating_sub(y)),
+        I16X8_SUB_SAT_U => zip!(u16x8, from_u16x8, a, b, |x, y| x.saturating_sub(y)),
+        I16X8_MUL => zip!(u16x8, from_u16x8, a, b, |x, y| x.wrapping_mul(y)),
+        I16X8_MIN_S => zip!(i16x8, from_i16x8, a, b, |x, y| if x < y { x } else { y }),
+        I16X8_MIN_U => zip!(u16x8, from_u16x8, a, b, |x, y| if x < y { x } else { y }),
+        I16X8_MAX_S => zip!(i16x8, from_i16x8, a, b, |x, y| if x > y { x } else { y }),
+        I16X8_MAX_U => zip!(u16x8, from_u16x8, a, b, |x, y| if x > y { x } else { y }),
+        I16X8_AVGR_U => zip!(u16x8, from_u16x8, a, b, |x, y| avgr_u16(x, y)),
+        I16X8_Q15MULR_SAT_S => zip!(i16x8, from_i16x8, a, b, |x, y| q15mulr_sat(x, y)),
+
+        I32X4_ADD => zip!(u32x4, from_u32x4, a, b, |x, y| x.wrapping_add(y)),
+        I32X4_SUB => zip!(u32x4, from_u32x4, a, b, |x, y| x.wrapping_sub(y)),
+        I32X4_MUL => zip!(u32x4, from_u32x4, a, b, |x, y| x.wrapping_mul(y)),
+        I32X4_MIN_S => zip!(i32x4, from_i32x4, a, b, |x, y| if x < y { x } else { y }),
+        I32X4_MIN_U => zip!(u32x4, from_u32x4, a, b, |x, y| if x < y { x } else { y }),
+        I32X4_MAX_S => zip!(i32x4, from_i32x4, a, b, |x, y| if x > y { x } else { y }),
+        I32X4_MAX_U => zip!(u32x4, from_u32x4, a, b, |x, y| if x > y { x } else { y }),
+        I32X4_DOT_I16X8_S => {
+            let (a, b) = (i16x8(a), i16x8(b));
+            let mut r = [0i32; 4];
+            for i in 0..4 {
+                let lo = a[2 * i] as i32 * b[2 * i] as i32;
+                let hi = a[2 * i + 1] as i32 * b[2 * i + 1] as i32;
+                r[i] = lo.wrapping_add(hi);
+            }
+            from_i32x4(r)
+        },
+
+        I64X2_ADD => zip!(u64x2, from_u64x2, a, b, |x, y| x.wrapping_add(y)),
+        I64X2_SUB => zip!(u64x2, from_u64x2, a, b, |x, y| x.wrapping_sub(y)),
+        I64X2_MUL => zip!(u64x2, from_u64x2, a, b, |x, y| x.wrapping_mul(y)),
+
+        I16X8_EXTMUL_LOW_I8X16_S => extmul!(i8x16, i16x8, from_i16x8, a, b, 0),
+        I16X8_EXTMUL_HIGH_I8X16_S => extmul!(i8x16, i16x8, from_i16x8, a, b, 8),
+        I16X8_EXTMUL_LOW_I8X16_U => extmul!(u8x16, u16x8, from_u16x8, a, b, 0),
+        I16X8_EXTMUL_HIGH_I8X16_U => extmul!(u8x16, u16x8, from_u16x8, a, b, 8),
+        I32X4_EXTMUL_LOW_I16X8_S => extmul!(i16x8, i32x4, from_i32x4, a, b, 0),
+        I32X4_EXTMUL_HIGH_I16X8_S => extmul!(i16x8, i32x4, from_i32x4, a, b, 4),
+        I32X4_EXTMUL_LOW_I16X8_U => extmul!(u16x8, u32x4, from_u32x4, a, b, 0),
+        I32X4_EXTMUL_HIGH_I16X8_U => extmul!(u16x8, u32x4, from_u32x4, a, b, 4),
+        I64X2_EXTMUL_LOW_I32X4_S => extmul!(i32x4, i64x2, from_i64x2, a, b, 0),
+        I64X2_EXTMUL_HIGH_I32X4_S => extmul!(i32x4, i64x2, from_i64x2, a, b, 2),
+        I64X2_EXTMUL_LOW_I32X4_U => extmul!(u32x4, u64x2, from_u64x2, a, b, 0),
+        I64X2_EXTMUL_HIGH_I32X4_U => extmul!(u32x4, u64x2, from_u64x2, a, b, 2),
+
+        F32X4_ADD => zip!(f32x4, from_f32x4, a, b, |x, y| single::add(x, y)),
+        F32X4_SUB => zip!(f32x4, from_f32x4, a, b, |x, y| single::sub(x, y)),
+        F32X4_MUL => zip!(f32x4, from_f32x4, a, b, |x, y| single::mul(x, y)),
+        F32X4_DIV => zip!(f32x4, from_f32x4, a, b, |x, y| single::div(x, y)),
+        F32X4_MIN => zip!(f32x4, from_f32x4, a, b, |x, y| f32_min(x, y)),
+        F32X4_MAX => zip!(f32x4, from_f32x4, a, b, |x, y| f32_max(x, y)),
+        F32X4_PMIN => zip!(f32x4, from_f32x4, a, b, |x, y| f32_pmin(x, y)),
+        F32X4_PMAX => zip!(f32x4, from_f32x4, a, b, |x, y| f32_pmax(x, y)),
+        F64X2_ADD => zip!(f64x2, from_f64x2, a, b, |x, y| double::add(x, y)),
+        F64X2_SUB => zip!(f64x2, from_f64x2, a, b, |x, y| double::sub(x, y)),
+        F64X2_MUL => zip!(f64x2, from_f64x2, a, b, |x, y| double::mul(x, y)),
+        F64X2_DIV => zip!(f64x2, from_f64x2, a, b, |x, y| double::div(x, y)),
+        F64X2_MIN => zip!(f64x2, from_f64x2, a, b, |x, y| f64_min(x, y)),
+        F64X2_MAX => zip!(f64x2, from_f64x2, a, b, |x, y| f64_max(x, y)),
+        F64X2_PMIN => zip!(f64x2, from_f64x2, a, b, |x, y| f64_pmin(x, y)),
+        F64X2_PMAX => zip!(f64x2, from_f64x2, a, b, |x, y| f64_pmax(x, y)),
+        _ => return None,
+    })
+}
+
+/// Replaces NaN lanes in `v`, the result of `sub`, with the canonical NaN if `sub` is float
+/// arithmetic. Instructions that only move or compare lanes are left alone.
+pub fn canonicalize(sub: u8, v: u128) -> u128 {
+    match sub {
+        F32X4_CEIL | F32X4_FLOOR | F32X4_TRUNC | F32X4_NEAREST | F32X4_SQRT |
+        F32X4_ADD | F32X4_SUB | F32X4_MUL | F32X4_DIV | F32X4_MIN | F32X4_MAX |
+        F32X4_DEMOTE_F64X2_ZERO => map!(f32x4, from_f32x4, v, |x| canonical_f32(x)),
+        F64X2_CEIL | F64X2_FLOOR | F64X2_TRUNC | F64X2_NEAREST | F64X2_SQRT |
+        F64X2_ADD | F64X2_SUB | F64X2_MUL | F64X2_DIV | F64X2_MIN | F64X2_MAX |
+        F64X2_PROMOTE_LOW_F32X4 => map!(f64x2, from_f64x2, v, |x| canonical_f64(x)),
+        _ => v,
+    }
+}
+
+/// Selects bits from `a` where `c` is set and from `b` where it is clear.
+pub fn bitselect(a: u128, b: u128, c: u128) -> u128 {
+    (a & c) | (b & !c)
+}
+
+/// Selects the bytes of `a` followed by `b` named by `lanes`, which are all below 32.
+pub fn shuffle(a: u128, b: u128, lanes: &[u8; 16]) -> u128 {
+    let (a, b) = (u8x16(a), u8x16(b));
+    let mut r = [0; 16];
+    for i in 0..16 {
+        let l = lanes[i] as usize;
+        r[i] = if l < 16 { a[l] } else { b[l - 16] };
+    }
+    from_u8x16(r)
+}
+
+/// Returns the number of lanes addressed by a lane immediate of `sub`.
+pub fn lane_count(sub: u8) -> u8 {
+    match sub {
+        I8X16_EXTRACT_LANE_S ..= I8X16_REPLACE_LANE => 16,
+        I16X8_EXTRACT_LANE_S ..= I16X8_REPLACE_LANE => 8,
+        I32X4_EXTRACT_LANE | I32X4_REPLACE_LANE | F32X4_EXTRACT_LANE | F32X4_REPLACE_LANE => 4,
+        V128_LOAD8_LANE | V128_STORE8_LANE => 16,
+        V128_LOAD16_LANE | V128_STORE16_LANE => 8,
+        V128_LOAD32_LANE | V128_STORE32_LANE => 4,
+        _ => 2,
+    }
+}
+
+/// Reads `lane` of `v` as a scalar.
+pub fn extract_lane(sub: u8, v: u128, lane: u8) -> Option<u128> {
+    let l = lane as usize;
+    Some(match sub {
+        I8X16_EXTRACT_LANE_S => i8x16(v)[l] as i32 as u32 as u128,
+        I8X16_EXTRACT_LANE_U | V128_STORE8_LANE => u8x16(v)[l] as u128,
+        I16X8_EXTRACT_LANE_S => i16x8(v)[l] as i32 as u32 as u128,
+        I16X8_EXTRACT_LANE_U | V128_STORE16_LANE => u16x8(v)[l] as u128,
+        I32X4_EXTRACT_LANE | F32X4_EXTRACT_LANE | V128_STORE32_LANE => u32x4(v)[l] as u128,
+        I64X2_EXTRACT_LANE | F64X2_EXTRACT_LANE | V128_STORE64_LANE => u64x2(v)[l] as u128,
+        _ => return None,
+    })
+}
+
+/// Returns `v` with `lane` set to the scalar `x`.
+pub fn replace_lane(sub: u8, v: u128, lane: u8, x: u128) -> Option<u128> {
+    let l = lane as usize;
+    Some(match sub {
+        I8X16_REPLACE_LANE | V128_LOAD8_LANE => { let mut r = u8x16(v); r[l] = x as u8; from_u8x16(r) },
+        I16X8_REPLACE_LANE | V128_LOAD16_LANE => { let mut r = u16x8(v); r[l] = x as u16; from_u16x8(r) },
+        I32X4_REPLACE_LANE | F32X4_REPLACE_LANE | V128_LOAD32_LANE => { let mut r = u32x4(v); r[l] = x as u32; from_u32x4(r) },
+        I64X2_REPLACE_LANE | F64X2_REPLACE_LANE | V128_LOAD64_LANE => { let mut r = u64x2(v); r[l] = x as u64; from_u64x2(r) },
+        _ => return None,
+    })
+}
+
+/// Maps the bits loaded by a `v128.load*` instruction to its result.
+pub type LoadFn = fn(u64) -> u128;
+
+/// Expands the bytes read by a `v128.load*` instruction other than `v128.load`. Returns
+/// the access width in bytes and a function mapping the loaded bits to the result.
+pub fn load_kind(sub: u8) -> Option<(usize, LoadFn)> {
+    fn extend_i8(x: u64) -> u128 { extend!(i8x16, i16x8, from_i16x8, x as u128, 0) }
+    fn extend_u8(x: u64) -> u128 { extend!(u8x16, u16x8, from_u16x8, x as u128, 0) }
+    fn extend_i16(x: u64) -> u128 { extend!(i16x8, i32x4, from_i32x4, x as u128, 0) }
+    fn extend_u16(x: u64) -> u128 { extend!(u16x8, u32x4, from_u32x4, x as u128, 0) }
+    fn extend_i32(x: u64) -> u128 { extend!(i32x4, i64x2, from_i64x2, x as u128, 0) }
+    fn extend_u32(x: u64) -> u128 { extend!(u32x4, u64x2, from_u64x2, x as u128, 0) }
+    fn splat8(x: u64) -> u128 { from_u8x16([x as u8; 16]) }
+    fn splat16(x: u64) -> u128 { from_u16x8([x as u16; 8]) }
+    fn splat32(x: u64) -> u128 { from_u32x4([x as u32; 4]) }
+    fn splat64(x: u64) -> u128 { from_u64x2([x; 2]) }
+    fn zero(x: u64) -> u128 { x as u128 }
+    Some(match sub {
+        V128_LOAD8X8_S => (8, extend_i8),
+        V128_LOAD8X8_U => (8, extend_u8),
+        V128_LOAD16X4_S => (8, extend_i16),
+        V128_LOAD16X4_U => (8, extend_u16),
+        V128_LOAD32X2_S => (8, extend_i32),
+        V128_LOAD32X2_U => (8, extend_u32),
+        V128_LOAD8_SPLAT => (1, splat8),
+        V128_LOAD16_SPLAT => (2, splat16),
+        V128_LOAD32_SPLAT => (4, splat32),
+        V128_LOAD64_SPLAT => (8, splat64),
+        V128_LOAD32_ZERO => (4, zero),
+        V128_LOAD64_ZERO => (8, zero),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn i32s(a: i32, b: i32, c: i32, d: i32) -> u128 {
+        from_i32x4([a, b, c, d])
+    }
+
+    #[test]
+    fn test_lanes() {
+        let v = 0x0f0e0d0c_0b0a0908_07060504_03020100u128;
+        assert_eq!(u8x16(v)[0], 0x00);
+        assert_eq!(u8x16(v)[15], 0x0f);
+        assert_eq!(u16x8(v)[1], 0x0302);
+        assert_eq!(u32x4(v)[3], 0x0f0e0d0c);
+        assert_eq!(from_u8x16(u8x16(v)), v);
+        assert_eq!(from_i16x8(i16x8(v)), v);
+        assert_eq!(from_f32x4(f32x4(v)), v);
+        assert_eq!(from_f64x2(f64x2(v)), v);
+        assert_eq!(extract_lane(I8X16_EXTRACT_LANE_S, !0, 3), Some(0xffff_ffff));
+        assert_eq!(extract_lane(I8X16_EXTRACT_LANE_U, !0, 3), Some(0xff));
+        assert_eq!(replace_lane(I32X4_REPLACE_LANE, 0, 2, 7), Some(i32s(0, 0, 7, 0)));
+    }
+
+    // Cases drawn from the spec's simd_i8x16_sat_arith, simd_i16x8_q15mulr_sat_s,
+    // simd_i32x4_dot_i16x8, simd_lane, simd_conversions and simd_f32x4 tests.
+    #[test]
+    fn test_integer_ops() {
+        let a = from_i8x16([0x7f, -0x80, 1, -1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let b = from_i8x16([1, -1, 1, -1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(i8x16(binary(I8X16_ADD_SAT_S, a, b).unwrap())[..4], [0x7f, -0x80, 2, -2]);
+        assert_eq!(i8x16(binary(I8X16_ADD, a, b).unwrap())[..4], [-0x80, 0x7f, 2, -2]);
+        assert_eq!(u8x16(binary(I8X16_ADD_SAT_U, a, b).unwrap())[..4], [0x80, 0xff, 2, 0xff]);
+        assert_eq!(u8x16(binary(I8X16_SUB_SAT_U, b, a).unwrap())[..4], [0, 0x7f, 0, 0]);
+        assert_eq!(u8x16(binary(I8X16_AVGR_U, a, b).unwrap())[..4], [0x40, 0xc0, 1, 0xff]);
+        assert_eq!(unary(I8X16_BITMASK, a), Some(0b1010));
+        assert_eq!(unary(I8X16_POPCNT, a).map(|v| u8x16(v)[..4] == [7, 1, 1, 8]), Some(true));
+        assert_eq!(unary(I8X16_ALL_TRUE, a), Some(0));
+        assert_eq!(unary(I8X16_ALL_TRUE, !0), Some(1));
+        assert_eq!(unary(V128_ANY_TRUE, a), Some(1));
+
+        let a = from_i16x8([-0x8000, 0x4000, 0x7fff, 0, 0, 0, 0, 0]);
+        let b = from_i16x8([-0x8000, 0x4000, 0x7fff, 0, 0, 0, 0, 0]);
+        assert_eq!(i16x8(binary(I16X8_Q15MULR_SAT_S, a, b).unwrap())[..3], [0x7fff, 0x2000, 0x7ffe]);
+        assert_eq!(binary(I32X4_DOT_I16X8_S, a, b), Some(i32s(0x5000_0000, 0x3fff_0001, 0, 0)));
+
+        let a = i32s(-1, 0x10000, -0x10000, 0x7fff);
+        assert_eq!(binary(I16X8_NARROW_I32X4_S, a, a).map(|v| i16x8(v)[..4] == [-1, 0x7fff, -0x8000, 0x7fff]), Some(true));
+        assert_eq!(binary(I16X8_NARROW_I32X4_U, a, a).map(|v| u16x8(v)[..4] == [0, 0xffff, 0, 0x7fff]), Some(true));
+        assert_eq!(unary(I64X2_EXTEND_HIGH_I32X4_S, a), Some(from_i64x2([-0x10000, 0x7fff])));
+        assert_eq!(unary(I64X2_EXTEND_LOW_I32X4_U, a), Some(from_u64x2([0xffff_ffff, 0x10000])));
+        assert_eq!(binary(I64X2_EXTMUL_LOW_I32X4_S, a, a), Some(from_i64x2([1, 0x1_0000_0000])));
+        assert_eq!(unary(I32X4_EXTADD_PAIRWISE_I16X8_U, a), Some(i32s(0x1fffe, 1, 0xffff, 0x7fff)));
+
+        // Shift counts wrap at the lane width
+        assert_eq!(binary(I32X4_SHL, i32s(1, 2, 3, 4), 33), Some(i32s(2, 4, 6, 8)));
+        assert_eq!(binary(I32X4_SHR_S, i32s(-8, 8, 0, 0), 2), Some(i32s(-2, 2, 0, 0)));
+        assert_eq!(binary(I8X16_SHR_U, !0, 7), Some(from_u8x16([1; 16])));
+        assert_eq!(binary(I64X2_LT_S, from_i64x2([-1, 1]), from_i64x2([0, 0])), Some(from_u64x2([!0, 0])));
+        assert_eq!(binary(I32X4_MIN_U, i32s(-1, 0, 5, 6), i32s(1, 1, 1, 7)), Some(i32s(1, 0, 1, 6)));
+    }
+
+    #[test]
+    fn test_float_ops() {
+        let nan = f32::from_bits(0x7fc0_0000);
+        let a = from_f32x4([-0.0, 1.5, nan, -2.5]);
+        let b = from_f32x4([0.0, -1.5, 1.0, 2.5]);
+        assert_eq!(f32x4(binary(F32X4_MIN, a, b).unwrap())[..2], [-0.0, -1.5]);
+        assert!(f32x4(binary(F32X4_MIN, a, b).unwrap())[2].is_nan());
+        // pmin returns the first operand unless the second is less
+        assert_eq!(u32x4(binary(F32X4_PMIN, a, b).unwrap()), u32x4(from_f32x4([-0.0, -1.5, nan, -2.5])));
+        assert_eq!(binary(F32X4_EQ, a, a), Some(from_u32x4([!0, !0, 0, !0])));
+        assert_eq!(f32x4(unary(F32X4_NEAREST, a).unwrap())[1], 2.0);
+        assert_eq!(f32x4(unary(F32X4_NEAREST, a).unwrap())[3], -2.0);
+        assert_eq!(unary(I32X4_TRUNC_SAT_F32X4_S, from_f32x4([nan, 3e9, -3e9, -1.9])), Some(i32s(0, 0x7fff_ffff, -0x8000_0000, -1)));
+        assert_eq!(unary(I32X4_TRUNC_SAT_F32X4_U, from_f32x4([nan, 5e9, -1.0, 1.9])), Some(from_u32x4([0, 0xffff_ffff, 0, 1])));
+        assert_eq!(unary(I32X4_TRUNC_SAT_F64X2_S_ZERO, from_f64x2([-1.5, 1e10])), Some(i32s(-1, 0x7fff_ffff, 0, 0)));
+        assert_eq!(unary(F64X2_CONVERT_LOW_I32X4_U, i32s(-1, 2, 3, 4)), Some(from_f64x2([4294967295.0, 2.0])));
+        assert_eq!(unary(F32X4_DEMOTE_F64X2_ZERO, from_f64x2([1.0, -0.5])), Some(from_f32x4([1.0, -0.5, 0.0, 0.0])));
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let a = 0x0f0e0d0c_0b0a0908_07060504_03020100u128;
+        let b = 0x1f1e1d1c_1b1a1918_17161514_13121110u128;
+        let lanes = [31, 0, 30, 1, 29, 2, 28, 3, 27, 4, 26, 5, 25, 6, 24, 7];
+        assert_eq!(u8x16(shuffle(a, b, &lanes)), lanes);
+        let idx = from_u8x16([15, 16, 0xff, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+        assert_eq!(u8x16(binary(I8X16_SWIZZLE, a, idx).unwrap())[..4], [15, 0, 0, 0]);
+        assert_eq!(bitselect(!0, 0, 0xff00), 0xff00);
+    }
+}
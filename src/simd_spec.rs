@@ -0,0 +1,522 @@
+//! A subset of the spec test suite's SIMD tests (`test/core/simd/simd_*.wast`), converted to
+//! tables. Each case is an `assert_return`, `assert_trap` or `assert_invalid` whose function
+//! is reduced to the instruction under test: the arguments of the `invoke` become constants
+//! ahead of it, and the function is assembled into a module with one page of memory holding
+//! `DATA`. Every case runs both compiled and in place.
+//!
+//! Only the first 4 KiB of a memory are addressable below 0x10_0000 (see
+//! `memory_inst::translate`), so the spec's accesses that end exactly at the end of the page
+//! end at 4096 instead. The out-of-bounds cases still cross the end of the page.
+
+use error::Error;
+use interp::Config;
+use module_inst::Value;
+use opcode::{
+    F32X4_EXTRACT_LANE, F32X4_REPLACE_LANE, F64X2_EXTRACT_LANE, F64X2_REPLACE_LANE,
+    I16X8_ADD_SAT_S, I16X8_ADD_SAT_U, I16X8_EXTRACT_LANE_S, I16X8_EXTRACT_LANE_U,
+    I16X8_REPLACE_LANE, I16X8_SUB_SAT_S, I16X8_SUB_SAT_U, I32X4_EXTRACT_LANE, I32X4_REPLACE_LANE,
+    I64X2_EXTRACT_LANE, I64X2_REPLACE_LANE, I8X16_ADD_SAT_S, I8X16_ADD_SAT_U,
+    I8X16_EXTRACT_LANE_S, I8X16_EXTRACT_LANE_U, I8X16_REPLACE_LANE, I8X16_SHUFFLE,
+    I8X16_SUB_SAT_S, I8X16_SUB_SAT_U, I8X16_SWIZZLE, V128_CONST, V128_LOAD, V128_LOAD16X4_S,
+    V128_LOAD16X4_U, V128_LOAD16_LANE, V128_LOAD16_SPLAT, V128_LOAD32X2_S, V128_LOAD32X2_U,
+    V128_LOAD32_LANE, V128_LOAD32_SPLAT, V128_LOAD32_ZERO, V128_LOAD64_LANE, V128_LOAD64_SPLAT,
+    V128_LOAD64_ZERO, V128_LOAD8X8_S, V128_LOAD8X8_U, V128_LOAD8_LANE, V128_LOAD8_SPLAT,
+    V128_STORE, V128_STORE16_LANE, V128_STORE32_LANE, V128_STORE64_LANE, V128_STORE8_LANE
+};
+use test_support::*;
+
+// The data of simd_address.wast ("\00\01...\09\10...\19\20...\25"), padded with zeros and
+// followed by bytes with the sign bit set for the extending loads.
+const DATA: &[u8] = &[
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09,
+    0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19,
+    0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x80, 0xff, 0x7f, 0x01, 0xfe, 0x00, 0x81, 0x7e,
+];
+
+// Result types
+const T_V128: u8 = 0x7b;
+const T_I32: u8 = 0x7f;
+const T_I64: u8 = 0x7e;
+
+#[derive(Debug, Clone, Copy)]
+enum Arg {
+    V(u128),
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+}
+
+use self::Arg::*;
+
+impl Arg {
+    fn ty(self) -> u8 {
+        match self {
+            V(_) => T_V128,
+            I32(_) => T_I32,
+            I64(_) => T_I64,
+            F32(_) => 0x7d,
+            F64(_) => 0x7c,
+        }
+    }
+
+    fn value(self) -> Value {
+        match self {
+            V(v) => Value::from(v),
+            I32(v) => Value::from(v),
+            I64(v) => Value::from(v),
+            F32(v) => Value::from(v),
+            F64(v) => Value::from(v),
+        }
+    }
+
+    fn put(self, buf: &mut [u8], len: &mut usize) {
+        match self {
+            V(v) => {
+                put(buf, len, &[0xfd, V128_CONST]);
+                put(buf, len, &v.to_le_bytes());
+            },
+            I32(v) => {
+                put(buf, len, &[0x41]);
+                put_sleb(buf, len, v as i64);
+            },
+            I64(v) => {
+                put(buf, len, &[0x42]);
+                put_sleb(buf, len, v);
+            },
+            F32(v) => {
+                put(buf, len, &[0x43]);
+                put(buf, len, &v.to_bits().to_le_bytes());
+            },
+            F64(v) => {
+                put(buf, len, &[0x44]);
+                put(buf, len, &v.to_bits().to_le_bytes());
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Expect {
+    Return(Arg),
+    Trap,
+    Invalid(Error),
+}
+
+use self::Expect::*;
+
+fn put_sleb(buf: &mut [u8], len: &mut usize, mut value: i64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0) {
+            return put(buf, len, &[byte])
+        }
+        put(buf, len, &[byte | 0x80]);
+    }
+}
+
+/// An instruction with its immediates.
+struct Instr {
+    buf: [u8; 24],
+    len: usize,
+}
+
+impl Instr {
+    fn new() -> Self {
+        Instr { buf: [0; 24], len: 0 }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+fn constant(arg: Arg) -> Instr {
+    let mut i = Instr::new();
+    arg.put(&mut i.buf, &mut i.len);
+    i
+}
+
+/// A SIMD instruction.
+fn op(sub: u8, imm: &[u8]) -> Instr {
+    let mut i = Instr::new();
+    put(&mut i.buf, &mut i.len, &[0xfd]);
+    put_leb(&mut i.buf, &mut i.len, sub as usize);
+    put(&mut i.buf, &mut i.len, imm);
+    i
+}
+
+/// A load or store with the alignment exponent and offset of its memarg.
+fn mem(sub: u8, align: usize, offset: usize) -> Instr {
+    let mut i = op(sub, &[]);
+    put_leb(&mut i.buf, &mut i.len, align);
+    put_leb(&mut i.buf, &mut i.len, offset);
+    i
+}
+
+fn mem_lane(sub: u8, align: usize, offset: usize, lane: u8) -> Instr {
+    let mut i = mem(sub, align, offset);
+    put(&mut i.buf, &mut i.len, &[lane]);
+    i
+}
+
+fn lanes(values: &[i64]) -> u128 {
+    let bits = 128 / values.len();
+    let mask = !0u128 >> (128 - bits);
+    values.iter().enumerate().fold(0, |v, (i, &x)| v | (x as u128 & mask) << (i * bits))
+}
+
+fn i8x16(values: [i64; 16]) -> u128 { lanes(&values) }
+fn i16x8(values: [i64; 8]) -> u128 { lanes(&values) }
+fn i32x4(values: [i64; 4]) -> u128 { lanes(&values) }
+fn i64x2(values: [i64; 2]) -> u128 { lanes(&values) }
+fn f32x4(values: [f32; 4]) -> u128 { lanes(&values.map(|f| f.to_bits() as i64)) }
+fn i8x16_splat(x: i64) -> u128 { i8x16([x; 16]) }
+fn i16x8_splat(x: i64) -> u128 { i16x8([x; 8]) }
+
+fn section(buf: &mut [u8], len: &mut usize, id: u8, content: &[u8]) {
+    put(buf, len, &[id]);
+    put_leb(buf, len, content.len());
+    put(buf, len, content);
+}
+
+/// Assembles `(func (result ..) args.. instrs..)` with `DATA` at 0. The environment starts
+/// with one page and grows it by the module's minimum, so `(memory 0)` gives the spec's
+/// `(memory 1)`.
+fn module<'a>(buf: &'a mut [u8], result: u8, args: &[Arg], instrs: &[&Instr]) -> &'a [u8] {
+    let mut body = [0u8; 256];
+    let mut body_len = 0;
+    put(&mut body, &mut body_len, &[0x00]);
+    for arg in args {
+        arg.put(&mut body, &mut body_len);
+    }
+    for i in instrs {
+        put(&mut body, &mut body_len, i.as_bytes());
+    }
+    put(&mut body, &mut body_len, &[0x0b]);
+
+    let mut code = [0u8; 260];
+    let mut code_len = 0;
+    put(&mut code, &mut code_len, &[0x01]);
+    put_leb(&mut code, &mut code_len, body_len);
+    put(&mut code, &mut code_len, &body[..body_len]);
+
+    let mut data = [0u8; 64];
+    let mut data_len = 0;
+    put(&mut data, &mut data_len, &[0x01, 0x00, 0x41, 0x00, 0x0b]);
+    put_leb(&mut data, &mut data_len, DATA.len());
+    put(&mut data, &mut data_len, DATA);
+
+    let mut len = 0;
+    put(buf, &mut len, HEADER);
+    section(buf, &mut len, 0x01, &[0x01, 0x60, 0x00, 0x01, result]);
+    section(buf, &mut len, 0x03, &[0x01, 0x00]);
+    section(buf, &mut len, 0x05, &[0x01, 0x00, 0x00]);
+    section(buf, &mut len, 0x0a, &code[..code_len]);
+    section(buf, &mut len, 0x0b, &data[..data_len]);
+    &buf[..len]
+}
+
+fn check(name: &str, result: u8, args: &[Arg], instrs: &[&Instr], expect: &Expect) {
+    let mut module_buf = [0u8; 512];
+    let module = module(&mut module_buf, result, args, instrs);
+    for &load in [Load::Compiled, Load::InPlace].iter() {
+        let mut buf = [0u8; 32768];
+        match (Fixture::load_with(&mut buf, module, load, Config::default()), expect) {
+            (Ok(mut f), &Return(arg)) =>
+                assert_eq!(f.call(0, &[]).map_err(Error::from), Ok(&[arg.value()][..]), "{} {:?} {:?}", name, args, load),
+            (Ok(mut f), &Trap) =>
+                assert_eq!(f.call_err(0, &[]), Error::OutOfBounds, "{} {:?} {:?}", name, args, load),
+            (Err(e), Invalid(error)) =>
+                assert_eq!(e.error(), error, "{} {:?}", name, load),
+            (Ok(_), _) => panic!("{} {:?}: invalid module loaded", name, load),
+            (Err(e), _) => panic!("{} {:?}: {:?}", name, load, e),
+        }
+    }
+}
+
+/// Checks `(instr a b)` for each `(a, b, expected)` in `cases`.
+fn binary(name: &str, sub: u8, cases: &[(u128, u128, u128)]) {
+    let instr = op(sub, &[]);
+    for &(a, b, expected) in cases {
+        check(name, T_V128, &[V(a), V(b)], &[&instr], &Return(V(expected)));
+    }
+}
+
+// simd_lane.wast
+#[test]
+fn test_lane() {
+    let iota = i8x16([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+    let cases: &[(&str, Instr, &[Arg], Expect)] = &[
+        ("i8x16.extract_lane_s", op(I8X16_EXTRACT_LANE_S, &[0]), &[V(i8x16_splat(127))], Return(I32(127))),
+        ("i8x16.extract_lane_s", op(I8X16_EXTRACT_LANE_S, &[0]), &[V(i8x16_splat(128))], Return(I32(-128))),
+        ("i8x16.extract_lane_s", op(I8X16_EXTRACT_LANE_S, &[15]), &[V(i8x16([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, -1]))], Return(I32(-1))),
+        ("i8x16.extract_lane_u", op(I8X16_EXTRACT_LANE_U, &[0]), &[V(i8x16_splat(255))], Return(I32(255))),
+        ("i8x16.extract_lane_u", op(I8X16_EXTRACT_LANE_U, &[15]), &[V(i8x16([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, -128]))], Return(I32(128))),
+        ("i8x16.extract_lane_u", op(I8X16_EXTRACT_LANE_U, &[7]), &[V(iota)], Return(I32(7))),
+        ("i16x8.extract_lane_s", op(I16X8_EXTRACT_LANE_S, &[0]), &[V(i16x8_splat(32767))], Return(I32(32767))),
+        ("i16x8.extract_lane_s", op(I16X8_EXTRACT_LANE_S, &[0]), &[V(i16x8_splat(32768))], Return(I32(-32768))),
+        ("i16x8.extract_lane_s", op(I16X8_EXTRACT_LANE_S, &[7]), &[V(i16x8([0, 0, 0, 0, 0, 0, 0, 0xffff]))], Return(I32(-1))),
+        ("i16x8.extract_lane_u", op(I16X8_EXTRACT_LANE_U, &[0]), &[V(i16x8_splat(-1))], Return(I32(65535))),
+        ("i16x8.extract_lane_u", op(I16X8_EXTRACT_LANE_U, &[7]), &[V(i16x8([0, 0, 0, 0, 0, 0, 0, -32768]))], Return(I32(32768))),
+        ("i32x4.extract_lane", op(I32X4_EXTRACT_LANE, &[0]), &[V(i32x4([2147483647, 0, 0, 0]))], Return(I32(2147483647))),
+        ("i32x4.extract_lane", op(I32X4_EXTRACT_LANE, &[3]), &[V(i32x4([0, 0, 0, 0x8000_0000]))], Return(I32(-2147483648))),
+        ("i64x2.extract_lane", op(I64X2_EXTRACT_LANE, &[0]), &[V(i64x2([-1, 0]))], Return(I64(-1))),
+        ("i64x2.extract_lane", op(I64X2_EXTRACT_LANE, &[1]), &[V(i64x2([0, 0x7fff_ffff_ffff_ffff]))], Return(I64(0x7fff_ffff_ffff_ffff))),
+        ("f32x4.extract_lane", op(F32X4_EXTRACT_LANE, &[3]), &[V(f32x4([0.0, 0.0, 0.0, -0.0]))], Return(F32(-0.0))),
+        // Lanes are moved as bits, so NaN payloads survive
+        ("f32x4.extract_lane", op(F32X4_EXTRACT_LANE, &[1]), &[V(i32x4([0, 0x7fa0_0001, 0, 0]))], Return(F32(f32::from_bits(0x7fa0_0001)))),
+        ("f64x2.extract_lane", op(F64X2_EXTRACT_LANE, &[1]), &[V(i64x2([0, 1e308f64.to_bits() as i64]))], Return(F64(1e308))),
+        ("i8x16.replace_lane", op(I8X16_REPLACE_LANE, &[0]), &[V(0), I32(128)], Return(V(i8x16([-128, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0])))),
+        ("i8x16.replace_lane", op(I8X16_REPLACE_LANE, &[15]), &[V(iota), I32(0x17f)], Return(V(i8x16([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 0x7f])))),
+        ("i16x8.replace_lane", op(I16X8_REPLACE_LANE, &[7]), &[V(0), I32(0x18000)], Return(V(i16x8([0, 0, 0, 0, 0, 0, 0, -32768])))),
+        ("i32x4.replace_lane", op(I32X4_REPLACE_LANE, &[2]), &[V(i32x4([1, 2, 3, 4])), I32(-1)], Return(V(i32x4([1, 2, -1, 4])))),
+        ("i64x2.replace_lane", op(I64X2_REPLACE_LANE, &[1]), &[V(0), I64(0x0123_4567_89ab_cdef)], Return(V(i64x2([0, 0x0123_4567_89ab_cdef])))),
+        ("f32x4.replace_lane", op(F32X4_REPLACE_LANE, &[3]), &[V(f32x4([1.0; 4])), F32(-1.5)], Return(V(f32x4([1.0, 1.0, 1.0, -1.5])))),
+        ("f64x2.replace_lane", op(F64X2_REPLACE_LANE, &[0]), &[V(0), F64(f64::INFINITY)], Return(V(i64x2([0x7ff0_0000_0000_0000, 0])))),
+    ];
+    for &(name, ref instr, args, ref expect) in cases {
+        let result = match *expect { Return(arg) => arg.ty(), _ => T_V128 };
+        check(name, result, args, &[instr], expect);
+    }
+
+    // Lane indices must be less than the lane count
+    check("i8x16.extract_lane_s", T_I32, &[V(0)], &[&op(I8X16_EXTRACT_LANE_S, &[16])], &Invalid(Error::InvalidLaneIndex { index: 16 }));
+    check("i16x8.extract_lane_u", T_I32, &[V(0)], &[&op(I16X8_EXTRACT_LANE_U, &[8])], &Invalid(Error::InvalidLaneIndex { index: 8 }));
+    check("i32x4.replace_lane", T_V128, &[V(0), I32(0)], &[&op(I32X4_REPLACE_LANE, &[4])], &Invalid(Error::InvalidLaneIndex { index: 4 }));
+    check("i64x2.extract_lane", T_I64, &[V(0)], &[&op(I64X2_EXTRACT_LANE, &[2])], &Invalid(Error::InvalidLaneIndex { index: 2 }));
+    check("f64x2.replace_lane", T_V128, &[V(0), F64(0.0)], &[&op(F64X2_REPLACE_LANE, &[255])], &Invalid(Error::InvalidLaneIndex { index: 255 }));
+}
+
+// simd_lane.wast: i8x16.shuffle and i8x16.swizzle
+#[test]
+fn test_shuffle() {
+    // With a = 0..15 and b = 16..31, each result lane is the index that selected it
+    let a = i8x16([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+    let b = i8x16([16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31]);
+    let patterns: &[[u8; 16]] = &[
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+        [16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31],
+        [15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+        [31, 30, 29, 28, 27, 26, 25, 24, 23, 22, 21, 20, 19, 18, 17, 16],
+        [0, 16, 1, 17, 2, 18, 3, 19, 4, 20, 5, 21, 6, 22, 7, 23],
+        [8, 24, 9, 25, 10, 26, 11, 27, 12, 28, 13, 29, 14, 30, 15, 31],
+        [5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+        [31, 0, 30, 1, 29, 2, 28, 3, 27, 4, 26, 5, 25, 6, 24, 7],
+    ];
+    for lanes in patterns {
+        let expected = i8x16(lanes.map(|l| l as i64));
+        check("i8x16.shuffle", T_V128, &[V(a), V(b)], &[&op(I8X16_SHUFFLE, lanes)], &Return(V(expected)));
+    }
+    check("i8x16.shuffle", T_V128, &[V(i8x16_splat(-1)), V(0)], &[&op(I8X16_SHUFFLE, &[0, 16, 1, 17, 2, 18, 3, 19, 4, 20, 5, 21, 6, 22, 7, 23])],
+        &Return(V(i16x8_splat(0x00ff))));
+    check("i8x16.shuffle", T_V128, &[V(a), V(b)], &[&op(I8X16_SHUFFLE, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 32])],
+        &Invalid(Error::InvalidLaneIndex { index: 32 }));
+
+    // Indices of 16 and up, including negative ones, select 0
+    let c = i8x16([0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff]);
+    let cases = [
+        (a, c),
+        (i8x16([15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0]),
+            i8x16([0xff, 0xfe, 0xfd, 0xfc, 0xfb, 0xfa, 0xf9, 0xf8, 0xf7, 0xf6, 0xf5, 0xf4, 0xf3, 0xf2, 0xf1, 0xf0])),
+        (i8x16([16, -1, -128, 0, 1, 17, 32, 15, 14, 100, 2, 3, 4, 5, 6, 7]),
+            i8x16([0, 0, 0, 0xf0, 0xf1, 0, 0, 0xff, 0xfe, 0, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7])),
+        (i8x16_splat(16), 0),
+    ];
+    binary("i8x16.swizzle", I8X16_SWIZZLE, &cases.map(|(s, r)| (c, s, r)));
+}
+
+// simd_i8x16_sat_arith.wast
+#[test]
+fn test_i8x16_sat_arith() {
+    let s = i8x16_splat;
+    binary("i8x16.add_sat_s", I8X16_ADD_SAT_S, &[
+        (s(0), s(0), s(0)),
+        (s(1), s(1), s(2)),
+        (s(-1), s(1), s(0)),
+        (s(127), s(1), s(127)),
+        (s(127), s(127), s(127)),
+        (s(-128), s(-1), s(-128)),
+        (s(-128), s(-128), s(-128)),
+        (i8x16([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]),
+            i8x16([0, -1, -2, -3, -4, -5, -6, -7, -8, -9, -10, -11, -12, -13, -14, -15]), s(0)),
+        (i8x16([127, -128, 100, -100, 127, -128, 100, -100, 127, -128, 100, -100, 127, -128, 100, -100]),
+            i8x16([100, -100, 100, -100, 100, -100, 100, -100, 100, -100, 100, -100, 100, -100, 100, -100]),
+            i8x16([127, -128, 127, -128, 127, -128, 127, -128, 127, -128, 127, -128, 127, -128, 127, -128])),
+    ]);
+    binary("i8x16.add_sat_u", I8X16_ADD_SAT_U, &[
+        (s(0), s(0), s(0)),
+        (s(1), s(1), s(2)),
+        (s(254), s(1), s(255)),
+        (s(255), s(1), s(255)),
+        (s(128), s(128), s(255)),
+        (s(0), s(255), s(255)),
+        (i8x16([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]), s(250),
+            i8x16([250, 251, 252, 253, 254, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255])),
+    ]);
+    binary("i8x16.sub_sat_s", I8X16_SUB_SAT_S, &[
+        (s(0), s(0), s(0)),
+        (s(5), s(3), s(2)),
+        (s(-128), s(1), s(-128)),
+        (s(127), s(-1), s(127)),
+        (s(0), s(-128), s(127)),
+        (s(-1), s(127), s(-128)),
+        (s(-128), s(-128), s(0)),
+        (i8x16([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]), s(-120),
+            i8x16([120, 121, 122, 123, 124, 125, 126, 127, 127, 127, 127, 127, 127, 127, 127, 127])),
+    ]);
+    binary("i8x16.sub_sat_u", I8X16_SUB_SAT_U, &[
+        (s(0), s(0), s(0)),
+        (s(0), s(1), s(0)),
+        (s(255), s(255), s(0)),
+        (s(1), s(255), s(0)),
+        (s(255), s(1), s(254)),
+        (s(128), s(127), s(1)),
+        (i8x16([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]), s(8),
+            i8x16([0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7])),
+    ]);
+}
+
+// simd_i16x8_sat_arith.wast
+#[test]
+fn test_i16x8_sat_arith() {
+    let s = i16x8_splat;
+    binary("i16x8.add_sat_s", I16X8_ADD_SAT_S, &[
+        (s(0), s(0), s(0)),
+        (s(1), s(-1), s(0)),
+        (s(32767), s(1), s(32767)),
+        (s(16384), s(16384), s(32767)),
+        (s(-32768), s(-1), s(-32768)),
+        (s(-32768), s(-32768), s(-32768)),
+        (i16x8([32767, -32768, 1, -1, 100, 200, -300, 0]), i16x8([1, -1, 32767, -32768, -100, 200, 300, 0]),
+            i16x8([32767, -32768, 32767, -32768, 0, 400, 0, 0])),
+    ]);
+    binary("i16x8.add_sat_u", I16X8_ADD_SAT_U, &[
+        (s(0), s(0), s(0)),
+        (s(100), s(200), s(300)),
+        (s(65535), s(1), s(65535)),
+        (s(32768), s(32768), s(65535)),
+        (s(65534), s(1), s(65535)),
+        (i16x8([0, 1, 2, 3, 65530, 65531, 65532, 65533]), s(3),
+            i16x8([3, 4, 5, 6, 65533, 65534, 65535, 65535])),
+    ]);
+    binary("i16x8.sub_sat_s", I16X8_SUB_SAT_S, &[
+        (s(0), s(0), s(0)),
+        (s(-32768), s(1), s(-32768)),
+        (s(32767), s(-1), s(32767)),
+        (s(0), s(-32768), s(32767)),
+        (s(-1), s(32767), s(-32768)),
+        (s(-2), s(32767), s(-32768)),
+        (i16x8([32767, -32768, 1, -1, 100, 200, -300, 0]), i16x8([-1, 1, -32768, 32767, -100, 200, 300, 0]),
+            i16x8([32767, -32768, 32767, -32768, 200, 0, -600, 0])),
+    ]);
+    binary("i16x8.sub_sat_u", I16X8_SUB_SAT_U, &[
+        (s(0), s(0), s(0)),
+        (s(0), s(1), s(0)),
+        (s(65535), s(1), s(65534)),
+        (s(1), s(65535), s(0)),
+        (s(32768), s(32767), s(1)),
+        (i16x8([0, 1, 2, 3, 4, 5, 6, 65535]), s(3), i16x8([0, 0, 0, 0, 1, 2, 3, 65532])),
+    ]);
+}
+
+// simd_address.wast, simd_align.wast, simd_load.wast, simd_load_splat.wast,
+// simd_load_extend.wast and simd_load_zero.wast
+#[test]
+fn test_load() {
+    let cases: &[(&str, Instr, i32, Expect)] = &[
+        ("v128.load", mem(V128_LOAD, 4, 0), 0, Return(V(i8x16([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15])))),
+        ("v128.load", mem(V128_LOAD, 0, 0), 0, Return(V(i32x4([0x03020100, 0x07060504, 0x11100908, 0x15141312])))),
+        ("v128.load", mem(V128_LOAD, 0, 1), 0, Return(V(i8x16([1, 2, 3, 4, 5, 6, 7, 8, 9, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16])))),
+        ("v128.load", mem(V128_LOAD, 0, 2), 1, Return(V(i8x16([3, 4, 5, 6, 7, 8, 9, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18])))),
+        ("v128.load", mem(V128_LOAD, 0, 15), 0, Return(V(i8x16([0x15, 0x16, 0x17, 0x18, 0x19, 0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0, 0, 0, 0, 0])))),
+        // The alignment is a hint, even when the address does not meet it
+        ("v128.load", mem(V128_LOAD, 4, 0), 1, Return(V(i8x16([1, 2, 3, 4, 5, 6, 7, 8, 9, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16])))),
+        ("v128.load", mem(V128_LOAD, 4, 0), 4080, Return(V(0))),
+        ("v128.load", mem(V128_LOAD, 4, 4064), 16, Return(V(0))),
+        ("v128.load", mem(V128_LOAD, 4, 0), 65521, Trap),
+        ("v128.load", mem(V128_LOAD, 0, 1), 65520, Trap),
+        ("v128.load", mem(V128_LOAD, 0, 65521), 0, Trap),
+        ("v128.load", mem(V128_LOAD, 0, 0), -1, Trap),
+        ("v128.load", mem(V128_LOAD, 0, 0xffff_ffff), 1, Trap),
+        ("v128.load8_splat", mem(V128_LOAD8_SPLAT, 0, 0), 10, Return(V(i8x16_splat(0x10)))),
+        ("v128.load8_splat", mem(V128_LOAD8_SPLAT, 0, 4095), 0, Return(V(0))),
+        ("v128.load8_splat", mem(V128_LOAD8_SPLAT, 0, 65535), 1, Trap),
+        ("v128.load16_splat", mem(V128_LOAD16_SPLAT, 1, 0), 9, Return(V(i16x8_splat(0x1009)))),
+        ("v128.load32_splat", mem(V128_LOAD32_SPLAT, 2, 20), 0, Return(V(i32x4([0x23222120; 4])))),
+        ("v128.load64_splat", mem(V128_LOAD64_SPLAT, 3, 0), 24, Return(V(i64x2([0x2524; 2])))),
+        ("v128.load64_splat", mem(V128_LOAD64_SPLAT, 3, 0), 65529, Trap),
+        ("v128.load8x8_s", mem(V128_LOAD8X8_S, 3, 0), 32, Return(V(i16x8([-128, -1, 127, 1, -2, 0, -127, 126])))),
+        ("v128.load8x8_u", mem(V128_LOAD8X8_U, 3, 0), 32, Return(V(i16x8([128, 255, 127, 1, 254, 0, 129, 126])))),
+        ("v128.load16x4_s", mem(V128_LOAD16X4_S, 3, 0), 32, Return(V(i32x4([-128, 0x017f, 0xfe, 0x7e81])))),
+        ("v128.load16x4_u", mem(V128_LOAD16X4_U, 3, 0), 32, Return(V(i32x4([0xff80, 0x017f, 0xfe, 0x7e81])))),
+        ("v128.load32x2_s", mem(V128_LOAD32X2_S, 3, 0), 29, Return(V(i64x2([0x8000_0000u32 as i32 as i64, 0xfe01_7fffu32 as i32 as i64])))),
+        ("v128.load32x2_u", mem(V128_LOAD32X2_U, 3, 0), 29, Return(V(i64x2([0x8000_0000, 0xfe01_7fff])))),
+        ("v128.load32x2_u", mem(V128_LOAD32X2_U, 3, 1), 65528, Trap),
+        ("v128.load32_zero", mem(V128_LOAD32_ZERO, 2, 0), 32, Return(V(i32x4([0x017f_ff80, 0, 0, 0])))),
+        ("v128.load64_zero", mem(V128_LOAD64_ZERO, 3, 0), 32, Return(V(i64x2([0x7e81_00fe_017f_ff80, 0])))),
+        ("v128.load64_zero", mem(V128_LOAD64_ZERO, 3, 0), 65529, Trap),
+        // The alignment may not be larger than the access
+        ("v128.load", mem(V128_LOAD, 5, 0), 0, Invalid(Error::InvalidAlignment { align: 5 })),
+        ("v128.load8_splat", mem(V128_LOAD8_SPLAT, 1, 0), 0, Invalid(Error::InvalidAlignment { align: 1 })),
+        ("v128.load16x4_s", mem(V128_LOAD16X4_S, 4, 0), 0, Invalid(Error::InvalidAlignment { align: 4 })),
+        ("v128.load32_zero", mem(V128_LOAD32_ZERO, 3, 0), 0, Invalid(Error::InvalidAlignment { align: 3 })),
+    ];
+    for &(name, ref instr, addr, ref expect) in cases {
+        check(name, T_V128, &[I32(addr)], &[instr], expect);
+    }
+}
+
+// simd_load8_lane.wast through simd_load64_lane.wast
+#[test]
+fn test_load_lane() {
+    let cases: &[(&str, Instr, i32, u128, Expect)] = &[
+        ("v128.load8_lane", mem_lane(V128_LOAD8_LANE, 0, 0, 0), 9, 0, Return(V(i8x16([9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0])))),
+        ("v128.load8_lane", mem_lane(V128_LOAD8_LANE, 0, 0, 15), 32, 0, Return(V(i8x16([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x80])))),
+        ("v128.load16_lane", mem_lane(V128_LOAD16_LANE, 1, 0, 3), 32, !0, Return(V(i16x8([-1, -1, -1, 0xff80, -1, -1, -1, -1])))),
+        ("v128.load32_lane", mem_lane(V128_LOAD32_LANE, 2, 4, 0), 0, 0, Return(V(i32x4([0x07060504, 0, 0, 0])))),
+        ("v128.load64_lane", mem_lane(V128_LOAD64_LANE, 3, 0, 1), 32, i64x2([1, 2]), Return(V(i64x2([1, 0x7e81_00fe_017f_ff80])))),
+        ("v128.load64_lane", mem_lane(V128_LOAD64_LANE, 0, 0, 0), 4088, !0, Return(V(i64x2([0, -1])))),
+        ("v128.load64_lane", mem_lane(V128_LOAD64_LANE, 0, 0, 0), 65529, 0, Trap),
+        ("v128.load16_lane", mem_lane(V128_LOAD16_LANE, 0, 65535, 0), 0, 0, Trap),
+        ("v128.load8_lane", mem_lane(V128_LOAD8_LANE, 0, 0, 16), 0, 0, Invalid(Error::InvalidLaneIndex { index: 16 })),
+        ("v128.load32_lane", mem_lane(V128_LOAD32_LANE, 0, 0, 4), 0, 0, Invalid(Error::InvalidLaneIndex { index: 4 })),
+        ("v128.load64_lane", mem_lane(V128_LOAD64_LANE, 4, 0, 0), 0, 0, Invalid(Error::InvalidAlignment { align: 4 })),
+    ];
+    for &(name, ref instr, addr, v, ref expect) in cases {
+        check(name, T_V128, &[I32(addr), V(v)], &[instr], expect);
+    }
+}
+
+// simd_store.wast, simd_address.wast and simd_store8_lane.wast through
+// simd_store64_lane.wast: each case stores `value` at `addr` and loads 16 bytes from `load`.
+#[test]
+fn test_store() {
+    let value = i32x4([1, 2, 3, 4]);
+    let cases: &[(&str, Instr, i32, i32, Expect)] = &[
+        ("v128.store", mem(V128_STORE, 4, 0), 0, 0, Return(V(value))),
+        ("v128.store", mem(V128_STORE, 0, 1), 0, 1, Return(V(value))),
+        ("v128.store", mem(V128_STORE, 0, 1), 0, 0, Return(V(i8x16([0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0])))),
+        ("v128.store", mem(V128_STORE, 4, 4080), 0, 4080, Return(V(value))),
+        ("v128.store", mem(V128_STORE, 4, 0), 65521, 0, Trap),
+        ("v128.store", mem(V128_STORE, 4, 1), -1, 0, Trap),
+        ("v128.store8_lane", mem_lane(V128_STORE8_LANE, 0, 0, 12), 0, 0,
+            Return(V(i8x16([4, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15])))),
+        ("v128.store16_lane", mem_lane(V128_STORE16_LANE, 1, 2, 2), 0, 0,
+            Return(V(i8x16([0, 1, 2, 0, 4, 5, 6, 7, 8, 9, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15])))),
+        ("v128.store32_lane", mem_lane(V128_STORE32_LANE, 2, 0, 3), 4092, 4080, Return(V(i32x4([0, 0, 0, 4])))),
+        ("v128.store64_lane", mem_lane(V128_STORE64_LANE, 3, 0, 1), 8, 0,
+            Return(V(i8x16([0, 1, 2, 3, 4, 5, 6, 7, 3, 0, 0, 0, 4, 0, 0, 0])))),
+        ("v128.store64_lane", mem_lane(V128_STORE64_LANE, 3, 0, 0), 65529, 0, Trap),
+        ("v128.store", mem(V128_STORE, 5, 0), 0, 0, Invalid(Error::InvalidAlignment { align: 5 })),
+        ("v128.store32_lane", mem_lane(V128_STORE32_LANE, 3, 0, 0), 0, 0, Invalid(Error::InvalidAlignment { align: 3 })),
+        ("v128.store16_lane", mem_lane(V128_STORE16_LANE, 1, 0, 8), 0, 0, Invalid(Error::InvalidLaneIndex { index: 8 })),
+    ];
+    let load = mem(V128_LOAD, 0, 0);
+    for &(name, ref instr, addr, load_addr, ref expect) in cases {
+        // (store (i32.const addr) (v128.const value)) (v128.load (i32.const load_addr))
+        check(name, T_V128, &[I32(addr), V(value)], &[instr, &constant(I32(load_addr)), &load], expect);
+    }
+}
@@ -1,3 +1,4 @@
+use Error;
 use core::ops::{Index, IndexMut};
 
 pub struct SmallVec<'a, T: 'a> {
@@ -22,13 +23,19 @@ impl<'a, T: 'a> SmallVec<'a, T> {
         self.pos
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.pos == 0
+    }
+
     pub fn rem(&self) -> usize {
         self.cap() - self.len()
     }
 
-    pub fn push(&mut self, value: T) {
+    pub fn push(&mut self, value: T) -> Result<(), Error> {
+        if self.pos == self.buf.len() { return Err(Error::OutOfMemory) }
         self.buf[self.pos] = value;
         self.pos += 1;
+        Ok(())
     }
 }
 
@@ -78,15 +85,16 @@ mod tests {
         let mut buf = [0u8; 16];
         let mut v = SmallVec::new(&mut buf);
         for i in 0..16 {
-            v.push(i as u8);
+            v.push(i as u8).unwrap();
         }
+        assert_eq!(v.push(16), Err(Error::OutOfMemory));
         assert_eq!(v.cap(), 16);
         assert_eq!(v.len(), 16);
         assert_eq!(v.rem(), 0);
 
         for i in 0..16 {
             assert_eq!(v[i], i as u8);
-            v[i] = v[i] * 2;
+            v[i] *= 2;
             assert_eq!(v[i], (i * 2) as u8);
         }
 
@@ -0,0 +1,599 @@
+//! An integer-only IEEE-754 implementation of the float operations used by the interpreter,
+//! for cores without an FPU.
+//!
+//! Results are rounded to nearest, ties to even, and match the native instructions bit for
+//! bit, including the NaN each operation produces. On x86 a NaN result is the first NaN
+//! operand, quieted, and invalid operations produce a negative default NaN. Other targets
+//! follow the ARM rules: signaling operands take precedence and the default NaN is positive.
+//!
+//! Which of two NaN operands the hardware returns depends on the order the code generator
+//! puts them in, so the native operations in `fp` also take NaN results from `propagate_nan`.
+
+macro_rules! ieee {
+    ($m:ident, $f:ident, $u:ident, $w:ident, $mant:expr, $exp:expr) => {
+        pub mod $m {
+            use core::cmp::Ordering;
+
+            const BITS: u32 = 1 + $exp + $mant;
+            const W_BITS: u32 = 2 * BITS;
+            const MANT: u32 = $mant;
+            const BIAS: i32 = (1 << ($exp - 1)) - 1;
+            const EXP_MAX: i32 = (1 << $exp) - 1;
+            const SIGN: $u = 1 << (BITS - 1);
+            const FRAC: $u = (1 << MANT) - 1;
+            const INF: $u = (EXP_MAX as $u) << MANT;
+            const QUIET: $u = 1 << (MANT - 1);
+            const ONE: $u = (BIAS as $u) << MANT;
+            const X86: bool = cfg!(any(target_arch = "x86", target_arch = "x86_64"));
+            const DEFAULT_NAN: $u = if X86 { SIGN | INF | QUIET } else { INF | QUIET };
+
+            fn nan(a: $u) -> bool {
+                a & !SIGN > INF
+            }
+
+            fn snan(a: $u) -> bool {
+                nan(a) && a & QUIET == 0
+            }
+
+            /// Returns the NaN produced by a binary operation with at least one NaN operand.
+            fn propagate(a: $u, b: $u) -> $u {
+                let first = if X86 { nan(a) } else { snan(a) || (!snan(b) && nan(a)) };
+                if first { a | QUIET } else { b | QUIET }
+            }
+
+            /// Returns the NaN produced by a binary operation on `a` and `b`, at least one of
+            /// which is NaN.
+            pub fn propagate_nan(a: $f, b: $f) -> $f {
+                $f::from_bits(propagate(a.to_bits(), b.to_bits()))
+            }
+
+            /// Splits a finite value into its biased exponent and significand, such that its
+            /// magnitude is `sig * 2^(e - BIAS - MANT)`. Subnormals have `e == 1`.
+            pub(crate) fn unpack(a: $u) -> (i32, $u) {
+                let e = (a >> MANT) as i32 & EXP_MAX;
+                if e == 0 {
+                    (1, a & FRAC)
+                } else {
+                    (e, (a & FRAC) | (1 << MANT))
+                }
+            }
+
+            /// Like `unpack`, but shifts subnormal significands up to the implicit bit.
+            fn unpack_normal(a: $u) -> (i32, $u) {
+                let (e, sig) = unpack(a);
+                let shift = sig.leading_zeros() - (BITS - 1 - MANT);
+                (e - shift as i32, sig << shift)
+            }
+
+            fn shr_sticky(x: $w, s: u32) -> $w {
+                if s == 0 {
+                    x
+                } else if s >= W_BITS {
+                    (x != 0) as $w
+                } else {
+                    (x >> s) | ((x & ((1 << s) - 1) != 0) as $w)
+                }
+            }
+
+            /// Rounds and packs `sig * 2^(e - BIAS - MANT - 3)`, where `sig` has its leading
+            /// bit at `MANT + 3` unless the value is subnormal.
+            fn round_pack(sign: $u, mut e: i32, mut sig: $w) -> $u {
+                if e < 1 {
+                    sig = shr_sticky(sig, (1 - e) as u32);
+                    e = 1;
+                }
+                let rest = sig & 7;
+                sig >>= 3;
+                if rest > 4 || (rest == 4 && sig & 1 == 1) {
+                    sig += 1;
+                    if sig >> (MANT + 1) != 0 {
+                        sig >>= 1;
+                        e += 1;
+                    }
+                }
+                if e >= EXP_MAX {
+                    return sign | INF
+                }
+                let sig = sig as $u;
+                let e = if sig >> MANT == 0 { 0 } else { e as $u };
+                sign | (e << MANT) | (sig & FRAC)
+            }
+
+            /// Rounds and packs the non-zero value `w * 2^(e - BIAS - MANT - 3)`.
+            pub(crate) fn pack(sign: $u, e: i32, w: $w) -> $u {
+                let top = W_BITS - 1 - w.leading_zeros();
+                let target = MANT + 3;
+                if top > target {
+                    let s = top - target;
+                    round_pack(sign, e + s as i32, shr_sticky(w, s))
+                } else {
+                    let s = target - top;
+                    round_pack(sign, e - s as i32, w << s)
+                }
+            }
+
+            fn add_bits(a: $u, b: $u) -> $u {
+                let (ma, mb) = (a & !SIGN, b & !SIGN);
+                if ma == INF {
+                    return if mb == INF && (a ^ b) & SIGN != 0 { DEFAULT_NAN } else { a }
+                }
+                if mb == INF {
+                    return b
+                }
+                if ma == 0 && mb == 0 {
+                    // -0.0 only if both are -0.0
+                    return a & b
+                }
+                let (x, y) = if ma >= mb { (a, b) } else { (b, a) };
+                let (ex, sx) = unpack(x);
+                let (ey, sy) = unpack(y);
+                let wx = (sx as $w) << 3;
+                let wy = shr_sticky((sy as $w) << 3, (ex - ey) as u32);
+                let w = if (x ^ y) & SIGN == 0 { wx + wy } else { wx - wy };
+                if w == 0 {
+                    return 0
+                }
+                pack(x & SIGN, ex, w)
+            }
+
+            pub fn add(a: $f, b: $f) -> $f {
+                let (a, b) = (a.to_bits(), b.to_bits());
+                $f::from_bits(if nan(a) || nan(b) { propagate(a, b) } else { add_bits(a, b) })
+            }
+
+            pub fn sub(a: $f, b: $f) -> $f {
+                let (a, b) = (a.to_bits(), b.to_bits());
+                $f::from_bits(if nan(a) || nan(b) { propagate(a, b) } else { add_bits(a, b ^ SIGN) })
+            }
+
+            pub fn mul(a: $f, b: $f) -> $f {
+                let (a, b) = (a.to_bits(), b.to_bits());
+                $f::from_bits({
+                    let sign = (a ^ b) & SIGN;
+                    let (ma, mb) = (a & !SIGN, b & !SIGN);
+                    if nan(a) || nan(b) {
+                        propagate(a, b)
+                    } else if ma == INF || mb == INF {
+                        if ma == 0 || mb == 0 { DEFAULT_NAN } else { sign | INF }
+                    } else if ma == 0 || mb == 0 {
+                        sign
+                    } else {
+                        let (ea, sa) = unpack_normal(a);
+                        let (eb, sb) = unpack_normal(b);
+                        pack(sign, ea + eb - BIAS - MANT as i32 + 3, sa as $w * sb as $w)
+                    }
+                })
+            }
+
+            pub fn div(a: $f, b: $f) -> $f {
+                let (a, b) = (a.to_bits(), b.to_bits());
+                $f::from_bits({
+                    let sign = (a ^ b) & SIGN;
+                    let (ma, mb) = (a & !SIGN, b & !SIGN);
+                    if nan(a) || nan(b) {
+                        propagate(a, b)
+                    } else if ma == INF {
+                        if mb == INF { DEFAULT_NAN } else { sign | INF }
+                    } else if mb == INF {
+                        sign
+                    } else if mb == 0 {
+                        if ma == 0 { DEFAULT_NAN } else { sign | INF }
+                    } else if ma == 0 {
+                        sign
+                    } else {
+                        let (ea, sa) = unpack_normal(a);
+                        let (eb, sb) = unpack_normal(b);
+                        let n = (sa as $w) << (MANT + 4);
+                        let (q, r) = (n / sb as $w, n % sb as $w);
+                        pack(sign, ea - eb + BIAS - 2, (q << 1) | (r != 0) as $w)
+                    }
+                })
+            }
+
+            fn isqrt(n: $w) -> ($w, bool) {
+                let (mut x, mut r) = (n, 0);
+                let mut bit: $w = 1 << ((W_BITS - 1 - n.leading_zeros()) & !1);
+                while bit != 0 {
+                    if x >= r + bit {
+                        x -= r + bit;
+                        r = (r >> 1) + bit;
+                    } else {
+                        r >>= 1;
+                    }
+                    bit >>= 2;
+                }
+                (r, x != 0)
+            }
+
+            pub fn sqrt(a: $f) -> $f {
+                let a = a.to_bits();
+                $f::from_bits({
+                    if nan(a) {
+                        a | QUIET
+                    } else if a & !SIGN == 0 || a == INF {
+                        a
+                    } else if a & SIGN != 0 {
+                        DEFAULT_NAN
+                    } else {
+                        // a = sig * 2^x with x made even, scaled up so the root has guard bits
+                        let (e, sig) = unpack_normal(a);
+                        let mut x = e - BIAS - MANT as i32;
+                        let mut n = sig as $w;
+                        if x & 1 != 0 {
+                            n <<= 1;
+                            x -= 1;
+                        }
+                        let k = MANT + 6 + (MANT & 1);
+                        let (r, inexact) = isqrt(n << k);
+                        pack(0, (x - k as i32) / 2 + BIAS + MANT as i32 + 2, (r << 1) | inexact as $w)
+                    }
+                })
+            }
+
+            /// Rounds `a` to an integral value. `up` decides whether to increase the
+            /// magnitude given the sign, how the discarded fraction compares to one half,
+            /// and whether the truncated value is odd.
+            fn integral(a: $f, up: fn(bool, Ordering, bool) -> bool) -> $f {
+                let a = a.to_bits();
+                $f::from_bits({
+                    let neg = a & SIGN != 0;
+                    let e = ((a >> MANT) as i32 & EXP_MAX) - BIAS;
+                    // Like libm, NaNs are returned unchanged
+                    if nan(a) || e >= MANT as i32 || a & !SIGN == 0 {
+                        a
+                    } else if e < 0 {
+                        let half = match e {
+                            -1 if a & FRAC == 0 => Ordering::Equal,
+                            -1 => Ordering::Greater,
+                            _ => Ordering::Less,
+                        };
+                        if up(neg, half, false) { (a & SIGN) | ONE } else { a & SIGN }
+                    } else {
+                        let mask = FRAC >> e;
+                        let frac = a & mask;
+                        let t = a & !mask;
+                        if frac != 0 && up(neg, frac.cmp(&((mask + 1) >> 1)), a & (mask + 1) != 0) {
+                            t + mask + 1
+                        } else {
+                            t
+                        }
+                    }
+                })
+            }
+
+            pub fn ceil(a: $f) -> $f {
+                integral(a, |neg, _, _| !neg)
+            }
+
+            pub fn floor(a: $f) -> $f {
+                integral(a, |neg, _, _| neg)
+            }
+
+            pub fn trunc(a: $f) -> $f {
+                integral(a, |_, _, _| false)
+            }
+
+            pub fn nearest(a: $f) -> $f {
+                integral(a, |_, half, odd| half == Ordering::Greater || (half == Ordering::Equal && odd))
+            }
+
+            pub fn is_nan(a: $f) -> bool {
+                nan(a.to_bits())
+            }
+
+            pub fn eq(a: $f, b: $f) -> bool {
+                let (a, b) = (a.to_bits(), b.to_bits());
+                !nan(a) && !nan(b) && (a == b || (a | b) & !SIGN == 0)
+            }
+
+            /// Maps a non-NaN value to an unsigned key with the same ordering.
+            fn key(a: $u) -> $u {
+                if a & SIGN != 0 { !a } else { a | SIGN }
+            }
+
+            pub fn lt(a: $f, b: $f) -> bool {
+                let (a, b) = (a.to_bits(), b.to_bits());
+                !nan(a) && !nan(b) && (a | b) & !SIGN != 0 && key(a) < key(b)
+            }
+
+            pub fn le(a: $f, b: $f) -> bool {
+                lt(a, b) || eq(a, b)
+            }
+
+            pub fn from_u64(x: u64) -> $f {
+                $f::from_bits(if x == 0 { 0 } else { pack(0, BIAS + MANT as i32 + 3, x as $w) })
+            }
+
+            pub fn from_i64(x: i64) -> $f {
+                let sign = if x < 0 { SIGN } else { 0 };
+                $f::from_bits(sign | from_u64(x.wrapping_abs() as u64).to_bits())
+            }
+
+            pub fn from_u32(x: u32) -> $f {
+                from_u64(x as u64)
+            }
+
+            pub fn from_i32(x: i32) -> $f {
+                from_i64(x as i64)
+            }
+
+            /// Returns the sign and truncated magnitude of a non-NaN value, saturated to 2^64.
+            fn to_int(a: $u) -> (bool, u128) {
+                let neg = a & SIGN != 0;
+                let e = ((a >> MANT) as i32 & EXP_MAX) - BIAS;
+                if e < 0 {
+                    (neg, 0)
+                } else if e >= 64 {
+                    (neg, 1 << 64)
+                } else {
+                    let sig = ((a & FRAC) | (1 << MANT)) as u128;
+                    let e = e as u32;
+                    (neg, if e >= MANT { sig << (e - MANT) } else { sig >> (MANT - e) })
+                }
+            }
+
+            // Conversions to integers truncate toward zero, saturate, and map NaN to 0.
+
+            pub fn to_i64(a: $f) -> i64 {
+                let a = a.to_bits();
+                if nan(a) {
+                    return 0
+                }
+                match to_int(a) {
+                    (true, m) if m >= 1 << 63 => i64::MIN,
+                    (true, m) => -(m as i64),
+                    (false, m) if m >= 1 << 63 => i64::MAX,
+                    (false, m) => m as i64,
+                }
+            }
+
+            pub fn to_u64(a: $f) -> u64 {
+                let a = a.to_bits();
+                if nan(a) {
+                    return 0
+                }
+                match to_int(a) {
+                    (true, _) => 0,
+                    (false, m) if m > u64::MAX as u128 => u64::MAX,
+                    (false, m) => m as u64,
+                }
+            }
+
+            pub fn to_i32(a: $f) -> i32 {
+                let x = to_i64(a);
+                if x > i32::MAX as i64 {
+                    i32::MAX
+                } else if x < i32::MIN as i64 {
+                    i32::MIN
+                } else {
+                    x as i32
+                }
+            }
+
+            pub fn to_u32(a: $f) -> u32 {
+                let x = to_u64(a);
+                if x > u32::MAX as u64 { u32::MAX } else { x as u32 }
+            }
+        }
+    }
+}
+
+ieee!(single, f32, u32, u64, 23, 8);
+ieee!(double, f64, u64, u128, 52, 11);
+
+pub fn promote(a: f32) -> f64 {
+    let a = a.to_bits();
+    let sign = ((a >> 31) as u64) << 63;
+    f64::from_bits(match a & 0x7fff_ffff {
+        0 => sign,
+        0x7f80_0000 => sign | 0x7ff0_0000_0000_0000,
+        m if m > 0x7f80_0000 => sign | 0x7ff8_0000_0000_0000 | ((m & 0x007f_ffff) as u64) << 29,
+        _ => {
+            let (e, sig) = single::unpack(a);
+            double::pack(sign, e + 928, sig as u128)
+        },
+    })
+}
+
+pub fn demote(a: f64) -> f32 {
+    let a = a.to_bits();
+    let sign = ((a >> 63) as u32) << 31;
+    f32::from_bits(match a & 0x7fff_ffff_ffff_ffff {
+        0 => sign,
+        0x7ff0_0000_0000_0000 => sign | 0x7f80_0000,
+        m if m > 0x7ff0_0000_0000_0000 => sign | 0x7fc0_0000 | (m >> 29) as u32 & 0x007f_ffff,
+        _ => {
+            let (e, sig) = double::unpack(a);
+            single::pack(sign, e - 922, sig)
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::intrinsics;
+
+    const F32_SPECIAL: &[u32] = &[
+        0x0000_0000, 0x8000_0000, 0x0000_0001, 0x8000_0001, 0x007f_ffff, 0x0080_0000,
+        0x3f80_0000, 0xbf80_0000, 0x3f00_0000, 0x3fc0_0000, 0x4020_0000, 0xbfc0_0000,
+        0x4b00_0001, 0x4effffff, 0x4f00_0000, 0x5f00_0000, 0x7f7f_ffff, 0xff7f_ffff,
+        0x7f80_0000, 0xff80_0000, 0x7fc0_0000, 0xffc0_0001, 0x7fa0_0000, 0x3eaa_aaab,
+    ];
+
+    const F64_SPECIAL: &[u64] = &[
+        0x0000_0000_0000_0000, 0x8000_0000_0000_0000, 0x0000_0000_0000_0001,
+        0x000f_ffff_ffff_ffff, 0x0010_0000_0000_0000, 0x3ff0_0000_0000_0000,
+        0xbff0_0000_0000_0000, 0x3fe0_0000_0000_0000, 0x3ff8_0000_0000_0000,
+        0x4004_0000_0000_0000, 0x4330_0000_0000_0001, 0x43e0_0000_0000_0000,
+        0x41df_ffff_ffc0_0000, 0x7fef_ffff_ffff_ffff, 0x7ff0_0000_0000_0000,
+        0xfff0_0000_0000_0000, 0x7ff8_0000_0000_0000, 0xfff8_0000_0000_0001,
+        0x7ff4_0000_0000_0000, 0x3fd5_5555_5555_5555, 0x47ef_ffff_e000_0000,
+        0x3690_0000_0000_0000,
+    ];
+
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    fn f32_values() -> impl Iterator<Item=f32> {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        F32_SPECIAL.iter().cloned().chain((0..2000).map(move |_| {
+            let x = rng.next();
+            // Cluster exponents so that operands often interact
+            (x as u32 & 0x807f_ffff) | (((x >> 32) as u32 % 64 + 96) << 23)
+        })).map(f32::from_bits)
+    }
+
+    fn f64_values() -> impl Iterator<Item=f64> {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        F64_SPECIAL.iter().cloned().chain((0..2000).map(move |_| {
+            let x = rng.next();
+            (x & 0x800f_ffff_ffff_ffff) | ((rng.next() % 128 + 960) << 52)
+        })).map(f64::from_bits)
+    }
+
+    // NaN payloads must match unless both operands are NaN, where the choice between them
+    // is up to the native code generator. `test_nan_pairs` checks those through `fp`.
+    fn same32(soft: f32, native: f32, a: f32, b: f32) -> bool {
+        soft.to_bits() == native.to_bits() || (a.is_nan() && b.is_nan() && soft.is_nan() && native.is_nan())
+    }
+
+    fn same64(soft: f64, native: f64, a: f64, b: f64) -> bool {
+        soft.to_bits() == native.to_bits() || (a.is_nan() && b.is_nan() && soft.is_nan() && native.is_nan())
+    }
+
+    #[test]
+    fn test_single() {
+        let values: [f32; 256] = {
+            let mut v = [0.0; 256];
+            for (o, x) in v.iter_mut().zip(f32_values().step_by(8)) { *o = x; }
+            v
+        };
+        for a in f32_values() {
+            for &b in values.iter() {
+                assert!(same32(single::add(a, b), a + b, a, b), "{:x} + {:x}", a.to_bits(), b.to_bits());
+                assert!(same32(single::sub(a, b), a - b, a, b), "{:x} - {:x}", a.to_bits(), b.to_bits());
+                assert!(same32(single::mul(a, b), a * b, a, b), "{:x} * {:x}", a.to_bits(), b.to_bits());
+                assert!(same32(single::div(a, b), a / b, a, b), "{:x} / {:x}", a.to_bits(), b.to_bits());
+                assert_eq!(single::eq(a, b), a == b);
+                assert_eq!(single::lt(a, b), a < b);
+                assert_eq!(single::le(a, b), a <= b);
+            }
+            let n = a.to_bits();
+            assert_eq!(single::sqrt(a).to_bits(), intrinsics::sqrtf32(a).to_bits(), "sqrt {:x}", n);
+            assert_eq!(single::ceil(a).to_bits(), intrinsics::ceilf32(a).to_bits(), "ceil {:x}", n);
+            assert_eq!(single::floor(a).to_bits(), intrinsics::floorf32(a).to_bits(), "floor {:x}", n);
+            assert_eq!(single::trunc(a).to_bits(), intrinsics::truncf32(a).to_bits(), "trunc {:x}", n);
+            assert_eq!(single::nearest(a).to_bits(), intrinsics::round_ties_even_f32(a).to_bits(), "nearest {:x}", n);
+            assert_eq!(promote(a).to_bits(), (a as f64).to_bits(), "promote {:x}", n);
+            assert_eq!(single::to_i32(a), a as i32);
+            assert_eq!(single::to_u32(a), a as u32);
+            assert_eq!(single::to_i64(a), a as i64);
+            assert_eq!(single::to_u64(a), a as u64);
+        }
+    }
+
+    #[test]
+    fn test_double() {
+        let values: [f64; 256] = {
+            let mut v = [0.0; 256];
+            for (o, x) in v.iter_mut().zip(f64_values().step_by(8)) { *o = x; }
+            v
+        };
+        for a in f64_values() {
+            for &b in values.iter() {
+                assert!(same64(double::add(a, b), a + b, a, b), "{:x} + {:x}", a.to_bits(), b.to_bits());
+                assert!(same64(double::sub(a, b), a - b, a, b), "{:x} - {:x}", a.to_bits(), b.to_bits());
+                assert!(same64(double::mul(a, b), a * b, a, b), "{:x} * {:x}", a.to_bits(), b.to_bits());
+                assert!(same64(double::div(a, b), a / b, a, b), "{:x} / {:x}", a.to_bits(), b.to_bits());
+                assert_eq!(double::eq(a, b), a == b);
+                assert_eq!(double::lt(a, b), a < b);
+                assert_eq!(double::le(a, b), a <= b);
+            }
+            let n = a.to_bits();
+            assert_eq!(double::sqrt(a).to_bits(), intrinsics::sqrtf64(a).to_bits(), "sqrt {:x}", n);
+            assert_eq!(double::ceil(a).to_bits(), intrinsics::ceilf64(a).to_bits(), "ceil {:x}", n);
+            assert_eq!(double::floor(a).to_bits(), intrinsics::floorf64(a).to_bits(), "floor {:x}", n);
+            assert_eq!(double::trunc(a).to_bits(), intrinsics::truncf64(a).to_bits(), "trunc {:x}", n);
+            assert_eq!(double::nearest(a).to_bits(), intrinsics::round_ties_even_f64(a).to_bits(), "nearest {:x}", n);
+            assert_eq!(demote(a).to_bits(), (a as f32).to_bits(), "demote {:x}", n);
+            assert_eq!(double::to_i32(a), a as i32);
+            assert_eq!(double::to_u32(a), a as u32);
+            assert_eq!(double::to_i64(a), a as i64);
+            assert_eq!(double::to_u64(a), a as u64);
+        }
+    }
+
+    #[cfg(not(any(feature="soft-float", feature="integer-only")))]
+    #[test]
+    fn test_nan_pairs() {
+        use fp;
+        use interp::{f32_min, f32_max, f64_min, f64_max};
+
+        // Quiet and signaling NaNs of both signs, with and without a payload
+        const F32_NANS: &[u32] = &[0x7fc0_0000, 0xffc0_0000, 0x7fc0_1234, 0xffc0_0001, 0x7fa0_0000, 0xff80_0001];
+        const F64_NANS: &[u64] = &[
+            0x7ff8_0000_0000_0000, 0xfff8_0000_0000_0000, 0x7ff8_0000_0000_1234,
+            0xfff8_0000_0000_0001, 0x7ff4_0000_0000_0000, 0xfff0_0000_0000_0001,
+        ];
+
+        // Both operands NaN: the first one wins on both x86 and ARM when neither signals
+        let (a, b) = (f32::from_bits(0x7fc0_0000), f32::from_bits(0xffc0_0000));
+        assert_eq!(fp::single::add(a, b).to_bits(), 0x7fc0_0000);
+        assert_eq!(fp::single::add(b, a).to_bits(), 0xffc0_0000);
+
+        let ones = [1.0f32, f32::from_bits(0x7f80_0000)];
+        for a in F32_NANS.iter().cloned().map(f32::from_bits).chain(ones.iter().cloned()) {
+            for b in F32_NANS.iter().cloned().map(f32::from_bits) {
+                for &(x, y) in [(a, b), (b, a)].iter() {
+                    let n = (x.to_bits(), y.to_bits());
+                    assert_eq!(fp::single::add(x, y).to_bits(), single::add(x, y).to_bits(), "{:x?} add", n);
+                    assert_eq!(fp::single::sub(x, y).to_bits(), single::sub(x, y).to_bits(), "{:x?} sub", n);
+                    assert_eq!(fp::single::mul(x, y).to_bits(), single::mul(x, y).to_bits(), "{:x?} mul", n);
+                    assert_eq!(fp::single::div(x, y).to_bits(), single::div(x, y).to_bits(), "{:x?} div", n);
+                    assert_eq!(f32_min(x, y).to_bits(), single::propagate_nan(x, y).to_bits(), "{:x?} min", n);
+                    assert_eq!(f32_max(x, y).to_bits(), single::propagate_nan(x, y).to_bits(), "{:x?} max", n);
+                }
+            }
+        }
+
+        let ones = [1.0f64, f64::from_bits(0x7ff0_0000_0000_0000)];
+        for a in F64_NANS.iter().cloned().map(f64::from_bits).chain(ones.iter().cloned()) {
+            for b in F64_NANS.iter().cloned().map(f64::from_bits) {
+                for &(x, y) in [(a, b), (b, a)].iter() {
+                    let n = (x.to_bits(), y.to_bits());
+                    assert_eq!(fp::double::add(x, y).to_bits(), double::add(x, y).to_bits(), "{:x?} add", n);
+                    assert_eq!(fp::double::sub(x, y).to_bits(), double::sub(x, y).to_bits(), "{:x?} sub", n);
+                    assert_eq!(fp::double::mul(x, y).to_bits(), double::mul(x, y).to_bits(), "{:x?} mul", n);
+                    assert_eq!(fp::double::div(x, y).to_bits(), double::div(x, y).to_bits(), "{:x?} div", n);
+                    assert_eq!(f64_min(x, y).to_bits(), double::propagate_nan(x, y).to_bits(), "{:x?} min", n);
+                    assert_eq!(f64_max(x, y).to_bits(), double::propagate_nan(x, y).to_bits(), "{:x?} max", n);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_int() {
+        let mut rng = Rng(0x1234_5678_9abc_def1);
+        let ints = [0, 1, u64::MAX, 1 << 63, (1 << 63) - 1, (1 << 24) + 1, (1 << 53) + 1, 0x8000_0080_0000_0000];
+        for i in 0..ints.len() + 5000 {
+            let x = if i < ints.len() { ints[i] } else { rng.next() >> (rng.next() % 64) };
+            assert_eq!(single::from_u64(x).to_bits(), (x as f32).to_bits(), "{:x}", x);
+            assert_eq!(single::from_i64(x as i64).to_bits(), (x as i64 as f32).to_bits(), "{:x}", x);
+            assert_eq!(single::from_u32(x as u32).to_bits(), (x as u32 as f32).to_bits(), "{:x}", x);
+            assert_eq!(single::from_i32(x as i32).to_bits(), (x as i32 as f32).to_bits(), "{:x}", x);
+            assert_eq!(double::from_u64(x).to_bits(), (x as f64).to_bits(), "{:x}", x);
+            assert_eq!(double::from_i64(x as i64).to_bits(), (x as i64 as f64).to_bits(), "{:x}", x);
+            assert_eq!(double::from_i32(x as i32).to_bits(), (x as i32 as f64).to_bits(), "{:x}", x);
+        }
+    }
+}
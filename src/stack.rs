@@ -9,6 +9,19 @@ pub enum Error {
     OutOfBounds,
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match *self {
+            Error::Overflow => "stack overflow",
+            Error::Underflow => "stack underflow",
+            Error::OutOfBounds => "stack index out of bounds",
+        })
+    }
+}
+
+#[cfg(feature="std")]
+impl ::std::error::Error for Error {}
+
 pub struct Stack<'a, T: 'a + Copy> {
     buf: &'a mut [T],
     pos: usize,
@@ -16,7 +29,7 @@ pub struct Stack<'a, T: 'a + Copy> {
 
 impl<'a, T: 'a + Copy> Stack<'a, T> {
     pub fn new(buf: &'a mut [T]) -> Self {
-        Stack { buf: buf, pos: 0 }
+        Stack { buf, pos: 0 }
     }
 
     #[inline]
@@ -29,6 +42,11 @@ impl<'a, T: 'a + Copy> Stack<'a, T> {
         self.pos
     }
 
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pos == 0
+    }
+
     #[inline]
     pub fn empty(&self) -> bool {
         self.pos == 0
@@ -41,12 +59,14 @@ impl<'a, T: 'a + Copy> Stack<'a, T> {
 
     #[inline]
     pub fn reset(&mut self) -> StackResult<()> {
-        Ok(self.pos = 0)
+        self.pos = 0;
+        Ok(())
     }
 
     #[inline]
     pub fn set_pos(&mut self, pos: usize) -> StackResult<()> {
-        Ok(self.pos = pos)
+        self.pos = pos;
+        Ok(())
     }
 
     #[inline]
@@ -75,7 +95,8 @@ impl<'a, T: 'a + Copy> Stack<'a, T> {
     #[inline]
     pub fn push(&mut self, value: T) -> StackResult<()> {
         let pos = self.pre_incr()?;
-        Ok(self.buf[pos] = value)
+        self.buf[pos] = value;
+        Ok(())
     }
 
     #[inline]
@@ -114,15 +135,33 @@ impl<'a, T: 'a + Copy> Stack<'a, T> {
         }        
     }
 
-    /// Drops `drop_count` items. If `keep_count` is 0, then items [0..`drop_count`) are
-    /// deleted. If `keep_count` is 1, then items [1..`drop_count`+1) are deleted.
+    /// Drops `drop_count` items below the top `keep_count` items, which are moved
+    /// down to take their place. If `keep_count` is 0, then items [0..`drop_count`)
+    /// are deleted.
     #[inline]
     pub fn drop_keep(&mut self, drop_count: usize, keep_count: usize) -> StackResult<()> {
-        assert!(keep_count <= 1, "keep_count must be 0 or 1");
-        if keep_count == 1 {
-            *self.pick(drop_count)? = self.top()?;
+        if drop_count + keep_count > self.pos {
+            return Err(Error::Underflow)
         }
-        Ok(self.pos -= drop_count)
+        if drop_count > 0 {
+            let src = self.pos - keep_count;
+            for i in 0..keep_count {
+                self.buf[src - drop_count + i] = self.buf[src + i];
+            }
+        }
+        self.pos -= drop_count;
+        Ok(())
+    }
+
+    /// Truncates the stack to `pos` items, returning the items that were removed.
+    #[inline]
+    pub fn split_off(&mut self, pos: usize) -> StackResult<&[T]> {
+        if pos > self.pos {
+            return Err(Error::OutOfBounds)
+        }
+        let end = self.pos;
+        self.pos = pos;
+        Ok(&self.buf[pos..end])
     }
 
     #[inline]
@@ -137,7 +176,8 @@ impl<'a, T: 'a + Copy> Stack<'a, T> {
     #[inline]
     pub fn set(&mut self, index: usize, value: T) -> StackResult<()> {
         if index < self.pos {
-            Ok(self.buf[index] = value)
+            self.buf[index] = value;
+            Ok(())
         } else {
             Err(Error::OutOfBounds)
         }
@@ -148,7 +188,7 @@ impl<'a, T: 'a + Copy + fmt::Debug> Stack<'a, T> {
     pub fn dump(&self) {
         for i in 0..self.len() {
             let ptr = unsafe {
-                self.buf.as_ptr().offset((self.pos - i - 1) as isize)
+                self.buf.as_ptr().add(self.pos - i - 1)
             };
             info!("0x{:04}: {:p} {:?}", i, ptr, self.buf[self.pos - i - 1]);
         }
@@ -226,5 +266,14 @@ mod tests {
         s.drop_keep(7, 1).unwrap();
         assert_eq!(s.len(), 1);
         assert_eq!(s.top().unwrap(), 7);
+
+        for i in 1..8 {
+            s.push(i as u8).unwrap();
+        }
+        s.drop_keep(4, 3).unwrap();
+        assert_eq!(s.len(), 4);
+        assert_eq!(s.split_off(0).unwrap(), &[7, 5, 6, 7]);
+        assert_eq!(s.len(), 0);
+        assert!(s.drop_keep(1, 0).is_err());
     }
 }
\ No newline at end of file
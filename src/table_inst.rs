@@ -0,0 +1,144 @@
+use Error;
+
+use types::ValueType;
+
+use core::cell::Cell;
+
+/// The value of a null reference. Non-null `funcref` elements hold a function index and
+/// non-null `externref` elements hold a host handle.
+pub const NULL_REF: u32 = 0xffff_ffff;
+
+pub struct TableInst<'a> {
+    elemtype: ValueType,
+    elements: &'a [Cell<u32>],
+    size: Cell<u32>,
+}
+
+impl<'a> TableInst<'a> {
+    /// Creates a table of `size` null elements that can grow up to `elements.len()`.
+    pub fn new(elemtype: ValueType, elements: &'a [Cell<u32>], size: u32) -> Self {
+        assert!(size as usize <= elements.len());
+        for e in elements {
+            e.set(NULL_REF);
+        }
+        TableInst { elemtype, elements, size: Cell::new(size) }
+    }
+
+    pub fn elemtype(&self) -> ValueType {
+        self.elemtype
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size.get()
+    }
+
+    pub fn cap(&self) -> u32 {
+        self.elements.len() as u32
+    }
+
+    fn check_access(&self, index: u32, len: u32) -> Result<(), Error> {
+        match index.checked_add(len) {
+            Some(end) if end <= self.size() => Ok(()),
+            _ => Err(Error::TableOutOfBounds),
+        }
+    }
+
+    pub fn get(&self, index: u32) -> Result<u32, Error> {
+        self.check_access(index, 1)?;
+        Ok(self.elements[index as usize].get())
+    }
+
+    pub fn set(&self, index: u32, value: u32) -> Result<(), Error> {
+        self.check_access(index, 1)?;
+        self.elements[index as usize].set(value);
+        Ok(())
+    }
+
+    /// Grows the table by `n` elements set to `init`, returning the previous size or -1 if
+    /// the table cannot grow that far.
+    pub fn grow(&self, n: u32, init: u32) -> i32 {
+        let size = self.size();
+        match size.checked_add(n) {
+            Some(new_size) if new_size <= self.cap() => {
+                for e in &self.elements[size as usize..new_size as usize] {
+                    e.set(init);
+                }
+                self.size.set(new_size);
+                size as i32
+            },
+            _ => -1,
+        }
+    }
+
+    pub fn fill(&self, dst: u32, value: u32, len: u32) -> Result<(), Error> {
+        self.check_access(dst, len)?;
+        for e in &self.elements[dst as usize..(dst + len) as usize] {
+            e.set(value);
+        }
+        Ok(())
+    }
+
+    /// Copies `src` into the table starting at `dst`.
+    pub fn init(&self, dst: u32, src: &[u32]) -> Result<(), Error> {
+        self.check_access(dst, src.len() as u32)?;
+        for (e, v) in self.elements[dst as usize..].iter().zip(src) {
+            e.set(*v);
+        }
+        Ok(())
+    }
+
+    /// Copies `len` elements starting at `src` in `other` to `dst` in this table. `other`
+    /// may be this table, in which case the ranges may overlap.
+    pub fn copy_from(&self, dst: u32, other: &TableInst, src: u32, len: u32) -> Result<(), Error> {
+        self.check_access(dst, len)?;
+        other.check_access(src, len)?;
+        let (dst, src, len) = (dst as usize, src as usize, len as usize);
+        if dst <= src {
+            for i in 0..len {
+                self.elements[dst + i].set(other.elements[src + i].get());
+            }
+        } else {
+            for i in (0..len).rev() {
+                self.elements[dst + i].set(other.elements[src + i].get());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        let buf: [Cell<u32>; 8] = Default::default();
+        let t = TableInst::new(ValueType::AnyFunc, &buf, 4);
+        assert_eq!(t.size(), 4);
+        assert_eq!(t.get(0), Ok(NULL_REF));
+        assert_eq!(t.get(4), Err(Error::TableOutOfBounds));
+
+        t.set(1, 7).unwrap();
+        assert_eq!(t.get(1), Ok(7));
+
+        assert_eq!(t.grow(2, 9), 4);
+        assert_eq!(t.size(), 6);
+        assert_eq!(t.get(5), Ok(9));
+        assert_eq!(t.grow(3, 0), -1);
+        assert_eq!(t.size(), 6);
+
+        t.init(0, &[1, 2, 3]).unwrap();
+        t.copy_from(1, &t, 0, 3).unwrap();
+        assert_eq!(t.get(1), Ok(1));
+        assert_eq!(t.get(2), Ok(2));
+        assert_eq!(t.get(3), Ok(3));
+        t.copy_from(0, &t, 1, 3).unwrap();
+        assert_eq!(t.get(0), Ok(1));
+        assert_eq!(t.get(2), Ok(3));
+
+        t.fill(4, NULL_REF, 2).unwrap();
+        assert_eq!(t.get(5), Ok(NULL_REF));
+        assert_eq!(t.fill(5, 0, 2), Err(Error::TableOutOfBounds));
+        assert_eq!(t.init(5, &[1, 2]), Err(Error::TableOutOfBounds));
+    }
+}
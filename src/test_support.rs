@@ -0,0 +1,359 @@
+//! Fixtures shared by the unit tests: a host without imports, helpers to assemble, load and
+//! call modules, and the sample modules that more than one test runs.
+
+// Without the compiler only precompiled images can be loaded, which leaves most of these unused.
+#![cfg_attr(not(feature="compiler"), allow(dead_code))]
+
+use error::Error;
+use environ::{Environment, HostHandler};
+use interp::{Interp, Trap};
+#[cfg(feature="compiler")]
+use interp::Config;
+use memory_inst::MemoryInst;
+use module_inst::{ModuleInst, Value};
+use types::ImportDesc;
+#[cfg(feature="compiler")]
+use LoadError;
+
+use core::fmt;
+
+/// A host that provides no imports.
+pub struct Handler {}
+
+impl HostHandler for Handler {
+    fn import(&self, _module: &str, _export: &str, _import_desc: &ImportDesc) -> Result<usize, Error> {
+        Err(Error::InvalidImport)
+    }
+
+    fn dispatch(&self, _interp: &mut Interp, _mem: &MemoryInst, _type_index: usize, index: usize) -> Result<(), Error> {
+        Err(Error::InvalidFunction { id: index as u32 })
+    }
+}
+
+/// How `Fixture::load_with` instantiates a module.
+#[cfg(feature="compiler")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Load {
+    Compiled,
+    InPlace,
+    /// Compiled on first call into a code area of the given size.
+    Lazy(usize),
+}
+
+/// An environment with a single module loaded and an interpreter to call it, all allocated
+/// from one buffer.
+pub struct Fixture<'a> {
+    pub env: Environment<'a, Handler>,
+    pub mi: &'a ModuleInst<'a>,
+    pub interp: Interp<'a>,
+}
+
+impl<'a> Fixture<'a> {
+    /// Compiles `module`, panicking if it fails to load.
+    #[cfg(feature="compiler")]
+    pub fn load(buf: &'a mut [u8], module: &'a [u8]) -> Self {
+        Fixture::load_with(buf, module, Load::Compiled, Config::default()).unwrap()
+    }
+
+    #[cfg(feature="compiler")]
+    pub fn load_with(buf: &'a mut [u8], module: &'a [u8], load: Load, cfg: Config) -> Result<Self, LoadError> {
+        let (buf, mut env) = Environment::new(buf, Handler {})?;
+        let (buf, mi) = match load {
+            Load::Compiled => env.load_module("test", buf, module)?,
+            Load::InPlace => env.load_module_in_place("test", buf, module)?,
+            Load::Lazy(code_size) => env.load_module_lazy("test", buf, module, code_size)?,
+        };
+        Ok(Fixture { env, mi, interp: Interp::new_with_config(cfg, buf)? })
+    }
+
+    /// Loads a precompiled `image`, panicking if it fails to load.
+    pub fn load_image(buf: &'a mut [u8], image: &'a [u8]) -> Self {
+        let (buf, mut env) = Environment::new(buf, Handler {}).unwrap();
+        let (buf, mi) = env.load_image("test", buf, image).unwrap();
+        Fixture { env, mi, interp: Interp::new(buf).unwrap() }
+    }
+
+    /// Pushes `args` and calls local function `func`.
+    pub fn call(&mut self, func: usize, args: &[i32]) -> Result<&[Value], Trap<'_>> {
+        for &arg in args {
+            self.interp.push(arg).unwrap();
+        }
+        self.interp.call(&self.env, self.mi, func)
+    }
+
+    /// Like `call`, but returns only the error of a trap.
+    pub fn call_err(&mut self, func: usize, args: &[i32]) -> Error {
+        self.call(func, args).unwrap_err().into()
+    }
+}
+
+pub const HEADER: &[u8] = &[0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+/// Writes a module made of `sections` into `buf`.
+pub fn assemble<'a>(buf: &'a mut [u8], sections: &[&[u8]]) -> &'a [u8] {
+    let mut len = 0;
+    for s in [HEADER].iter().chain(sections) {
+        put(buf, &mut len, s);
+    }
+    &buf[..len]
+}
+
+pub fn put(buf: &mut [u8], len: &mut usize, bytes: &[u8]) {
+    buf[*len..*len + bytes.len()].copy_from_slice(bytes);
+    *len += bytes.len();
+}
+
+pub fn put_leb(buf: &mut [u8], len: &mut usize, mut value: usize) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 { return put(buf, len, &[byte]) }
+        put(buf, len, &[byte | 0x80]);
+    }
+}
+
+/// A fixed buffer to format into.
+pub struct Text {
+    buf: [u8; 128],
+    len: usize,
+}
+
+impl Text {
+    pub fn new() -> Self {
+        Text { buf: [0; 128], len: 0 }
+    }
+
+    pub fn format<T: fmt::Display>(value: T) -> Self {
+        use core::fmt::Write;
+        let mut text = Text::new();
+        write!(text, "{}", value).unwrap();
+        text
+    }
+
+    pub fn as_str(&self) -> &str {
+        ::core::str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+}
+
+impl fmt::Write for Text {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buf.len() { return Err(fmt::Error) }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// A module buffer aligned like the buffers the environment allocates from.
+#[repr(align(16))]
+pub struct Aligned(pub [u8; 16384]);
+
+// (type $t0 (func (result i32 i32)))
+// (type $t1 (func (param i32) (result i32 i32)))
+// (type $t2 (func (param i32 i32) (result i32)))
+// (func $pair (type $t0) i32.const 1 i32.const 2)
+// (func $br (type $t0) i32.const 3 block (type $t1) i32.const 4 i32.const 5 br 0 end)
+// (func $sub (type $t0) call $pair block (type $t2) i32.sub end i32.const 10)
+// (func $loop (type $t0) (local i32)
+//   i32.const 0
+//   loop (type $t1) i32.const 1 i32.add tee_local 0 get_local 0 i32.const 3 i32.lt_s br_if 0 end
+//   i32.const 100)
+pub const MULTI_VALUE_MODULE: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    0x01, 0x12, 0x03,
+        0x60, 0x00, 0x02, 0x7f, 0x7f,
+        0x60, 0x01, 0x7f, 0x02, 0x7f, 0x7f,
+        0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f,
+    0x03, 0x05, 0x04, 0x00, 0x00, 0x00, 0x00,
+    0x0a, 0x3a, 0x04,
+        0x06, 0x00, 0x41, 0x01, 0x41, 0x02, 0x0b,
+        0x0d, 0x00, 0x41, 0x03, 0x02, 0x01, 0x41, 0x04, 0x41, 0x05, 0x0c, 0x00, 0x0b, 0x0b,
+        0x0a, 0x00, 0x10, 0x00, 0x02, 0x02, 0x6b, 0x0b, 0x41, 0x0a, 0x0b,
+        0x18, 0x01, 0x01, 0x7f, 0x41, 0x00, 0x03, 0x01, 0x41, 0x01, 0x6a, 0x22, 0x00, 0x20, 0x00,
+              0x41, 0x03, 0x48, 0x0d, 0x00, 0x41, 0xe4, 0x00, 0x0b, 0x0b,
+];
+
+// (func (result i32) i32.const 0x80 i32.extend8_s)
+// (func (result i64) i64.const 0x8000 i64.extend16_s)
+// (func (result i32) f32.const 3e9 i32.trunc_sat_f32_s)
+// (func (result i64) f64.const -1.5 i64.trunc_sat_f64_u)
+// (func (result i32) f32.const nan i32.trunc_sat_f32_u)
+// (func (result i32) f32.const nan i32.trunc_f32_s)
+// (func (result i32) i32.const 1 i32.const 0 i32.div_s)
+// (func (result f32) f32.const -0 f32.const 0 f32.min)
+pub const CONVERSION_MODULE: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    0x01, 0x0d, 0x03, 0x60, 0x00, 0x01, 0x7f, 0x60, 0x00, 0x01, 0x7e, 0x60, 0x00, 0x01, 0x7d,
+    0x03, 0x09, 0x08, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02,
+    0x0a, 0x51, 0x08,
+        0x06, 0x00, 0x41, 0x80, 0x01, 0xc0, 0x0b,
+        0x07, 0x00, 0x42, 0x80, 0x80, 0x02, 0xc3, 0x0b,
+        0x09, 0x00, 0x43, 0x5e, 0xd0, 0x32, 0x4f, 0xfc, 0x00, 0x0b,
+        0x0d, 0x00, 0x44, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf8, 0xbf, 0xfc, 0x07, 0x0b,
+        0x09, 0x00, 0x43, 0x00, 0x00, 0xc0, 0x7f, 0xfc, 0x01, 0x0b,
+        0x08, 0x00, 0x43, 0x00, 0x00, 0xc0, 0x7f, 0xa8, 0x0b,
+        0x07, 0x00, 0x41, 0x01, 0x41, 0x00, 0x6d, 0x0b,
+        0x0d, 0x00, 0x43, 0x00, 0x00, 0x00, 0x80, 0x43, 0x00, 0x00, 0x00, 0x00, 0x96, 0x0b,
+];
+
+// (func (result i32)
+//   i32.const 32 i32.const 0x2a i32.const 4 memory.fill
+//   i32.const 34 i32.const 16 i32.const 2 memory.copy
+//   i32.const 32 i32.load)
+// (func (result i32) data.drop 0 i32.const 0 i32.const 0 i32.const 1 memory.init 0 i32.const 0)
+// (func (result i32) i32.const 0x1fff0 i32.const 0 i32.const 0x20 memory.fill i32.const 1)
+pub const BULK_MEMORY_MODULE: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f,
+    0x03, 0x05, 0x04, 0x00, 0x00, 0x00, 0x00,
+    0x05, 0x03, 0x01, 0x00, 0x01,
+    0x0c, 0x01, 0x01,
+    0x0a, 0x50, 0x04,
+        0x11, 0x00, 0x41, 0x10, 0x41, 0x01, 0x41, 0x04, 0xfc, 0x08, 0x00, 0x00, 0x41, 0x10, 0x28, 0x02, 0x00, 0x0b,
+        0x1a, 0x00, 0x41, 0x20, 0x41, 0x2a, 0x41, 0x04, 0xfc, 0x0b, 0x00,
+              0x41, 0x22, 0x41, 0x10, 0x41, 0x02, 0xfc, 0x0a, 0x00, 0x00, 0x41, 0x20, 0x28, 0x02, 0x00, 0x0b,
+        0x11, 0x00, 0xfc, 0x09, 0x00, 0x41, 0x00, 0x41, 0x00, 0x41, 0x01, 0xfc, 0x08, 0x00, 0x00, 0x41, 0x00, 0x0b,
+        0x0f, 0x00, 0x41, 0xf0, 0xff, 0x07, 0x41, 0x00, 0x41, 0x20, 0xfc, 0x0b, 0x00, 0x41, 0x01, 0x0b,
+    0x0b, 0x08, 0x01, 0x01, 0x05, b'h', b'e', b'l', b'l', b'o',
+];
+
+// (memory 17)
+// (data (i32.const 0x100ffe) "abcd")
+pub const ACTIVE_DATA_MODULE: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    0x05, 0x03, 0x01, 0x00, 0x11,
+    0x0b, 0x0d, 0x01, 0x00, 0x41, 0xfe, 0x9f, 0xc0, 0x00, 0x0b, 0x04, b'a', b'b', b'c', b'd',
+];
+
+// One funcref table (min 2, max 4) with an active segment [func 0] and a passive
+// expression segment [ref.func 1]. func 0 returns 7 and func 1 returns 11; the rest are:
+//   2: call_indirect through table[0]
+//   3: table.init table[1..2] from segment 1, then call_indirect through table[1]
+//   4: table.grow 2 with ref.func 1, then table.size
+//   5: table.fill table[2..4] with ref.null, then ref.is_null of table[3]
+//   6: elem.drop 1, then table.init from the dropped segment
+//   7: call_indirect through table[3]
+//   8: table.get table[9]
+//   9: table.set table[1] to ref.func 0, then call_indirect through table[1]
+pub const REFERENCE_TYPES_MODULE: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f,
+    0x03, 0x0b, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x05, 0x01, 0x70, 0x01, 0x02, 0x04,
+    0x09, 0x0d, 0x02, 0x00, 0x41, 0x00, 0x0b, 0x01, 0x00, 0x05, 0x70, 0x01, 0xd2, 0x01, 0x0b,
+    0x0a, 0x74, 0x0a,
+        0x04, 0x00, 0x41, 0x07, 0x0b,
+        0x04, 0x00, 0x41, 0x0b, 0x0b,
+        0x07, 0x00, 0x41, 0x00, 0x11, 0x00, 0x00, 0x0b,
+        0x11, 0x00, 0x41, 0x01, 0x41, 0x00, 0x41, 0x01, 0xfc, 0x0c, 0x01, 0x00, 0x41, 0x01, 0x11, 0x00, 0x00, 0x0b,
+        0x0d, 0x00, 0xd2, 0x01, 0x41, 0x02, 0xfc, 0x0f, 0x00, 0x1a, 0xfc, 0x10, 0x00, 0x0b,
+        0x10, 0x00, 0x41, 0x02, 0xd0, 0x70, 0x41, 0x02, 0xfc, 0x11, 0x00, 0x41, 0x03, 0x25, 0x00, 0xd1, 0x0b,
+        0x11, 0x00, 0xfc, 0x0d, 0x01, 0x41, 0x00, 0x41, 0x00, 0x41, 0x01, 0xfc, 0x0c, 0x01, 0x00, 0x41, 0x00, 0x0b,
+        0x07, 0x00, 0x41, 0x03, 0x11, 0x00, 0x00, 0x0b,
+        0x07, 0x00, 0x41, 0x09, 0x25, 0x00, 0xd1, 0x0b,
+        0x0d, 0x00, 0x41, 0x01, 0xd2, 0x00, 0x26, 0x00, 0x41, 0x01, 0x11, 0x00, 0x00, 0x0b,
+];
+
+// func 0 is sum(n, acc) with one local, which leaves an extra operand on the stack
+// before tail calling itself with (n - 1, acc + n). func 1 returns sum(1000, 0) via
+// return_call, func 2 returns sum(100, 0) via return_call_indirect through table[0],
+// and func 3 returns func 1 + 1 using a regular call.
+pub const TAIL_CALL_MODULE: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    0x01, 0x0b, 0x02, 0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f, 0x60, 0x00, 0x01, 0x7f,
+    0x03, 0x05, 0x04, 0x00, 0x01, 0x01, 0x01,
+    0x04, 0x04, 0x01, 0x70, 0x00, 0x01,
+    0x09, 0x07, 0x01, 0x00, 0x41, 0x00, 0x0b, 0x01, 0x00,
+    0x0a, 0x41, 0x04,
+        0x20, 0x01, 0x01, 0x7f, 0x20, 0x00, 0x45, 0x04, 0x40, 0x20, 0x01, 0x0f, 0x0b,
+              0x20, 0x00, 0x21, 0x02, 0x41, 0xe3, 0x00, 0x20, 0x02, 0x41, 0x01, 0x6b,
+              0x20, 0x01, 0x20, 0x02, 0x6a, 0x12, 0x00, 0x0b,
+        0x09, 0x00, 0x41, 0xe8, 0x07, 0x41, 0x00, 0x12, 0x00, 0x0b,
+        0x0c, 0x00, 0x41, 0xe4, 0x00, 0x41, 0x00, 0x41, 0x00, 0x13, 0x00, 0x00, 0x0b,
+        0x07, 0x00, 0x10, 0x01, 0x41, 0x01, 0x6a, 0x0b,
+];
+
+// tag 0 and tag 1 both carry an i32. func 0 throws tag 0 with its argument, func 1
+// catches it from a callee, func 2 falls through to catch_all for tag 1, func 3
+// rethrows from an inner catch into an outer one, func 4 throws tag 1 uncaught,
+// func 5 completes a try without throwing and func 6 branches out of a try body and
+// a catch clause in a loop before returning the caught payload.
+pub const EXCEPTION_MODULE: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    0x01, 0x0e, 0x03, 0x60, 0x00, 0x01, 0x7f, 0x60, 0x01, 0x7f, 0x00, 0x60, 0x01, 0x7f, 0x01, 0x7f,
+    0x03, 0x08, 0x07, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x0d, 0x05, 0x02, 0x00, 0x01, 0x00, 0x01,
+    0x0a, 0x78, 0x07,
+        0x06, 0x00, 0x20, 0x00, 0x08, 0x00, 0x0b,
+        0x0e, 0x00, 0x06, 0x7f, 0x41, 0x2a, 0x10, 0x00, 0x07, 0x00, 0x41, 0x01, 0x6a, 0x0b, 0x0b,
+        0x12, 0x00, 0x06, 0x7f, 0x41, 0x05, 0x08, 0x01, 0x07, 0x00, 0x41, 0xe4, 0x00, 0x6a,
+              0x0a, 0x41, 0x07, 0x0b, 0x0b,
+        0x17, 0x00, 0x06, 0x7f, 0x06, 0x7f, 0x41, 0x09, 0x08, 0x00, 0x07, 0x00, 0x1a, 0x09,
+              0x00, 0x0b, 0x07, 0x00, 0x41, 0xe8, 0x07, 0x6a, 0x0b, 0x0b,
+        0x06, 0x00, 0x41, 0x03, 0x08, 0x01, 0x0b,
+        0x09, 0x00, 0x06, 0x7f, 0x41, 0x0b, 0x07, 0x00, 0x0b, 0x0b,
+        0x24, 0x01, 0x01, 0x7f, 0x02, 0x7f, 0x03, 0x40, 0x06, 0x40, 0x20, 0x00, 0x41, 0x01,
+              0x6a, 0x22, 0x00, 0x41, 0xe4, 0x00, 0x48, 0x0d, 0x01, 0x20, 0x00, 0x08, 0x00,
+              0x07, 0x00, 0x0c, 0x02, 0x0b, 0x0b, 0x41, 0x7f, 0x0b, 0x0b,
+];
+
+// A shared memory of one page. func 0 stores, adds and loads at 8, func 1 runs a
+// matching and a mismatched cmpxchg, func 2 wraps 8-bit add and cmpxchg, func 3 runs
+// i64 sub and a 32-bit load, func 4 waits not-equal, waits with a timeout and
+// notifies, and funcs 5 and 6 load unaligned and out of bounds.
+pub const ATOMIC_MODULE: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    0x01, 0x09, 0x02, 0x60, 0x00, 0x01, 0x7f, 0x60, 0x00, 0x01, 0x7e,
+    0x03, 0x08, 0x07, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x05, 0x04, 0x01, 0x03, 0x01, 0x01,
+    0x0a, 0xc5, 0x01, 0x07,
+        0x19, 0x00, 0x41, 0x08, 0x41, 0x05, 0xfe, 0x17, 0x02, 0x00, 0x41, 0x08, 0x41, 0x03,
+              0xfe, 0x1e, 0x02, 0x00, 0x41, 0x08, 0xfe, 0x10, 0x02, 0x00, 0x6a, 0x0b,
+        0x26, 0x00, 0x41, 0x08, 0x41, 0x07, 0xfe, 0x17, 0x02, 0x00, 0x41, 0x08, 0x41, 0x07,
+              0x41, 0x2a, 0xfe, 0x48, 0x02, 0x00, 0x41, 0x08, 0x41, 0x00, 0x41, 0x01, 0xfe,
+              0x48, 0x02, 0x00, 0x6a, 0x41, 0x08, 0xfe, 0x10, 0x02, 0x00, 0x6a, 0x0b,
+        0x2d, 0x00, 0x41, 0x10, 0x41, 0xff, 0x01, 0xfe, 0x19, 0x00, 0x00, 0x41, 0x10, 0x41,
+              0x01, 0xfe, 0x20, 0x00, 0x00, 0x41, 0x10, 0xfe, 0x12, 0x00, 0x00, 0x6a, 0x41,
+              0x10, 0x41, 0x80, 0x02, 0x41, 0x09, 0xfe, 0x4a, 0x00, 0x00, 0x6a, 0x41, 0x10,
+              0xfe, 0x12, 0x00, 0x00, 0x6a, 0x0b,
+        0x19, 0x00, 0x41, 0x18, 0x42, 0x7f, 0xfe, 0x18, 0x03, 0x00, 0x41, 0x18, 0x42, 0x01,
+              0xfe, 0x26, 0x03, 0x00, 0x1a, 0x41, 0x18, 0xfe, 0x16, 0x02, 0x00, 0x0b,
+        0x26, 0x00, 0xfe, 0x03, 0x00, 0x41, 0x20, 0x41, 0x01, 0x42, 0x7f, 0xfe, 0x01, 0x02,
+              0x00, 0x41, 0x0a, 0x6c, 0x41, 0x20, 0x41, 0x00, 0x42, 0x00, 0xfe, 0x01, 0x02,
+              0x00, 0x6a, 0x41, 0x20, 0x41, 0x01, 0xfe, 0x00, 0x02, 0x00, 0x6a, 0x0b,
+        0x08, 0x00, 0x41, 0x02, 0xfe, 0x10, 0x02, 0x00, 0x0b,
+        0x0a, 0x00, 0x41, 0xfc, 0xff, 0x7f, 0xfe, 0x10, 0x02, 0x00, 0x0b,
+];
+
+// Func 0 adds two i32x4 constants, func 1 round-trips bytes through memory and
+// reverses them with a shuffle, func 2 uses bitselect on a splat, func 3 multiplies
+// f32 lanes, func 4 returns a v128 and func 5 loads out of bounds.
+#[cfg(feature="simd")]
+pub const SIMD_MODULE: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    0x01, 0x09, 0x02, 0x60, 0x00, 0x01, 0x7f, 0x60, 0x00, 0x01, 0x7b,
+    0x03, 0x07, 0x06, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00,
+    0x05, 0x03, 0x01, 0x00, 0x01,
+    0x0a, 0xea, 0x01, 0x06,
+        0x2c, 0x00, 0xfd, 0x0c, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, 0x00,
+              0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0xfd, 0x0c, 0x0a, 0x00, 0x00, 0x00, 0x14, 0x00,
+              0x00, 0x00, 0x1e, 0x00, 0x00, 0x00, 0x28, 0x00, 0x00, 0x00, 0xfd, 0xae, 0x01, 0xfd,
+              0x1b, 0x02, 0x0b,
+        0x47, 0x00, 0x41, 0x10, 0xfd, 0x0c, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+              0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0xfd, 0x0b, 0x04, 0x00, 0x41, 0x10,
+              0xfd, 0x00, 0x04, 0x00, 0xfd, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+              0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xfd, 0x0d, 0x0f, 0x0e, 0x0d, 0x0c,
+              0x0b, 0x0a, 0x09, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x00, 0xfd, 0x1b,
+              0x00, 0x0b,
+        0x2f, 0x00, 0x41, 0x05, 0xfd, 0x11, 0xfd, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+              0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xfd, 0x0c, 0xff, 0xff,
+              0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00,
+              0xfd, 0x52, 0xfd, 0x1b, 0x02, 0x0b,
+        0x22, 0x00, 0xfd, 0x0c, 0x00, 0x00, 0xc0, 0x3f, 0x00, 0x00, 0x20, 0x40, 0x00, 0x00,
+              0x00, 0xbf, 0x00, 0x00, 0x80, 0x40, 0x43, 0x00, 0x00, 0x00, 0x40, 0xfd, 0x13, 0xfd,
+              0xe6, 0x01, 0xfd, 0x1f, 0x01, 0xa8, 0x0b,
+        0x14, 0x00, 0xfd, 0x0c, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, 0x00,
+              0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x0b,
+        0x0b, 0x00, 0x41, 0x78, 0xfd, 0x00, 0x04, 0x00, 0x1a, 0x41, 0x01, 0x0b,
+];
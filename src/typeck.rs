@@ -1,4 +1,4 @@
-use {Error, ValueType};
+use {Error, Types, ValueType};
 use stack::Stack;
 use opcode::*;
 
@@ -15,56 +15,93 @@ pub enum LabelType {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Label {
+pub struct Label<'t> {
     pub label_type: LabelType,
-    pub signature: ValueType,
+    pub params: &'t [ValueType],
+    pub results: &'t [ValueType],
     pub stack_limit: usize, 
     pub unreachable: bool,
 }
 
+impl LabelType {
+    /// Names the construct a label belongs to, for describing type mismatches at its end.
+    pub fn as_str(&self) -> &'static str {
+        use self::LabelType::*;
+        match *self {
+            Func => "function",
+            Block => "block",
+            Loop => "loop",
+            If => "if",
+            Else => "if false branch",
+            Try => "try",
+            Catch => "catch",
+        }
+    }
+}
+
+impl<'t> Label<'t> {
+    /// Returns the types a branch to this label must carry: the params of a loop,
+    /// the results of anything else.
+    pub fn br_types(&self) -> &'t [ValueType] {
+        if self.label_type == LabelType::Loop {
+            self.params
+        } else {
+            self.results
+        }
+    }
+}
+
 pub struct TypeChecker<'m> {
-    label_stack: Stack<'m, Label>,
+    label_stack: Stack<'m, Label<'m>>,
     type_stack: Stack<'m, ValueType>,
-    br_table_sig: Option<ValueType>,
+    br_table_sig: Option<&'m [ValueType]>,
 }
 
 impl<'m> TypeChecker<'m> {
-    pub fn new(label_stack: Stack<'m, Label>, type_stack: Stack<'m, ValueType>) -> Self {
+    pub fn new(label_stack: Stack<'m, Label<'m>>, type_stack: Stack<'m, ValueType>) -> Self {
         let br_table_sig = None;
         TypeChecker { label_stack, type_stack, br_table_sig }
     }
 
-    pub fn get_label(&self, depth: usize) -> Result<Label, Error> {
+    pub fn get_label(&self, depth: usize) -> Result<Label<'m>, Error> {
         info!("  get_label({})", depth);
         Ok(self.label_stack.peek(depth)?)
     }
 
-    pub fn get_label_ref(&mut self, depth: usize) -> Result<&mut Label, Error> {
+    pub fn get_label_ref(&mut self, depth: usize) -> Result<&mut Label<'m>, Error> {
         info!("  get_label_ref({})", depth);
         Ok(self.label_stack.pick(depth)?)
     }
 
-    pub fn top_label(&self) -> Result<Label, Error> {
+    pub fn top_label(&self) -> Result<Label<'m>, Error> {
         self.get_label(0)
     }
 
+    pub fn func_label(&self) -> Result<Label<'m>, Error> {
+        let len = self.label_stack.len();
+        if len == 0 {
+            return Err(Error::TypeCheck("no function label"))
+        }
+        self.get_label(len - 1)
+    }
+
     pub fn type_stack_size(&self) -> usize { self.type_stack.len() }
     pub fn label_stack_size(&self) -> usize { self.label_stack.len() }
     
-    pub fn push_label(&mut self, label_type: LabelType, signature: ValueType) -> Result<(), Error> {        
-        Ok({
-            let stack_limit = self.type_stack.len();
-            let unreachable = false;
-            let label = Label {
-                label_type,
-                signature,
-                stack_limit,
-                unreachable,
-            };
-            let d = self.label_stack.len();
-            info!("  PUSH_LABEL: {} {:?}", d, label);
-            self.label_stack.push(label)?;
-        })
+    pub fn push_label(&mut self, label_type: LabelType, params: &'m [ValueType], results: &'m [ValueType]) -> Result<(), Error> {        
+        let stack_limit = self.type_stack.len();
+        let unreachable = false;
+        let label = Label {
+            label_type,
+            params,
+            results,
+            stack_limit,
+            unreachable,
+        };
+        let d = self.label_stack.len();
+        info!("  PUSH_LABEL: {} {:?}", d, label);
+        self.label_stack.push(label)?;
+        Ok(())
     }
 
     pub fn set_unreachable(&mut self, value: bool) -> Result<(), Error> {        
@@ -90,11 +127,10 @@ impl<'m> TypeChecker<'m> {
     }
 
     pub fn push_types(&mut self, types: &[ValueType]) -> Result<(), Error> {
-        Ok({
-            for t in types {
-                self.push_type(*t)?;
-            }
-        })
+        for t in types {
+            self.push_type(*t)?;
+        }
+        Ok(())
     }
 
     pub fn pop_type(&mut self) -> Result<ValueType, Error> {        
@@ -104,14 +140,14 @@ impl<'m> TypeChecker<'m> {
         Ok(v)
     }
 
-    pub fn pop_label(&mut self) -> Result<Label, Error> {
+    pub fn pop_label(&mut self) -> Result<Label<'m>, Error> {
         let d = self.label_stack.len();
         let label = self.label_stack.pop()?;
         info!("  POP_LABEL:  {} {:?}", d, label);
         Ok(label)
     }    
 
-    pub fn reset_type_stack_to_label(&mut self, label: Label) -> Result<(), Error> {    
+    pub fn reset_type_stack_to_label(&mut self, label: Label<'m>) -> Result<(), Error> {    
         info!("  reset_type_stack_to_label({:?})", label);
         self.type_stack.set_pos(label.stack_limit)?;
         Ok(())
@@ -172,17 +208,52 @@ impl<'m> TypeChecker<'m> {
         Ok(())
     }
 
-    pub fn check_type_stack_end(&mut self) -> Result<(), Error> {
+    pub fn check_type_stack_end(&mut self, desc: &'static str) -> Result<(), Error> {
         info!("  check_stack_type_end()");
         let label = self.top_label()?;
         info!("   -> type_stack: {} stack_limit: {}", self.type_stack.len(), label.stack_limit);
         if self.type_stack.len() != label.stack_limit {
-            return Err(Error::TypeCheck("type_stack length != label.stack_limit"))
+            return Err(self.mismatch(desc, &[]))
         }
         Ok(())
     }
 
-    pub fn check_label_type(&mut self, label: Label, label_type: LabelType) -> Result<(), Error> {
+    /// Returns true if the top of the type stack matches `expected`, treating missing
+    /// types as `any` when the current label is unreachable.
+    fn matches(&self, expected: &[ValueType]) -> Result<bool, Error> {
+        let label = self.top_label()?;
+        for (i, &e) in expected.iter().enumerate() {
+            let depth = expected.len() - i - 1;
+            let t = if label.stack_limit + depth >= self.type_stack.len() {
+                if !label.unreachable {
+                    return Ok(false)
+                }
+                ValueType::Any
+            } else {
+                self.type_stack.peek(depth)?
+            };
+            if !(e == t || e == ValueType::Any || t == ValueType::Any) {
+                return Ok(false)
+            }
+        }
+        Ok(true)
+    }
+
+    /// Describes a failed check of `expected` by `desc` against the types on the stack above
+    /// the current label: as many as were expected, or all of them if none were.
+    fn mismatch(&self, desc: &'static str, expected: &[ValueType]) -> Error {
+        let limit = self.top_label().map(|label| label.stack_limit).unwrap_or(0);
+        let available = self.type_stack.len().saturating_sub(limit);
+        let depth = if expected.is_empty() { available } else { expected.len().min(available) };
+        let mut got = [ValueType::Any; 4];
+        let shown = depth.min(got.len());
+        for (i, t) in got[..shown].iter_mut().enumerate() {
+            *t = self.type_stack.peek(shown - i - 1).unwrap_or(ValueType::Any);
+        }
+        Error::TypeMismatch { desc, expected: Types::new(expected), got: Types::tail(&got[..shown], depth) }
+    }
+
+    pub fn check_label_type(&mut self, label: Label<'m>, label_type: LabelType) -> Result<(), Error> {
         info!("  check_label_type({:?}, {:?})", label, label_type);
         if label.label_type == label_type {
             Ok(())
@@ -191,91 +262,72 @@ impl<'m> TypeChecker<'m> {
         }
     }
 
-    pub fn check_signature(&mut self, sig: &[ValueType]) -> Result<(), Error> {
-        info!("  check_signature({:?})", sig);
-
-        for i in 0..sig.len() {
-            self.peek_and_check_type(sig.len() - i - 1, sig[i])?;
+    pub fn check_signature(&mut self, sig: &[ValueType], desc: &'static str) -> Result<(), Error> {
+        info!("  check_signature({:?}, {:?})", sig, desc);
+        if !self.matches(sig)? {
+            return Err(self.mismatch(desc, sig))
         }
         Ok(())
-
     }
 
-    pub fn pop_and_check_signature(&mut self, sig: &[ValueType]) -> Result<(), Error> {
+    pub fn pop_and_check_signature(&mut self, sig: &[ValueType], desc: &'static str) -> Result<(), Error> {
         info!("  pop_and_check_signature({:?})", sig);
-        self.check_signature(sig)?;        
+        self.check_signature(sig, desc)?;        
         self.drop_types(sig.len())?;
         Ok(())
     }
 
-    pub fn pop_and_check_call(&mut self, parameters: &[ValueType], returns: &[ValueType]) -> Result<(), Error> {
+    pub fn pop_and_check_call(&mut self, parameters: &[ValueType], returns: &[ValueType], desc: &'static str) -> Result<(), Error> {
         info!("  pop_and_check_call({:?}, {:?})", parameters, returns);
-        Ok({
-            self.check_signature(parameters)?;
-            self.drop_types(parameters.len())?;            
-            self.push_types(returns)?;
-        })
+        self.check_signature(parameters, desc)?;
+        self.drop_types(parameters.len())?;            
+        self.push_types(returns)?;
+        Ok(())
     }
 
-    pub fn pop_and_check_one_type(&mut self, expected: ValueType) -> Result<(), Error> {
+    pub fn pop_and_check_one_type(&mut self, expected: ValueType, desc: &'static str) -> Result<(), Error> {
         info!("  pop_and_check_one_type({:?})", expected);
-        Ok({
-            self.peek_and_check_type(0, expected)?;
-            self.drop_types(1)?;
-        })
+        self.pop_and_check_signature(&[expected], desc)
     }
 
-    pub fn pop_and_check_two_types(&mut self, expected1: ValueType, expected2: ValueType) -> Result<(), Error> {
+    pub fn pop_and_check_two_types(&mut self, expected1: ValueType, expected2: ValueType, desc: &'static str) -> Result<(), Error> {
         info!("  pop_and_check_two_types({:?}, {:?})", expected1, expected2);
-        Ok({
-            self.peek_and_check_type(0, expected2)?;
-            self.peek_and_check_type(1, expected1)?;
-            self.drop_types(2)?;
-        })
+        self.pop_and_check_signature(&[expected1, expected2], desc)
     }    
 
-    pub fn pop_and_check_three_types(&mut self, expected1: ValueType, expected2: ValueType, expected3: ValueType) -> Result<(), Error> {
-        info!("  pop_and_check_two_types({:?}, {:?}, {:?})", expected1, expected2, expected3);
-        Ok({
-            self.peek_and_check_type(0, expected3)?;
-            self.peek_and_check_type(1, expected2)?;
-            self.peek_and_check_type(2, expected1)?;
-            self.drop_types(3)?;
-        })
+    pub fn pop_and_check_three_types(&mut self, expected1: ValueType, expected2: ValueType, expected3: ValueType, desc: &'static str) -> Result<(), Error> {
+        info!("  pop_and_check_three_types({:?}, {:?}, {:?})", expected1, expected2, expected3);
+        self.pop_and_check_signature(&[expected1, expected2, expected3], desc)
     }    
 
-    pub fn begin_function(&mut self, sig: ValueType) -> Result<(), Error> {
-        info!("begin_function({:?})", sig);
+    pub fn begin_function(&mut self, results: &'m [ValueType]) -> Result<(), Error> {
+        info!("begin_function({:?})", results);
         self.type_stack.reset()?;
         self.label_stack.reset()?;
-        self.push_label(LabelType::Func, sig)?;
+        self.push_label(LabelType::Func, &[], results)?;
         Ok(())
     }
 
     pub fn end_function(&mut self) -> Result<(), Error> {
         info!("end_function()");
-        Ok({
-            let label = self.top_label()?;
-            self.check_label_type(label, LabelType::Func)?;
-            self.on_end_label(label)?;
-        })
+        let label = self.top_label()?;
+        self.check_label_type(label, LabelType::Func)?;
+        self.on_end_label(label)?;
+        Ok(())
     }
 
     pub fn enter(&self) -> Result<(), Error> {
-        Ok({
-            info!("--- L: {} T: {} ---", self.label_stack.len(), self.type_stack.len());
-        })
+        info!("--- L: {} T: {} ---", self.label_stack.len(), self.type_stack.len());
+        Ok(())
     }
 
     pub fn exit(&self) -> Result<(), Error> {
-        Ok({
-            self.dump_type_stack()?;
-        })
+        self.dump_type_stack()?;
+        Ok(())
     }    
 
     pub fn on_select(&mut self) -> Result<(), Error> {
         info!("on_select()");
-        Ok({            
         //   Type type = Type::Any;
         //   result |= PeekAndCheckType(0, Type::I32);
         //   result |= PeekType(1, &type);
@@ -283,276 +335,315 @@ impl<'m> TypeChecker<'m> {
         //   PrintStackIfFailed(result, "select", Type::I32, type, type);
         //   result |= DropTypes(3);
         //   PushType(type);            
-            self.peek_and_check_type(0, ValueType::I32)?;
-            let t = self.peek_type(1)?;
-            self.peek_and_check_type(2, t)?;
-            self.drop_types(3)?;
-            self.push_type(t)?;
-        })
+        // A stack too short to hold the operands fails the check below
+        let t = self.peek_type(1).unwrap_or(ValueType::Any);
+        self.check_signature(&[t, t, ValueType::I32], "select")?;
+        if t.is_ref() {
+            return Err(Error::TypeCheck("select on reference types requires a type annotation"))
+        }
+        self.drop_types(3)?;
+        self.push_type(t)?;
+        Ok(())
+    }
+
+    pub fn on_select_t(&mut self, types: &[ValueType]) -> Result<(), Error> {
+        info!("on_select_t({:?})", types);
+        if types.len() != 1 {
+            return Err(Error::TypeCheck("invalid select arity"))
+        }
+        let t = types[0];
+        self.pop_and_check_three_types(t, t, ValueType::I32, "select")?;
+        self.push_type(t)?;
+        Ok(())
+    }
+
+    pub fn on_unreachable(&mut self) -> Result<(), Error> {
+        info!("on_unreachable()");
+        self.set_unreachable(true)
     }
 
     pub fn on_drop(&mut self) -> Result<(), Error> {
         info!("on_drop()");
-        Ok({
-            self.drop_types(1)?;
-        })
+        self.check_signature(&[ValueType::Any], "drop")?;
+        self.drop_types(1)?;
+        Ok(())
     }
 
 
-    pub fn on_block(&mut self, sig: ValueType) -> Result<(), Error> {
-        info!("on_block({:?})", sig);
-        Ok({
-            self.push_label(LabelType::Block, sig)?;
-        })
+    pub fn on_block(&mut self, params: &'m [ValueType], results: &'m [ValueType]) -> Result<(), Error> {
+        info!("on_block({:?}, {:?})", params, results);
+        self.pop_and_check_signature(params, "block")?;
+        self.push_label(LabelType::Block, params, results)?;
+        self.push_types(params)?;
+        Ok(())
     }
 
-    pub fn on_loop(&mut self, sig: ValueType) -> Result<(), Error> {
-        info!("on_loop({:?})", sig);
-        Ok({
-            self.push_label(LabelType::Loop, sig)?;
-        })
+    pub fn on_loop(&mut self, params: &'m [ValueType], results: &'m [ValueType]) -> Result<(), Error> {
+        info!("on_loop({:?}, {:?})", params, results);
+        self.pop_and_check_signature(params, "loop")?;
+        self.push_label(LabelType::Loop, params, results)?;
+        self.push_types(params)?;
+        Ok(())
     }
 
     pub fn on_call(&mut self, parameters: &[ValueType], result_types: &[ValueType]) -> Result<(), Error> {
         info!("on_call({:?}, {:?})", parameters, result_types);
-        Ok({
-            self.pop_and_check_call(parameters, result_types)?;
-        })
+        self.pop_and_check_call(parameters, result_types, "call")?;
+        Ok(())
     }
 
     pub fn on_call_indirect(&mut self, parameters: &[ValueType], result_types: &[ValueType]) -> Result<(), Error> {
         info!("on_call_indirect({:?}, {:?})", parameters, result_types);
-        Ok({
-            self.pop_and_check_one_type(ValueType::I32)?;
-            self.pop_and_check_call(parameters, result_types)?;
-        })
+        self.pop_and_check_one_type(ValueType::I32, "call_indirect")?;
+        self.pop_and_check_call(parameters, result_types, "call_indirect")?;
+        Ok(())
+    }
+
+    pub fn on_return_call(&mut self, parameters: &[ValueType], result_types: &[ValueType]) -> Result<(), Error> {
+        info!("on_return_call({:?}, {:?})", parameters, result_types);
+        // The callee's results become the caller's, so they must match exactly
+        let label = self.func_label()?;
+        if label.results != result_types {
+            return Err(Error::TypeCheck("return_call result types do not match function results"))
+        }
+        self.pop_and_check_signature(parameters, "return_call")?;
+        self.set_unreachable(true)?;
+        Ok(())
+    }
+
+    pub fn on_return_call_indirect(&mut self, parameters: &[ValueType], result_types: &[ValueType]) -> Result<(), Error> {
+        info!("on_return_call_indirect({:?}, {:?})", parameters, result_types);
+        self.pop_and_check_one_type(ValueType::I32, "return_call_indirect")?;
+        self.on_return_call(parameters, result_types)?;
+        Ok(())
     }
 
     pub fn on_return(&mut self) -> Result<(), Error> {
         info!("on_return()");
-        Ok({
-            let label = self.get_label(0)?;
-            info!("checking {:?}", label);         
-            if label.signature != VOID {   
-                self.pop_and_check_signature(&[label.signature])?;
-            }
-            self.set_unreachable(true)?;
-        })
+        let label = self.func_label()?;
+        info!("checking {:?}", label);         
+        self.pop_and_check_signature(label.results, "return")?;
+        self.set_unreachable(true)?;
+        Ok(())
     }
 
-    pub fn on_end_label(&mut self, label: Label) -> Result<(), Error> {
+    pub fn on_end_label(&mut self, label: Label<'m>) -> Result<(), Error> {
         info!("on_end_label({:?})", label);
-        Ok({
-            if label.signature != VOID {
-                self.pop_and_check_signature(&[label.signature])?;
-            }
-            self.check_type_stack_end()?;
-            self.reset_type_stack_to_label(label)?;
-            if label.signature != VOID {
-                self.push_type(label.signature)?;
-            }
-            self.pop_label()?;               
-        })
+        let desc = label.label_type.as_str();
+        self.pop_and_check_signature(label.results, desc)?;
+        self.check_type_stack_end(desc)?;
+        self.reset_type_stack_to_label(label)?;
+        self.push_types(label.results)?;
+        self.pop_label()?;               
+        Ok(())
     }
 
-    pub fn on_if(&mut self, sig: ValueType) -> Result<(), Error> {
-        info!("on_if()");
-        Ok({
-            self.pop_and_check_one_type(I32)?;
-            self.push_label(LabelType::If, sig)?;
-        })
+    pub fn on_if(&mut self, params: &'m [ValueType], results: &'m [ValueType]) -> Result<(), Error> {
+        info!("on_if({:?}, {:?})", params, results);
+        self.pop_and_check_one_type(I32, "if")?;
+        self.pop_and_check_signature(params, "if")?;
+        self.push_label(LabelType::If, params, results)?;
+        self.push_types(params)?;
+        Ok(())
     }
 
     pub fn on_else(&mut self) -> Result<(), Error> {
         info!("on_else()");
-        Ok({
-            let label = self.get_label(0)?;                
-            self.check_label_type(label, LabelType::If)?;
-            if label.signature != VOID {
-                self.pop_and_check_signature(&[label.signature])?;
-            }
-            self.check_type_stack_end()?;
-            self.reset_type_stack_to_label(label)?;
+        let label = self.get_label(0)?;                
+        self.check_label_type(label, LabelType::If)?;
+        self.pop_and_check_signature(label.results, "if true branch")?;
+        self.check_type_stack_end("if true branch")?;
+        self.reset_type_stack_to_label(label)?;
+        self.push_types(label.params)?;
+
+        let label = self.get_label_ref(0)?;                
+        label.label_type = LabelType::Else;
+        label.unreachable = false;
+
+        //   result |= CheckLabelType(label, LabelType::If);
+        //   result |= PopAndCheckSignature(label->sig, "if true branch");
+        //   result |= CheckTypeStackEnd("if true branch");
+        //   ResetTypeStackToLabel(label);
+        //   label->label_type = LabelType::Else;
+        //   label->unreachable = false;
+        Ok(())
+    }
+
+    pub fn on_try(&mut self, params: &'m [ValueType], results: &'m [ValueType]) -> Result<(), Error> {
+        info!("on_try({:?}, {:?})", params, results);
+        self.pop_and_check_signature(params, "try")?;
+        self.push_label(LabelType::Try, params, results)?;
+        self.push_types(params)?;
+        Ok(())
+    }
+
+    /// Starts a `catch` clause whose body receives `payload`, or a `catch_all` clause
+    /// if `payload` is empty.
+    pub fn on_catch(&mut self, payload: &[ValueType]) -> Result<(), Error> {
+        info!("on_catch({:?})", payload);
+        let label = self.get_label(0)?;
+        if label.label_type != LabelType::Try && label.label_type != LabelType::Catch {
+            return Err(Error::TypeCheck("catch without try"))
+        }
+        let desc = label.label_type.as_str();
+        self.pop_and_check_signature(label.results, desc)?;
+        self.check_type_stack_end(desc)?;
+        self.reset_type_stack_to_label(label)?;
+        self.push_types(payload)?;
+
+        let label = self.get_label_ref(0)?;
+        label.label_type = LabelType::Catch;
+        label.unreachable = false;
+        Ok(())
+    }
 
-            let label = self.get_label_ref(0)?;                
-            label.label_type = LabelType::Else;
-            label.unreachable = false;
+    pub fn on_throw(&mut self, payload: &[ValueType]) -> Result<(), Error> {
+        info!("on_throw({:?})", payload);
+        self.pop_and_check_signature(payload, "throw")?;
+        self.set_unreachable(true)?;
+        Ok(())
+    }
 
-            //   result |= CheckLabelType(label, LabelType::If);
-            //   result |= PopAndCheckSignature(label->sig, "if true branch");
-            //   result |= CheckTypeStackEnd("if true branch");
-            //   ResetTypeStackToLabel(label);
-            //   label->label_type = LabelType::Else;
-            //   label->unreachable = false;
-        })
+    pub fn on_rethrow(&mut self, depth: usize) -> Result<(), Error> {
+        info!("on_rethrow({})", depth);
+        let label = self.get_label(depth)?;
+        self.check_label_type(label, LabelType::Catch)?;
+        self.set_unreachable(true)?;
+        Ok(())
     }
 
     pub fn on_end(&mut self) -> Result<(), Error> {
         info!("on_end()");
-        Ok({
-            let label = self.get_label(0)?;                
-            if let LabelType::If = label.label_type {
-                info!("IF signature: {:?}", label.signature);
-                if label.signature != VOID {                    
-                    return Err(Error::TypeCheck("if without else cannot have type signature"))
-                }
+        let label = self.get_label(0)?;                
+        if let LabelType::If = label.label_type {
+            info!("IF signature: {:?} -> {:?}", label.params, label.results);
+            if label.params != label.results {                    
+                return Err(Error::TypeCheck("if without else cannot have type signature"))
             }
-            self.on_end_label(label)?;
-            info!("on_end() done");
-        })
+        }
+        self.on_end_label(label)?;
+        info!("on_end() done");
+        Ok(())
     }
     pub fn on_br(&mut self, depth: usize) -> Result<(), Error> {
         info!("on_br({})", depth);
-        Ok({
-            let label = self.get_label(depth)?;
-            if label.label_type != LabelType::Loop && label.signature != ValueType::Void {
-                self.check_signature(&[label.signature])?;
-            }
-            self.set_unreachable(true)?;
-            //   CHECK_RESULT(GetLabel(depth, &label));
-            //   if (label->label_type != LabelType::Loop) {
-            //     result |= CheckSignature(label->sig);
-            //   }
-            //   PrintStackIfFailed(result, "br", label->sig);
-            //   CHECK_RESULT(SetUnreachable());
-        })
+        let label = self.get_label(depth)?;
+        self.check_signature(label.br_types(), "br")?;
+        self.set_unreachable(true)?;
+        //   CHECK_RESULT(GetLabel(depth, &label));
+        //   result |= CheckSignature(label->br_types());
+        //   PrintStackIfFailed(result, "br", label->sig);
+        //   CHECK_RESULT(SetUnreachable());
+        Ok(())
     }
     pub fn on_br_if(&mut self, depth: usize) -> Result<(), Error> {
         info!("on_br_if({})", depth);
-        Ok({
-            let label = self.get_label(depth)?;
-            if label.label_type != LabelType::Loop && label.signature != ValueType::Void {
-                self.pop_and_check_signature(&[label.signature])?;
-                self.push_type(label.signature)?;
-            }
-            self.set_unreachable(true)?;            
-            //   Result result = PopAndCheck1Type(Type::I32, "br_if");
-            //   Label* label;
-            //   CHECK_RESULT(GetLabel(depth, &label));
-            //   if (label->label_type != LabelType::Loop) {
-            //     result |= PopAndCheckSignature(label->sig, "br_if");
-            //     PushTypes(label->sig);
-            //   }
-        })
+        self.pop_and_check_one_type(I32, "br_if")?;
+        let label = self.get_label(depth)?;
+        self.pop_and_check_signature(label.br_types(), "br_if")?;
+        self.push_types(label.br_types())?;
+        //   Result result = PopAndCheck1Type(Type::I32, "br_if");
+        //   Label* label;
+        //   CHECK_RESULT(GetLabel(depth, &label));
+        //   result |= PopAndCheckSignature(label->br_types(), "br_if");
+        //   PushTypes(label->br_types());
+        Ok(())
     }
 
     pub fn begin_br_table(&mut self) -> Result<(), Error> {
         info!("begin_br_table()");
-        Ok({
-            self.br_table_sig = Some(ValueType::Any);
-            self.pop_and_check_one_type(ValueType::I32)?;
-        })
+        self.br_table_sig = None;
+        self.pop_and_check_one_type(ValueType::I32, "br_table")?;
+        Ok(())
     }
 
     pub fn on_br_table_target(&mut self, depth: usize) -> Result<(), Error> {
         info!("on_br_table_target()");
-        Ok({
-            let label = self.get_label(depth)?;
-            let label_sig = if label.label_type == LabelType::Loop {
-                ValueType::Void
-            } else {
-                label.signature
-            };
-            if label.signature != ValueType::Void {
-                self.check_signature(&[label.signature])?;
+        let label = self.get_label(depth)?;
+        let label_sig = label.br_types();
+        self.check_signature(label_sig, "br_table")?;
+        if let Some(br_table_sig) = self.br_table_sig {
+            if br_table_sig.len() != label_sig.len() {
+                return Err(Error::TypeCheck("br_table labels have inconsistent types"));
             }
-            if let Some(br_table_sig) = self.br_table_sig {
-                if self.check_type(br_table_sig, label_sig).is_err() {
-                    return Err(Error::TypeCheck("br_table labels have inconsistent types"));
-                }
-            } else {
-                panic!("br_table_target without begin_br_table call");
-            }
-            self.br_table_sig = Some(label_sig);
-            info!("  => done");
-
-            // CHECK_RESULT(GetLabel(depth, &label));
-            //   Type label_sig;
-            //   if (label->label_type == LabelType::Loop) {
-            //     label_sig = Type::Void;
-            //   } else {
-            //     assert(label->sig.size() <= 1);
-            //     label_sig = label->sig.size() == 0 ? Type::Void : label->sig[0];
-
-            //     result |= CheckSignature(label->sig);
-            //     PrintStackIfFailed(result, "br_table", label_sig);
-            //   }
-
-            //   // Make sure this label's signature is consistent with the previous labels'
-            //   // signatures.
-            //   if (Failed(CheckType(br_table_sig_, label_sig))) {
-            //     result |= Result::Error;
-            //     PrintError("br_table labels have inconsistent types: expected %s, got %s",
-            //                GetTypeName(br_table_sig_), GetTypeName(label_sig));
-            //   }
-            //   br_table_sig_ = label_sig;
+        }
+        self.br_table_sig = Some(label_sig);
+        info!("  => done");
+
+        // CHECK_RESULT(GetLabel(depth, &label));
+        //   TypeVector& label_sig = label->br_types();
+        //   result |= CheckSignature(label_sig, "br_table");
+
+        //   // Make sure this label's signature is consistent with the previous labels'
+        //   // signatures.
+        //   if (br_table_sig_ == nullptr) {
+        //     br_table_sig_ = &label_sig;
+        //   }
+        //   if (br_table_sig_->size() != label_sig.size()) {
+        //     result |= Result::Error;
+        //     PrintError("br_table labels have inconsistent types: expected %s, got %s",
+        //                TypesToString(*br_table_sig_).c_str(),
+        //                TypesToString(label_sig).c_str());
+        //   }
 
-        })
+        Ok(())
     }
 
     pub fn end_br_table(&mut self) -> Result<(), Error> {
         info!("end_br_table()");
-        Ok({
-            self.set_unreachable(true)?;
-            self.br_table_sig = None;
-        })
+        self.set_unreachable(true)?;
+        self.br_table_sig = None;
+        Ok(())
     }
 
     pub fn on_get_local(&mut self, t: ValueType) -> Result<(), Error> {
         info!("on_get_local({})", t);
-        Ok({
-            self.push_type(t)?;
-        })
+        self.push_type(t)?;
+        Ok(())
     }
 
     pub fn on_set_local(&mut self, t: ValueType) -> Result<(), Error> {
         info!("on_set_local({})", t);
-        Ok({
-            self.pop_and_check_one_type(t)?;
-        })
+        self.pop_and_check_one_type(t, "local.set")?;
+        Ok(())
     }
 
     pub fn on_tee_local(&mut self, t: ValueType) -> Result<(), Error> {
         info!("on_tee_local({})", t);
-        Ok({
-            self.pop_and_check_one_type(t)?;
-            self.push_type(t)?;
-        })
+        self.pop_and_check_one_type(t, "local.tee")?;
+        self.push_type(t)?;
+        Ok(())
     }    
 
     pub fn on_get_global(&mut self, t: ValueType) -> Result<(), Error> {
         info!("on_get_global({})", t);
-        Ok({
-            self.push_type(t)?;
-        })
+        self.push_type(t)?;
+        Ok(())
     }
 
     pub fn on_set_global(&mut self, t: ValueType) -> Result<(), Error> {
         info!("on_set_global({})", t);
-        Ok({
-            self.pop_and_check_one_type(t)?;
-        })
+        self.pop_and_check_one_type(t, "global.set")?;
+        Ok(())
     }
 
 
     pub fn check_opcode1(&mut self, op: &Op) -> Result<(), Error> {
         info!("check_opcode1({:?})", op.text);
-        Ok({
-            self.pop_and_check_one_type(op.t1)?;
-            if op.tr != VOID {
-                self.push_type(op.tr)?;
-            }
-        })
+        self.pop_and_check_one_type(op.t1, op.text)?;
+        if op.tr != VOID {
+            self.push_type(op.tr)?;
+        }
+        Ok(())
     }
 
     pub fn check_opcode2(&mut self, op: &Op) -> Result<(), Error> {
         info!("check_opcode2({:?})", op.text);
-        Ok({
-            self.pop_and_check_two_types(op.t1, op.t2)?;
-            if op.tr != VOID {
-                self.push_type(op.tr)?;            
-            }
-        })
+        self.pop_and_check_two_types(op.t1, op.t2, op.text)?;
+        if op.tr != VOID {
+            self.push_type(op.tr)?;            
+        }
+        Ok(())
     }
 
     pub fn on_load(&mut self, op: &Op) -> Result<(), Error> {
@@ -565,9 +656,8 @@ impl<'m> TypeChecker<'m> {
 
     pub fn on_const(&mut self, t: ValueType) -> Result<(), Error> {
         info!("on_const({:?})", t);
-        Ok({
-            self.push_type(t)?;
-        })
+        self.push_type(t)?;
+        Ok(())
     }
     
     pub fn on_unary(&mut self, op: &Op) -> Result<(), Error> {
@@ -585,6 +675,107 @@ impl<'m> TypeChecker<'m> {
     pub fn on_grow_memory(&mut self, op: &Op) -> Result<(), Error> {
         self.check_opcode1(op)
     }
+
+    pub fn on_memory_init(&mut self) -> Result<(), Error> {
+        self.pop_and_check_three_types(I32, I32, I32, "memory.init")
+    }
+
+    pub fn on_memory_copy(&mut self) -> Result<(), Error> {
+        self.pop_and_check_three_types(I32, I32, I32, "memory.copy")
+    }
+
+    pub fn on_memory_fill(&mut self) -> Result<(), Error> {
+        self.pop_and_check_three_types(I32, I32, I32, "memory.fill")
+    }
+
+    pub fn on_atomic_load(&mut self, op: &Op) -> Result<(), Error> {
+        self.check_opcode1(op)
+    }
+
+    pub fn on_atomic_store(&mut self, op: &Op) -> Result<(), Error> {
+        self.check_opcode2(op)
+    }
+
+    pub fn on_atomic_rmw(&mut self, op: &Op) -> Result<(), Error> {
+        self.check_opcode2(op)
+    }
+
+    pub fn on_atomic_cmpxchg(&mut self, op: &Op) -> Result<(), Error> {
+        self.pop_and_check_three_types(I32, op.t2, op.t2, op.text)?;
+        self.push_type(op.tr)?;
+        Ok(())
+    }
+
+    pub fn on_atomic_wait(&mut self, op: &Op) -> Result<(), Error> {
+        self.pop_and_check_three_types(I32, op.t2, I64, op.text)?;
+        self.push_type(I32)?;
+        Ok(())
+    }
+
+    pub fn on_atomic_notify(&mut self, op: &Op) -> Result<(), Error> {
+        self.check_opcode2(op)
+    }
+
+    pub fn on_atomic_fence(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(feature="simd")]
+    pub fn on_simd_bitselect(&mut self) -> Result<(), Error> {
+        self.pop_and_check_three_types(V128, V128, V128, "v128.bitselect")?;
+        self.push_type(V128)?;
+        Ok(())
+    }
+
+    pub fn on_ref_null(&mut self, t: ValueType) -> Result<(), Error> {
+        self.push_type(t)
+    }
+
+    pub fn on_ref_is_null(&mut self) -> Result<(), Error> {
+        let t = self.peek_type(0)?;
+        if !(t.is_ref() || t == ValueType::Any) {
+            return Err(Error::TypeCheck("ref.is_null requires a reference type"))
+        }
+        self.drop_types(1)?;
+        self.push_type(I32)?;
+        Ok(())
+    }
+
+    pub fn on_ref_func(&mut self) -> Result<(), Error> {
+        self.push_type(ValueType::AnyFunc)
+    }
+
+    pub fn on_table_get(&mut self, elemtype: ValueType) -> Result<(), Error> {
+        self.pop_and_check_one_type(I32, "table.get")?;
+        self.push_type(elemtype)?;
+        Ok(())
+    }
+
+    pub fn on_table_set(&mut self, elemtype: ValueType) -> Result<(), Error> {
+        self.pop_and_check_two_types(I32, elemtype, "table.set")
+    }
+
+    pub fn on_table_grow(&mut self, elemtype: ValueType) -> Result<(), Error> {
+        self.pop_and_check_two_types(elemtype, I32, "table.grow")?;
+        self.push_type(I32)?;
+        Ok(())
+    }
+
+    pub fn on_table_size(&mut self) -> Result<(), Error> {
+        self.push_type(I32)
+    }
+
+    pub fn on_table_fill(&mut self, elemtype: ValueType) -> Result<(), Error> {
+        self.pop_and_check_three_types(I32, elemtype, I32, "table.fill")
+    }
+
+    pub fn on_table_init(&mut self) -> Result<(), Error> {
+        self.pop_and_check_three_types(I32, I32, I32, "table.init")
+    }
+
+    pub fn on_table_copy(&mut self) -> Result<(), Error> {
+        self.pop_and_check_three_types(I32, I32, I32, "table.copy")
+    }
 }
 
 
@@ -655,14 +846,13 @@ impl<'a> TypeStack for Stack<'a, ValueType> {
     }    
     fn erase(&mut self, bottom: usize, top: usize) -> Result<(), Error> {
         info!("  erase({},{})", bottom, top);
-        Ok({
-            if top == self.len() {
-                self.set_pos(bottom)?;
-            }
-            info!("new len: {}", self.len());
-            // for i in bottom..top {            
-            //     self.set(i, VOID)?;
-            // }
-        })
+        if top == self.len() {
+            self.set_pos(bottom)?;
+        }
+        info!("new len: {}", self.len());
+        // for i in bottom..top {            
+        //     self.set(i, VOID)?;
+        // }
+        Ok(())
     }
 }
\ No newline at end of file
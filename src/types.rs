@@ -1,2 +1,182 @@
-pub use parser::module::{Id, Global, Import, Export, Element, Data, Initializer, ImportDesc, ExportDesc, Immediate};
-pub use parser::types::{Index, Limits, MemoryType, TableType, ValueType, GlobalType};
\ No newline at end of file
+//! The types shared by the parser and the runtime. A module loaded from an image only
+//! needs these, so they are available without the `compiler` feature.
+
+#[cfg(feature="compiler")]
+pub use parser::module::{Global, Import, Export, Element, Data, Initializer, Immediate};
+#[cfg(feature="compiler")]
+pub use parser::types::BlockType;
+
+use core::fmt;
+
+pub type Index = u32;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Id {
+    Custom = 0,
+    Type = 1,
+    Import = 2,
+    Function = 3,
+    Table = 4,
+    Memory = 5,
+    Global = 6,
+    Export = 7,
+    Start = 8,
+    Element = 9,
+    Code = 10,
+    Data = 11,
+    DataCount = 12,
+    Tag = 13,
+}
+
+impl Id {
+    pub fn as_str(&self) -> &'static str {
+        use self::Id::*;
+        match *self {
+            Custom => "Custom",
+            Type => "Type",
+            Import => "Import",
+            Function => "Function",
+            Table => "Table",
+            Memory => "Memory",
+            Global => "Global",
+            Export => "Export",
+            Start => "Start",
+            Element => "Elem",
+            Code => "Code",
+            Data => "Data",            
+            DataCount => "DataCount",
+            Tag => "Tag",
+        }
+    }    
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ValueType {
+    Any = 0x00,
+    Void = 0x40,    
+    Func = 0x60,
+    AnyFunc = 0x70,
+    ExternRef = 0x6f,
+    I32 = 0x7f,
+    I64 = 0x7e,
+    F32 = 0x7d,
+    F64 = 0x7c,
+    V128 = 0x7b,
+}
+
+impl ValueType {
+    pub fn from_u8(other: u8) -> Option<ValueType> {
+        Some(match other {
+            0x00 => ValueType::Any,
+            0x40 => ValueType::Void,
+            0x60 => ValueType::Func,
+            0x70 => ValueType::AnyFunc,
+            0x6f => ValueType::ExternRef,
+            0x7f => ValueType::I32,
+            0x7e => ValueType::I64,
+            0x7d => ValueType::F32,
+            0x7c => ValueType::F64,
+            #[cfg(feature = "simd")]
+            0x7b => ValueType::V128,
+            _ => return None
+        })
+    }
+
+    /// Returns true for the reference types `anyfunc` (funcref) and `externref`.
+    pub fn is_ref(&self) -> bool {
+        matches!(*self, ValueType::AnyFunc | ValueType::ExternRef)
+    }
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::ValueType::*;
+        write!(f, "{}", match *self {
+            Any => "any",
+            I32 => "i32",
+            I64 => "i64",
+            F32 => "f32",
+            F64 => "f64",
+            V128 => "v128",
+            AnyFunc => "anyfunc",
+            ExternRef => "externref",
+            Func => "func",
+            Void => "void",
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct FunctionType<'a> {
+    pub functype: u8,
+    pub parameters: &'a [ValueType],
+    pub results: &'a [ValueType],
+}
+
+#[derive(Debug)]
+pub struct Limits {
+    pub flag: bool,
+    /// Set for memories declared `shared` by threaded targets. Shared limits must have a
+    /// maximum.
+    pub shared: bool,
+    pub min: u32,
+    pub max: Option<u32>,
+}
+
+#[derive(Debug)]
+pub struct MemoryType {
+    pub limits: Limits,
+}
+
+#[derive(Debug)]
+pub struct TableType {
+    pub elemtype: ValueType,
+    pub limits: Limits,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlobalType {
+    pub valtype: ValueType,
+    pub mutable: bool,
+}
+
+#[derive(Debug)]
+pub enum ImportDesc {
+    Func(Index),
+    Table(TableType),
+    Memory(MemoryType),
+    Global(GlobalType),
+    Tag(Tag),
+}
+
+/// An exception tag. `type_index` names a function type whose parameters are the
+/// exception's payload and whose results must be empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tag {
+    pub attribute: u8,
+    pub type_index: Index,
+}
+
+#[derive(Debug)]
+pub enum ExportDesc {
+    Func(Index),
+    Table(Index),
+    Memory(Index),
+    Global(Index),
+    Tag(Index),
+}
+
+/// Where in a module an error was found.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Location {
+    /// The module offset of the item being read or checked.
+    pub offset: u32,
+    /// The section containing the item, if any.
+    pub section: Option<Id>,
+    /// The index of the function whose body contains the item, counting imported functions.
+    pub function: Option<u32>,
+    /// The offset of the item from the start of that function's body.
+    pub instruction: Option<u32>,
+}
+
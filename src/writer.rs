@@ -11,11 +11,25 @@ pub type WriteResult<T> = Result<T, Error>;
 pub struct Writer<'a> {
     pub(crate) buf: &'a mut [u8],
     pos: usize,
+    origin: usize,
+    sink: bool,
 }
 
 impl<'a> Writer<'a> {
     pub fn new(buf: &'a mut [u8]) -> Self {
-        Writer { buf: buf, pos: 0 }
+        Writer { buf, pos: 0, origin: 0, sink: false }
+    }
+
+    /// Creates a writer whose positions start at `origin` rather than zero, for appending to
+    /// a buffer that already holds `origin` bytes before `buf`.
+    pub fn new_at(buf: &'a mut [u8], origin: usize) -> Self {
+        Writer { buf, pos: 0, origin, sink: false }
+    }
+
+    /// Creates a writer with no buffer that discards everything written to it while still
+    /// tracking its position. Sinks cannot be split or allocated from.
+    pub fn sink() -> Self {
+        Writer { buf: &mut [], pos: 0, origin: 0, sink: true }
     }
 
     pub fn cap(&self) -> usize {
@@ -23,66 +37,110 @@ impl<'a> Writer<'a> {
     }
 
     pub fn pos(&self) -> usize {
-        self.pos
+        self.origin + self.pos
     }
 
     pub fn advance(&mut self, len: usize) {
         self.pos += len;
     }
 
+    /// Moves back to `pos`, so that everything written after it is written again.
+    pub fn rewind(&mut self, pos: usize) -> WriteResult<()> {
+        if pos < self.origin || pos > self.pos() { return Err(Error::End) }
+        self.pos = pos - self.origin;
+        Ok(())
+    }
+
+    /// Advances past the next `len` bytes, returning them unless this writer is a sink.
+    fn next_bytes(&mut self, len: usize) -> WriteResult<Option<&mut [u8]>> {
+        let pos = self.pos;
+        if self.sink {
+            self.pos += len;
+            return Ok(None)
+        }
+        if pos + len >= self.buf.len() { return Err(Error::End) }
+        self.pos += len;
+        Ok(Some(&mut self.buf[pos..]))
+    }
+
     pub fn write_u8(&mut self, value: u8) -> WriteResult<()> {
-        if self.pos + 1 >= self.buf.len() { return Err(Error::End) }
-        self.buf[self.pos] = value;
-        self.pos += 1;        
+        if let Some(buf) = self.next_bytes(1)? {
+            buf[0] = value;
+        }
+        Ok(())
+    }
+
+    pub fn write_u16(&mut self, value: u16) -> WriteResult<()> {
+        if let Some(buf) = self.next_bytes(2)? {
+            LittleEndian::write_u16(buf, value);
+        }
+        Ok(())
+    }
+
+    pub fn write_u16_at(&mut self, value: u16, offset: usize) -> WriteResult<()> {
+        let offset = offset.checked_sub(self.origin).ok_or(Error::End)?;
+        if offset + 2 > self.pos { return Err(Error::End) }
+        if self.sink { return Ok(()) }
+        if offset + 2 > self.buf.len() { return Err(Error::End) }
+        LittleEndian::write_u16(&mut self.buf[offset..], value);
         Ok(())
     }
 
     pub fn write_u32(&mut self, value: u32) -> WriteResult<()> {
-        if self.pos + 4 >= self.buf.len() { return Err(Error::End) }
-        LittleEndian::write_u32(&mut self.buf[self.pos..], value);
-        self.pos += 4;
+        if let Some(buf) = self.next_bytes(4)? {
+            LittleEndian::write_u32(buf, value);
+        }
         Ok(())
     }
 
     pub fn write_u32_at(&mut self, value: u32, offset: usize) -> WriteResult<()> {
-        if offset + 4 > self.buf.len() { return Err(Error::End) }
+        let offset = offset.checked_sub(self.origin).ok_or(Error::End)?;
         if offset + 4 > self.pos { return Err(Error::End) }
+        if self.sink { return Ok(()) }
+        if offset + 4 > self.buf.len() { return Err(Error::End) }
         LittleEndian::write_u32(&mut self.buf[offset..], value);
         Ok(())
     }
 
     pub fn write_i8(&mut self, value: i8) -> WriteResult<()> {
-        if self.pos + 1 >= self.buf.len() { return Err(Error::End) }
-        self.buf[self.pos] = value as u8;
-        self.pos += 1;        
+        if let Some(buf) = self.next_bytes(1)? {
+            buf[0] = value as u8;
+        }
+        Ok(())
+    }
+
+    pub fn write_i16(&mut self, value: i16) -> WriteResult<()> {
+        if let Some(buf) = self.next_bytes(2)? {
+            LittleEndian::write_i16(buf, value);
+        }
         Ok(())
     }
 
     pub fn write_i32(&mut self, value: i32) -> WriteResult<()> {
-        if self.pos + 4 >= self.buf.len() { return Err(Error::End) }
-        LittleEndian::write_i32(&mut self.buf[self.pos..], value);
-        self.pos += 4;
+        if let Some(buf) = self.next_bytes(4)? {
+            LittleEndian::write_i32(buf, value);
+        }
         Ok(())
     }
 
     pub fn write_f32(&mut self, value: f32) -> WriteResult<()> {
-        if self.pos + 4 >= self.buf.len() { return Err(Error::End) }
-        LittleEndian::write_f32(&mut self.buf[self.pos..], value);
-        self.pos += 4;
+        if let Some(buf) = self.next_bytes(4)? {
+            LittleEndian::write_f32(buf, value);
+        }
         Ok(())
     }
 
     pub fn write_i64(&mut self, value: i64) -> WriteResult<()> {
-        if self.pos + 8 >= self.buf.len() { return Err(Error::End) }
-        LittleEndian::write_i64(&mut self.buf[self.pos..], value);
-        self.pos += 8;
+        if let Some(buf) = self.next_bytes(8)? {
+            LittleEndian::write_i64(buf, value);
+        }
         Ok(())
     }
 
     pub fn write_f64(&mut self, value: f64) -> WriteResult<()> {
-        if self.pos + 8 >= self.buf.len() { return Err(Error::End) }
-        LittleEndian::write_f64(&mut self.buf[self.pos..], value);
-        self.pos += 8;
+        if let Some(buf) = self.next_bytes(8)? {
+            LittleEndian::write_f64(buf, value);
+        }
         Ok(())
     }
 
@@ -184,13 +242,10 @@ impl<'a> Writer<'a> {
         self.write_u32(len as u32)
     }
 
-    pub fn copy_str(&mut self, s: &str) -> &'a str {
-        assert!(self.pos == 0, "Allocation can only happen with empty writer");
-        for b in s.bytes() {
-            self.buf[self.pos] = b;
-            self.pos += 1;
-        }
-        unsafe { str::from_utf8_unchecked(self.split()) }
+    pub fn copy_str(&mut self, s: &str) -> WriteResult<&'a str> {
+        self.reserve::<u8>(s.len())?;
+        self.buf[..s.len()].copy_from_slice(s.as_bytes());
+        Ok(unsafe { str::from_utf8_unchecked(self.split()) })
     }
 
     pub fn split<T>(&mut self) -> &'a [T] {
@@ -200,8 +255,8 @@ impl<'a> Writer<'a> {
             let a_len = self.pos;
 
             // Second Half
-            let b_ptr = self.buf.as_mut_ptr().offset(self.pos() as isize);
-            let b_len = self.buf.len() - self.pos();
+            let b_ptr = self.buf.as_mut_ptr().add(self.pos);
+            let b_len = self.buf.len() - self.pos;
 
             // Update Writer
             self.buf = slice::from_raw_parts_mut(b_ptr, b_len);
@@ -219,8 +274,8 @@ impl<'a> Writer<'a> {
             let a_len = self.pos / mem::size_of::<T>();
 
             // Second Half
-            let b_ptr = self.buf.as_mut_ptr().offset(self.pos() as isize);
-            let b_len = self.buf.len() - self.pos();
+            let b_ptr = self.buf.as_mut_ptr().add(self.pos);
+            let b_len = self.buf.len() - self.pos;
 
             // Update Writer
             self.buf = slice::from_raw_parts_mut(b_ptr, b_len);
@@ -235,23 +290,33 @@ impl<'a> Writer<'a> {
     //     Reader::new(self.split())
     // }
 
-    pub fn alloc_stack<T: Copy>(&mut self, len: usize) -> Stack<'a, T> {
+    /// Reserves `len` items of `T` at the start of the buffer, failing if the buffer is too
+    /// small.
+    fn reserve<T>(&mut self, len: usize) -> WriteResult<()> {
         assert!(self.pos == 0, "Allocation can only happen with an empty writer.");
-        self.pos += len * mem::size_of::<T>();
+        match len.checked_mul(mem::size_of::<T>()) {
+            Some(size) if size <= self.buf.len() => {
+                self.pos = size;
+                Ok(())
+            },
+            _ => Err(Error::OutOfMemory),
+        }
+    }
+
+    pub fn alloc_stack<T: Copy>(&mut self, len: usize) -> WriteResult<Stack<'a, T>> {
+        self.reserve::<T>(len)?;
         // info!("alloc_stack: len = {} pos={}", len, self.pos);
-        Stack::new(self.split_mut())
+        Ok(Stack::new(self.split_mut()))
     }
 
-    pub fn alloc_smallvec<T>(&mut self, len: usize) -> SmallVec<'a, T> {
-        assert!(self.pos == 0, "Allocation can only happen with an empty writer.");        
-        self.pos += len * mem::size_of::<T>();
-        SmallVec::new(self.split_mut())
+    pub fn alloc_smallvec<T>(&mut self, len: usize) -> WriteResult<SmallVec<'a, T>> {
+        self.reserve::<T>(len)?;
+        Ok(SmallVec::new(self.split_mut()))
     }
 
-    pub fn alloc_slice<T>(&mut self, len: usize) -> &'a mut [T] {
-        assert!(self.pos == 0, "Allocation can only happen with an empty writer.");        
-        self.pos += len * mem::size_of::<T>();
-        self.split_mut()
+    pub fn alloc_slice<T>(&mut self, len: usize) -> WriteResult<&'a mut [T]> {
+        self.reserve::<T>(len)?;
+        Ok(self.split_mut())
     }
 
     // pub fn copy<T>(&mut self, value: T) -> WriteResult<&'a mut T> {
@@ -260,8 +325,9 @@ impl<'a> Writer<'a> {
 
     pub fn align_to<T>(&mut self) -> WriteResult<()> {
         let align_of = mem::align_of::<T>();
-        let cur_ptr = (&self.buf[self.pos..]).as_ptr();
+        let cur_ptr = self.buf[self.pos..].as_ptr();
         let align_offset = cur_ptr.align_offset(align_of);
+        if self.pos + align_offset > self.buf.len() { return Err(Error::OutOfMemory) }
         self.pos += align_offset;
         Ok(())
     }
@@ -271,14 +337,14 @@ impl<'a> Writer<'a> {
             assert!(self.pos == 0, "Allocation can only happen with an empty writer.");
             let (size_of, align_of) = (mem::size_of::<T>(), mem::align_of::<T>());
 
-            let buf_pos = self.pos();
+            let buf_pos = self.pos;
             let buf_len = self.buf.len();
             let buf_ptr = self.buf.as_mut_ptr();
 
-            let cur_ptr = buf_ptr.offset(buf_pos as isize);
-            let end_ptr = cur_ptr.offset(buf_len as isize);
-            let val_ptr = buf_ptr.offset(buf_ptr.align_offset(align_of) as isize);
-            let new_ptr = val_ptr.offset(size_of as isize);
+            let cur_ptr = buf_ptr.add(buf_pos);
+            let end_ptr = cur_ptr.add(buf_len);
+            let val_ptr = buf_ptr.add(buf_ptr.align_offset(align_of));
+            let new_ptr = val_ptr.add(size_of);
             let new_len = end_ptr.offset_from(new_ptr);
             if new_len < 0 {
                 return Err(Error::OutOfBounds);
@@ -356,7 +422,7 @@ mod tests {
     fn test_alloc_stack() {
         let mut buf = [0u8; 32];
         let mut w = Writer::new(&mut buf);
-        let mut v: Stack<u32> = w.alloc_stack(4);
+        let mut v: Stack<u32> = w.alloc_stack(4).unwrap();
         assert_eq!(v.cap(), 4);
         for i in 0..4 {
             v.push(i as u32).unwrap();
@@ -368,19 +434,20 @@ mod tests {
     fn test_alloc_smallvec() {
         let mut buf = [0u8; 32];
         let mut w = Writer::new(&mut buf);
-        let mut v: SmallVec<u32> = w.alloc_smallvec(4);
+        let mut v: SmallVec<u32> = w.alloc_smallvec(4).unwrap();
         assert_eq!(v.cap(), 4);
         for i in 0..4 {
-            v.push(i as u32);
+            v.push(i as u32).unwrap();
         }
         assert_eq!(v.len(), 4);
+        assert!(w.alloc_smallvec::<u32>(5).is_err());
     }
 
     #[test]
     fn test_copy_str() {
         let mut buf = [0u8; 256];
         let mut w = Writer::new(&mut buf);
-        let s = w.copy_str("Hello There!");
+        let s = w.copy_str("Hello There!").unwrap();
         assert_eq!(s, "Hello There!");
     }
 